@@ -730,6 +730,32 @@ pub trait RealField: ComplexField<Real = Self> + PartialOrd {
     ) -> SimdIndexFor<Self, S>;
 
     fn faer_simd_abs<S: Simd>(simd: S, values: SimdGroupFor<Self, S>) -> SimdGroupFor<Self, S>;
+
+    /// Returns the sum of `partials`, computed by adding adjacent pairs of partial sums until a
+    /// single one is left.
+    ///
+    /// The order of the additions only depends on `N`, and the rounding error grows as
+    /// $\log_2(N)$ instead of $N$ for a sequential sum. For `N == 8`, this computes
+    /// `((p0 + p1) + (p2 + p3)) + ((p4 + p5) + (p6 + p7))`.
+    #[inline(always)]
+    fn faer_horizontal_sum<const N: usize>(partials: [Self; N]) -> Self {
+        let mut partials = partials;
+        let mut len = N;
+        if len == 0 {
+            return Self::faer_zero();
+        }
+        while len > 1 {
+            let half = len / 2;
+            for i in 0..half {
+                partials[i] = partials[2 * i].faer_add(partials[2 * i + 1]);
+            }
+            if len % 2 == 1 {
+                partials[half] = partials[len - 1];
+            }
+            len -= half;
+        }
+        partials[0]
+    }
 }
 
 impl ComplexField for f32 {
@@ -3279,4 +3305,19 @@ mod tests {
             assert_approx_eq!(target_im, sqrt_im);
         }
     }
+
+    #[test]
+    fn test_horizontal_sum() {
+        let p: [f64; 8] = core::array::from_fn(|_| rand::random());
+        assert!(
+            f64::faer_horizontal_sum(p)
+                == ((p[0] + p[1]) + (p[2] + p[3])) + ((p[4] + p[5]) + (p[6] + p[7]))
+        );
+
+        let p: [f64; 5] = core::array::from_fn(|_| rand::random());
+        assert!(f64::faer_horizontal_sum(p) == ((p[0] + p[1]) + (p[2] + p[3])) + p[4]);
+
+        assert!(f64::faer_horizontal_sum([]) == 0.0);
+        assert!(f32::faer_horizontal_sum([2.5]) == 2.5);
+    }
 }
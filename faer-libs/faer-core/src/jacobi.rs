@@ -1,4 +1,4 @@
-use crate::{assert, group_helpers::*, unzipped, zipped, MatMut, RealField};
+use crate::{assert, group_helpers::*, unzipped, zipped, ComplexField, MatMut, RealField};
 use faer_entity::{SimdCtx, SimdGroupFor};
 use reborrow::*;
 
@@ -314,3 +314,280 @@ impl<E: RealField> core::ops::Mul for JacobiRotation<E> {
         }
     }
 }
+
+/// Side of a matrix on which [`apply_jacobi_sequence`] applies plane rotations.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RotationSide {
+    /// Each rotation acts on a pair of rows.
+    Left,
+    /// Each rotation acts on a pair of columns.
+    Right,
+}
+
+/// Applies the plane rotations of `rotations` to `mat`, in order.
+///
+/// With [`RotationSide::Left`], each `(i, j, rot)` updates the rows `i` and `j` of `mat` the same
+/// way as `rot.apply_on_the_left_in_place(row_i, row_j)`. With [`RotationSide::Right`], it
+/// updates the columns `i` and `j` the same way as `rot.apply_on_the_right_in_place(col_i,
+/// col_j)`. Both use the SIMD kernels of [`JacobiRotation`], so replaying a sequence of rotations
+/// gives bitwise identical results to applying them one by one.
+///
+/// # Panics
+/// Panics if `i == j`, or if `i` or `j` is out of bounds, for any of the rotations.
+#[track_caller]
+pub fn apply_jacobi_sequence<E: RealField>(
+    rotations: &[(usize, usize, JacobiRotation<E>)],
+    mat: MatMut<'_, E>,
+    side: RotationSide,
+) {
+    // rotations on the right are rotations on the left of the transpose, which is what
+    // `apply_on_the_right_in_place` does as well
+    let (mut mat, transpose) = match side {
+        RotationSide::Left => (mat, false),
+        RotationSide::Right => (mat.transpose_mut(), true),
+    };
+    let n = mat.nrows();
+    let arch = E::Simd::default();
+
+    for &(i, j, rot) in rotations {
+        assert!(i != j);
+        assert!(i < n);
+        assert!(j < n);
+
+        let rot = if transpose { rot.transpose() } else { rot };
+        let (x, y) = if i < j {
+            let (top, bot) = mat.rb_mut().split_at_row_mut(j);
+            (top.subrows_mut(i, 1), bot.subrows_mut(0, 1))
+        } else {
+            let (top, bot) = mat.rb_mut().split_at_row_mut(i);
+            (bot.subrows_mut(0, 1), top.subrows_mut(j, 1))
+        };
+        rot.apply_on_the_left_in_place_arch(arch, x, y);
+    }
+}
+
+/// Plane rotation with a real cosine `c` and a possibly complex sine `s`, representing the unitary
+/// matrix
+/// $$\begin{bmatrix} c & s \\ -\bar{s} & c \end{bmatrix}.$$
+///
+/// This is the complex counterpart of [`JacobiRotation`], with methods of the same names and the
+/// same conventions, so code applying rotations can be written the same way for real and complex
+/// entries. When `s` is real, it performs exactly the same operations.
+#[derive(Copy, Clone, Debug)]
+pub struct ComplexJacobiRotation<E: ComplexField> {
+    pub c: E::Real,
+    pub s: E,
+}
+
+impl<E: ComplexField> ComplexJacobiRotation<E> {
+    /// Returns the rotation $G$ such that applying it on the right of the row $[p, q]$ gives
+    /// $[r, 0]$, with $|r|^2 = |p|^2 + |q|^2$. The cosine is nonnegative.
+    #[inline]
+    pub fn make_givens(p: E, q: E) -> Self {
+        let zero = E::Real::faer_zero();
+        let one = E::Real::faer_one();
+
+        let abs_p = p.faer_abs();
+        let abs_q = q.faer_abs();
+        if abs_q == zero {
+            Self {
+                c: one,
+                s: E::faer_zero(),
+            }
+        } else if abs_p == zero {
+            Self {
+                c: zero,
+                s: q.faer_scale_real(abs_q.faer_inv()).faer_neg(),
+            }
+        } else {
+            let (max, min) = if abs_p > abs_q {
+                (abs_p, abs_q)
+            } else {
+                (abs_q, abs_p)
+            };
+            let ratio = min.faer_div(max);
+            let norm = max.faer_mul(one.faer_add(ratio.faer_mul(ratio)).faer_sqrt());
+
+            // c = |p| / norm, s = -conj(p) q / (|p| norm)
+            let c = abs_p.faer_div(norm);
+            let s = p
+                .faer_scale_real(abs_p.faer_inv())
+                .faer_conj()
+                .faer_mul(q)
+                .faer_scale_real(norm.faer_inv())
+                .faer_neg();
+            Self { c, s }
+        }
+    }
+
+    /// Applies the rotation on the left of the matrix with rows `x` and `y`:
+    /// $x \gets c x + s y$ and $y \gets -\bar{s} x + c y$.
+    #[inline]
+    pub fn apply_on_the_left_in_place(&self, x: MatMut<'_, E>, y: MatMut<'_, E>) {
+        let Self { c, s } = *self;
+        assert!(all(x.nrows() == y.nrows(), x.ncols() == y.ncols(),));
+        let s_conj = s.faer_conj();
+        zipped!(x, y).for_each(move |unzipped!(mut x, mut y)| {
+            let x_ = x.read();
+            let y_ = y.read();
+            x.write(x_.faer_scale_real(c).faer_add(s.faer_mul(y_)));
+            y.write(y_.faer_scale_real(c).faer_sub(s_conj.faer_mul(x_)));
+        });
+    }
+
+    /// Applies the rotation on the right of the matrix with columns `x` and `y`:
+    /// $x \gets c x - \bar{s} y$ and $y \gets s x + c y$.
+    #[inline]
+    pub fn apply_on_the_right_in_place(&self, x: MatMut<'_, E>, y: MatMut<'_, E>) {
+        let Self { c, s } = *self;
+        assert!(all(x.nrows() == y.nrows(), x.ncols() == y.ncols(),));
+        let s_conj = s.faer_conj();
+        zipped!(x, y).for_each(move |unzipped!(mut x, mut y)| {
+            let x_ = x.read();
+            let y_ = y.read();
+            x.write(x_.faer_scale_real(c).faer_sub(s_conj.faer_mul(y_)));
+            y.write(y_.faer_scale_real(c).faer_add(s.faer_mul(x_)));
+        });
+    }
+
+    /// Returns the inverse rotation.
+    #[inline]
+    pub fn adjoint(&self) -> Self {
+        Self {
+            c: self.c,
+            s: self.s.faer_neg(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert, c64, Mat};
+    use assert_approx_eq::assert_approx_eq;
+
+    #[test]
+    fn test_complex_givens() {
+        let p = c64::new(0.3, -1.2);
+        let q = c64::new(-2.5, 0.7);
+
+        for (p, q) in [
+            (p, q),
+            (q, p),
+            (p, c64::new(0.0, 0.0)),
+            (c64::new(0.0, 0.0), q),
+        ] {
+            let rot = ComplexJacobiRotation::make_givens(p, q);
+            assert!(rot.c >= 0.0);
+            assert_approx_eq!(rot.c * rot.c + rot.s.faer_abs2(), 1.0);
+
+            let mut row = Mat::from_fn(1, 2, |_, j| if j == 0 { p } else { q });
+            let (x, y) = row.as_mut().split_at_col_mut(1);
+            rot.apply_on_the_right_in_place(x, y);
+            let norm = (p.faer_abs2() + q.faer_abs2()).sqrt();
+            assert_approx_eq!(row.read(0, 0).faer_abs(), norm);
+            assert_approx_eq!(row.read(0, 1).faer_abs(), 0.0);
+        }
+    }
+
+    #[test]
+    fn test_complex_rotation_is_unitary() {
+        let rot = ComplexJacobiRotation::make_givens(c64::new(1.0, 2.0), c64::new(-0.5, 3.0));
+        let mat = Mat::from_fn(2, 5, |i, j| c64::new(i as f64 + 0.5, j as f64 - 1.0));
+
+        let mut rotated = mat.clone();
+        let (x, y) = rotated.as_mut().split_at_row_mut(1);
+        rot.apply_on_the_left_in_place(x, y);
+        let norm = |m: &Mat<c64>| m.norm_l2();
+        assert_approx_eq!(norm(&rotated), norm(&mat));
+
+        let (x, y) = rotated.as_mut().split_at_row_mut(1);
+        rot.adjoint().apply_on_the_left_in_place(x, y);
+        for j in 0..5 {
+            for i in 0..2 {
+                assert_approx_eq!(rotated.read(i, j).re, mat.read(i, j).re);
+                assert_approx_eq!(rotated.read(i, j).im, mat.read(i, j).im);
+            }
+        }
+    }
+
+    #[test]
+    fn test_complex_rotation_matches_real() {
+        let real = JacobiRotation::make_givens(0.6, -1.7);
+        let complex = ComplexJacobiRotation::<c64> {
+            c: real.c,
+            s: c64::new(real.s, 0.0),
+        };
+
+        let mat = Mat::from_fn(4, 2, |i, j| (i * 2 + j) as f64 - 3.25);
+        let mut mat_real = mat.clone();
+        let mut mat_complex = Mat::from_fn(4, 2, |i, j| c64::new(mat.read(i, j), 0.0));
+
+        let (x, y) = mat_real.as_mut().split_at_col_mut(1);
+        real.apply_on_the_right_in_place(x, y);
+        let (x, y) = mat_complex.as_mut().split_at_col_mut(1);
+        complex.apply_on_the_right_in_place(x, y);
+
+        for j in 0..2 {
+            for i in 0..4 {
+                assert_approx_eq!(mat_complex.read(i, j).re, mat_real.read(i, j));
+                assert!(mat_complex.read(i, j).im == 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_apply_jacobi_sequence() {
+        let rotations = [
+            (0, 3, JacobiRotation::make_givens(0.6, -1.7)),
+            (2, 1, JacobiRotation::make_givens(-2.0, 0.3)),
+            (3, 2, JacobiRotation::make_givens(1.1, 1.4)),
+            (1, 0, JacobiRotation::make_givens(0.2, -0.9)),
+        ];
+        let mat = Mat::from_fn(4, 7, |i, j| (i * 7 + j) as f64 / 3.0 - 4.5);
+
+        // rows
+        let mut expected = mat.clone();
+        for &(i, j, rot) in &rotations {
+            let mut x = Mat::from_fn(1, 7, |_, k| expected.read(i, k));
+            let mut y = Mat::from_fn(1, 7, |_, k| expected.read(j, k));
+            rot.apply_on_the_left_in_place(x.as_mut(), y.as_mut());
+            for k in 0..7 {
+                expected.write(i, k, x.read(0, k));
+                expected.write(j, k, y.read(0, k));
+            }
+        }
+        let mut rotated = mat.clone();
+        apply_jacobi_sequence(&rotations, rotated.as_mut(), RotationSide::Left);
+        assert!(rotated == expected);
+
+        // columns
+        let mat = mat.transpose().to_owned();
+        let mut expected = mat.clone();
+        for &(i, j, rot) in &rotations {
+            let mut x = Mat::from_fn(7, 1, |k, _| expected.read(k, i));
+            let mut y = Mat::from_fn(7, 1, |k, _| expected.read(k, j));
+            rot.apply_on_the_right_in_place(x.as_mut(), y.as_mut());
+            for k in 0..7 {
+                expected.write(k, i, x.read(k, 0));
+                expected.write(k, j, y.read(k, 0));
+            }
+        }
+        let mut rotated = mat.clone();
+        apply_jacobi_sequence(&rotations, rotated.as_mut(), RotationSide::Right);
+        assert!(rotated == expected);
+
+        // replaying the transposed rotations in reverse order undoes the sequence
+        let inverse = rotations
+            .iter()
+            .rev()
+            .map(|&(i, j, rot)| (i, j, rot.transpose()))
+            .collect::<Vec<_>>();
+        apply_jacobi_sequence(&inverse, rotated.as_mut(), RotationSide::Right);
+        for j in 0..4 {
+            for i in 0..7 {
+                assert_approx_eq!(rotated.read(i, j), mat.read(i, j));
+            }
+        }
+    }
+}
@@ -2802,6 +2802,12 @@ pub type RowMut<'a, E> = Matrix<inner::DenseRowMut<'a, E>>;
 /// ```
 pub type MatMut<'a, E> = Matrix<inner::DenseMut<'a, E>>;
 
+/// Immutable view over a diagonal matrix, whose diagonal entries are stored in a column vector.
+pub type DiagRef<'a, E> = Matrix<inner::DiagRef<'a, E>>;
+
+/// Mutable view over a diagonal matrix, whose diagonal entries are stored in a column vector.
+pub type DiagMut<'a, E> = Matrix<inner::DiagMut<'a, E>>;
+
 /// Wrapper around a scalar value that allows scalar multiplication by matrices.
 pub type MatScale<E> = Matrix<inner::Scale<E>>;
 
@@ -7780,29 +7786,39 @@ struct RawMatUnit<T: 'static> {
 
 impl<T: 'static> RawMatUnit<T> {
     pub fn new(row_capacity: usize, col_capacity: usize) -> Self {
+        match Self::try_new_impl(row_capacity, col_capacity) {
+            Ok(this) => this,
+            Err(None) => capacity_overflow(),
+            Err(Some(layout)) => alloc::alloc::handle_alloc_error(layout),
+        }
+    }
+
+    pub fn try_new(row_capacity: usize, col_capacity: usize) -> Result<Self, FaerError> {
+        Self::try_new_impl(row_capacity, col_capacity).map_err(|_| FaerError::OutOfMemory)
+    }
+
+    /// returns the layout that failed to allocate on error, or `None` if the capacity overflows
+    fn try_new_impl(
+        row_capacity: usize,
+        col_capacity: usize,
+    ) -> Result<Self, Option<alloc::alloc::Layout>> {
         let dangling = NonNull::<T>::dangling();
         if core::mem::size_of::<T>() == 0 {
-            Self {
+            Ok(Self {
                 ptr: dangling,
                 row_capacity,
                 col_capacity,
-            }
+            })
         } else {
-            let cap = row_capacity
-                .checked_mul(col_capacity)
-                .unwrap_or_else(capacity_overflow);
-            let cap_bytes = cap
-                .checked_mul(core::mem::size_of::<T>())
-                .unwrap_or_else(capacity_overflow);
+            let cap = row_capacity.checked_mul(col_capacity).ok_or(None)?;
+            let cap_bytes = cap.checked_mul(core::mem::size_of::<T>()).ok_or(None)?;
             if cap_bytes > isize::MAX as usize {
-                capacity_overflow::<()>();
+                return Err(None);
             }
 
-            use alloc::alloc::{alloc, handle_alloc_error, Layout};
+            use alloc::alloc::{alloc, Layout};
 
-            let layout = Layout::from_size_align(cap_bytes, align_for::<T>())
-                .ok()
-                .unwrap_or_else(capacity_overflow);
+            let layout = Layout::from_size_align(cap_bytes, align_for::<T>()).map_err(|_| None)?;
 
             let ptr = if layout.size() == 0 {
                 dangling
@@ -7810,18 +7826,18 @@ impl<T: 'static> RawMatUnit<T> {
                 // SAFETY: we checked that layout has non zero size
                 let ptr = unsafe { alloc(layout) } as *mut T;
                 if ptr.is_null() {
-                    handle_alloc_error(layout)
+                    return Err(Some(layout));
                 } else {
                     // SAFETY: we checked that the pointer is not null
                     unsafe { NonNull::<T>::new_unchecked(ptr) }
                 }
             };
 
-            Self {
+            Ok(Self {
                 ptr,
                 row_capacity,
                 col_capacity,
-            }
+            })
         }
     }
 }
@@ -7941,6 +7957,30 @@ impl<E: Entity> RawMat<E> {
             col_capacity,
         }
     }
+
+    pub fn try_new(row_capacity: usize, col_capacity: usize) -> Result<Self, FaerError> {
+        let group = E::faer_map(E::UNIT, |()| {
+            RawMatUnit::<E::Unit>::try_new(row_capacity, col_capacity)
+        });
+
+        // the unit matrices that were successfully allocated are freed when `group` is dropped
+        let mut ok = true;
+        E::faer_map(E::faer_as_ref(&group), |unit| ok &= unit.is_ok());
+        if !ok {
+            return Err(FaerError::OutOfMemory);
+        }
+
+        let group = E::faer_map(group, |unit| match unit {
+            Ok(unit) => ManuallyDrop::new(unit),
+            Err(_) => unreachable!(),
+        });
+
+        Ok(Self {
+            ptr: into_copy::<E, _>(E::faer_map(group, |mat| mat.ptr)),
+            row_capacity,
+            col_capacity,
+        })
+    }
 }
 
 impl<E: Entity> Drop for RawMat<E> {
@@ -9219,6 +9259,27 @@ impl<E: Entity> Mat<E> {
         }
     }
 
+    /// Same as [`Mat::with_capacity`], but returns an error instead of panicking if the total
+    /// capacity in bytes exceeds `isize::MAX`, or aborting if the allocation fails.
+    ///
+    /// Filling the returned matrix up to its capacity, e.g., with [`Mat::resize_with`], does not
+    /// allocate.
+    #[inline]
+    pub fn try_with_capacity(row_capacity: usize, col_capacity: usize) -> Result<Self, FaerError> {
+        let raw = ManuallyDrop::new(RawMat::<E>::try_new(row_capacity, col_capacity)?);
+        Ok(Self {
+            inner: inner::DenseOwn {
+                inner: MatOwnImpl {
+                    ptr: raw.ptr,
+                    nrows: 0,
+                    ncols: 0,
+                },
+                row_capacity: raw.row_capacity,
+                col_capacity: raw.col_capacity,
+            },
+        })
+    }
+
     /// Returns a new matrix with dimensions `(nrows, ncols)`, filled with the provided function.
     ///
     /// # Panics
@@ -13372,6 +13433,24 @@ mod tests {
         let _ = Mat::<f64>::with_capacity(isize::MAX as usize, isize::MAX as usize);
     }
 
+    #[test]
+    fn try_with_capacity() {
+        let mut m = Mat::<f64>::try_with_capacity(10, 20).unwrap();
+        let ptr = m.as_ptr();
+        m.resize_with(10, 20, |i, j| (i + j) as f64);
+        assert!(m.as_ptr() == ptr);
+        assert!(m.read(3, 4) == 7.0);
+
+        assert!(
+            Mat::<f64>::try_with_capacity(isize::MAX as usize, isize::MAX as usize).unwrap_err()
+                == FaerError::OutOfMemory
+        );
+        // representable, but too large for any allocator
+        assert!(
+            Mat::<c64>::try_with_capacity(1 << 30, 1 << 28).unwrap_err() == FaerError::OutOfMemory
+        );
+    }
+
     #[test]
     fn matrix_macro() {
         let mut x = mat![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]];
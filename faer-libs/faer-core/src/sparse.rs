@@ -29,6 +29,7 @@
 
 use super::*;
 use crate::{assert, group_helpers::VecGroup};
+use alloc::vec::Vec;
 use core::{cell::Cell, iter::zip, ops::Range, slice::SliceIndex};
 use dyn_stack::GlobalPodBuffer;
 use group_helpers::SliceGroup;
@@ -1301,7 +1302,7 @@ impl<I: Index> core::fmt::Debug for SymbolicSparseColMatRef<'_, I> {
         let mut iter = (0..mat.ncols()).into_iter().flat_map(move |j| {
             struct Wrapper(usize, usize);
             impl core::fmt::Debug for Wrapper {
-                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                     let row = self.0;
                     let col = self.1;
                     write!(f, "({row}, {col}")
@@ -1321,7 +1322,7 @@ impl<I: Index> core::fmt::Debug for SymbolicSparseRowMatRef<'_, I> {
         let mut iter = (0..mat.nrows()).into_iter().flat_map(move |i| {
             struct Wrapper(usize, usize);
             impl core::fmt::Debug for Wrapper {
-                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                     let row = self.0;
                     let col = self.1;
                     write!(f, "({row}, {col}")
@@ -1340,7 +1341,7 @@ impl<I: Index, E: Entity> core::fmt::Debug for SparseColMatRef<'_, I, E> {
         let mut iter = (0..mat.ncols()).into_iter().flat_map(move |j| {
             struct Wrapper<E>(usize, usize, E);
             impl<E: core::fmt::Debug> core::fmt::Debug for Wrapper<E> {
-                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                     let row = self.0;
                     let col = self.1;
                     let val = &self.2;
@@ -1363,7 +1364,7 @@ impl<I: Index, E: Entity> core::fmt::Debug for SparseRowMatRef<'_, I, E> {
         let mut iter = (0..mat.nrows()).into_iter().flat_map(move |i| {
             struct Wrapper<E>(usize, usize, E);
             impl<E: core::fmt::Debug> core::fmt::Debug for Wrapper<E> {
-                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                     let row = self.0;
                     let col = self.1;
                     let val = &self.2;
@@ -1279,6 +1279,46 @@ mod herm_tests {
         }
     }
 
+    #[test]
+    fn test_real_divide_and_conquer() {
+        // large enough to go through the divide and conquer tridiagonal solver
+        for n in [64, 300] {
+            let half = Mat::from_fn(n, n, |_, _| rand::random::<f64>());
+            let mat = &half + half.transpose();
+
+            let mut s = Mat::zeros(n, n);
+            let mut u = Mat::zeros(n, n);
+
+            compute_hermitian_evd(
+                mat.as_ref(),
+                s.as_mut().diagonal_mut().column_vector_mut().as_2d_mut(),
+                Some(u.as_mut()),
+                Parallelism::None,
+                make_stack!(compute_hermitian_evd_req::<f64>(
+                    n,
+                    ComputeVectors::Yes,
+                    Parallelism::None,
+                    Default::default(),
+                )),
+                Default::default(),
+            );
+
+            for i in 1..n {
+                assert!(s.read(i - 1, i - 1) <= s.read(i, i));
+            }
+
+            let reconstructed = &u * &s * u.transpose();
+            let gram = u.transpose() * &u;
+            for j in 0..n {
+                for i in 0..n {
+                    assert_approx_eq!(reconstructed.read(i, j), mat.read(i, j), 1e-10);
+                    let target = if i == j { 1.0 } else { 0.0 };
+                    assert_approx_eq!(gram.read(i, j), target, 1e-10);
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_cplx() {
         for n in [2, 3, 4, 5, 6, 7, 10, 15, 25] {
@@ -1,7 +1,7 @@
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use faer_svd::{
     bidiag::bidiagonalize_in_place, bidiag_real_svd::compute_bidiag_real_svd, compute_svd,
-    SvdParams,
+    MergeParallelism, SplitStrategy, SvdAlgorithm, SvdParams,
 };
 use std::time::Duration;
 
@@ -116,6 +116,7 @@ fn bidiag_svd(c: &mut Criterion) {
                         f64::MIN_POSITIVE,
                         Parallelism::None,
                         stack.rb_mut(),
+                        SvdParams::default(),
                     );
                 });
             });
@@ -153,6 +154,56 @@ fn bidiag_svd(c: &mut Criterion) {
                         f64::MIN_POSITIVE,
                         Parallelism::Rayon(0),
                         stack.rb_mut(),
+                        SvdParams::default(),
+                    );
+                });
+            });
+        }
+    }
+}
+
+fn bidiag_svd_qr(c: &mut Criterion) {
+    for n in [32, 64, 128] {
+        let diag = (0..n).map(|_| rand::random::<f64>()).collect::<Vec<_>>();
+        let mut subdiag = (0..n).map(|_| rand::random::<f64>()).collect::<Vec<_>>();
+        subdiag[n - 1] = 0.0;
+
+        for compute_vectors in [true, false] {
+            let mut diag_copy = diag.clone();
+            let mut subdiag_copy = subdiag.clone();
+
+            let mut u = Mat::zeros(n + 1, n + 1);
+            let mut v = Mat::zeros(n, n);
+
+            let mut mem = GlobalPodBuffer::new(
+                faer_svd::bidiag_real_svd::bidiag_real_svd_req::<f64>(
+                    n,
+                    4,
+                    compute_vectors,
+                    compute_vectors,
+                    Parallelism::None,
+                )
+                .unwrap(),
+            );
+            let mut stack = PodStack::new(&mut mem);
+
+            let name = if compute_vectors { "vectors" } else { "values" };
+            c.bench_function(&format!("faer-st-bidiag-svd-qr-{name}-{n}"), |bencher| {
+                bencher.iter(|| {
+                    diag_copy.clone_from_slice(&diag);
+                    subdiag_copy.clone_from_slice(&subdiag);
+                    compute_bidiag_real_svd(
+                        &mut diag_copy,
+                        &mut subdiag_copy,
+                        compute_vectors.then_some(u.as_mut()),
+                        compute_vectors.then_some(v.as_mut()),
+                        4,
+                        128,
+                        f64::EPSILON,
+                        f64::MIN_POSITIVE,
+                        Parallelism::None,
+                        stack.rb_mut(),
+                        SvdParams::default(),
                     );
                 });
             });
@@ -160,6 +211,195 @@ fn bidiag_svd(c: &mut Criterion) {
     }
 }
 
+fn bidiag_svd_split(c: &mut Criterion) {
+    for n in [1024, 2048] {
+        // blocks of strongly coupled entries, separated by negligible subdiagonal entries away
+        // from the middle of the matrix
+        let diag = (0..n).map(|_| 1.0 + random::<f64>()).collect::<Vec<_>>();
+        let mut subdiag = (0..n).map(|_| 1.0 + random::<f64>()).collect::<Vec<_>>();
+        for i in [n / 5, n * 9 / 20, n * 7 / 10] {
+            subdiag[i - 1] *= 1e-20;
+            subdiag[i] *= 1e-20;
+        }
+
+        for compute_vectors in [true, false] {
+            for split_strategy in [SplitStrategy::Half, SplitStrategy::ByMass] {
+                let mut diag_copy = diag.clone();
+                let mut subdiag_copy = subdiag.clone();
+
+                let mut u = Mat::zeros(n + 1, n + 1);
+                let mut v = Mat::zeros(n, n);
+
+                let mut mem = GlobalPodBuffer::new(
+                    faer_svd::bidiag_real_svd::bidiag_real_svd_req::<f64>(
+                        n,
+                        4,
+                        compute_vectors,
+                        compute_vectors,
+                        Parallelism::None,
+                    )
+                    .unwrap(),
+                );
+                let mut stack = PodStack::new(&mut mem);
+
+                let name = if compute_vectors { "vectors" } else { "values" };
+                let mut params = SvdParams::default();
+                params.split_strategy = split_strategy;
+                c.bench_function(
+                    &format!("faer-st-bidiag-svd-split-{split_strategy:?}-{name}-{n}"),
+                    |bencher| {
+                        bencher.iter(|| {
+                            diag_copy.clone_from_slice(&diag);
+                            subdiag_copy.clone_from_slice(&subdiag);
+                            compute_bidiag_real_svd(
+                                &mut diag_copy,
+                                &mut subdiag_copy,
+                                compute_vectors.then_some(u.as_mut()),
+                                compute_vectors.then_some(v.as_mut()),
+                                4,
+                                128,
+                                f64::EPSILON,
+                                f64::MIN_POSITIVE,
+                                Parallelism::None,
+                                stack.rb_mut(),
+                                params,
+                            );
+                        });
+                    },
+                );
+            }
+        }
+    }
+}
+
+fn bidiag_svd_merge(c: &mut Criterion) {
+    for n in [1024, 2048, 4096] {
+        let diag = (0..n).map(|_| random::<f64>()).collect::<Vec<_>>();
+        let subdiag = (0..n).map(|_| random::<f64>()).collect::<Vec<_>>();
+
+        for merge_parallelism in [MergeParallelism::Nested, MergeParallelism::Sequential] {
+            let mut diag_copy = diag.clone();
+            let mut subdiag_copy = subdiag.clone();
+
+            let mut u = Mat::zeros(n + 1, n + 1);
+            let mut v = Mat::zeros(n, n);
+
+            let mut mem = GlobalPodBuffer::new(
+                faer_svd::bidiag_real_svd::bidiag_real_svd_req::<f64>(
+                    n,
+                    4,
+                    true,
+                    true,
+                    Parallelism::Rayon(0),
+                )
+                .unwrap(),
+            );
+            let mut stack = PodStack::new(&mut mem);
+
+            let mut params = SvdParams::default();
+            params.merge_parallelism = merge_parallelism;
+            c.bench_function(
+                &format!("faer-mt-bidiag-svd-merge-{merge_parallelism:?}-{n}"),
+                |bencher| {
+                    bencher.iter(|| {
+                        diag_copy.clone_from_slice(&diag);
+                        subdiag_copy.clone_from_slice(&subdiag);
+                        compute_bidiag_real_svd(
+                            &mut diag_copy,
+                            &mut subdiag_copy,
+                            Some(u.as_mut()),
+                            Some(v.as_mut()),
+                            4,
+                            128,
+                            f64::EPSILON,
+                            f64::MIN_POSITIVE,
+                            Parallelism::Rayon(0),
+                            stack.rb_mut(),
+                            params,
+                        );
+                    });
+                },
+            );
+        }
+    }
+}
+
+/// compares the three algorithms of the bidiagonal svd over a range of sizes, for each set of
+/// requested singular vectors, to find the crossover points used by `jacobi_fallback_threshold`,
+/// `bidiag_qr_fallback_threshold` and `SvdParams::parallel_threshold`
+///
+/// the throughput is reported in singular values per second
+fn bidiag_real_svd_bench(c: &mut Criterion) {
+    for (name, compute_u, compute_v) in [
+        ("values", false, false),
+        ("v", false, true),
+        ("full", true, true),
+    ] {
+        for (par_name, parallelism) in [("st", Parallelism::None), ("mt", Parallelism::Rayon(0))] {
+            let mut group = c.benchmark_group(format!("faer-{par_name}-bidiag-svd-{name}"));
+            for n in [8, 16, 32, 64, 128, 256, 512] {
+                let diag = (0..n).map(|_| random::<f64>()).collect::<Vec<_>>();
+                let subdiag = (0..n).map(|_| random::<f64>()).collect::<Vec<_>>();
+                let mut diag_copy = diag.clone();
+                let mut subdiag_copy = subdiag.clone();
+
+                let mut u = Mat::zeros(n + 1, n + 1);
+                let mut v = Mat::zeros(n, n);
+
+                let mut mem = GlobalPodBuffer::new(
+                    faer_svd::bidiag_real_svd::bidiag_real_svd_req::<f64>(
+                        n,
+                        4,
+                        compute_u,
+                        compute_v,
+                        parallelism,
+                    )
+                    .unwrap(),
+                );
+                let mut stack = PodStack::new(&mut mem);
+
+                group.throughput(Throughput::Elements(n as u64));
+                for algorithm in [
+                    SvdAlgorithm::Jacobi,
+                    SvdAlgorithm::QrIteration,
+                    SvdAlgorithm::DivideConquer,
+                ] {
+                    // the cost of the jacobi sweeps grows too fast for the larger sizes, where
+                    // it's already far behind the other algorithms
+                    if matches!(algorithm, SvdAlgorithm::Jacobi) && n > 64 {
+                        continue;
+                    }
+                    let mut params = SvdParams::default();
+                    params.algorithm = algorithm;
+                    group.bench_function(
+                        BenchmarkId::new(format!("{algorithm:?}"), n),
+                        |bencher| {
+                            bencher.iter(|| {
+                                diag_copy.clone_from_slice(&diag);
+                                subdiag_copy.clone_from_slice(&subdiag);
+                                compute_bidiag_real_svd(
+                                    &mut diag_copy,
+                                    &mut subdiag_copy,
+                                    compute_u.then_some(u.as_mut()),
+                                    compute_v.then_some(v.as_mut()),
+                                    4,
+                                    128,
+                                    f64::EPSILON,
+                                    f64::MIN_POSITIVE,
+                                    parallelism,
+                                    stack.rb_mut(),
+                                    params,
+                                );
+                            });
+                        },
+                    );
+                }
+            }
+            group.finish();
+        }
+    }
+}
+
 fn real_svd(c: &mut Criterion) {
     for (m, n) in [
         (8, 8),
@@ -169,6 +409,7 @@ fn real_svd(c: &mut Criterion) {
         (128, 128),
         (256, 256),
         (512, 512),
+        (4096, 32),
         (32, 4096),
         (1024, 1024),
         (10000, 128),
@@ -269,6 +510,7 @@ criterion_group!(
         .warm_up_time(Duration::from_secs(1))
         .measurement_time(Duration::from_secs(5))
         .sample_size(10);
-    targets = bidiag, bidiag_svd, real_svd,
+    targets = bidiag, bidiag_svd, bidiag_svd_qr, bidiag_svd_split, bidiag_svd_merge,
+        bidiag_real_svd_bench, real_svd,
 );
 criterion_main!(benches);
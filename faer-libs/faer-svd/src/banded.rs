@@ -0,0 +1,406 @@
+//! Reduction of a square banded matrix to lower bidiagonal form, for computing its singular
+//! values with [`compute_bidiag_real_svd`](crate::bidiag_real_svd::compute_bidiag_real_svd)
+//! without going through a dense bidiagonalization.
+
+use dyn_stack::{PodStack, SizeOverflow, StackReq};
+use faer_core::{
+    assert, jacobi::JacobiRotation, temp_mat_req, temp_mat_zeroed, Entity, MatMut, MatRef,
+    RealField,
+};
+use reborrow::*;
+
+/// Immutable view over a square banded matrix of dimension $n$, with `lower` nonzero diagonals
+/// below the main diagonal and `upper` nonzero diagonals above it.
+///
+/// The entries are stored column by column in the LAPACK general band layout: `data` has shape
+/// `(lower + upper + 1, n)`, and the entry at position $(i, j)$ of the matrix is stored at
+/// `data[(upper + i - j, j)]`. The storage entries that fall outside the matrix are ignored.
+#[derive(Copy, Clone, Debug)]
+pub struct BandedMatRef<'a, E: Entity> {
+    data: MatRef<'a, E>,
+    lower: usize,
+    upper: usize,
+}
+
+impl<'a, E: Entity> BandedMatRef<'a, E> {
+    /// Creates a banded view from its band storage.
+    ///
+    /// # Panics
+    /// Panics if `data.nrows() != lower + upper + 1`.
+    #[track_caller]
+    pub fn new(data: MatRef<'a, E>, lower: usize, upper: usize) -> Self {
+        assert!(data.nrows() == lower + upper + 1);
+        Self { data, lower, upper }
+    }
+
+    /// Returns the dimension of the matrix.
+    #[inline]
+    pub fn dim(&self) -> usize {
+        self.data.ncols()
+    }
+
+    /// Returns the number of nonzero diagonals below the main diagonal.
+    #[inline]
+    pub fn lower_bandwidth(&self) -> usize {
+        self.lower
+    }
+
+    /// Returns the number of nonzero diagonals above the main diagonal.
+    #[inline]
+    pub fn upper_bandwidth(&self) -> usize {
+        self.upper
+    }
+
+    /// Returns the band storage of the matrix.
+    #[inline]
+    pub fn storage(&self) -> MatRef<'a, E> {
+        self.data
+    }
+}
+
+impl<E: RealField> BandedMatRef<'_, E> {
+    /// Reads the entry at position $(i, j)$ of the matrix, which is zero outside the band.
+    #[inline]
+    #[track_caller]
+    pub fn read(&self, i: usize, j: usize) -> E {
+        let n = self.dim();
+        assert!(all(i < n, j < n));
+        if i + self.upper >= j && j + self.lower >= i {
+            self.data.read(self.upper + i - j, j)
+        } else {
+            E::faer_zero()
+        }
+    }
+}
+
+/// upper bandwidth of the reduced matrix after the lower part is eliminated
+fn reduced_bandwidth(n: usize, lower: usize, upper: usize) -> usize {
+    Ord::min(lower + upper, n.saturating_sub(1))
+}
+
+/// band storage of the working matrix, with room for one bulge on each side of the band
+struct Workspace<'a, E: Entity> {
+    data: MatMut<'a, E>,
+    upper: usize,
+}
+
+impl<E: RealField> Workspace<'_, E> {
+    #[inline]
+    fn read(&self, i: usize, j: usize) -> E {
+        self.data.read(self.upper + i - j, j)
+    }
+
+    #[inline]
+    fn write(&mut self, i: usize, j: usize, value: E) {
+        self.data.write(self.upper + i - j, j, value)
+    }
+
+    /// applies `rot` to the rows `i` and `i + 1` of the columns in `cols`
+    fn rotate_rows(&mut self, rot: JacobiRotation<E>, i: usize, cols: core::ops::Range<usize>) {
+        for j in cols {
+            let x = self.read(i, j);
+            let y = self.read(i + 1, j);
+            self.write(i, j, rot.c.faer_mul(x).faer_sub(rot.s.faer_mul(y)));
+            self.write(i + 1, j, rot.s.faer_mul(x).faer_add(rot.c.faer_mul(y)));
+        }
+    }
+
+    /// applies `rot` to the columns `j` and `j + 1` of the rows in `rows`
+    fn rotate_cols(&mut self, rot: JacobiRotation<E>, j: usize, rows: core::ops::Range<usize>) {
+        for i in rows {
+            let x = self.read(i, j);
+            let y = self.read(i, j + 1);
+            self.write(i, j, rot.c.faer_mul(x).faer_sub(rot.s.faer_mul(y)));
+            self.write(i, j + 1, rot.s.faer_mul(x).faer_add(rot.c.faer_mul(y)));
+        }
+    }
+}
+
+fn accumulate<E: RealField>(mat: Option<MatMut<'_, E>>, rot: JacobiRotation<E>, j: usize) {
+    if let Some(mat) = mat {
+        let (x, y) = mat.subcols_mut(j, 2).split_at_col_mut(1);
+        rot.apply_on_the_right_in_place(x, y);
+    }
+}
+
+/// Computes the size and alignment of required workspace for reducing a banded matrix to
+/// bidiagonal form.
+pub fn banded_to_bidiag_req<E: Entity>(
+    n: usize,
+    lower: usize,
+    upper: usize,
+) -> Result<StackReq, SizeOverflow> {
+    // the working matrix is the transpose of the input, so the bandwidths are swapped
+    let b = reduced_bandwidth(n, upper, lower);
+    temp_mat_req::<E>(b + 2 + Ord::max(upper, 1), n)
+}
+
+/// Reduces the square banded matrix $A$ to a lower bidiagonal matrix $B$ with orthogonal
+/// transformations, such that $A = Q B P^\top$.
+///
+/// The output has the layout expected by
+/// [`compute_bidiag_real_svd`](crate::bidiag_real_svd::compute_bidiag_real_svd): `diag[i]` is
+/// stored at position $(i, i)$ of $B$ and `subdiag[i]` at position $(i + 1, i)$, with
+/// `subdiag[n - 1]` set to zero. The singular values of $A$ are those of $B$, and if
+/// $B = U S V^\top$, then the singular vectors of $A$ are $QU$ and $PV$.
+///
+/// `q` and `p` are optional and overwritten with the orthogonal factors if provided. The
+/// reduction uses $\mathcal{O}(n \cdot w)$ storage and $\mathcal{O}(n^2 \cdot w)$ operations,
+/// with $w$ the total bandwidth, compared to the $\mathcal{O}(n^3)$ operations of a dense
+/// bidiagonalization. Accumulating `q` and `p` adds $\mathcal{O}(n^3)$ operations.
+///
+/// # Panics
+/// Panics if `diag` or `subdiag` don't have length $n$, or if `q` or `p` are provided and don't
+/// have shape $(n, n)$.
+#[track_caller]
+pub fn banded_to_bidiag<E: RealField>(
+    band: BandedMatRef<'_, E>,
+    diag: &mut [E],
+    subdiag: &mut [E],
+    mut q: Option<MatMut<'_, E>>,
+    mut p: Option<MatMut<'_, E>>,
+    stack: PodStack<'_>,
+) {
+    let n = band.dim();
+    assert!(all(diag.len() == n, subdiag.len() == n));
+    if let Some(q) = q.rb() {
+        assert!(all(q.nrows() == n, q.ncols() == n));
+    }
+    if let Some(p) = p.rb() {
+        assert!(all(p.nrows() == n, p.ncols() == n));
+    }
+
+    if let Some(mut q) = q.rb_mut() {
+        q.fill_zero();
+        q.diagonal_mut().column_vector_mut().fill(E::faer_one());
+    }
+    if let Some(mut p) = p.rb_mut() {
+        p.fill_zero();
+        p.diagonal_mut().column_vector_mut().fill(E::faer_one());
+    }
+
+    if n == 0 {
+        return;
+    }
+
+    // we reduce the transpose W = A^T to upper bidiagonal form, W = P B^T Q^T, which is what
+    // gives the lower bidiagonal layout for A. left rotations applied to W are accumulated in
+    // `p`, and right rotations in `q`.
+    let lower = band.upper_bandwidth();
+    let upper = band.lower_bandwidth();
+    let b = reduced_bandwidth(n, lower, upper);
+
+    let (data, _) = temp_mat_zeroed::<E>(b + 2 + Ord::max(lower, 1), n, stack);
+    let mut w = Workspace { data, upper: b + 1 };
+
+    for j in 0..n {
+        for i in j.saturating_sub(upper)..Ord::min(n, j + lower + 1) {
+            w.write(i, j, band.read(j, i));
+        }
+    }
+
+    // eliminate the lower part, which widens the upper part to `b` diagonals
+    for j in 0..n {
+        for i in (j + 1..Ord::min(n, j + lower + 1)).rev() {
+            let y = w.read(i, j);
+            if y == E::faer_zero() {
+                continue;
+            }
+            let rot = JacobiRotation::make_givens(w.read(i - 1, j), y);
+            w.rotate_rows(rot, i - 1, j..Ord::min(n, i + b));
+            w.write(i, j, E::faer_zero());
+            accumulate(p.rb_mut(), rot, i - 1);
+        }
+    }
+
+    // eliminate the upper part row by row, chasing each bulge down the band
+    for i in 0..n.saturating_sub(2) {
+        for k in (i + 2..Ord::min(n, i + b + 1)).rev() {
+            let y = w.read(i, k);
+            if y == E::faer_zero() {
+                continue;
+            }
+            let rot = JacobiRotation::make_givens(w.read(i, k - 1), y);
+            w.rotate_cols(rot, k - 1, i..k + 1);
+            w.write(i, k, E::faer_zero());
+            accumulate(q.rb_mut(), rot, k - 1);
+
+            // bulge at (c + 1, c) below the diagonal
+            let mut c = k - 1;
+            loop {
+                let y = w.read(c + 1, c);
+                if y != E::faer_zero() {
+                    let rot = JacobiRotation::make_givens(w.read(c, c), y);
+                    w.rotate_rows(rot, c, c..Ord::min(n, c + b + 2));
+                    w.write(c + 1, c, E::faer_zero());
+                    accumulate(p.rb_mut(), rot, c);
+                }
+
+                // bulge at (c, c + b + 1) outside the band
+                let k = c + b + 1;
+                if k >= n {
+                    break;
+                }
+                let y = w.read(c, k);
+                if y == E::faer_zero() {
+                    break;
+                }
+                let rot = JacobiRotation::make_givens(w.read(c, k - 1), y);
+                w.rotate_cols(rot, k - 1, c..k + 1);
+                w.write(c, k, E::faer_zero());
+                accumulate(q.rb_mut(), rot, k - 1);
+                c = k - 1;
+            }
+        }
+    }
+
+    for i in 0..n {
+        diag[i] = w.read(i, i);
+        subdiag[i] = if i + 1 < n {
+            w.read(i, i + 1)
+        } else {
+            E::faer_zero()
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        bidiag_real_svd::{bidiag_real_svd_req, compute_bidiag_real_svd},
+        compute_svd, compute_svd_req, ComputeVectors, SvdParams,
+    };
+    use assert_approx_eq::assert_approx_eq;
+    use faer_core::{assert, Mat, Parallelism};
+
+    macro_rules! make_stack {
+        ($req: expr) => {
+            ::dyn_stack::PodStack::new(&mut ::dyn_stack::GlobalPodBuffer::new($req.unwrap()))
+        };
+    }
+
+    const SHAPES: [(usize, usize, usize); 9] = [
+        (1, 0, 0),
+        (2, 1, 1),
+        (5, 4, 4),
+        (10, 0, 3),
+        (10, 3, 0),
+        (20, 1, 1),
+        (40, 3, 2),
+        (40, 2, 5),
+        (100, 4, 4),
+    ];
+
+    fn random_band(n: usize, lower: usize, upper: usize) -> (Mat<f64>, Mat<f64>) {
+        let storage = Mat::from_fn(lower + upper + 1, n, |_, _| rand::random::<f64>());
+        let dense = Mat::from_fn(n, n, |i, j| {
+            BandedMatRef::new(storage.as_ref(), lower, upper).read(i, j)
+        });
+        (storage, dense)
+    }
+
+    #[test]
+    fn test_banded_to_bidiag() {
+        for (n, lower, upper) in SHAPES {
+            let (storage, dense) = random_band(n, lower, upper);
+            let band = BandedMatRef::new(storage.as_ref(), lower, upper);
+
+            let mut diag = vec![f64::NAN; n];
+            let mut subdiag = vec![f64::NAN; n];
+            let mut q = Mat::from_fn(n, n, |_, _| f64::NAN);
+            let mut p = Mat::from_fn(n, n, |_, _| f64::NAN);
+            banded_to_bidiag(
+                band,
+                &mut diag,
+                &mut subdiag,
+                Some(q.as_mut()),
+                Some(p.as_mut()),
+                make_stack!(banded_to_bidiag_req::<f64>(n, lower, upper)),
+            );
+            assert!(subdiag[n - 1] == 0.0);
+
+            let bidiag = Mat::from_fn(n, n, |i, j| {
+                if i == j {
+                    diag[j]
+                } else if i == j + 1 {
+                    subdiag[j]
+                } else {
+                    0.0
+                }
+            });
+            let reconstructed = &q * &bidiag * p.transpose();
+            let q_orth = q.transpose() * &q;
+            let p_orth = p.transpose() * &p;
+            for j in 0..n {
+                for i in 0..n {
+                    let id = if i == j { 1.0 } else { 0.0 };
+                    assert_approx_eq!(reconstructed.read(i, j), dense.read(i, j), 1e-12);
+                    assert_approx_eq!(q_orth.read(i, j), id, 1e-12);
+                    assert_approx_eq!(p_orth.read(i, j), id, 1e-12);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_banded_svd_matches_dense() {
+        for (n, lower, upper) in SHAPES {
+            let (storage, dense) = random_band(n, lower, upper);
+            let band = BandedMatRef::new(storage.as_ref(), lower, upper);
+
+            let mut diag = vec![0.0; n];
+            let mut subdiag = vec![0.0; n];
+            banded_to_bidiag(
+                band,
+                &mut diag,
+                &mut subdiag,
+                None,
+                None,
+                make_stack!(banded_to_bidiag_req::<f64>(n, lower, upper)),
+            );
+            compute_bidiag_real_svd(
+                &mut diag,
+                &mut subdiag,
+                None,
+                None,
+                4,
+                128,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(bidiag_real_svd_req::<f64>(
+                    n,
+                    4,
+                    false,
+                    false,
+                    Parallelism::None
+                )),
+                SvdParams::default(),
+            );
+
+            let mut s = Mat::zeros(n, 1);
+            compute_svd(
+                dense.as_ref(),
+                s.as_mut(),
+                None,
+                None,
+                Parallelism::None,
+                make_stack!(compute_svd_req::<f64>(
+                    n,
+                    n,
+                    ComputeVectors::No,
+                    ComputeVectors::No,
+                    Parallelism::None,
+                    SvdParams::default(),
+                )),
+                SvdParams::default(),
+            );
+
+            diag.sort_by(|a, b| b.partial_cmp(a).unwrap());
+            for (i, &d) in diag.iter().enumerate() {
+                assert_approx_eq!(d, s.read(i, 0), 1e-10);
+            }
+        }
+    }
+}
@@ -12,17 +12,40 @@
 // Public License v. 2.0. If a copy of the MPL was not distributed
 // with this file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use crate::jacobi::{jacobi_svd, Skip};
+use crate::{
+    jacobi::{jacobi_svd_with_perm, Skip},
+    ConvergenceCriterion, MergeParallelism, SecularBracketMode, ShiftStrategy, SplitStrategy,
+    SvdAlgorithm, SvdParams, PARALLEL_THRESHOLD,
+};
+use alloc::vec::Vec;
 use coe::Coerce;
 use core::{iter::zip, mem::swap};
 use dyn_stack::{PodStack, SizeOverflow, StackReq};
 use faer_core::{
-    assert, group_helpers::SimdFor, jacobi::JacobiRotation, join_raw, temp_mat_req,
-    temp_mat_uninit, temp_mat_zeroed, unzipped, zipped, ComplexField, Conj, Entity, MatMut, MatRef,
-    Parallelism, RealField,
+    assert,
+    group_helpers::{SimdFor, SliceGroupMut, Write},
+    jacobi::JacobiRotation,
+    join_raw, temp_mat_req, temp_mat_uninit, temp_mat_zeroed, unzipped, zipped, Col, ColRef,
+    ComplexField, Conj, Entity, MatMut, MatRef, Parallelism, RealField,
 };
+use faer_entity::SimdGroupFor;
 use reborrow::*;
 
+/// checks an internal invariant of the algorithm. these checks sit in hot loops, so they're only
+/// enabled in debug builds, or when the `svd-invariant-checks` feature is enabled
+macro_rules! check_invariant {
+    ($cond: expr $(,)?) => {
+        if cfg!(any(debug_assertions, feature = "svd-invariant-checks")) {
+            assert!($cond);
+        }
+    };
+    ($cond: expr, $($arg: tt)+) => {
+        if cfg!(any(debug_assertions, feature = "svd-invariant-checks")) {
+            assert!($cond, $($arg)+);
+        }
+    };
+}
+
 #[allow(dead_code)]
 fn bidiag_to_mat<E: RealField>(diag: &[E], subdiag: &[E]) -> faer_core::Mat<E> {
     let mut mat = faer_core::Mat::<E>::zeros(diag.len() + 1, diag.len());
@@ -41,23 +64,84 @@ fn norm<E: RealField>(v: MatRef<'_, E>) -> E {
     faer_core::mul::inner_prod::inner_prod_with_conj(v, Conj::No, v, Conj::No).faer_sqrt()
 }
 
+/// multiplies the contiguous column vector `x` by `factor`
+fn scale_in_place<E: RealField>(x: MatMut<'_, E>, factor: E) {
+    struct Impl<'a, E: RealField> {
+        x: MatMut<'a, E>,
+        factor: E,
+    }
+
+    impl<E: RealField> pulp::WithSimd for Impl<'_, E> {
+        type Output = ();
+
+        #[inline(always)]
+        fn with_simd<S: pulp::Simd>(self, simd: S) -> Self::Output {
+            let Self { mut x, factor } = self;
+
+            let simd = SimdFor::<E, S>::new(simd);
+            let x = SliceGroupMut::<'_, E>::new(x.rb_mut().try_get_contiguous_col_mut(0));
+            let offset = simd.align_offset(x.rb());
+            let (x_head, x_body, x_tail) = simd.as_aligned_simd_mut(x, offset);
+            let factor = simd.splat(factor);
+
+            #[inline(always)]
+            fn process<E: RealField, S: pulp::Simd>(
+                simd: SimdFor<E, S>,
+                mut x: impl Write<Output = SimdGroupFor<E, S>>,
+                factor: SimdGroupFor<E, S>,
+            ) {
+                let zero = simd.splat(E::faer_zero());
+                x.write(simd.mul(factor, x.read_or(zero)));
+            }
+
+            process(simd, x_head, factor);
+            for x in x_body.into_mut_iter() {
+                process(simd, x, factor);
+            }
+            process(simd, x_tail, factor);
+        }
+    }
+
+    assert!(x.ncols() == 1);
+    assert!(x.row_stride() == 1);
+
+    use faer_entity::SimdCtx;
+    E::Simd::default().dispatch(Impl { x, factor });
+}
+
 fn compute_svd_of_m<E: RealField>(
     mut um: Option<MatMut<'_, E>>,
     mut vm: Option<MatMut<'_, E>>,
+    smallest_only: bool,
     diag: &mut [E],
     col0: &[E],
     outer_perm: &[usize],
+    col_perm_out: Option<&mut [usize]>,
+    range: Option<(E, E)>,
     epsilon: E,
-    _consider_zero_threshold: E,
+    refine_iters: usize,
+    bracket_mode: SecularBracketMode,
+    initial_guess: Option<&[E]>,
+    consider_zero_threshold: E,
+    parallelism: Parallelism,
     stack: PodStack<'_>,
-) {
+) -> BidiagSvdStats {
     let n = diag.len();
 
     diag[0] = E::faer_zero();
     let mut actual_n = n;
+    let mut inconsistent_inputs = 0;
     while actual_n > 1 && diag[actual_n - 1] == E::faer_zero() {
         actual_n -= 1;
-        assert!(col0[actual_n] == E::faer_zero());
+        // the trailing zeros of `diag` belong to deflated singular values, whose entries of `col0`
+        // have been zeroed. in release builds, the malformed input is reported in the statistics
+        check_invariant!(
+            col0[actual_n] == E::faer_zero(),
+            "`col0[{actual_n}]` must be zero, since the corresponding entry of `diag` is zero",
+        );
+        if col0[actual_n] != E::faer_zero() {
+            inconsistent_inputs += 1;
+        }
     }
 
     let (perm, stack) = stack.collect(
@@ -86,16 +170,23 @@ fn compute_svd_of_m<E: RealField>(
     let mut s = singular_vals.col_mut(0);
     let mut zhat = zhat.col_mut(0);
 
-    compute_singular_values(
+    let mut stats = compute_singular_values(
         shifts.rb_mut().as_2d_mut(),
         mus.rb_mut().as_2d_mut(),
         s.rb_mut().as_2d_mut(),
+        0,
         diag,
         diag_perm,
         col0,
         col0_perm,
+        range,
         epsilon,
+        refine_iters,
+        bracket_mode,
+        initial_guess,
+        parallelism,
     );
+    stats.inconsistent_inputs += inconsistent_inputs;
     perturb_col0(
         zhat.rb_mut().as_2d_mut(),
         col0,
@@ -107,18 +198,9 @@ fn compute_svd_of_m<E: RealField>(
     );
 
     let (col_perm, stack) = stack.make_with(actual_n, |i| i);
-    let (col_perm_inv, _) = stack.make_with(actual_n, |i| i);
+    let (col_perm_inv, stack) = stack.make_with(actual_n, |i| i);
 
-    for i in 0..actual_n - 1 {
-        if s.read(i) > s.read(i + 1) {
-            let si = s.read(i);
-            let sj = s.read(i + 1);
-            s.write(i, sj);
-            s.write(i + 1, si);
-
-            col_perm.swap(i, i + 1);
-        }
-    }
+    sort_singular_values(s.rb().as_2d(), col_perm);
     for (i, p) in col_perm.iter().copied().enumerate() {
         col_perm_inv[p] = i;
     }
@@ -126,6 +208,7 @@ fn compute_svd_of_m<E: RealField>(
     compute_singular_vectors(
         um.rb_mut(),
         vm.rb_mut(),
+        smallest_only,
         zhat.rb().as_2d(),
         diag,
         perm,
@@ -134,21 +217,55 @@ fn compute_svd_of_m<E: RealField>(
         actual_n,
         shifts.rb().as_2d(),
         mus.rb().as_2d(),
+        consider_zero_threshold,
     );
 
     for (idx, diag) in diag[..actual_n].iter_mut().enumerate() {
-        *diag = s.read(actual_n - idx - 1);
+        *diag = s.read(col_perm[actual_n - idx - 1]);
     }
 
     for (idx, diag) in diag[actual_n..n].iter_mut().enumerate() {
         *diag = s.read(actual_n + idx);
     }
+
+    // `col_perm_out` maps each column of m to the index it originates from. compose it with the
+    // deflation permutation and the final sort so that it is indexed by the output position
+    if let Some(col_perm_out) = col_perm_out {
+        let (src, _) = stack.collect(col_perm_out.iter().copied());
+        for (idx, p) in col_perm_out[..actual_n].iter_mut().enumerate() {
+            *p = src[outer_perm[col_perm[actual_n - idx - 1]]];
+        }
+        for (idx, p) in col_perm_out[actual_n..n].iter_mut().enumerate() {
+            *p = src[outer_perm[actual_n + idx]];
+        }
+    }
+
+    stats
+}
+
+/// sorts the indices in `col_perm` so that the corresponding entries of the column vector `s`
+/// are in nondecreasing order, leaving `s` unchanged
+///
+/// the root of each entry that isn't deflated lies between its entry of the sorted diagonal and
+/// the next entry that isn't deflated, so the roots are sorted among themselves, but a root can
+/// be larger than the deflated entries that lie in its interval. rounding errors, or a root that
+/// failed to converge, can also break the order, so this is a full sort. ties are broken by index
+/// so that the order is deterministic, and the NaN values of the roots that diverged are moved to
+/// the end
+fn sort_singular_values<E: RealField>(s: MatRef<'_, E>, col_perm: &mut [usize]) {
+    col_perm.sort_unstable_by(|&i, &j| {
+        let (si, sj) = (s.read(i, 0), s.read(j, 0));
+        si.partial_cmp(&sj)
+            .unwrap_or_else(|| si.faer_is_nan().cmp(&sj.faer_is_nan()))
+            .then(i.cmp(&j))
+    });
 }
 
 #[inline(never)]
 fn compute_singular_vectors<E: RealField>(
     mut um: Option<MatMut<E>>,
     mut vm: Option<MatMut<E>>,
+    smallest_only: bool,
     zhat: MatRef<E>,
     diag: &[E],
     perm: &[usize],
@@ -157,6 +274,7 @@ fn compute_singular_vectors<E: RealField>(
     actual_n: usize,
     shifts: MatRef<E>,
     mus: MatRef<E>,
+    consider_zero_threshold: E,
 ) {
     let n = diag.len();
 
@@ -166,10 +284,23 @@ fn compute_singular_vectors<E: RealField>(
         } else {
             actual_n - col_perm_inv[k] - 1
         };
+        // when only the vectors of the smallest singular value are requested, `um` and `vm` have
+        // a single column, which stands for the last one of the full matrices
+        let actual_k = if smallest_only {
+            if actual_k != n - 1 {
+                continue;
+            }
+            0
+        } else {
+            actual_k
+        };
         let mut u = um.rb_mut().map(|u| u.col_mut(actual_k));
         let mut v = vm.rb_mut().map(|v| v.col_mut(actual_k));
 
-        if zhat.read(k, 0) == E::faer_zero() {
+        // the singular vectors of the deflated entries are columns of the identity. this is also
+        // the case of every singular vector when all of `col0` was deflated, in which case `perm`
+        // is empty and can't be indexed below
+        if zhat.read(k, 0) == E::faer_zero() || perm.is_empty() {
             if let Some(mut u) = u.rb_mut() {
                 u.write(outer_perm[k], E::faer_one());
             }
@@ -182,10 +313,34 @@ fn compute_singular_vectors<E: RealField>(
         let mu = mus.read(k, 0);
         let shift = shifts.read(k, 0);
 
-        assert_eq!(zhat.row_stride(), 1);
+        check_invariant!(zhat.row_stride() == 1);
+
+        // when the singular value coincides with an entry of `diag` up to rounding, the
+        // denominators of the corresponding entries underflow, or vanish, and the vectors overflow.
+        // they are then computed again with the denominators clamped away from zero, and divided
+        // by the smallest ones first. this keeps every factor bounded by one, without changing the
+        // direction of the vectors
+        let scaled_factor = || {
+            let mut min_sub = E::faer_zero().faer_inv();
+            let mut min_add = E::faer_zero().faer_inv();
+            for &i in perm {
+                let (sub, add) = clamped_denominators(diag[i], shift, mu, consider_zero_threshold);
+                if sub.faer_abs() < min_sub {
+                    min_sub = sub.faer_abs();
+                }
+                if add.faer_abs() < min_add {
+                    min_add = add.faer_abs();
+                }
+            }
+            let factor = move |i: usize| {
+                let (sub, add) = clamped_denominators(diag[i], shift, mu, consider_zero_threshold);
+                (min_sub.faer_div(sub)).faer_mul(min_add.faer_div(add))
+            };
+            (min_sub.faer_mul(min_add), factor)
+        };
 
         if let Some(mut u) = u.rb_mut() {
-            assert_eq!(u.row_stride(), 1);
+            check_invariant!(u.row_stride() == 1);
             for &i in perm {
                 u.write(
                     outer_perm[i],
@@ -195,13 +350,19 @@ fn compute_singular_vectors<E: RealField>(
                 );
             }
             u.write(n, E::faer_zero());
-            let norm_inv = norm(u.rb().as_2d()).faer_inv();
-            zipped!(u.rb_mut().as_2d_mut())
-                .for_each(|unzipped!(mut x)| x.write(x.read().faer_mul(norm_inv)));
+            let mut norm_u = norm(u.rb().as_2d());
+            if !(norm_u.faer_is_finite() && norm_u > E::faer_zero()) {
+                let (_, factor) = scaled_factor();
+                for &i in perm {
+                    u.write(outer_perm[i], zhat.read(i, 0).faer_mul(factor(i)));
+                }
+                norm_u = norm(u.rb().as_2d());
+            }
+            scale_in_place(u.rb_mut().as_2d_mut(), norm_u.faer_inv());
         }
 
         if let Some(mut v) = v {
-            assert_eq!(v.row_stride(), 1);
+            check_invariant!(v.row_stride() == 1);
             for &i in &perm[1..] {
                 v.write(
                     outer_perm[i],
@@ -212,16 +373,49 @@ fn compute_singular_vectors<E: RealField>(
                 );
             }
             v.write(outer_perm[0], E::faer_one().faer_neg());
-            let norm_inv = norm(v.rb().as_2d()).faer_inv();
-            zipped!(v.rb_mut().as_2d_mut())
-                .for_each(|unzipped!(mut x)| x.write(x.read().faer_mul(norm_inv)));
+            let mut norm_v = norm(v.rb().as_2d());
+            if !(norm_v.faer_is_finite() && norm_v > E::faer_zero()) {
+                let (scale, factor) = scaled_factor();
+                for &i in &perm[1..] {
+                    v.write(
+                        outer_perm[i],
+                        diag[i].faer_mul(zhat.read(i, 0)).faer_mul(factor(i)),
+                    );
+                }
+                // the first entry has no denominator, so it's only multiplied by the scaling
+                v.write(outer_perm[0], scale.faer_neg());
+                norm_v = norm(v.rb().as_2d());
+            }
+            scale_in_place(v.rb_mut().as_2d_mut(), norm_v.faer_inv());
         }
     }
+    // an economy `um` has no last column
     if let Some(mut um) = um {
-        um.write(n, n, E::faer_one());
+        if um.ncols() == n + 1 {
+            um.write(n, n, E::faer_one());
+        }
     }
 }
 
+/// returns the denominators `diag_i - shift - mu` and `diag_i + shift + mu` of the entries of the
+/// singular vectors, where those of magnitude below `min_den` are replaced by `±min_den`
+#[inline(always)]
+fn clamped_denominators<E: RealField>(diag_i: E, shift: E, mu: E, min_den: E) -> (E, E) {
+    let clamp = |x: E| {
+        if x.faer_abs() >= min_den {
+            x
+        } else if x < E::faer_zero() {
+            min_den.faer_neg()
+        } else {
+            min_den
+        }
+    };
+    (
+        clamp(diag_i.faer_sub(shift).faer_sub(mu)),
+        clamp(diag_i.faer_add(shift.faer_add(mu))),
+    )
+}
+
 fn perturb_col0<E: RealField>(
     mut zhat: MatMut<E>,
     col0: &[E],
@@ -284,29 +478,161 @@ fn perturb_col0<E: RealField>(
     }
 }
 
+/// Returns the first column $\hat z$ of the arrow matrix $\hat M$ whose diagonal is `diag` past
+/// its first entry, and whose singular values are exactly `singular_values`, where those are
+/// computed singular values of the arrow matrix $M$ with first column `col0` and the same
+/// diagonal.
+///
+/// This is the reconstruction of Gu and Eisenstat, based on the Löwner theorem, that the merge
+/// step of the divide and conquer algorithm uses to compute singular vectors that are
+/// orthogonal to working precision. $\hat z$ is close to `col0` when the singular values are
+/// accurate, and the singular vectors are then computed from $\hat M$, whose singular values
+/// are known exactly, instead of $M$.
+///
+/// `diag[0]` is ignored and taken to be zero, and the entries of `diag[1..]` whose entries of
+/// `col0` are nonzero must be nonnegative and sorted in nondecreasing order. For each index `k`
+/// with `col0[k] != 0`, `singular_values[k]` is the singular value between `diag[k]` and the
+/// entry of `diag` at the next index with a nonzero entry of `col0`, or above `diag[k]` for the
+/// last one. It's given as `shifts[k] + mus[k]`, where `shifts[k]` is one of these two entries of
+/// `diag`, and `mus[k]` is computed directly, so that the distances to the poles close to the
+/// singular value are accurate. This is the form in which the secular equation solver of the
+/// divide and conquer algorithm returns them. The other entries of `singular_values`, `shifts`
+/// and `mus` are ignored, and the entries of $\hat z$ are zero where `col0` is zero, and have the
+/// same signs as `col0` elsewhere.
+///
+/// # Panics
+/// Panics if `col0`, `diag`, `singular_values`, `shifts` and `mus` don't all have the same
+/// length.
+#[track_caller]
+pub fn reconstruct_lowner_vector<E: RealField>(
+    col0: ColRef<'_, E>,
+    diag: ColRef<'_, E>,
+    singular_values: ColRef<'_, E>,
+    shifts: ColRef<'_, E>,
+    mus: ColRef<'_, E>,
+) -> Col<E> {
+    let n = diag.nrows();
+    assert!(all(
+        col0.nrows() == n,
+        singular_values.nrows() == n,
+        shifts.nrows() == n,
+        mus.nrows() == n,
+    ));
+
+    let col0 = (0..n).map(|i| col0.read(i)).collect::<Vec<_>>();
+    let diag = (0..n)
+        .map(|i| if i == 0 { E::faer_zero() } else { diag.read(i) })
+        .collect::<Vec<_>>();
+    let perm = (0..n)
+        .filter(|&i| col0[i] != E::faer_zero())
+        .collect::<Vec<_>>();
+
+    let mut zhat = Col::<E>::zeros(n);
+    perturb_col0(
+        zhat.as_mut().as_2d_mut(),
+        &col0,
+        &diag,
+        &perm,
+        singular_values.as_2d(),
+        shifts.as_2d(),
+        mus.as_2d(),
+    );
+    zhat
+}
+
+/// number of singular values below which the secular equation solves aren't split into
+/// separate tasks
+const SECULAR_EQ_PARALLEL_BLOCKSIZE: usize = 32;
+
+/// solves the secular equation for the singular values with indices
+/// `k_offset..k_offset + s.nrows()`, writing the results in `shifts`, `mus` and `s`
 fn compute_singular_values<E: RealField>(
     shifts: MatMut<E>,
     mus: MatMut<E>,
     s: MatMut<E>,
+    k_offset: usize,
     diag: &[E],
     diag_perm: &[E],
     col0: &[E],
     col0_perm: &[E],
+    range: Option<(E, E)>,
     epsilon: E,
-) {
+    refine_iters: usize,
+    bracket_mode: SecularBracketMode,
+    initial_guess: Option<&[E]>,
+    parallelism: Parallelism,
+) -> BidiagSvdStats {
+    // each singular value is computed independently from the others, so the rows can be split
+    // between tasks
+    let count = s.nrows();
+    if parallelism != Parallelism::None && count > SECULAR_EQ_PARALLEL_BLOCKSIZE {
+        let mid = count / 2;
+        let (shifts0, shifts1) = shifts.split_at_row_mut(mid);
+        let (mus0, mus1) = mus.split_at_row_mut(mid);
+        let (s0, s1) = s.split_at_row_mut(mid);
+        let mut stats0 = BidiagSvdStats::default();
+        let mut stats1 = BidiagSvdStats::default();
+        join_raw(
+            |parallelism| {
+                stats0 = compute_singular_values(
+                    shifts0,
+                    mus0,
+                    s0,
+                    k_offset,
+                    diag,
+                    diag_perm,
+                    col0,
+                    col0_perm,
+                    range,
+                    epsilon,
+                    refine_iters,
+                    bracket_mode,
+                    initial_guess,
+                    parallelism,
+                )
+            },
+            |parallelism| {
+                stats1 = compute_singular_values(
+                    shifts1,
+                    mus1,
+                    s1,
+                    k_offset + mid,
+                    diag,
+                    diag_perm,
+                    col0,
+                    col0_perm,
+                    range,
+                    epsilon,
+                    refine_iters,
+                    bracket_mode,
+                    initial_guess,
+                    parallelism,
+                )
+            },
+            parallelism,
+        );
+        stats0 += stats1;
+        return stats0;
+    }
+
     if coe::is_same::<f64, E>() {
         struct ImplF64<'a> {
             shifts: MatMut<'a, f64>,
             mus: MatMut<'a, f64>,
             s: MatMut<'a, f64>,
+            k_offset: usize,
             diag: &'a [f64],
             diag_perm: &'a [f64],
             col0: &'a [f64],
             col0_perm: &'a [f64],
+            range: Option<(f64, f64)>,
             epsilon: f64,
+            refine_iters: usize,
+            bracket_mode: SecularBracketMode,
+            initial_guess: Option<&'a [f64]>,
         }
         impl pulp::WithSimd for ImplF64<'_> {
-            type Output = ();
+            type Output = BidiagSvdStats;
 
             #[inline(always)]
             fn with_simd<S: pulp::Simd>(self, simd: S) -> Self::Output {
@@ -314,14 +640,32 @@ fn compute_singular_values<E: RealField>(
                     shifts,
                     mus,
                     s,
+                    k_offset,
                     diag,
                     diag_perm,
                     col0,
                     col0_perm,
+                    range,
                     epsilon,
+                    refine_iters,
+                    bracket_mode,
+                    initial_guess,
                 } = self;
                 compute_singular_values_generic::<f64>(
-                    simd, shifts, mus, s, diag, diag_perm, col0, col0_perm, epsilon,
+                    simd,
+                    shifts,
+                    mus,
+                    s,
+                    k_offset,
+                    diag,
+                    diag_perm,
+                    col0,
+                    col0_perm,
+                    range,
+                    epsilon,
+                    refine_iters,
+                    bracket_mode,
+                    initial_guess,
                 )
             }
         }
@@ -330,25 +674,35 @@ fn compute_singular_values<E: RealField>(
             shifts: shifts.coerce(),
             mus: mus.coerce(),
             s: s.coerce(),
+            k_offset,
             diag: diag.coerce(),
             diag_perm: diag_perm.coerce(),
             col0: col0.coerce(),
             col0_perm: col0_perm.coerce(),
+            range: range.map(|(lo, hi)| (coe::coerce_static(lo), coe::coerce_static(hi))),
             epsilon: coe::coerce_static(epsilon),
-        });
+            refine_iters,
+            bracket_mode,
+            initial_guess: initial_guess.map(|guess| guess.coerce()),
+        })
     } else if coe::is_same::<f32, E>() {
         struct ImplF32<'a> {
             shifts: MatMut<'a, f32>,
             mus: MatMut<'a, f32>,
             s: MatMut<'a, f32>,
+            k_offset: usize,
             diag: &'a [f32],
             diag_perm: &'a [f32],
             col0: &'a [f32],
             col0_perm: &'a [f32],
+            range: Option<(f32, f32)>,
             epsilon: f32,
+            refine_iters: usize,
+            bracket_mode: SecularBracketMode,
+            initial_guess: Option<&'a [f32]>,
         }
         impl pulp::WithSimd for ImplF32<'_> {
-            type Output = ();
+            type Output = BidiagSvdStats;
 
             #[inline(always)]
             fn with_simd<S: pulp::Simd>(self, simd: S) -> Self::Output {
@@ -356,14 +710,32 @@ fn compute_singular_values<E: RealField>(
                     shifts,
                     mus,
                     s,
+                    k_offset,
                     diag,
                     diag_perm,
                     col0,
                     col0_perm,
+                    range,
                     epsilon,
+                    refine_iters,
+                    bracket_mode,
+                    initial_guess,
                 } = self;
                 compute_singular_values_generic::<f32>(
-                    simd, shifts, mus, s, diag, diag_perm, col0, col0_perm, epsilon,
+                    simd,
+                    shifts,
+                    mus,
+                    s,
+                    k_offset,
+                    diag,
+                    diag_perm,
+                    col0,
+                    col0_perm,
+                    range,
+                    epsilon,
+                    refine_iters,
+                    bracket_mode,
+                    initial_guess,
                 )
             }
         }
@@ -372,24 +744,34 @@ fn compute_singular_values<E: RealField>(
             shifts: shifts.coerce(),
             mus: mus.coerce(),
             s: s.coerce(),
+            k_offset,
             diag: diag.coerce(),
             diag_perm: diag_perm.coerce(),
             col0: col0.coerce(),
             col0_perm: col0_perm.coerce(),
+            range: range.map(|(lo, hi)| (coe::coerce_static(lo), coe::coerce_static(hi))),
             epsilon: coe::coerce_static(epsilon),
-        });
+            refine_iters,
+            bracket_mode,
+            initial_guess: initial_guess.map(|guess| guess.coerce()),
+        })
     } else {
         compute_singular_values_generic(
             pulp::Scalar::new(),
             shifts,
             mus,
             s,
+            k_offset,
             diag,
             diag_perm,
             col0,
             col0_perm,
+            range,
             epsilon,
-        );
+            refine_iters,
+            bracket_mode,
+            initial_guess,
+        )
     }
 }
 
@@ -399,15 +781,32 @@ fn compute_singular_values_generic<E: RealField>(
     mut shifts: MatMut<E>,
     mut mus: MatMut<E>,
     mut s: MatMut<E>,
+    k_offset: usize,
     diag: &[E],
     diag_perm: &[E],
     col0: &[E],
     col0_perm: &[E],
+    range: Option<(E, E)>,
     epsilon: E,
-) {
+    refine_iters: usize,
+    bracket_mode: SecularBracketMode,
+    initial_guess: Option<&[E]>,
+) -> BidiagSvdStats {
     simd.vectorize(
         #[inline(always)]
         || {
+            let mut stats = BidiagSvdStats::default();
+
+            let evaluations = core::cell::Cell::new(0usize);
+            let eval_secular_eq = |mu: E, shift: E| {
+                evaluations.set(evaluations.get() + 1);
+                secular_eq(mu, col0_perm, diag_perm, shift)
+            };
+            let eval_secular_eq_derivative = |mu: E, shift: E| {
+                evaluations.set(evaluations.get() + 1);
+                secular_eq_derivative(mu, col0_perm, diag_perm, shift)
+            };
+
             let n = diag.len();
             let mut actual_n = n;
             while actual_n > 1 && col0[actual_n - 1] == E::faer_zero() {
@@ -421,26 +820,29 @@ fn compute_singular_values_generic<E: RealField>(
                 .faer_scale_power_of_two(two);
             let one_half = two.faer_inv();
 
-            'kth_value: for k in 0..n {
-                s.write(k, 0, E::faer_zero());
-                shifts.write(k, 0, E::faer_zero());
-                mus.write(k, 0, E::faer_zero());
+            // upper bound of the largest singular value, only needed by the task that computes it
+            let last_right = if (k_offset..k_offset + s.nrows()).contains(&(actual_n - 1)) {
+                diag[actual_n - 1].faer_add(norm_l2_scaled(col0))
+            } else {
+                E::faer_zero()
+            };
+
+            'kth_value: for k in k_offset..k_offset + s.nrows() {
+                s.write(k - k_offset, 0, E::faer_zero());
+                shifts.write(k - k_offset, 0, E::faer_zero());
+                mus.write(k - k_offset, 0, E::faer_zero());
 
                 if col0[k] == E::faer_zero() || actual_n == 1 {
-                    s.write(k, 0, if k == 0 { col0[0] } else { diag[k] });
-                    shifts.write(k, 0, s.read(k, 0));
-                    mus.write(k, 0, E::faer_zero());
+                    s.write(k - k_offset, 0, if k == 0 { col0[0] } else { diag[k] });
+                    shifts.write(k - k_offset, 0, s.read(k - k_offset, 0));
+                    mus.write(k - k_offset, 0, E::faer_zero());
                     continue 'kth_value;
                 }
 
                 let last_k = k == actual_n - 1;
                 let left = diag[k];
                 let right = if last_k {
-                    let mut norm2 = E::faer_zero();
-                    for &x in col0 {
-                        norm2 = norm2.faer_add(x.faer_mul(x));
-                    }
-                    diag[actual_n - 1].faer_add(norm2.faer_sqrt())
+                    last_right
                 } else {
                     let mut l = k + 1;
                     while col0[l] == E::faer_zero() {
@@ -449,6 +851,20 @@ fn compute_singular_values_generic<E: RealField>(
                     diag[l]
                 };
 
+                // the singular value lies in `[left, right]`, so it can be skipped if that
+                // interval doesn't intersect the requested range. `left` is then used as a lower
+                // bound that also lies outside of the range
+                if let Some((lo, hi)) = range {
+                    if right < lo || left > hi {
+                        s.write(k - k_offset, 0, left);
+                        shifts.write(k - k_offset, 0, left);
+                        mus.write(k - k_offset, 0, E::faer_zero());
+                        continue 'kth_value;
+                    }
+                }
+
+                stats.secular_eq_solves += 1;
+
                 let mid = left.faer_add(right.faer_sub(left).faer_scale_power_of_two(one_half));
                 let [mut f_mid, f_max, f_mid_left_shift, f_mid_right_shift] = secular_eq_multi_fast(
                     [
@@ -465,6 +881,7 @@ fn compute_singular_values_generic<E: RealField>(
                     diag_perm,
                     [E::faer_zero(), left, left, right],
                 );
+                evaluations.set(evaluations.get() + 4);
 
                 let mut shift = if last_k || f_mid > E::faer_zero() {
                     left
@@ -534,7 +951,7 @@ fn compute_singular_values_generic<E: RealField>(
                                 .faer_div(mu_prev.faer_sub(mu_cur));
                             let b = f_cur.faer_sub(a.faer_div(mu_cur));
                             let mu_zero = a.faer_div(b).faer_neg();
-                            let f_zero = secular_eq(mu_zero, col0_perm, diag_perm, shift);
+                            let f_zero = eval_secular_eq(mu_zero, shift);
 
                             if f_zero < E::faer_zero() {
                                 left_candidate = Some(mu_zero);
@@ -568,8 +985,7 @@ fn compute_singular_values_generic<E: RealField>(
                                 for _ in 0..4 {
                                     let mu_opposite =
                                         a.faer_neg().faer_div(k.faer_mul(f_zero).faer_add(b));
-                                    let f_opposite =
-                                        secular_eq(mu_opposite, col0_perm, diag_perm, shift);
+                                    let f_opposite = eval_secular_eq(mu_opposite, shift);
                                     if f_zero < E::faer_zero() && f_opposite >= E::faer_zero() {
                                         // this will be our right candidate
                                         right_candidate = Some(mu_opposite);
@@ -613,14 +1029,135 @@ fn compute_singular_values_generic<E: RealField>(
                         )
                     };
 
-                assert!(
-                    PartialOrd::partial_cmp(&f_left, &E::faer_zero())
-                        != Some(core::cmp::Ordering::Greater)
-                );
-                assert!(
-                    PartialOrd::partial_cmp(&f_right, &E::faer_zero())
-                        != Some(core::cmp::Ordering::Less)
-                );
+                // the secular equation is increasing on the interval, so it must be nonpositive at
+                // the left end of the bracket and nonnegative at the right end. the evaluations
+                // above can disagree with each other when they are dominated by rounding errors,
+                // e.g. for tiny entries of `col0` that escaped deflation, in which case the
+                // bracket is widened to the whole interval
+                let f_left_ok = f_left <= E::faer_zero();
+                let f_right_ok = f_right >= E::faer_zero();
+                if !(f_left_ok && f_right_ok) {
+                    if bracket_mode == SecularBracketMode::Strict {
+                        panic!(
+                            "the bracket of the secular equation of singular value {k} doesn't \
+                             contain its root"
+                        );
+                    }
+                    stats.bracket_repairs += 1;
+
+                    let width = right.faer_sub(left);
+                    if !f_left_ok {
+                        // the secular equation goes to -inf at the pole `left`
+                        left_shifted = if shift == left {
+                            E::faer_zero()
+                        } else {
+                            width.faer_neg()
+                        };
+                        f_left = E::faer_zero().faer_inv().faer_neg();
+                    }
+                    if !f_right_ok {
+                        if last_k {
+                            // `right` is only an upper bound of the largest singular value, and
+                            // the secular equation has no pole there. the shift is `left`
+                            let mut upper = right_shifted;
+                            let mut f_upper = f_right;
+                            for _ in 0..8 {
+                                upper = upper.faer_add(upper);
+                                f_upper = eval_secular_eq(upper, shift);
+                                if f_upper >= E::faer_zero() {
+                                    break;
+                                }
+                            }
+                            let found = f_upper >= E::faer_zero() && upper.faer_is_finite();
+                            if !found {
+                                stats.secular_divergences += 1;
+                                let nan = E::faer_nan();
+                                s.write(k - k_offset, 0, nan);
+                                shifts.write(k - k_offset, 0, shift);
+                                mus.write(k - k_offset, 0, nan);
+                                continue 'kth_value;
+                            }
+                            right_shifted = upper;
+                            f_right = f_upper;
+                        } else {
+                            // the secular equation goes to +inf at the pole `right`
+                            right_shifted = if shift == left { width } else { E::faer_zero() };
+                            f_right = E::faer_zero().faer_inv();
+                        }
+                    }
+                }
+
+                // newton steps on the converged root, for callers that need more digits than the
+                // secant or bisection tolerance gives. a step is only taken if it stays inside the
+                // interval that contains the root and reduces the residual
+                let refine = |mut mu_cur: E| {
+                    if refine_iters == 0 {
+                        return mu_cur;
+                    }
+                    let (lo, hi) = if shift == left {
+                        (E::faer_zero(), right.faer_sub(left))
+                    } else {
+                        (left.faer_sub(right), E::faer_zero())
+                    };
+                    let mut f_cur = eval_secular_eq(mu_cur, shift);
+                    for _ in 0..refine_iters {
+                        let df = eval_secular_eq_derivative(mu_cur, shift);
+                        if f_cur == E::faer_zero() || df <= E::faer_zero() || !df.faer_is_finite() {
+                            break;
+                        }
+                        let mu_next = mu_cur.faer_sub(f_cur.faer_div(df));
+                        if mu_next <= lo || mu_next >= hi || !mu_next.faer_is_finite() {
+                            break;
+                        }
+                        let f_next = eval_secular_eq(mu_next, shift);
+                        if f_next.faer_abs() >= f_cur.faer_abs() || !f_next.faer_is_finite() {
+                            break;
+                        }
+                        mu_cur = mu_next;
+                        f_cur = f_next;
+                    }
+                    mu_cur
+                };
+
+                // a guess of the root, e.g. a singular value of a nearby matrix, is used as the
+                // starting point of a newton-like step followed by the secant method. the result is
+                // only kept if the guess and all the iterates stay inside the bracket and the
+                // secant method converges, otherwise the root is searched from the bracket alone
+                let warm_start = |guess: E, lo: E, hi: E| -> Option<E> {
+                    let mu_guess = guess.faer_sub(shift);
+                    if !(mu_guess > lo && mu_guess < hi) {
+                        return None;
+                    }
+                    let f_guess = eval_secular_eq(mu_guess, shift);
+                    if f_guess == E::faer_zero() {
+                        return Some(mu_guess);
+                    }
+                    let df = eval_secular_eq_derivative(mu_guess, shift);
+                    // same rational model `a / mu + b` as the secant method, fitted to the value and
+                    // the derivative at the guess
+                    let a = df.faer_mul(mu_guess).faer_mul(mu_guess).faer_neg();
+                    let b = f_guess.faer_sub(a.faer_div(mu_guess));
+                    let mu_next = a.faer_div(b).faer_neg();
+                    if !(df > E::faer_zero() && mu_next > lo && mu_next < hi) {
+                        return None;
+                    }
+                    let f_next = eval_secular_eq(mu_next, shift);
+                    let (use_bisection, mu_cur, _, _, _) =
+                        secant(mu_next, mu_guess, f_next, f_guess);
+                    (!use_bisection && mu_cur > lo && mu_cur < hi).then_some(mu_cur)
+                };
+                if let Some(guess) = initial_guess {
+                    let i = guess.partition_point(|&g| g <= left);
+                    if i < guess.len() && guess[i] < right {
+                        if let Some(mu_cur) = warm_start(guess[i], left_shifted, right_shifted) {
+                            let mu_cur = refine(mu_cur);
+                            s.write(k - k_offset, 0, shift.faer_add(mu_cur));
+                            shifts.write(k - k_offset, 0, shift);
+                            mus.write(k - k_offset, 0, mu_cur);
+                            continue 'kth_value;
+                        }
+                    }
+                }
 
                 let mut iteration_count = 0;
                 let mut f_prev = f_mid;
@@ -660,6 +1197,7 @@ fn compute_singular_values_generic<E: RealField>(
                 };
                 let f_values =
                     secular_eq_multi_fast(mu_values, col0_perm, diag_perm, [(); 8].map(|_| shift));
+                evaluations.set(evaluations.get() + 8);
 
                 if shift == left {
                     let mut i = 0;
@@ -713,12 +1251,12 @@ fn compute_singular_values_generic<E: RealField>(
                     } else {
                         mid_shifted_geometric
                     };
-                    let f_mid = secular_eq(mid_shifted, col0_perm, diag_perm, shift);
+                    let f_mid = eval_secular_eq(mid_shifted, shift);
 
                     if f_mid == E::faer_zero() {
-                        s.write(k, 0, shift.faer_add(mid_shifted));
-                        shifts.write(k, 0, shift);
-                        mus.write(k, 0, mid_shifted);
+                        s.write(k - k_offset, 0, shift.faer_add(mid_shifted));
+                        shifts.write(k - k_offset, 0, shift);
+                        mus.write(k - k_offset, 0, mid_shifted);
                         continue 'kth_value;
                     } else if f_mid > E::faer_zero() {
                         right_shifted = mid_shifted;
@@ -773,6 +1311,7 @@ fn compute_singular_values_generic<E: RealField>(
 
                 // secant failed, use bisection again
                 if use_bisection {
+                    stats.bisection_fallbacks += 1;
                     while (right_shifted.faer_sub(left_shifted))
                         > two.faer_mul(epsilon).faer_mul(
                             if left_shifted.faer_abs() > right_shifted.faer_abs() {
@@ -784,7 +1323,7 @@ fn compute_singular_values_generic<E: RealField>(
                     {
                         let mid_shifted = (left_shifted.faer_add(right_shifted))
                             .faer_scale_power_of_two(one_half);
-                        let f_mid = secular_eq(mid_shifted, col0_perm, diag_perm, shift);
+                        let f_mid = eval_secular_eq(mid_shifted, shift);
 
                         if f_mid == E::faer_zero() {
                             break;
@@ -798,12 +1337,47 @@ fn compute_singular_values_generic<E: RealField>(
                     mu_cur = (left_shifted.faer_add(right_shifted)).faer_mul(one_half);
                 }
 
-                s.write(k, 0, shift.faer_add(mu_cur));
-                shifts.write(k, 0, shift);
-                mus.write(k, 0, mu_cur);
+                let mu_cur = refine(mu_cur);
+
+                s.write(k - k_offset, 0, shift.faer_add(mu_cur));
+                shifts.write(k - k_offset, 0, shift);
+                mus.write(k - k_offset, 0, mu_cur);
             }
+            stats.secular_eq_evaluations += evaluations.get();
+            stats
         },
-    );
+    )
+}
+
+/// euclidean norm of `x`, scaled by its largest entry so that squaring the entries can't overflow
+/// or underflow
+///
+/// the squares are summed with kahan's compensated summation, since this gives the upper end of
+/// the bracket of the largest singular value, and the naive sum can drop many small entries
+/// whose squares are each below the rounding error of the sum
+fn norm_l2_scaled<E: RealField>(x: &[E]) -> E {
+    let mut max = E::faer_zero();
+    for &x in x {
+        let abs = x.faer_abs();
+        if abs > max {
+            max = abs;
+        }
+    }
+    if max == E::faer_zero() || !max.faer_is_finite() {
+        return max;
+    }
+
+    let max_inv = max.faer_inv();
+    let mut norm2 = E::faer_zero();
+    let mut compensation = E::faer_zero();
+    for &x in x {
+        let x = x.faer_mul(max_inv);
+        let term = x.faer_mul(x).faer_sub(compensation);
+        let sum = norm2.faer_add(term);
+        compensation = sum.faer_sub(norm2).faer_sub(term);
+        norm2 = sum;
+    }
+    max.faer_mul(norm2.faer_sqrt())
 }
 
 #[inline(always)]
@@ -828,6 +1402,23 @@ fn secular_eq_multi_fast<const N: usize, E: RealField>(
     res0
 }
 
+/// derivative of [`secular_eq`] with respect to `mu`
+fn secular_eq_derivative<E: RealField>(mu: E, col0_perm: &[E], diag_perm: &[E], shift: E) -> E {
+    // d/dσ (c^2 / ((d - σ) (d + σ))) = 2σ c^2 / ((d - σ) (d + σ))^2, with σ = shift + mu
+    let sigma = shift.faer_add(mu);
+    let two_sigma = sigma.faer_add(sigma);
+    let mut res = E::faer_zero();
+    for (&c, &d) in zip(col0_perm, diag_perm) {
+        let d_minus = d.faer_sub(shift).faer_sub(mu);
+        let d_plus = d.faer_add(shift).faer_add(mu);
+        let term = (c.faer_div(d_minus).faer_div(d_minus))
+            .faer_mul(c.faer_div(d_plus).faer_div(d_plus))
+            .faer_mul(two_sigma);
+        res = res.faer_add(term);
+    }
+    res
+}
+
 #[inline(always)]
 fn secular_eq<E: RealField>(mu: E, col0_perm: &[E], diag_perm: &[E], shift: E) -> E {
     let mut res0 = E::faer_one();
@@ -919,8 +1510,16 @@ fn secular_eq<E: RealField>(mu: E, col0_perm: &[E], diag_perm: &[E], shift: E) -
         );
     }
 
-    ((res0.faer_add(res1)).faer_add(res2.faer_add(res3)))
-        .faer_add((res4.faer_add(res5)).faer_add(res6.faer_add(res7)))
+    E::faer_horizontal_sum([res0, res1, res2, res3, res4, res5, res6, res7])
+}
+
+/// returns the threshold of the condition 4.2 of the deflation relative to the largest entry of
+/// the diagonal, which is `params.col0_flush_threshold`, but no smaller than `epsilon`
+fn col0_flush<E: RealField>(epsilon: E, params: &SvdParams) -> E {
+    match params.col0_flush_threshold {
+        Some(threshold) if E::faer_from_f64(threshold) > epsilon => E::faer_from_f64(threshold),
+        _ => epsilon,
+    }
 }
 
 fn deflate<E: RealField>(
@@ -934,6 +1533,7 @@ fn deflate<E: RealField>(
     perm: &mut [usize],
     k: usize,
     epsilon: E,
+    col0_flush: E,
     consider_zero_threshold: E,
     stack: PodStack<'_>,
 ) -> (usize, usize) {
@@ -964,6 +1564,12 @@ fn deflate<E: RealField>(
     } else {
         &consider_zero_threshold
     };
+    let flush_threshold = col0_flush.faer_mul(max_diag);
+    let flush_threshold = if flush_threshold > consider_zero_threshold {
+        flush_threshold
+    } else {
+        consider_zero_threshold
+    };
 
     let two = E::faer_one().faer_add(E::faer_one());
     let eight = two
@@ -981,9 +1587,10 @@ fn deflate<E: RealField>(
         col0[0] = epsilon_coarse;
     }
 
-    // condition 4.2
+    // condition 4.2, whose threshold can be raised above `epsilon_strict` to deflate more
+    // entries
     for x in &mut col0[1..] {
-        if x.faer_abs() < *epsilon_strict {
+        if x.faer_abs() < flush_threshold {
             *x = E::faer_zero();
         }
     }
@@ -1108,7 +1715,7 @@ fn deflation43<E: RealField>(
 ) -> Option<JacobiRotation<E>> {
     let c = col0[0];
     let s = col0[i];
-    let r = ((c.faer_mul(c)).faer_add(s.faer_mul(s))).faer_sqrt();
+    let r = norm_l2_scaled(&[c, s]);
     if r == E::faer_zero() {
         diag[i] = E::faer_zero();
         return None;
@@ -1136,7 +1743,7 @@ fn deflation44<E: RealField>(
 ) -> Option<JacobiRotation<E>> {
     let c = col0[i];
     let s = col0[j];
-    let r = ((c.faer_mul(c)).faer_add(s.faer_mul(s))).faer_sqrt();
+    let r = norm_l2_scaled(&[c, s]);
     if r == E::faer_zero() {
         diag[i] = diag[j];
         return None;
@@ -1152,18 +1759,105 @@ fn deflation44<E: RealField>(
     Some(rot)
 }
 
+/// Returns the Wilkinson shift of the symmetric $2 \times 2$ matrix
+/// $$\begin{bmatrix} t_{00} & t_{01} \\ t_{01} & t_{11} \end{bmatrix},$$
+/// which is its eigenvalue closest to $t_{11}$.
+///
+/// This is the shift that the implicit QR algorithm of the bidiagonal SVD uses for the trailing
+/// $2 \times 2$ block of $B^\top B$, so that it converges quadratically to the square of the
+/// smallest singular value. The eigenvalue is computed as
+/// $$t_{11} - \frac{t_{01}^2}{d + \operatorname{sign}(d) \sqrt{d^2 + t_{01}^2}},
+/// \quad d = \frac{t_{00} - t_{11}}{2},$$
+/// which doesn't suffer from cancellation when $t_{01}$ is small compared to $d$. When
+/// $t_{01}^2$ is at most `zero_threshold`, the matrix is treated as diagonal and $t_{11}$ is
+/// returned, which also avoids dividing by zero when $t_{00} = t_{11}$.
+pub fn wilkinson_shift_2x2<E: RealField>(t00: E, t11: E, t01: E, zero_threshold: E) -> E {
+    let t01_2 = t01.faer_abs2();
+    if t01_2 > zero_threshold {
+        let d = (t00.faer_sub(t11)).faer_mul(E::faer_from_f64(0.5));
+        let mut delta = d.faer_abs2().faer_add(t01_2).faer_sqrt();
+        if d < E::faer_zero() {
+            delta = delta.faer_neg();
+        }
+
+        t11.faer_sub(t01_2.faer_div(d.faer_add(delta)))
+    } else {
+        t11
+    }
+}
+
+/// returns `true` if the shifted qr sweep on the bidiagonal block with diagonal `diag` and
+/// subdiagonal `subdiag` would lose the relative accuracy of its smallest singular values, either
+/// because they're tiny compared to the largest ones, or because the shift `mu` (which
+/// approximates the square of the smallest singular value) is negligible anyway
+fn use_zero_shift<E: RealField>(diag: &[E], subdiag: &[E], mu: E, epsilon: E) -> bool {
+    let n = diag.len();
+
+    let mut lower = diag[0].faer_abs();
+    let mut smin = lower;
+    let mut smax = lower;
+    for i in 0..n - 1 {
+        let e = subdiag[i].faer_abs();
+        let d = diag[i + 1].faer_abs();
+        lower = d.faer_mul(lower.faer_div(lower.faer_add(e)));
+        if lower < smin {
+            smin = lower;
+        }
+        if e > smax {
+            smax = e;
+        }
+        if d > smax {
+            smax = d;
+        }
+    }
+
+    smin.faer_mul(E::faer_from_f64(100.0 * n as f64)) <= smax
+        || mu.faer_abs() < epsilon.faer_mul(diag[0].faer_abs2())
+}
+
+/// svd of the square bidiagonal matrix of size `n`, by implicitly shifted qr sweeps.
+///
+/// unlike the divide and conquer driver, which decomposes the `(n + 1, n)` matrix, this only
+/// handles the upper `(n, n)` block, so `subdiag[n - 1]` must be zero. the caller rotates the
+/// last row away beforehand with `bidiag_zero_last_row` otherwise.
+///
+/// `v` must have shape `(n, n)`. `u` may have shape `(n, n)`, or the shape `(n + 1, n + 1)` or
+/// `(n + 1, n)` of the divide and conquer driver, in which case the extra row and column are
+/// those of the identity, since the last row of the matrix is zero.
 fn bidiag_svd_qr_algorithm_impl<E: RealField>(
     diag: &mut [E],
     subdiag: &mut [E],
     mut u: Option<MatMut<'_, E>>,
     mut v: Option<MatMut<'_, E>>,
+    perm: Option<&mut [usize]>,
     epsilon: E,
     consider_zero_threshold: E,
-) {
+    high_relative_accuracy: bool,
+    shift_strategy: ShiftStrategy,
+    convergence_criterion: ConvergenceCriterion,
+) -> QrStats {
     let n = diag.len();
-    let max_iter = 30usize.saturating_mul(n).saturating_mul(n);
-
-    let epsilon = epsilon.faer_scale_real(E::faer_from_f64(128.0));
+    assert!(
+        subdiag.len() == n,
+        "`subdiag` must have the same length as `diag`, with its last element in the last row",
+    );
+    assert!(
+        n == 0 || subdiag[n - 1] == E::faer_zero(),
+        "the qr algorithm needs the last row to be zero, so `subdiag[n - 1]` must be zero",
+    );
+    if let Some(u) = u.rb() {
+        assert!(
+            (u.nrows(), u.ncols()) == (n, n)
+                || (u.nrows() == n + 1 && (u.ncols() == n + 1 || u.ncols() == n)),
+            "`u` must have shape `(n, n)`, `(n + 1, n + 1)` or `(n + 1, n)`",
+        );
+    }
+    if let Some(v) = v.rb() {
+        assert!(
+            (v.nrows(), v.ncols()) == (n, n),
+            "`v` must have shape `(n, n)`",
+        );
+    }
 
     if let Some(mut u) = u.rb_mut() {
         u.fill_zero();
@@ -1174,41 +1868,64 @@ fn bidiag_svd_qr_algorithm_impl<E: RealField>(
         v.diagonal_mut().column_vector_mut().fill(E::faer_one());
     }
 
-    u = u.map(|u| u.submatrix_mut(0, 0, n, n));
-    v = v.map(|v| v.submatrix_mut(0, 0, n, n));
-
-    let mut max_val = E::faer_zero();
+    bidiag_svd_qr_algorithm_accumulate(
+        diag,
+        subdiag,
+        u.map(|u| u.submatrix_mut(0, 0, n, n)),
+        v.map(|v| v.submatrix_mut(0, 0, n, n)),
+        perm,
+        epsilon,
+        consider_zero_threshold,
+        high_relative_accuracy,
+        shift_strategy,
+        convergence_criterion,
+    )
+}
 
-    for x in &*diag {
-        let val = x.faer_abs();
-        if val > max_val {
-            max_val = val;
-        }
-    }
-    for x in &*subdiag {
-        let val = x.faer_abs();
-        if val > max_val {
-            max_val = val;
-        }
-    }
+/// same as `bidiag_svd_qr_algorithm_impl`, except that the rotations are applied to the columns
+/// of `u` and `v` as they are, instead of being accumulated from the identity. `u` and `v` must
+/// have `n` columns, and may have any number of rows, so that the rows end up multiplied by the
+/// singular vectors of the first `n` rows of the bidiagonal matrix
+pub(crate) fn bidiag_svd_qr_algorithm_accumulate<E: RealField>(
+    diag: &mut [E],
+    subdiag: &mut [E],
+    mut u: Option<MatMut<'_, E>>,
+    mut v: Option<MatMut<'_, E>>,
+    mut perm: Option<&mut [usize]>,
+    epsilon: E,
+    consider_zero_threshold: E,
+    high_relative_accuracy: bool,
+    shift_strategy: ShiftStrategy,
+    convergence_criterion: ConvergenceCriterion,
+) -> QrStats {
+    let n = diag.len();
+    let max_iter = 30usize.saturating_mul(n).saturating_mul(n);
 
-    let max_val = E::faer_one();
+    let epsilon = epsilon.faer_scale_real(E::faer_from_f64(128.0));
 
-    if max_val == E::faer_zero() {
-        return;
+    if let Some(perm) = perm.as_deref_mut() {
+        for (i, p) in perm.iter_mut().enumerate() {
+            *p = i;
+        }
     }
 
-    for x in &mut *diag {
-        *x = (*x).faer_div(max_val);
-    }
-    for x in &mut *subdiag {
-        *x = (*x).faer_div(max_val);
+    let scale = normalize_bidiagonal(diag, subdiag);
+    if scale == E::faer_zero() {
+        diag.fill(E::faer_zero());
+        return QrStats::default();
     }
+    // the largest entry is only normalized to `(1/2, 1]`, and the entries that are negligible
+    // in absolute terms are those that are negligible compared to it
+    let negligible = epsilon.faer_mul(bidiagonal_max_abs(diag, subdiag));
 
     struct Impl<'a, E: Entity> {
         epsilon: E,
+        negligible: E,
         consider_zero_threshold: E,
         max_iter: usize,
+        high_relative_accuracy: bool,
+        shift_strategy: ShiftStrategy,
+        convergence_criterion: ConvergenceCriterion,
         diag: &'a mut [E],
         subdiag: &'a mut [E],
         u: Option<MatMut<'a, E>>,
@@ -1216,14 +1933,18 @@ fn bidiag_svd_qr_algorithm_impl<E: RealField>(
     }
 
     impl<E: RealField> pulp::WithSimd for Impl<'_, E> {
-        type Output = ();
+        type Output = QrStats;
 
         #[inline(always)]
         fn with_simd<S: pulp::Simd>(self, simd: S) -> Self::Output {
             let Self {
                 epsilon,
+                negligible,
                 consider_zero_threshold,
                 max_iter,
+                high_relative_accuracy,
+                shift_strategy,
+                convergence_criterion,
                 diag,
                 subdiag,
                 mut u,
@@ -1232,64 +1953,117 @@ fn bidiag_svd_qr_algorithm_impl<E: RealField>(
             let n = diag.len();
             let arch = E::Simd::default();
 
-            for iter in 0..max_iter {
-                let _ = iter;
-                for i in 0..n - 1 {
-                    if subdiag[i].faer_abs()
-                        <= epsilon.faer_mul(diag[i].faer_abs().faer_add(diag[i + 1].faer_abs()))
-                        || subdiag[i].faer_abs() <= epsilon
-                    {
-                        subdiag[i] = E::faer_zero();
-                    }
-                }
-                for i in 0..n {
-                    if diag[i].faer_abs() <= epsilon {
-                        diag[i] = E::faer_zero();
-                    }
-                }
+            // the alignment offsets only depend on the addresses of `u` and `v`, which don't
+            // change between sweeps. when a matrix isn't requested, no rotation is ever applied
+            // to it, so its offset is never used
+            let simde = SimdFor::<E, S>::new(simd);
+            let u_offset = simde.align_offset_ptr(
+                u.rb()
+                    .map(|mat| mat.as_ptr())
+                    .unwrap_or(E::faer_map(E::UNIT, |()| core::ptr::null())),
+                u.rb().map(|mat| mat.nrows()).unwrap_or(0),
+            );
+            let v_offset = simde.align_offset_ptr(
+                v.rb()
+                    .map(|mat| mat.as_ptr())
+                    .unwrap_or(E::faer_map(E::UNIT, |()| core::ptr::null())),
+                v.rb().map(|mat| mat.nrows()).unwrap_or(0),
+            );
+
+            let mut stats = QrStats::default();
+            // the trailing entries that were deflated are never touched again, so the end of the
+            // active block only decreases
+            let mut prev_end = n;
+            let mut converged = n <= 1;
+
+            for _ in 0..max_iter {
+                if high_relative_accuracy {
+                    // demmel-kahan criterion, where `lower` is a lower bound on the smallest
+                    // singular value of the block ending at row `i`. this only neglects entries
+                    // that are small relative to the singular values they affect
+                    let mut lower = diag[0].faer_abs();
+                    for i in 0..n - 1 {
+                        if subdiag[i].faer_abs() <= epsilon.faer_mul(lower) {
+                            subdiag[i] = E::faer_zero();
+                        }
+                        lower = if subdiag[i] == E::faer_zero() {
+                            diag[i + 1].faer_abs()
+                        } else {
+                            diag[i + 1]
+                                .faer_abs()
+                                .faer_mul(lower.faer_div(lower.faer_add(subdiag[i].faer_abs())))
+                        };
+                    }
+                } else {
+                    // the entries that are negligible in absolute terms are only neglected by
+                    // the mixed criterion
+                    let absolute =
+                        convergence_criterion == ConvergenceCriterion::RelativeOrAbsolute;
+                    for i in 0..n - 1 {
+                        if subdiag[i].faer_abs()
+                            <= epsilon.faer_mul(diag[i].faer_abs().faer_add(diag[i + 1].faer_abs()))
+                            || (absolute && subdiag[i].faer_abs() <= negligible)
+                        {
+                            subdiag[i] = E::faer_zero();
+                        }
+                    }
+                    if absolute {
+                        for i in 0..n {
+                            if diag[i].faer_abs() <= negligible {
+                                diag[i] = E::faer_zero();
+                            }
+                        }
+                    }
+                }
 
                 let mut end = n;
                 while end > 1 && subdiag[end - 2].faer_abs() <= consider_zero_threshold.faer_sqrt()
                 {
                     end -= 1;
                 }
+                stats.deflations += prev_end - end;
+                prev_end = end;
 
+                // the leading 1x1 block is already diagonal, and is left to the sign fix and the
+                // sort below like the deflated entries, wherever its nonzero value ends up
                 if end == 1 {
+                    converged = true;
                     break;
                 }
+                stats.iterations += 1;
 
                 let mut start = end - 1;
                 while start > 0 && subdiag[start - 1] != E::faer_zero() {
                     start -= 1;
                 }
 
+                // a zero diagonal entry leaves `subdiag[i]`, at `(i + 1, i)`, alone in its
+                // column. it's chased down the diagonal by rotating column `i` with the columns
+                // `j` to its right, each of which only has entries in rows `j` and `j + 1`, so
+                // the rows are never mixed and only `v` is updated
                 let mut found_zero_diag = false;
                 for i in start..end - 1 {
                     if diag[i] == E::faer_zero() {
                         found_zero_diag = true;
+                        stats.deflations += 1;
                         let mut val = subdiag[i];
                         subdiag[i] = E::faer_zero();
                         for j in i + 1..end {
                             let rot = JacobiRotation::make_givens(diag[j], val);
-                            diag[j] = rot
-                                .c
-                                .faer_mul(diag[j])
-                                .faer_sub(rot.s.faer_mul(val))
-                                .faer_abs();
+                            stats.givens_applied += 1;
+                            diag[j] = rot.c.faer_mul(diag[j]).faer_sub(rot.s.faer_mul(val));
 
                             if j < end - 1 {
-                                (val, subdiag[j]) = (
-                                    rot.s.faer_mul(subdiag[j]).faer_neg(),
-                                    rot.c.faer_mul(subdiag[j]),
-                                );
+                                (val, subdiag[j]) =
+                                    (rot.s.faer_mul(subdiag[j]), rot.c.faer_mul(subdiag[j]));
                             }
 
                             if let Some(v) = v.rb_mut() {
                                 unsafe {
                                     rot.apply_on_the_right_in_place_arch(
                                         arch,
-                                        v.rb().col(i).as_2d().const_cast(),
                                         v.rb().col(j).as_2d().const_cast(),
+                                        v.rb().col(i).as_2d().const_cast(),
                                     );
                                 }
                             }
@@ -1313,8 +2087,7 @@ fn bidiag_svd_qr_algorithm_impl<E: RealField>(
                     .faer_add(subdiag[end - 2].faer_abs2());
                 let t01 = diag[end - 2].faer_mul(subdiag[end - 2]);
 
-                let mu;
-                if false {
+                let mu = if shift_strategy == ShiftStrategy::Discriminant {
                     let delta = E::faer_sub(
                         t00.faer_add(t11).faer_abs2(),
                         t00.faer_mul(t11)
@@ -1322,7 +2095,7 @@ fn bidiag_svd_qr_algorithm_impl<E: RealField>(
                             .faer_scale_power_of_two(E::faer_from_f64(4.0)),
                     );
 
-                    mu = if delta > E::faer_zero() {
+                    if delta > E::faer_zero() {
                         let lambda0 = t00
                             .faer_add(t11)
                             .faer_add(delta.faer_sqrt())
@@ -1339,43 +2112,73 @@ fn bidiag_svd_qr_algorithm_impl<E: RealField>(
                         }
                     } else {
                         t11
-                    };
+                    }
                 } else {
-                    let t01_2 = t01.faer_abs2();
-                    if t01_2 > consider_zero_threshold {
-                        let d = (t00.faer_sub(t11)).faer_mul(E::faer_from_f64(0.5));
-                        let mut delta = d.faer_abs2().faer_add(t01_2).faer_sqrt();
-                        if d < E::faer_zero() {
-                            delta = delta.faer_neg();
+                    wilkinson_shift_2x2(t00, t11, t01, consider_zero_threshold)
+                };
+
+                if high_relative_accuracy
+                    && use_zero_shift(&diag[start..end], &subdiag[start..end - 1], mu, epsilon)
+                {
+                    // demmel-kahan zero shift sweep, which computes every entry of the updated
+                    // matrix to high relative accuracy, so that tiny singular values aren't
+                    // swamped by the roundoff of the shift
+                    let mut cs = E::faer_one();
+                    let mut old_cs = E::faer_one();
+                    let mut old_sn = E::faer_zero();
+                    stats.givens_applied += 2 * (end - 1 - start);
+                    for k in start..end - 1 {
+                        let p = diag[k].faer_mul(cs);
+                        let q = subdiag[k];
+                        let rot = JacobiRotation::make_givens(p, q);
+                        let r = rot.c.faer_mul(p).faer_sub(rot.s.faer_mul(q));
+                        cs = rot.c;
+                        let sn = rot.s.faer_neg();
+                        if k > start {
+                            subdiag[k - 1] = old_sn.faer_mul(r);
+                        }
+                        if let Some(u) = u.rb_mut() {
+                            unsafe {
+                                rot.apply_on_the_right_in_place_with_simd_and_offset(
+                                    simd,
+                                    u_offset,
+                                    u.rb().col(k).as_2d().const_cast(),
+                                    u.rb().col(k + 1).as_2d().const_cast(),
+                                );
+                            }
                         }
 
-                        mu = t11.faer_sub(t01_2.faer_div(d.faer_add(delta)));
-                    } else {
-                        mu = t11
+                        let p = old_cs.faer_mul(r);
+                        let q = diag[k + 1].faer_mul(sn);
+                        let rot = JacobiRotation::make_givens(p, q);
+                        diag[k] = rot.c.faer_mul(p).faer_sub(rot.s.faer_mul(q));
+                        old_cs = rot.c;
+                        old_sn = rot.s.faer_neg();
+                        if let Some(v) = v.rb_mut() {
+                            unsafe {
+                                rot.apply_on_the_right_in_place_with_simd_and_offset(
+                                    simd,
+                                    v_offset,
+                                    v.rb().col(k).as_2d().const_cast(),
+                                    v.rb().col(k + 1).as_2d().const_cast(),
+                                );
+                            }
+                        }
                     }
+                    let h = diag[end - 1].faer_mul(cs);
+                    diag[end - 1] = h.faer_mul(old_cs);
+                    subdiag[end - 2] = h.faer_mul(old_sn);
+                    continue;
                 }
 
                 let mut y = diag[start].faer_abs2().faer_sub(mu);
                 let mut z = diag[start].faer_mul(subdiag[start]);
 
-                let simde = SimdFor::<E, S>::new(simd);
-                let u_offset = simde.align_offset_ptr(
-                    u.rb()
-                        .map(|mat| mat.as_ptr())
-                        .unwrap_or(E::faer_map(E::UNIT, |()| core::ptr::null())),
-                    diag.len(),
-                );
-                let v_offset = simde.align_offset_ptr(
-                    v.rb()
-                        .map(|mat| mat.as_ptr())
-                        .unwrap_or(E::faer_map(E::UNIT, |()| core::ptr::null())),
-                    diag.len(),
-                );
-
+                stats.givens_applied += 2 * (end - 1 - start);
                 for k in start..end - 1 {
                     let rot = JacobiRotation::make_givens(y, z);
                     if k > start {
-                        subdiag[k - 1] = rot.c.faer_mul(y).faer_sub(rot.s.faer_mul(z)).faer_abs();
+                        subdiag[k - 1] = rot.c.faer_mul(y).faer_sub(rot.s.faer_mul(z));
                     }
 
                     let mut diag_k = diag[k];
@@ -1408,7 +2211,7 @@ fn bidiag_svd_qr_algorithm_impl<E: RealField>(
 
                     let rot = JacobiRotation::make_givens(y, z);
 
-                    diag_k = rot.c.faer_mul(y).faer_sub(rot.s.faer_mul(z)).faer_abs();
+                    diag_k = rot.c.faer_mul(y).faer_sub(rot.s.faer_mul(z));
                     diag[k] = diag_k;
                     (subdiag[k], diag[k + 1]) = (
                         simde.scalar_mul_add_e(
@@ -1443,14 +2246,24 @@ fn bidiag_svd_qr_algorithm_impl<E: RealField>(
                     }
                 }
             }
+
+            if !converged {
+                stats.unconverged += prev_end;
+            }
+
+            stats
         }
     }
 
     use faer_entity::SimdCtx;
-    E::Simd::default().dispatch(Impl {
+    let stats = E::Simd::default().dispatch(Impl {
         epsilon,
+        negligible,
         consider_zero_threshold,
         max_iter,
+        high_relative_accuracy,
+        shift_strategy,
+        convergence_criterion,
         diag,
         subdiag,
         u: u.rb_mut(),
@@ -1461,10 +2274,14 @@ fn bidiag_svd_qr_algorithm_impl<E: RealField>(
         if *d < E::faer_zero() {
             *d = d.faer_neg();
             if let Some(mut v) = v.rb_mut() {
-                for i in 0..n {
+                for i in 0..v.nrows() {
                     v.write(i, j, v.read(i, j).faer_neg());
                 }
             }
+        } else if *d == E::faer_zero() {
+            // negative zeros compare equal to zero, so they aren't flipped by the branch above,
+            // but would keep their sign through the scaling
+            *d = E::faer_zero();
         }
     }
 
@@ -1486,261 +2303,2620 @@ fn bidiag_svd_qr_algorithm_impl<E: RealField>(
             if let Some(v) = v.rb_mut() {
                 faer_core::permutation::swap_cols(v, k, max_idx);
             }
+            if let Some(perm) = perm.as_deref_mut() {
+                perm.swap(k, max_idx);
+            }
         }
     }
 
-    for x in &mut *diag {
-        *x = (*x).faer_mul(max_val);
+    denormalize(diag, scale);
+
+    stats
+}
+
+/// returns `true` if two distinct positions of `mat` refer to the same memory location
+fn has_self_overlap<E: Entity>(mat: MatRef<'_, E>) -> bool {
+    let m = mat.nrows() as isize;
+    let n = mat.ncols() as isize;
+    if m == 0 || n == 0 {
+        return false;
+    }
+
+    let rs = mat.row_stride();
+    let cs = mat.col_stride();
+    if (m > 1 && rs == 0) || (n > 1 && cs == 0) {
+        return true;
     }
+
+    // (i0, j0) and (i1, j1) overlap iff (i1 - i0) * rs == (j0 - j1) * cs
+    (1..n).any(|dj| {
+        let offset = dj.wrapping_mul(cs);
+        rs != 0 && offset % rs == 0 && (offset / rs).abs() < m
+    })
 }
 
-/// svd of bidiagonal lower matrix of shape (n + 1, n), with the last row being all zeros
-pub fn compute_bidiag_real_svd<E: RealField>(
+/// rotates the only nonzero entry of the last row of the bidiagonal matrix into the rows above it
+/// until the last row is zero, passing to `on_rotation` each index `j`, from `n - 1` down to `0`,
+/// with the rotation that acts on the rows `j` and `n`
+fn bidiag_zero_last_row<E: RealField>(
     diag: &mut [E],
     subdiag: &mut [E],
-    mut u: Option<MatMut<'_, E>>,
-    v: Option<MatMut<'_, E>>,
-    jacobi_fallback_threshold: usize,
-    bidiag_qr_fallback_threshold: usize,
-    epsilon: E,
-    consider_zero_threshold: E,
-    parallelism: Parallelism,
-    stack: PodStack<'_>,
+    mut on_rotation: impl FnMut(usize, JacobiRotation<E>),
 ) {
     let n = diag.len();
+    let mut bulge = subdiag[n - 1];
+    subdiag[n - 1] = E::faer_zero();
 
-    if n <= jacobi_fallback_threshold {
-        let (mut s, _) = temp_mat_zeroed::<E>(n, n, stack);
-        let mut s = s.as_mut();
-
-        for i in 0..n {
-            s.write(i, i, diag[i]);
-            if i + 1 < n {
-                s.write(i + 1, i, subdiag[i]);
-            }
+    for j in (0..n).rev() {
+        let rot = JacobiRotation::make_givens(diag[j], bulge.faer_neg());
+        diag[j] = rot.c.faer_mul(diag[j]).faer_add(rot.s.faer_mul(bulge));
+        if j > 0 {
+            bulge = rot.s.faer_neg().faer_mul(subdiag[j - 1]);
+            subdiag[j - 1] = rot.c.faer_mul(subdiag[j - 1]);
         }
+        on_rotation(j, rot);
+    }
+}
 
-        jacobi_svd(
-            s.rb_mut(),
-            u.rb_mut().map(|u| u.submatrix_mut(0, 0, n, n)),
-            v,
-            Skip::None,
-            epsilon,
-            consider_zero_threshold,
+/// applies the transposes of the rotations computed by [`bidiag_zero_last_row`] to the left
+/// singular vectors of the reduced matrix, giving those of the original one
+fn bidiag_undo_last_row<E: RealField>(u: MatMut<'_, E>, rots: &[JacobiRotation<E>]) {
+    let n = rots.len();
+    let (mut u_top, mut u_last) = u.split_at_row_mut(n);
+    for (j, rot) in rots.iter().enumerate() {
+        rot.transpose().apply_on_the_left_in_place(
+            u_top.rb_mut().row_mut(j).as_2d_mut(),
+            u_last.rb_mut().row_mut(0).as_2d_mut(),
         );
-
-        for (i, diag) in diag.iter_mut().enumerate() {
-            *diag = s.read(i, i);
-        }
-        if let Some(mut u) = u {
-            zipped!(u.rb_mut().row_mut(n).as_2d_mut())
-                .for_each(|unzipped!(mut x)| x.write(E::faer_zero()));
-            zipped!(u.rb_mut().col_mut(n).as_2d_mut())
-                .for_each(|unzipped!(mut x)| x.write(E::faer_zero()));
-            u.write(n, n, E::faer_one());
-        }
-    } else if n <= bidiag_qr_fallback_threshold {
-        bidiag_svd_qr_algorithm_impl(diag, subdiag, u, v, epsilon, consider_zero_threshold);
-    } else {
-        match u {
-            Some(u) => bidiag_svd_impl(
-                diag,
-                subdiag,
-                u,
-                v,
-                true,
-                jacobi_fallback_threshold,
-                epsilon,
-                consider_zero_threshold,
-                parallelism,
-                stack,
-            ),
-            None => {
-                let (mut u, stack) = temp_mat_uninit::<E>(2, n + 1, stack);
-                let u = u.as_mut();
-                bidiag_svd_impl(
-                    diag,
-                    subdiag,
-                    u,
-                    v,
-                    false,
-                    jacobi_fallback_threshold,
-                    epsilon,
-                    consider_zero_threshold,
-                    parallelism,
-                    stack,
-                );
-            }
-        }
     }
 }
 
-/// svd of bidiagonal lower matrix
-fn bidiag_svd_impl<E: RealField>(
-    diag: &mut [E],
-    subdiag: &mut [E],
-    mut u: MatMut<'_, E>,
-    mut v: Option<MatMut<'_, E>>,
-    fill_u: bool,
-    jacobi_fallback_threshold: usize,
+/// singular values and vectors of the upper triangular matrix `[[f, g], [0, h]]`, ported from
+/// LAPACK's `dlasv2`.
+///
+/// returns `(ssmax, ssmin, left, right)` such that
+/// `left.transpose() * [[f, g], [0, h]] * right = diag(ssmax, ssmin)`, where `left` and `right`
+/// are the rotations `[[c, -s], [s, c]]`. the singular values are signed, with
+/// `|ssmax| >= |ssmin|`, and both are accurate to high relative accuracy
+fn svd_2x2_upper<E: RealField>(
+    f: E,
+    g: E,
+    h: E,
     epsilon: E,
-    consider_zero_threshold: E,
-    parallelism: Parallelism,
-    mut stack: PodStack<'_>,
-) {
-    let n = diag.len();
-
-    let mut max_val = E::faer_zero();
-
-    for x in &*diag {
-        let val = x.faer_abs();
-        if val > max_val {
-            max_val = val;
-        }
-    }
-    for x in &*subdiag {
-        let val = x.faer_abs();
-        if val > max_val {
-            max_val = val;
-        }
-    }
+) -> (E, E, JacobiRotation<E>, JacobiRotation<E>) {
+    let zero = E::faer_zero();
+    let one = E::faer_one();
+    let two = one.faer_add(one);
+    let four = two.faer_add(two);
+    let one_half = two.faer_inv();
 
-    if max_val == E::faer_zero() {
-        u.fill_zero();
-        if u.nrows() == n + 1 {
-            u.diagonal_mut().column_vector_mut().fill(E::faer_one());
+    // |a| with the sign of b
+    let sign = |a: E, b: E| {
+        if b < zero {
+            a.faer_abs().faer_neg()
         } else {
-            u.write(0, 0, E::faer_one());
-            u.write(1, n, E::faer_one());
+            a.faer_abs()
         }
-        if let Some(mut v) = v {
-            v.fill_zero();
-            v.diagonal_mut().column_vector_mut().fill(E::faer_one());
-        };
-        return;
-    }
-
-    for x in &mut *diag {
-        *x = (*x).faer_div(max_val);
-    }
-    for x in &mut *subdiag {
-        *x = (*x).faer_div(max_val);
-    }
-
-    assert!(subdiag.len() == n);
-    assert!(n > jacobi_fallback_threshold);
+    };
 
-    let k = n / 2;
-    let rem = n - 1 - k;
+    let mut ft = f;
+    let mut fa = f.faer_abs();
+    let mut ht = h;
+    let mut ha = h.faer_abs();
 
-    let (d1, alpha_d2) = diag.split_at_mut(k);
-    let (sub_d1, beta_sub_d2) = subdiag.split_at_mut(k);
-    let (alpha, d2) = alpha_d2.split_first_mut().unwrap();
-    let (beta, sub_d2) = beta_sub_d2.split_first_mut().unwrap();
-    let alpha = *alpha;
-    let beta = *beta;
+    // pmax is the position of the entry of largest magnitude: 1 for f, 2 for g and 3 for h
+    let mut pmax = 1;
+    let swapped = ha > fa;
+    if swapped {
+        pmax = 3;
+        swap(&mut ft, &mut ht);
+        swap(&mut fa, &mut ha);
+    }
 
-    let compact_u = (u.nrows() != n + 1) as usize;
+    let gt = g;
+    let ga = g.faer_abs();
 
-    if k <= jacobi_fallback_threshold || rem <= jacobi_fallback_threshold {
-        let (mut u1_alloc, stack) =
-            temp_mat_uninit::<E>(k + 1, compact_u * (k + 1), stack.rb_mut());
-        let mut u1_alloc = u1_alloc.as_mut();
-        let (mut u2_alloc, stack) = temp_mat_uninit::<E>(rem + 1, compact_u * (rem + 1), stack);
-        let mut u2_alloc = u2_alloc.as_mut();
+    if ga > fa {
+        pmax = 2;
+    }
 
-        let (_u0, mut u1, mut u2) = if compact_u == 0 {
-            let (u1, u2) = u.rb_mut().split_at_row_mut(k + 1);
-            let (u0, u1) = u1.split_at_col_mut(1);
-            (
-                u0,
-                u1.submatrix_mut(0, 0, k + 1, k + 1),
-                u2.submatrix_mut(0, k, rem + 1, rem + 1),
-            )
+    let (ssmax, ssmin, clt, slt, crt, srt) = if ga == zero {
+        (fa, ha, one, zero, one, zero)
+    } else if ga > fa && fa.faer_div(ga) < epsilon {
+        // g is so large that the singular values are `ga` and `fa * ha / ga`
+        let ssmin = if ha > one {
+            fa.faer_div(ga.faer_div(ha))
         } else {
-            (
-                u.rb_mut().col_mut(0).as_2d_mut(),
-                u1_alloc.rb_mut(),
-                u2_alloc.rb_mut(),
-            )
+            fa.faer_div(ga).faer_mul(ha)
+        };
+        (ga, ssmin, one, ht.faer_div(gt), ft.faer_div(gt), one)
+    } else {
+        let d = fa.faer_sub(ha);
+        let l = if d == fa { one } else { d.faer_div(fa) };
+        let m = gt.faer_div(ft);
+        let t = two.faer_sub(l);
+        let mm = m.faer_mul(m);
+        let tt = t.faer_mul(t);
+        let s = tt.faer_add(mm).faer_sqrt();
+        let r = if l == zero {
+            m.faer_abs()
+        } else {
+            l.faer_mul(l).faer_add(mm).faer_sqrt()
         };
+        let a = one_half.faer_mul(s.faer_add(r));
 
-        let (mut v1, mut v2) = match v.rb_mut() {
-            Some(v) => {
-                let (v1, v2) = v.split_at_row_mut(k);
-                (
-                    Some(v1.submatrix_mut(0, 1, k, k + 1)),
-                    Some(v2.submatrix_mut(1, k, rem, rem + 1)),
-                )
+        let t = if mm == zero {
+            if l == zero {
+                sign(two, ft).faer_mul(sign(one, gt))
+            } else {
+                gt.faer_div(sign(d, ft)).faer_add(m.faer_div(t))
             }
-            None => (None, None),
+        } else {
+            m.faer_div(s.faer_add(t))
+                .faer_add(m.faer_div(r.faer_add(l)))
+                .faer_mul(one.faer_add(a))
         };
+        let l = t.faer_mul(t).faer_add(four).faer_sqrt();
+        let crt = two.faer_div(l);
+        let srt = t.faer_div(l);
+        let clt = crt.faer_add(srt.faer_mul(m)).faer_div(a);
+        let slt = ht.faer_div(ft).faer_mul(srt).faer_div(a);
+        (fa.faer_mul(a), ha.faer_div(a), clt, slt, crt, srt)
+    };
 
-        let (mut matrix1, stack) = temp_mat_zeroed::<E>(k + 1, k + 1, stack);
-        let (mut matrix2, _) = temp_mat_zeroed::<E>(rem + 1, rem + 1, stack);
-        let mut matrix1 = matrix1.as_mut();
-        let mut matrix2 = matrix2.as_mut();
+    let (csl, snl, csr, snr) = if swapped {
+        (srt, crt, slt, clt)
+    } else {
+        (clt, slt, crt, srt)
+    };
 
-        for j in 0..k {
-            matrix1.write(j, j, d1[j]);
-            matrix1.write(j + 1, j, sub_d1[j]);
-        }
-        for j in 0..rem {
-            matrix2.write(j, j + 1, d2[j]);
-            matrix2.write(j + 1, j + 1, sub_d2[j]);
-        }
+    let tsign = match pmax {
+        1 => sign(one, csr)
+            .faer_mul(sign(one, csl))
+            .faer_mul(sign(one, f)),
+        2 => sign(one, snr)
+            .faer_mul(sign(one, csl))
+            .faer_mul(sign(one, g)),
+        _ => sign(one, snr)
+            .faer_mul(sign(one, snl))
+            .faer_mul(sign(one, h)),
+    };
+    let ssmax = sign(ssmax, tsign);
+    let ssmin = sign(ssmin, tsign.faer_mul(sign(one, f)).faer_mul(sign(one, h)));
 
-        jacobi_svd(
-            matrix1.rb_mut(),
-            Some(u1.rb_mut()),
-            v1.rb_mut(),
-            Skip::Last,
-            epsilon,
-            consider_zero_threshold,
-        );
-        for j in 0..matrix1.ncols() {
-            for i in 0..matrix1.nrows() {
-                if i != j {
-                    matrix1.write(i, j, E::faer_zero());
-                }
-            }
-        }
-        jacobi_svd(
-            matrix2.rb_mut(),
-            Some(u2.rb_mut()),
-            v2.rb_mut(),
-            Skip::First,
-            epsilon,
-            consider_zero_threshold,
-        );
-        for j in 0..matrix2.ncols() {
-            for i in 0..matrix1.nrows() {
-                if i != j {
-                    matrix1.write(i, j, E::faer_zero());
-                }
-            }
-        }
+    (
+        ssmax,
+        ssmin,
+        JacobiRotation { c: csl, s: snl },
+        JacobiRotation { c: csr, s: snr },
+    )
+}
 
-        if cfg!(debug_assertions) {
-            if let Some(v1) = v1 {
-                zipped!(v1.col_mut(k).as_2d_mut())
-                    .for_each(|unzipped!(mut x)| x.write(E::faer_nan()));
-            }
-            if let Some(v2) = v2 {
-                zipped!(v2.col_mut(0).as_2d_mut())
-                    .for_each(|unzipped!(mut x)| x.write(E::faer_nan()));
-            }
+/// closed form svd of a bidiagonal matrix of size 2 whose last row is zero, i.e., of the lower
+/// triangular matrix `[[diag[0], 0], [subdiag[0], diag[1]]]`
+///
+/// this is exact up to a few ulps, and unlike the jacobi and qr fallbacks doesn't iterate. if
+/// `accumulate_v` is true, the columns of `v` are rotated instead of overwritten, and `v` may have
+/// any number of rows
+fn bidiag_svd_2x2<E: RealField>(
+    diag: &mut [E],
+    subdiag: &mut [E],
+    u: Option<MatMut<'_, E>>,
+    v: Option<MatMut<'_, E>>,
+    perm: Option<&mut [usize]>,
+    accumulate_v: bool,
+    epsilon: E,
+) {
+    // the transpose is upper triangular, and its left and right singular vectors are swapped
+    let (ssmax, ssmin, left, right) = svd_2x2_upper(diag[0], subdiag[0], diag[1], epsilon);
+
+    let zero = E::faer_zero();
+    let signs = [ssmax < zero, ssmin < zero];
+    diag[0] = ssmax.faer_abs();
+    diag[1] = ssmin.faer_abs();
+    subdiag[0] = zero;
+
+    // the columns of `[[c, -s], [s, c]]`
+    let col = |rot: &JacobiRotation<E>, j: usize| {
+        if j == 0 {
+            [rot.c, rot.s]
+        } else {
+            [rot.s.faer_neg(), rot.c]
         }
+    };
 
-        for j in 0..k {
-            diag[j + 1] = matrix1.read(j, j);
+    if let Some(mut u) = u {
+        u.fill_zero();
+        for (j, &negative) in signs.iter().enumerate() {
+            let [x0, x1] = col(&right, j);
+            let (x0, x1) = if negative {
+                (x0.faer_neg(), x1.faer_neg())
+            } else {
+                (x0, x1)
+            };
+            u.write(0, j, x0);
+            u.write(1, j, x1);
         }
-        for j in 0..rem {
-            diag[j + k + 1] = matrix2.read(j + 1, j + 1);
+        if u.ncols() == 3 {
+            u.write(2, 2, E::faer_one());
+        }
+    }
+    if let Some(mut v) = v {
+        if accumulate_v {
+            let (v0, v1) = v.split_at_col_mut(1);
+            left.transpose()
+                .apply_on_the_right_in_place(v0.col_mut(0).as_2d_mut(), v1.col_mut(0).as_2d_mut());
+        } else {
+            for j in 0..2 {
+                let [x0, x1] = col(&left, j);
+                v.write(0, j, x0);
+                v.write(1, j, x1);
+            }
         }
+    }
+    if let Some(perm) = perm {
+        perm.copy_from_slice(&[0, 1]);
+    }
+}
 
-        if compact_u == 1 {
-            // need to copy the first and last rows
+/// svd of bidiagonal lower matrix of shape (n + 1, n).
+///
+/// `diag` and `subdiag` must both have length `n`. `diag[i]` is the entry at `(i, i)` and
+/// `subdiag[i]` the one at `(i + 1, i)`, so `subdiag[n - 1]` is the only entry of the last row,
+/// which may be nonzero. `u` has shape `(n + 1, n + 1)` and `v` has shape `(n, n)`.
+///
+/// `u` may also have shape `(n + 1, n)`, in which case only the first `n` left singular vectors
+/// are computed. The last one corresponds to the extra row and isn't needed to reconstruct the
+/// matrix, so this saves a column of storage and of work. The workspace computed with `compute_u`
+/// set to `true` covers both shapes.
+///
+/// The algorithm is selected from the size of the matrix and the fallback thresholds, unless
+/// `params.algorithm` forces one of [`SvdAlgorithm::Jacobi`], [`SvdAlgorithm::QrIteration`] or
+/// [`SvdAlgorithm::DivideConquer`]. Otherwise, matrices with `n == 2` are decomposed with the
+/// analytic formula for the SVD of a `2×2` triangular matrix, without any iteration.
+///
+/// The subnormal entries of `diag` and `subdiag`, whose magnitudes are below the smallest
+/// positive normal value of `E`, are replaced by zeros before the decomposition, and so are the
+/// entries that become subnormal when the matrix is scaled. This perturbs the matrix by less than
+/// that value, and makes the results independent of whether the CPU flushes subnormal numbers to
+/// zero, as the FTZ and DAZ modes of x86 do.
+///
+/// On output, `diag` contains the singular values in nonincreasing order, so that the `rank`
+/// nonzero ones occupy `diag[0..rank]` and are followed by the zero ones, and the columns of `u`
+/// and `v` are the corresponding singular vectors. The contents of `subdiag` are unspecified.
+/// If `params.singular_value_range` is set, the singular values outside of the range are
+/// replaced by bounds and may not be sorted. See [`compute_bidiag_real_svd_out_of_place`] and
+/// [`compute_bidiag_real_singular_values`] for versions that leave their inputs untouched.
+///
+/// # Panics
+/// Panics if `subdiag.len() != diag.len()`.
+#[track_caller]
+pub fn compute_bidiag_real_svd<E: RealField>(
+    diag: &mut [E],
+    subdiag: &mut [E],
+    u: Option<MatMut<'_, E>>,
+    v: Option<MatMut<'_, E>>,
+    jacobi_fallback_threshold: usize,
+    bidiag_qr_fallback_threshold: usize,
+    epsilon: E,
+    consider_zero_threshold: E,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+    params: SvdParams,
+) {
+    compute_bidiag_real_svd_impl(
+        diag,
+        subdiag,
+        u,
+        v,
+        None,
+        None,
+        None,
+        false,
+        jacobi_fallback_threshold,
+        bidiag_qr_fallback_threshold,
+        epsilon,
+        consider_zero_threshold,
+        parallelism,
+        stack,
+        params,
+    );
+}
+
+/// Same as [`compute_bidiag_real_svd`], with `epsilon` and `consider_zero_threshold` set to the
+/// machine epsilon and the smallest positive normalized value of `E`.
+///
+/// # Panics
+/// Panics if the type `E` does not have a fixed precision at compile time, e.g. a dynamic
+/// multiprecision floating point type.
+#[track_caller]
+pub fn compute_bidiag_real_svd_auto<E: RealField>(
+    diag: &mut [E],
+    subdiag: &mut [E],
+    u: Option<MatMut<'_, E>>,
+    v: Option<MatMut<'_, E>>,
+    jacobi_fallback_threshold: usize,
+    bidiag_qr_fallback_threshold: usize,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+    params: SvdParams,
+) {
+    compute_bidiag_real_svd(
+        diag,
+        subdiag,
+        u,
+        v,
+        jacobi_fallback_threshold,
+        bidiag_qr_fallback_threshold,
+        E::faer_epsilon().unwrap(),
+        E::faer_zero_threshold().unwrap(),
+        parallelism,
+        stack,
+        params,
+    );
+}
+
+/// Computes the singular values of the bidiagonal matrix with diagonal `diag` and subdiagonal
+/// `subdiag`, described in [`compute_bidiag_real_svd`], and stores them in `out` in nonincreasing
+/// order.
+///
+/// Unlike [`compute_bidiag_real_svd`], `diag` and `subdiag` are left untouched. The only temporary
+/// storage is taken from `stack`, so this doesn't allocate. The workspace is given by
+/// [`bidiag_real_singular_values_req`].
+///
+/// # Panics
+/// Panics if `diag`, `subdiag` and `out` don't all have the same length.
+#[track_caller]
+pub fn compute_bidiag_real_singular_values<E: RealField>(
+    out: &mut [E],
+    diag: &[E],
+    subdiag: &[E],
+    jacobi_fallback_threshold: usize,
+    bidiag_qr_fallback_threshold: usize,
+    epsilon: E,
+    consider_zero_threshold: E,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+    params: SvdParams,
+) {
+    compute_bidiag_real_svd_out_of_place(
+        out,
+        diag,
+        subdiag,
+        None,
+        None,
+        jacobi_fallback_threshold,
+        bidiag_qr_fallback_threshold,
+        epsilon,
+        consider_zero_threshold,
+        parallelism,
+        stack,
+        params,
+    );
+}
+
+/// Same as [`compute_bidiag_real_singular_values`], but reads the bidiagonal matrix from the
+/// single slice `bidiag`, in which the diagonal and subdiagonal are interleaved.
+///
+/// With `n = out.len()`, `diag[i]` is read from `bidiag[i * stride]` and `subdiag[i]` from
+/// `bidiag[i * stride + 1]`, so a `stride` of `2` corresponds to the layout
+/// `[d0, e0, d1, e1, ...]`, and larger strides skip the padding between the pairs. `bidiag` is
+/// left untouched, and the entries are gathered into `out` and `stack`, so this doesn't allocate.
+/// The workspace is given by [`bidiag_real_singular_values_req`].
+///
+/// # Panics
+/// Panics if `stride < 2`, or if `bidiag` is too short to hold `out.len()` pairs.
+#[track_caller]
+pub fn compute_bidiag_real_singular_values_interleaved<E: RealField>(
+    out: &mut [E],
+    bidiag: &[E],
+    stride: usize,
+    jacobi_fallback_threshold: usize,
+    bidiag_qr_fallback_threshold: usize,
+    epsilon: E,
+    consider_zero_threshold: E,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+    params: SvdParams,
+) {
+    let n = out.len();
+    assert!(stride >= 2);
+    assert!(n == 0 || bidiag.len() >= (n - 1) * stride + 2);
+
+    for (i, d) in out.iter_mut().enumerate() {
+        *d = bidiag[i * stride];
+    }
+    let (subdiag, stack) = stack.collect((0..n).map(|i| bidiag[i * stride + 1]));
+    compute_bidiag_real_svd(
+        out,
+        subdiag,
+        None,
+        None,
+        jacobi_fallback_threshold,
+        bidiag_qr_fallback_threshold,
+        epsilon,
+        consider_zero_threshold,
+        parallelism,
+        stack,
+        params,
+    );
+}
+
+/// Same as [`compute_bidiag_real_svd`], but leaves `diag` and `subdiag` untouched and stores the
+/// singular values in `out` instead.
+///
+/// This is meant for bidiagonal matrices borrowed from a larger structure. `diag` is copied into
+/// `out`, and `subdiag` into `stack`, before the decomposition, so this doesn't allocate. The
+/// workspace is given by [`bidiag_real_svd_out_of_place_req`].
+///
+/// # Panics
+/// Panics if `diag`, `subdiag` and `out` don't all have the same length, or if `u` or `v` have
+/// the wrong shape.
+#[track_caller]
+pub fn compute_bidiag_real_svd_out_of_place<E: RealField>(
+    out: &mut [E],
+    diag: &[E],
+    subdiag: &[E],
+    u: Option<MatMut<'_, E>>,
+    v: Option<MatMut<'_, E>>,
+    jacobi_fallback_threshold: usize,
+    bidiag_qr_fallback_threshold: usize,
+    epsilon: E,
+    consider_zero_threshold: E,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+    params: SvdParams,
+) {
+    assert!(all(out.len() == diag.len(), subdiag.len() == diag.len()));
+
+    out.copy_from_slice(diag);
+    let (subdiag, stack) = stack.collect(subdiag.iter().copied());
+    compute_bidiag_real_svd(
+        out,
+        subdiag,
+        u,
+        v,
+        jacobi_fallback_threshold,
+        bidiag_qr_fallback_threshold,
+        epsilon,
+        consider_zero_threshold,
+        parallelism,
+        stack,
+        params,
+    );
+}
+
+/// Same as [`compute_bidiag_real_svd`], but composes the right singular vectors onto the
+/// transformation stored in `v` on input, instead of storing them on their own.
+///
+/// `v` must have `n` columns, and may have any number of rows. On output, it contains $V_0 V$,
+/// where $V_0$ is its value on input and $V$ is the matrix of right singular vectors of the
+/// bidiagonal matrix. This is meant for problems such as the generalized SVD, where the
+/// bidiagonal matrix is the result of an earlier right transformation. The rotations of the
+/// Jacobi and QR algorithms are applied to $V_0$ directly, and the merge steps of the divide and
+/// conquer algorithm multiply it by the right singular vectors of their arrow matrix, so $V$ is
+/// never formed on its own. The workspace is given by [`bidiag_real_svd_accumulate_v_req`].
+///
+/// # Panics
+/// Panics if `subdiag.len() != diag.len()`, if `v.ncols() != diag.len()`, or if `u` has the
+/// wrong shape.
+#[track_caller]
+pub fn compute_bidiag_real_svd_accumulate_v<E: RealField>(
+    diag: &mut [E],
+    subdiag: &mut [E],
+    u: Option<MatMut<'_, E>>,
+    v: MatMut<'_, E>,
+    jacobi_fallback_threshold: usize,
+    bidiag_qr_fallback_threshold: usize,
+    epsilon: E,
+    consider_zero_threshold: E,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+    params: SvdParams,
+) {
+    let n = diag.len();
+    assert!(v.ncols() == n);
+
+    compute_bidiag_real_svd_impl(
+        diag,
+        subdiag,
+        u,
+        Some(v),
+        None,
+        None,
+        None,
+        true,
+        jacobi_fallback_threshold,
+        bidiag_qr_fallback_threshold,
+        epsilon,
+        consider_zero_threshold,
+        parallelism,
+        stack,
+        params,
+    );
+}
+
+/// Same as [`compute_bidiag_real_svd`], but also stores in `perm` the overall permutation applied
+/// to the singular values, composed of the deflation transpositions and the final sort of every
+/// step of the algorithm.
+///
+/// The `j`-th computed singular value originates from the `perm[j]`-th index of the bidiagonal
+/// matrix.
+///
+/// # Panics
+/// Panics if `perm.len()` is not equal to `diag.len()`.
+#[track_caller]
+pub fn compute_bidiag_real_svd_with_perm<E: RealField>(
+    diag: &mut [E],
+    subdiag: &mut [E],
+    u: Option<MatMut<'_, E>>,
+    v: Option<MatMut<'_, E>>,
+    perm: &mut [usize],
+    jacobi_fallback_threshold: usize,
+    bidiag_qr_fallback_threshold: usize,
+    epsilon: E,
+    consider_zero_threshold: E,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+    params: SvdParams,
+) {
+    assert!(perm.len() == diag.len());
+    compute_bidiag_real_svd_impl(
+        diag,
+        subdiag,
+        u,
+        v,
+        Some(perm),
+        None,
+        None,
+        false,
+        jacobi_fallback_threshold,
+        bidiag_qr_fallback_threshold,
+        epsilon,
+        consider_zero_threshold,
+        parallelism,
+        stack,
+        params,
+    );
+}
+
+/// Computes the smallest singular value of the bidiagonal matrix with diagonal `diag` and
+/// subdiagonal `subdiag`, described in [`compute_bidiag_real_svd`], along with its left and right
+/// singular vectors, which are stored in `u` and `v`.
+///
+/// The result is the last singular triplet computed by [`compute_bidiag_real_svd`], to the same
+/// accuracy. When the divide and conquer algorithm is used, the final merge step only computes and
+/// accumulates the singular vectors of the smallest singular value, which skips the largest matrix
+/// products of the decomposition. The other algorithms compute all the singular vectors in
+/// temporary storage.
+///
+/// `diag` and `subdiag` are left untouched. The workspace is given by
+/// [`bidiag_real_smallest_singular_triplet_req`].
+///
+/// # Panics
+/// Panics if `diag` is empty, if `subdiag.len() != diag.len()`, or if `u` and `v` don't have the
+/// shapes `(n + 1, 1)` and `(n, 1)`.
+#[track_caller]
+pub fn compute_bidiag_real_smallest_singular_triplet<E: RealField>(
+    diag: &[E],
+    subdiag: &[E],
+    u: MatMut<'_, E>,
+    v: MatMut<'_, E>,
+    jacobi_fallback_threshold: usize,
+    bidiag_qr_fallback_threshold: usize,
+    epsilon: E,
+    consider_zero_threshold: E,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+    params: SvdParams,
+) -> E {
+    let n = diag.len();
+    assert!(all(
+        n > 0,
+        subdiag.len() == n,
+        u.nrows() == n + 1,
+        u.ncols() == 1,
+        v.nrows() == n,
+        v.ncols() == 1,
+    ));
+
+    let (diag, stack) = stack.collect(diag.iter().copied());
+    let (subdiag, stack) = stack.collect(subdiag.iter().copied());
+    flush_subnormals(diag, subdiag);
+
+    let (jacobi_threshold, qr_threshold, closed_form) = dispatch_thresholds(
+        n,
+        jacobi_fallback_threshold,
+        bidiag_qr_fallback_threshold,
+        params.algorithm,
+    );
+    let divide_conquer = !closed_form
+        && n > Ord::max(jacobi_threshold, qr_threshold)
+        && !subdiag[..n - 1].contains(&E::faer_zero());
+
+    if divide_conquer {
+        let mut stats = BidiagSvdStats::default();
+        bidiag_svd_impl(
+            diag,
+            subdiag,
+            u,
+            Some(v),
+            None,
+            &mut stats,
+            true,
+            true,
+            false,
+            None,
+            0,
+            jacobi_threshold,
+            epsilon,
+            consider_zero_threshold,
+            parallelism,
+            stack,
+            params,
+        );
+    } else {
+        let (mut u_full, stack) = temp_mat_uninit::<E>(n + 1, n + 1, stack);
+        let (mut v_full, stack) = temp_mat_uninit::<E>(n, n, stack);
+        compute_bidiag_real_svd(
+            diag,
+            subdiag,
+            Some(u_full.as_mut()),
+            Some(v_full.as_mut()),
+            jacobi_fallback_threshold,
+            bidiag_qr_fallback_threshold,
+            epsilon,
+            consider_zero_threshold,
+            parallelism,
+            stack,
+            params,
+        );
+        zipped!(u, u_full.as_ref().col(n - 1).as_2d())
+            .for_each(|unzipped!(mut dst, src)| dst.write(src.read()));
+        zipped!(v, v_full.as_ref().col(n - 1).as_2d())
+            .for_each(|unzipped!(mut dst, src)| dst.write(src.read()));
+    }
+
+    diag[n - 1]
+}
+
+/// deflates the arrow matrix with first column `col0` and diagonal `diag[1..]`, and compacts the
+/// entries that are left to the front of `diag` and `col0`.
+///
+/// the singular values of the deflated entries are stored at the end of `out`, and the number of
+/// remaining entries is returned. if `u` is provided, the rotations of the deflation are applied
+/// to its columns, and `src` is filled with the column of `u` that each entry of `out` and of the
+/// compacted `col0` comes from
+fn deflate_arrow_in_place<E: RealField>(
+    out: &mut [E],
+    diag: &mut [E],
+    col0: &mut [E],
+    mut u: Option<MatMut<'_, E>>,
+    src: &mut [usize],
+    epsilon: E,
+    col0_flush: E,
+    consider_zero_threshold: E,
+) -> usize {
+    let n = diag.len();
+
+    let mut max_diag = E::faer_zero();
+    let mut max_col0 = E::faer_zero();
+    for d in diag[1..].iter() {
+        if *d > max_diag {
+            max_diag = *d;
+        }
+    }
+    for c in col0.iter() {
+        if *c > max_col0 {
+            max_col0 = *c;
+        }
+    }
+
+    let epsilon_strict = epsilon.faer_mul(max_diag);
+    let epsilon_strict = if epsilon_strict > consider_zero_threshold {
+        epsilon_strict
+    } else {
+        consider_zero_threshold
+    };
+    let flush_threshold = col0_flush.faer_mul(max_diag);
+    let flush_threshold = if flush_threshold > consider_zero_threshold {
+        flush_threshold
+    } else {
+        consider_zero_threshold
+    };
+    let eight = E::faer_from_f64(8.0);
+    let epsilon_coarse = eight.faer_mul(epsilon).faer_mul(if max_diag > max_col0 {
+        max_diag
+    } else {
+        max_col0
+    });
+
+    // the same conditions as `deflate`. each rotation zeroes the entry `j` of `col0` against the
+    // entry `i`, and is applied to the rows `i` and `j` of the arrow matrix, so the left singular
+    // vectors are multiplied by its transpose
+    let mut rotate = |i: usize, j: usize, col0: &mut [E]| {
+        let r = norm_l2_scaled(&[col0[i], col0[j]]);
+        if let Some(u) = u.rb_mut() {
+            let c = col0[i].faer_div(r);
+            let s = col0[j].faer_div(r);
+            let (ui, uj) = u.split_at_col_mut(j);
+            JacobiRotation { c, s }
+                .transpose()
+                .apply_on_the_right_in_place(ui.col_mut(i).as_2d_mut(), uj.col_mut(0).as_2d_mut());
+        }
+        col0[i] = r;
+        col0[j] = E::faer_zero();
+    };
+
+    if col0[0] < epsilon_coarse {
+        col0[0] = epsilon_coarse;
+    }
+    for c in &mut col0[1..] {
+        if *c < flush_threshold {
+            *c = E::faer_zero();
+        }
+    }
+    for i in 1..n {
+        if diag[i] < epsilon_coarse {
+            if col0[i] != E::faer_zero() {
+                rotate(0, i, col0);
+            }
+            diag[i] = E::faer_zero();
+        }
+    }
+    let mut prev = None;
+    for i in 1..n {
+        if col0[i] == E::faer_zero() {
+            continue;
+        }
+        match prev {
+            Some(p) if diag[i].faer_sub(diag[p]) < epsilon_strict => {
+                rotate(p, i, col0);
+                diag[i] = diag[p];
+            }
+            _ => prev = Some(i),
+        }
+    }
+
+    let mut kept = 1;
+    let mut deflated = 0;
+    if let Some(src) = src.first_mut() {
+        *src = 0;
+    }
+    for i in 1..n {
+        if col0[i] == E::faer_zero() {
+            deflated += 1;
+            out[n - deflated] = diag[i];
+            if !src.is_empty() {
+                src[n - deflated] = i;
+            }
+        } else {
+            diag[kept] = diag[i];
+            col0[kept] = col0[i];
+            if !src.is_empty() {
+                src[kept] = i;
+            }
+            kept += 1;
+        }
+    }
+    kept
+}
+
+/// Computes the singular values of the arrow matrix $M$ of size `n`, whose first column is `col0`
+/// and whose diagonal is `diag` past its first entry, and stores them in `out` in nonincreasing
+/// order.
+///
+/// This is the matrix that is decomposed by the merge step of the divide and conquer algorithm.
+/// Once it's deflated, each singular value is the root of a secular equation that only depends
+/// on `diag` and `col0`, so the singular values are computed `window` at a time. The workspace is
+/// given by [`arrow_real_singular_values_windowed_req`], and is proportional to `window` instead
+/// of `n`, which makes this usable for arrow matrices whose `O(n)` temporaries in the merge step
+/// would be too large. Each singular value still costs `O(n)` operations. Only the
+/// `refine_iters`, `secular_bracket` and `col0_flush_threshold` fields of `params` are used. The
+/// merge steps of [`compute_bidiag_real_svd`] use this when [`SvdParams::secular_window`] is set
+/// and no singular vectors are requested, in which case the merge steps below the top level also
+/// form the left singular vectors one at a time to update the first and last rows of $U$.
+///
+/// `diag[0]` is ignored, and `diag[1..]` must be nonnegative and sorted in nondecreasing order.
+/// `diag` and `col0` are used as the storage of the deflated matrix, and their contents are
+/// unspecified on output.
+///
+/// # Panics
+/// Panics if `diag`, `col0` and `out` don't all have the same length, if `window == 0`, or if
+/// `diag[1..]` is not nonnegative and sorted.
+#[track_caller]
+pub fn compute_arrow_real_singular_values_windowed<E: RealField>(
+    out: &mut [E],
+    diag: &mut [E],
+    col0: &mut [E],
+    window: usize,
+    epsilon: E,
+    consider_zero_threshold: E,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+    params: SvdParams,
+) {
+    let n = diag.len();
+    assert!(all(col0.len() == n, out.len() == n, window > 0));
+    if n == 0 {
+        return;
+    }
+    diag[0] = E::faer_zero();
+    assert!(diag.windows(2).all(|d| d[0] <= d[1]));
+
+    arrow_singular_values_windowed_impl(
+        out,
+        diag,
+        col0,
+        None,
+        window,
+        epsilon,
+        consider_zero_threshold,
+        parallelism,
+        stack,
+        params,
+    );
+}
+
+/// implementation of [`compute_arrow_real_singular_values_windowed`], once its inputs are
+/// checked, which returns the statistics of the secular equation solves
+///
+/// if `u` is provided as `(u_in, u_out)`, the first `n` columns of `u_in` are multiplied by the
+/// left singular vectors of the arrow matrix, and the product is stored in `u_out` in the same
+/// order as `out`. `u_in` is used as storage. the secular equations are then solved twice, once
+/// for the singular values and the entries of `col0` that are consistent with them, and once for
+/// the singular vectors, which are formed one at a time
+fn arrow_singular_values_windowed_impl<E: RealField>(
+    out: &mut [E],
+    diag: &mut [E],
+    col0: &mut [E],
+    u: Option<(MatMut<'_, E>, MatMut<'_, E>)>,
+    window: usize,
+    epsilon: E,
+    consider_zero_threshold: E,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+    params: SvdParams,
+) -> BidiagSvdStats {
+    let n = diag.len();
+    let (mut u_in, u_out) = match u {
+        Some((u_in, u_out)) => (Some(u_in), Some(u_out)),
+        None => (None, None),
+    };
+    let compute_u = u_in.is_some();
+
+    // the sign of `col0[i]` can be flipped by flipping the signs of the row `i` of $M$, then of
+    // its column `i` if `i > 0`. the left singular vectors are flipped along with the row
+    let mut max_val = diag[n - 1];
+    for (i, c) in col0.iter_mut().enumerate() {
+        if *c < E::faer_zero() {
+            *c = c.faer_neg();
+            if let Some(u) = u_in.rb_mut() {
+                zipped!(u.col_mut(i).as_2d_mut())
+                    .for_each(|unzipped!(mut x)| x.write(x.read().faer_neg()));
+            }
+        }
+        if *c > max_val {
+            max_val = *c;
+        }
+    }
+    if max_val == E::faer_zero() {
+        out.fill(E::faer_zero());
+        if let (Some(u_in), Some(u_out)) = (u_in, u_out) {
+            zipped!(u_out, u_in.rb()).for_each(|unzipped!(mut dst, src)| dst.write(src.read()));
+        }
+        return BidiagSvdStats::default();
+    }
+    let max_val_inv = max_val.faer_inv();
+    for x in diag.iter_mut().chain(col0.iter_mut()) {
+        *x = (*x).faer_mul(max_val_inv);
+    }
+
+    let (src, stack) = stack.make_with(compute_u as usize * n, |_| 0usize);
+    let kept = deflate_arrow_in_place(
+        out,
+        diag,
+        col0,
+        u_in.rb_mut(),
+        src,
+        epsilon,
+        col0_flush(epsilon, &params),
+        consider_zero_threshold,
+    );
+    let diag = &diag[..kept];
+    let col0 = &col0[..kept];
+
+    let window = Ord::min(window, kept);
+    let (mut shifts, stack) = temp_mat_uninit::<E>(window, 1, stack);
+    let (mut mus, stack) = temp_mat_uninit::<E>(window, 1, stack);
+    let (mut s, stack) = temp_mat_uninit::<E>(window, 1, stack);
+    // the products of `perturb_col0`, which are accumulated one root at a time. this has `n`
+    // entries so that it can be reused to sort `out` at the end
+    let (zhat, stack) = stack.make_with(compute_u as usize * n, |_| E::faer_one());
+
+    // every entry of the compacted `col0` is nonzero, so it's its own compressed version
+    let mut stats = BidiagSvdStats::default();
+    let mut start = 0;
+    while start < kept {
+        let len = Ord::min(window, kept - start);
+        stats += compute_singular_values(
+            shifts.rb_mut().subrows_mut(0, len),
+            mus.rb_mut().subrows_mut(0, len),
+            s.rb_mut().subrows_mut(0, len),
+            start,
+            diag,
+            diag,
+            col0,
+            col0,
+            None,
+            epsilon,
+            params.refine_iters,
+            params.secular_bracket,
+            None,
+            parallelism,
+        );
+        for i in 0..len {
+            let j = start + i;
+            let (sj, shift, mu) = (s.read(i, 0), shifts.read(i, 0), mus.read(i, 0));
+            out[j] = sj;
+
+            // the root `j` is divided by the pole `j` in the products of the entries past `j`, by
+            // the pole `j + 1` in the others, and by no pole if it's the last one
+            for (l, z) in zhat[..compute_u as usize * kept].iter_mut().enumerate() {
+                let dl = diag[l];
+                let add = sj.faer_add(dl);
+                let sub = mu.faer_add(shift.faer_sub(dl));
+                let pole = if j < l {
+                    Some(j)
+                } else if j + 1 < kept {
+                    Some(j + 1)
+                } else {
+                    None
+                };
+                let term = match pole {
+                    Some(p) => (add.faer_div(diag[p].faer_add(dl)))
+                        .faer_mul(sub.faer_div(diag[p].faer_sub(dl))),
+                    None => add.faer_mul(sub),
+                };
+                *z = (*z).faer_mul(term);
+            }
+        }
+        start += len;
+    }
+
+    for x in out.iter_mut() {
+        *x = (*x).faer_mul(max_val);
+    }
+
+    let (u_in, mut u_out) = match (u_in, u_out) {
+        (Some(u_in), Some(u_out)) => (u_in, u_out),
+        _ => {
+            out.sort_unstable_by(|a, b| b.partial_cmp(a).unwrap_or(core::cmp::Ordering::Equal));
+            return stats;
+        }
+    };
+
+    for z in &mut zhat[..kept] {
+        *z = (*z).faer_sqrt();
+    }
+
+    // the columns of `u_out` are written in the final order of the singular values, which puts
+    // the nans last
+    let (order, stack) = stack.make_with(n, |i| i);
+    order.sort_unstable_by(|&i, &j| {
+        out[j]
+            .partial_cmp(&out[i])
+            .unwrap_or_else(|| out[i].faer_is_nan().cmp(&out[j].faer_is_nan()))
+            .then(i.cmp(&j))
+    });
+    let (pos, _) = stack.make_with(n, |_| 0usize);
+    for (p, &i) in order.iter().enumerate() {
+        pos[i] = p;
+    }
+
+    // the singular vectors of the deflated entries are columns of the identity
+    for i in kept..n {
+        zipped!(
+            u_out.rb_mut().col_mut(pos[i]).as_2d_mut(),
+            u_in.rb().col(src[i]).as_2d(),
+        )
+        .for_each(|unzipped!(mut dst, src)| dst.write(src.read()));
+    }
+
+    let mut start = 0;
+    while start < kept {
+        let len = Ord::min(window, kept - start);
+        // the roots are the same as the ones above, and are only counted once
+        compute_singular_values(
+            shifts.rb_mut().subrows_mut(0, len),
+            mus.rb_mut().subrows_mut(0, len),
+            s.rb_mut().subrows_mut(0, len),
+            start,
+            diag,
+            diag,
+            col0,
+            col0,
+            None,
+            epsilon,
+            params.refine_iters,
+            params.secular_bracket,
+            None,
+            parallelism,
+        );
+        for i in 0..len {
+            let j = start + i;
+            let mut dst = u_out.rb_mut().col_mut(pos[j]).as_2d_mut();
+            if zhat[j] == E::faer_zero() {
+                zipped!(dst, u_in.rb().col(src[j]).as_2d())
+                    .for_each(|unzipped!(mut dst, src)| dst.write(src.read()));
+                continue;
+            }
+
+            // the left singular vector of the compacted arrow matrix, with the same fallback as
+            // `compute_singular_vectors` when it overflows
+            let (shift, mu) = (shifts.read(i, 0), mus.read(i, 0));
+            let combined = combine_columns_normalized(dst.rb_mut(), u_in.rb(), &src[..kept], |l| {
+                zhat[l]
+                    .faer_div(diag[l].faer_sub(shift).faer_sub(mu))
+                    .faer_div(diag[l].faer_add(shift.faer_add(mu)))
+            });
+            if !combined {
+                let mut min_sub = E::faer_zero().faer_inv();
+                let mut min_add = E::faer_zero().faer_inv();
+                for &d in diag {
+                    let (sub, add) = clamped_denominators(d, shift, mu, consider_zero_threshold);
+                    if sub.faer_abs() < min_sub {
+                        min_sub = sub.faer_abs();
+                    }
+                    if add.faer_abs() < min_add {
+                        min_add = add.faer_abs();
+                    }
+                }
+                combine_columns_normalized(dst, u_in.rb(), &src[..kept], |l| {
+                    let (sub, add) =
+                        clamped_denominators(diag[l], shift, mu, consider_zero_threshold);
+                    zhat[l].faer_mul((min_sub.faer_div(sub)).faer_mul(min_add.faer_div(add)))
+                });
+            }
+        }
+        start += len;
+    }
+
+    for (p, &i) in order.iter().enumerate() {
+        zhat[p] = out[i];
+    }
+    out.copy_from_slice(zhat);
+    stats
+}
+
+/// overwrites `dst` with the sum of the columns `src[l]` of `u` multiplied by `weight(l)`,
+/// divided by the norm of the weights, and returns `false` without writing anything if that norm
+/// is zero or not finite
+fn combine_columns_normalized<E: RealField>(
+    mut dst: MatMut<'_, E>,
+    u: MatRef<'_, E>,
+    src: &[usize],
+    weight: impl Fn(usize) -> E,
+) -> bool {
+    let mut max = E::faer_zero();
+    for l in 0..src.len() {
+        let abs = weight(l).faer_abs();
+        if !(abs <= max) {
+            max = abs;
+        }
+    }
+    if !(max.faer_is_finite() && max > E::faer_zero()) {
+        return false;
+    }
+
+    let max_inv = max.faer_inv();
+    let mut norm2 = E::faer_zero();
+    for l in 0..src.len() {
+        let x = weight(l).faer_mul(max_inv);
+        norm2 = norm2.faer_add(x.faer_mul(x));
+    }
+    let norm_inv = max.faer_mul(norm2.faer_sqrt()).faer_inv();
+
+    dst.fill_zero();
+    for (l, &j) in src.iter().enumerate() {
+        let w = weight(l).faer_mul(norm_inv);
+        zipped!(dst.rb_mut(), u.col(j).as_2d()).for_each(|unzipped!(mut dst, src)| {
+            dst.write(dst.read().faer_add(w.faer_mul(src.read())))
+        });
+    }
+    true
+}
+
+/// Counters describing the work done by the implicit QR iteration of
+/// [`compute_bidiag_real_svd`], summed over every block it was used on.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct QrStats {
+    /// Number of sweeps over the active block, including the ones that only chase a zero diagonal
+    /// entry out of it.
+    pub iterations: usize,
+    /// Number of singular values that converged at the end of the active block, plus the number
+    /// of zero diagonal entries that split it.
+    pub deflations: usize,
+    /// Number of Givens rotations applied to the bidiagonal matrix, each of which is also applied
+    /// to the singular vectors when they are requested.
+    pub givens_applied: usize,
+    /// Number of singular values that were still in the active block when the iteration limit
+    /// was reached. These are left at their last iterate, and aren't accurate.
+    pub unconverged: usize,
+}
+
+impl core::ops::AddAssign for QrStats {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        self.iterations += rhs.iterations;
+        self.deflations += rhs.deflations;
+        self.givens_applied += rhs.givens_applied;
+        self.unconverged += rhs.unconverged;
+    }
+}
+
+/// Counters describing the work done by the divide and conquer part of
+/// [`compute_bidiag_real_svd`], summed over every merge step, and by the QR iteration.
+///
+/// A high ratio of `bisection_fallbacks` to `secular_eq_solves` indicates that the secular
+/// equations are ill-conditioned, typically because of clustered singular values, and that the
+/// solver spent most of its time in the slower bisection.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct BidiagSvdStats {
+    /// Number of singular values computed by solving a secular equation, excluding the ones that
+    /// were deflated or skipped.
+    pub secular_eq_solves: usize,
+    /// Number of secular equation solves where the secant method failed to converge and the
+    /// solver fell back to bisection.
+    pub bisection_fallbacks: usize,
+    /// Number of secular equation solves whose initial bracket didn't contain the root, because
+    /// of rounding errors, and had to be widened. These only happen with
+    /// [`SecularBracketMode::Permissive`].
+    pub bracket_repairs: usize,
+    /// Number of secular equation solves for which no bracket of the root could be found. The
+    /// corresponding singular values are set to NaN.
+    pub secular_divergences: usize,
+    /// Total number of evaluations of the secular equations and of their derivatives, over all
+    /// the solves.
+    pub secular_eq_evaluations: usize,
+    /// Number of merge steps whose arrow matrix had a nonzero entry of its first column in a row
+    /// where its diagonal is zero. This only happens for malformed inputs, and the results are then
+    /// unreliable. Debug builds panic instead.
+    pub inconsistent_inputs: usize,
+    /// Work done by the QR iteration, which is only nonzero if it was selected for the full
+    /// matrix or for some of its blocks.
+    pub qr: QrStats,
+    /// Algorithm selected for the matrix, one of [`SvdAlgorithm::ClosedForm`],
+    /// [`SvdAlgorithm::Jacobi`], [`SvdAlgorithm::QrIteration`] or
+    /// [`SvdAlgorithm::DivideConquer`].
+    ///
+    /// If the matrix is split into independent blocks by exact zeros of its subdiagonal, this is
+    /// the algorithm selected for the largest block.
+    pub algorithm: SvdAlgorithm,
+}
+
+/// orders the algorithms selected for the blocks of a matrix by the size of the blocks they are
+/// selected for, which is the order of the size based dispatch
+fn algorithm_rank(algorithm: SvdAlgorithm) -> usize {
+    match algorithm {
+        SvdAlgorithm::ClosedForm => 1,
+        SvdAlgorithm::Jacobi => 2,
+        SvdAlgorithm::QrIteration => 3,
+        SvdAlgorithm::DivideConquer => 4,
+        _ => 0,
+    }
+}
+
+impl core::ops::AddAssign for BidiagSvdStats {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        self.secular_eq_solves += rhs.secular_eq_solves;
+        self.bisection_fallbacks += rhs.bisection_fallbacks;
+        self.bracket_repairs += rhs.bracket_repairs;
+        self.secular_divergences += rhs.secular_divergences;
+        self.secular_eq_evaluations += rhs.secular_eq_evaluations;
+        self.inconsistent_inputs += rhs.inconsistent_inputs;
+        self.qr += rhs.qr;
+        if algorithm_rank(rhs.algorithm) > algorithm_rank(self.algorithm) {
+            self.algorithm = rhs.algorithm;
+        }
+    }
+}
+
+/// Same as [`compute_bidiag_real_svd`], but also stores in `stats` counters describing how the
+/// secular equations were solved, and how many sweeps the QR iteration took.
+///
+/// `stats` is overwritten. The counters of the secular equations are left zeroed if the matrix is
+/// small enough to be handled by the Jacobi or QR fallbacks, and the ones of the QR iteration are
+/// left zeroed if it's never used. [`BidiagSvdStats::algorithm`] records which of them was
+/// selected.
+pub fn compute_bidiag_real_svd_with_stats<E: RealField>(
+    diag: &mut [E],
+    subdiag: &mut [E],
+    u: Option<MatMut<'_, E>>,
+    v: Option<MatMut<'_, E>>,
+    stats: &mut BidiagSvdStats,
+    jacobi_fallback_threshold: usize,
+    bidiag_qr_fallback_threshold: usize,
+    epsilon: E,
+    consider_zero_threshold: E,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+    params: SvdParams,
+) {
+    compute_bidiag_real_svd_impl(
+        diag,
+        subdiag,
+        u,
+        v,
+        None,
+        Some(stats),
+        None,
+        false,
+        jacobi_fallback_threshold,
+        bidiag_qr_fallback_threshold,
+        epsilon,
+        consider_zero_threshold,
+        parallelism,
+        stack,
+        params,
+    );
+}
+
+/// Same as [`compute_bidiag_real_svd_with_stats`], but the secular equations are solved starting
+/// from `initial_guess`, which holds approximations of the singular values in nonincreasing
+/// order, e.g. the singular values of the same matrix before a small perturbation.
+///
+/// Each guess is only used if it lies in the interval that contains its singular value, and if the
+/// iterates that start from it converge, so that a bad guess only costs a few evaluations of the
+/// secular equation before the solver falls back to its usual search. The guess only seeds the
+/// final merge step of the divide and conquer algorithm, whose singular values are the ones of the
+/// whole matrix, and is ignored by the Jacobi and QR fallbacks, and by matrices that are split into
+/// blocks by exact zeros of their subdiagonal.
+///
+/// The workspace is given by [`bidiag_real_svd_with_guess_req`].
+///
+/// # Panics
+/// Panics if `initial_guess` doesn't have the same length as `diag`.
+#[track_caller]
+pub fn compute_bidiag_real_svd_with_guess<E: RealField>(
+    diag: &mut [E],
+    subdiag: &mut [E],
+    u: Option<MatMut<'_, E>>,
+    v: Option<MatMut<'_, E>>,
+    initial_guess: &[E],
+    stats: &mut BidiagSvdStats,
+    jacobi_fallback_threshold: usize,
+    bidiag_qr_fallback_threshold: usize,
+    epsilon: E,
+    consider_zero_threshold: E,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+    params: SvdParams,
+) {
+    assert!(initial_guess.len() == diag.len());
+
+    // the secular equations are solved for the singular values in increasing order
+    let (guess, stack) = stack.collect(initial_guess.iter().rev().copied());
+    compute_bidiag_real_svd_impl(
+        diag,
+        subdiag,
+        u,
+        v,
+        None,
+        Some(stats),
+        Some(guess),
+        false,
+        jacobi_fallback_threshold,
+        bidiag_qr_fallback_threshold,
+        epsilon,
+        consider_zero_threshold,
+        parallelism,
+        stack,
+        params,
+    );
+}
+
+/// Same as [`compute_bidiag_real_svd`], but `f32` matrices are decomposed in `f64`, and the
+/// results are rounded back to `f32`.
+///
+/// This is slower than the native path, and is meant to be used as a reference when measuring
+/// its accuracy. Other types are decomposed in their own precision. In both cases, the epsilon and
+/// the zero threshold of the type used for the computation are used.
+///
+/// The workspace is given by [`bidiag_real_svd_refined_req`].
+#[track_caller]
+pub fn compute_bidiag_real_svd_refined<E: RealField>(
+    diag: &mut [E],
+    subdiag: &mut [E],
+    u: Option<MatMut<'_, E>>,
+    v: Option<MatMut<'_, E>>,
+    jacobi_fallback_threshold: usize,
+    bidiag_qr_fallback_threshold: usize,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+    params: SvdParams,
+) {
+    if !coe::is_same::<f32, E>() {
+        return compute_bidiag_real_svd(
+            diag,
+            subdiag,
+            u,
+            v,
+            jacobi_fallback_threshold,
+            bidiag_qr_fallback_threshold,
+            E::faer_epsilon().unwrap(),
+            E::faer_zero_threshold().unwrap(),
+            parallelism,
+            stack,
+            params,
+        );
+    }
+
+    let diag: &mut [f32] = diag.coerce();
+    let subdiag: &mut [f32] = subdiag.coerce();
+    let u: Option<MatMut<'_, f32>> = u.map(|u| u.coerce());
+    let v: Option<MatMut<'_, f32>> = v.map(|v| v.coerce());
+
+    let n = diag.len();
+    let (diag_f64, stack) = stack.collect(diag.iter().map(|&x| x as f64));
+    let (subdiag_f64, stack) = stack.collect(subdiag.iter().map(|&x| x as f64));
+    let (mut u_f64, stack) =
+        temp_mat_uninit::<f64>(n + 1, u.as_ref().map_or(0, |u| u.ncols()), stack);
+    let mut u_f64 = u_f64.as_mut();
+    let (mut v_f64, stack) = temp_mat_uninit::<f64>(n, if v.is_some() { n } else { 0 }, stack);
+    let mut v_f64 = v_f64.as_mut();
+
+    compute_bidiag_real_svd::<f64>(
+        diag_f64,
+        subdiag_f64,
+        u.is_some().then_some(u_f64.rb_mut()),
+        v.is_some().then_some(v_f64.rb_mut()),
+        jacobi_fallback_threshold,
+        bidiag_qr_fallback_threshold,
+        f64::EPSILON,
+        f64::MIN_POSITIVE,
+        parallelism,
+        stack,
+        params,
+    );
+
+    for (dst, &src) in zip(&mut *diag, &*diag_f64) {
+        *dst = src as f32;
+    }
+    for (dst, &src) in zip(&mut *subdiag, &*subdiag_f64) {
+        *dst = src as f32;
+    }
+    if let Some(u) = u {
+        zipped!(u, u_f64.rb()).for_each(|unzipped!(mut dst, src)| dst.write(src.read() as f32));
+    }
+    if let Some(v) = v {
+        zipped!(v, v_f64.rb()).for_each(|unzipped!(mut dst, src)| dst.write(src.read() as f32));
+    }
+}
+
+#[track_caller]
+/// returns the jacobi and qr thresholds used by the size based dispatch of
+/// `compute_bidiag_real_svd_impl`, and whether the closed form svd is used
+fn dispatch_thresholds(
+    n: usize,
+    jacobi_fallback_threshold: usize,
+    bidiag_qr_fallback_threshold: usize,
+    algorithm: SvdAlgorithm,
+) -> (usize, usize, bool) {
+    // a forced algorithm replaces the thresholds of the size based dispatch. the divide and
+    // conquer algorithm needs two nonempty subproblems, and a jacobi threshold below `n` that its
+    // leaves then use
+    let (jacobi_fallback_threshold, bidiag_qr_fallback_threshold) = match algorithm {
+        SvdAlgorithm::Jacobi => (n, n),
+        SvdAlgorithm::QrIteration => (0, n),
+        SvdAlgorithm::DivideConquer if n >= 3 => (Ord::min(jacobi_fallback_threshold, n - 1), 0),
+        SvdAlgorithm::DivideConquer => (n, n),
+        _ => (jacobi_fallback_threshold, bidiag_qr_fallback_threshold),
+    };
+    // a matrix of size 2 has a closed form svd, unless an iterative algorithm was forced
+    let closed_form = n == 2
+        && !matches!(
+            algorithm,
+            SvdAlgorithm::Jacobi | SvdAlgorithm::QrIteration | SvdAlgorithm::DivideConquer
+        );
+    (
+        jacobi_fallback_threshold,
+        bidiag_qr_fallback_threshold,
+        closed_form,
+    )
+}
+
+fn compute_bidiag_real_svd_impl<E: RealField>(
+    diag: &mut [E],
+    subdiag: &mut [E],
+    mut u: Option<MatMut<'_, E>>,
+    v: Option<MatMut<'_, E>>,
+    perm: Option<&mut [usize]>,
+    stats: Option<&mut BidiagSvdStats>,
+    initial_guess: Option<&mut [E]>,
+    accumulate_v: bool,
+    jacobi_fallback_threshold: usize,
+    bidiag_qr_fallback_threshold: usize,
+    epsilon: E,
+    consider_zero_threshold: E,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+    params: SvdParams,
+) {
+    let n = diag.len();
+    assert!(
+        subdiag.len() == n,
+        "`subdiag` must have the same length as `diag`, with its last element in the last row",
+    );
+    flush_subnormals(diag, subdiag);
+
+    // the jacobi and qr fallbacks don't solve any secular equation, so they leave this unchanged
+    let mut local_stats = BidiagSvdStats::default();
+    let stats = stats.unwrap_or(&mut local_stats);
+    *stats = BidiagSvdStats::default();
+
+    let (jacobi_fallback_threshold, bidiag_qr_fallback_threshold, closed_form) =
+        dispatch_thresholds(
+            n,
+            jacobi_fallback_threshold,
+            bidiag_qr_fallback_threshold,
+            params.algorithm,
+        );
+
+    if let Some(u) = u.rb() {
+        assert!(u.nrows() == n + 1);
+        assert!(
+            u.ncols() == n + 1 || u.ncols() == n,
+            "`u` must have shape `(n + 1, n + 1)`, or `(n + 1, n)` for the economy vectors",
+        );
+    }
+
+    // the singular vectors are updated by rotating pairs of columns in place, which silently
+    // produces garbage if the columns share memory
+    if let Some(u) = u.rb() {
+        debug_assert!(
+            !has_self_overlap(u),
+            "the columns of `u` must not overlap in memory",
+        );
+    }
+    if let Some(v) = v.rb() {
+        debug_assert!(
+            !has_self_overlap(v),
+            "the columns of `v` must not overlap in memory",
+        );
+    }
+
+    if closed_form || n <= Ord::max(jacobi_fallback_threshold, bidiag_qr_fallback_threshold) {
+        // the jacobi and closed form fallbacks don't normalize the matrix themselves. the qr
+        // algorithm normalizes it again, which then leaves it unchanged
+        let scale = normalize_bidiagonal(diag, subdiag);
+
+        // the fallbacks assume that the last row is zero, so its entry is first rotated into the
+        // rows above it
+        let (rots, stack) = stack.make_with(n, |_| JacobiRotation {
+            c: E::faer_one(),
+            s: E::faer_zero(),
+        });
+        let has_last_row = n > 0 && subdiag[n - 1] != E::faer_zero();
+        if has_last_row {
+            bidiag_zero_last_row(diag, subdiag, |j, rot| rots[j] = rot);
+        }
+
+        if closed_form {
+            stats.algorithm = SvdAlgorithm::ClosedForm;
+            bidiag_svd_2x2(diag, subdiag, u.rb_mut(), v, perm, accumulate_v, epsilon);
+        } else if n <= jacobi_fallback_threshold {
+            stats.algorithm = SvdAlgorithm::Jacobi;
+            let (mut s, _) = temp_mat_zeroed::<E>(n, n, stack);
+            let mut s = s.as_mut();
+
+            for i in 0..n {
+                s.write(i, i, diag[i]);
+                if i + 1 < n {
+                    s.write(i + 1, i, subdiag[i]);
+                }
+            }
+
+            jacobi_svd_with_perm(
+                s.rb_mut(),
+                u.rb_mut().map(|u| u.submatrix_mut(0, 0, n, n)),
+                v,
+                perm,
+                Skip::None,
+                accumulate_v,
+                epsilon,
+                consider_zero_threshold,
+            );
+
+            for (i, diag) in diag.iter_mut().enumerate() {
+                *diag = s.read(i, i);
+            }
+            if let Some(mut u) = u.rb_mut() {
+                zipped!(u.rb_mut().row_mut(n).as_2d_mut())
+                    .for_each(|unzipped!(mut x)| x.write(E::faer_zero()));
+                if u.ncols() == n + 1 {
+                    zipped!(u.rb_mut().col_mut(n).as_2d_mut())
+                        .for_each(|unzipped!(mut x)| x.write(E::faer_zero()));
+                    u.write(n, n, E::faer_one());
+                }
+            }
+        } else {
+            stats.algorithm = SvdAlgorithm::QrIteration;
+            stats.qr += if accumulate_v {
+                // the rotations are applied to the starting matrix in `v`, and only `u` starts
+                // from the identity
+                if let Some(mut u) = u.rb_mut() {
+                    u.fill_zero();
+                    u.diagonal_mut().column_vector_mut().fill(E::faer_one());
+                }
+                bidiag_svd_qr_algorithm_accumulate(
+                    diag,
+                    subdiag,
+                    u.rb_mut().map(|u| u.submatrix_mut(0, 0, n, n)),
+                    v,
+                    perm,
+                    epsilon,
+                    consider_zero_threshold,
+                    params.high_relative_accuracy,
+                    params.shift_strategy,
+                    params.convergence_criterion,
+                )
+            } else {
+                bidiag_svd_qr_algorithm_impl(
+                    diag,
+                    subdiag,
+                    u.rb_mut(),
+                    v,
+                    perm,
+                    epsilon,
+                    consider_zero_threshold,
+                    params.high_relative_accuracy,
+                    params.shift_strategy,
+                    params.convergence_criterion,
+                )
+            };
+        }
+
+        if let (Some(u), true) = (u, has_last_row) {
+            bidiag_undo_last_row(u, rots);
+        }
+        denormalize(diag, scale);
+    } else if subdiag[..n - 1].contains(&E::faer_zero()) {
+        bidiag_svd_blocks(
+            diag,
+            subdiag,
+            u,
+            v,
+            perm,
+            stats,
+            accumulate_v,
+            jacobi_fallback_threshold,
+            bidiag_qr_fallback_threshold,
+            epsilon,
+            consider_zero_threshold,
+            parallelism,
+            stack,
+            params,
+        );
+    } else {
+        stats.algorithm = SvdAlgorithm::DivideConquer;
+        match u {
+            Some(u) => bidiag_svd_impl(
+                diag,
+                subdiag,
+                u,
+                v,
+                perm,
+                stats,
+                true,
+                false,
+                accumulate_v,
+                initial_guess,
+                0,
+                jacobi_fallback_threshold,
+                epsilon,
+                consider_zero_threshold,
+                parallelism,
+                stack,
+                params,
+            ),
+            None => {
+                // the two rows are stored contiguously, since each column of a column major
+                // matrix is padded to the alignment of the scalars
+                let (mut u, stack) = temp_mat_uninit::<E>(n + 1, 2, stack);
+                let u = u.as_mut().transpose_mut();
+                bidiag_svd_impl(
+                    diag,
+                    subdiag,
+                    u,
+                    v,
+                    perm,
+                    stats,
+                    false,
+                    false,
+                    accumulate_v,
+                    initial_guess,
+                    0,
+                    jacobi_fallback_threshold,
+                    epsilon,
+                    consider_zero_threshold,
+                    parallelism,
+                    stack,
+                    params,
+                );
+            }
+        }
+    }
+}
+
+/// returns the end of the block starting at `start`, i.e., the index following the first exact
+/// zero of the subdiagonal, excluding its last element
+fn block_end<E: RealField>(subdiag: &[E], start: usize) -> usize {
+    let n = subdiag.len();
+    (start..n - 1)
+        .find(|&i| subdiag[i] == E::faer_zero())
+        .map_or(n, |i| i + 1)
+}
+
+/// svd of a bidiagonal matrix whose subdiagonal has exact zeros, which splits it into independent
+/// blocks
+///
+/// each block is solved on its own, and the results are concatenated and sorted, with no merge
+/// step between the blocks
+fn bidiag_svd_blocks<E: RealField>(
+    diag: &mut [E],
+    subdiag: &mut [E],
+    mut u: Option<MatMut<'_, E>>,
+    mut v: Option<MatMut<'_, E>>,
+    mut perm: Option<&mut [usize]>,
+    stats: &mut BidiagSvdStats,
+    accumulate_v: bool,
+    jacobi_fallback_threshold: usize,
+    bidiag_qr_fallback_threshold: usize,
+    epsilon: E,
+    consider_zero_threshold: E,
+    parallelism: Parallelism,
+    mut stack: PodStack<'_>,
+    params: SvdParams,
+) {
+    let n = diag.len();
+
+    if let Some(mut u) = u.rb_mut() {
+        u.fill_zero();
+    }
+    // the starting matrix in `v` is multiplied by the right singular vectors of each block, which
+    // only mix the columns of that block
+    if let (Some(mut v), false) = (v.rb_mut(), accumulate_v) {
+        v.fill_zero();
+    }
+
+    // a block of size k is a square matrix that is solved as a matrix of shape (k + 1, k) with a
+    // zero last row, so its singular vectors overlap the first row and column of the next block.
+    // the blocks with an even index are solved first, then the ones with an odd index, so that
+    // blocks that are solved concurrently never touch the same memory
+    let first_end = block_end(subdiag, 0);
+    for offset in [0, first_end] {
+        let mut stats_phase = BidiagSvdStats::default();
+        bidiag_svd_alternate_blocks(
+            &mut diag[offset..],
+            &mut subdiag[offset..],
+            u.rb_mut().map(|u| {
+                let ncols = u.ncols();
+                u.submatrix_mut(offset, offset, n + 1 - offset, ncols - offset)
+            }),
+            v.rb_mut().map(|v| {
+                if accumulate_v {
+                    let nrows = v.nrows();
+                    v.submatrix_mut(0, offset, nrows, n - offset)
+                } else {
+                    v.submatrix_mut(offset, offset, n - offset, n - offset)
+                }
+            }),
+            perm.as_deref_mut().map(|perm| &mut perm[offset..]),
+            &mut stats_phase,
+            offset,
+            true,
+            accumulate_v,
+            jacobi_fallback_threshold,
+            bidiag_qr_fallback_threshold,
+            epsilon,
+            consider_zero_threshold,
+            parallelism,
+            stack.rb_mut(),
+            params,
+        );
+        *stats += stats_phase;
+    }
+
+    // the matrix is now diagonal
+    for x in &mut *subdiag {
+        *x = E::faer_zero();
+    }
+
+    for k in 0..n {
+        let mut max = diag[k];
+        let mut max_idx = k;
+        for (kk, &d) in diag.iter().enumerate().skip(k + 1) {
+            if d > max {
+                max = d;
+                max_idx = kk;
+            }
+        }
+
+        if k != max_idx {
+            diag.swap(k, max_idx);
+            if let Some(u) = u.rb_mut() {
+                faer_core::permutation::swap_cols(u, k, max_idx);
+            }
+            if let Some(v) = v.rb_mut() {
+                faer_core::permutation::swap_cols(v, k, max_idx);
+            }
+            if let Some(perm) = perm.as_deref_mut() {
+                perm.swap(k, max_idx);
+            }
+        }
+    }
+}
+
+/// returns the workspace needed to solve the blocks of `subdiag` with an even index concurrently
+fn alternate_blocks_req<E: RealField>(
+    subdiag: &[E],
+    jacobi_fallback_threshold: usize,
+    compute_u: bool,
+    compute_v: bool,
+    accumulate_v_nrows: Option<usize>,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    let n = subdiag.len();
+    let mut req = StackReq::empty();
+    let mut start = 0;
+    let mut active = true;
+    while start < n {
+        let end = block_end(subdiag, start);
+        if active {
+            req = req.try_and(bidiag_real_svd_req_impl::<E>(
+                end - start,
+                jacobi_fallback_threshold,
+                compute_u,
+                compute_v,
+                accumulate_v_nrows,
+                parallelism,
+            )?)?;
+        }
+        active = !active;
+        start = end;
+    }
+    Ok(req)
+}
+
+/// solves the blocks of the bidiagonal matrix with an even index, relative to the start of
+/// `diag`, and leaves the other ones untouched
+///
+/// `u` has shape `(n + 1, n + 1)`, and its last row and column are only meaningful if
+/// `contains_last` is true, i.e., if the last block is the last block of the full matrix. in that
+/// case, `u` may also have shape `(n + 1, n)` for the economy left singular vectors.
+/// `offset` is the position of `diag` in the full matrix, and is added to the permutation. if
+/// `accumulate_v` is true, `v` holds the columns of the starting matrix that belong to `diag`, and
+/// may have any number of rows
+fn bidiag_svd_alternate_blocks<E: RealField>(
+    diag: &mut [E],
+    subdiag: &mut [E],
+    u: Option<MatMut<'_, E>>,
+    v: Option<MatMut<'_, E>>,
+    perm: Option<&mut [usize]>,
+    stats: &mut BidiagSvdStats,
+    offset: usize,
+    contains_last: bool,
+    accumulate_v: bool,
+    jacobi_fallback_threshold: usize,
+    bidiag_qr_fallback_threshold: usize,
+    epsilon: E,
+    consider_zero_threshold: E,
+    parallelism: Parallelism,
+    mut stack: PodStack<'_>,
+    params: SvdParams,
+) {
+    let n = diag.len();
+
+    // look for a skipped block close to the middle, and solve the blocks on each side of it
+    // independently
+    let mut split = None;
+    let mut start = 0;
+    let mut active = true;
+    while start < n {
+        let end = block_end(subdiag, start);
+        if !active {
+            split = Some((start, end));
+            if end >= n / 2 {
+                break;
+            }
+        }
+        active = !active;
+        start = end;
+    }
+
+    let (mid_start, mid_end) = match split {
+        Some(split) => split,
+        None => {
+            return bidiag_svd_single_block(
+                diag,
+                subdiag,
+                u,
+                v,
+                perm,
+                stats,
+                offset,
+                contains_last,
+                accumulate_v,
+                jacobi_fallback_threshold,
+                bidiag_qr_fallback_threshold,
+                epsilon,
+                consider_zero_threshold,
+                parallelism,
+                stack,
+                params,
+            )
+        }
+    };
+
+    // the blocks are too small for the task overhead to pay off
+    let parallelism = if n < params.parallel_threshold.unwrap_or(PARALLEL_THRESHOLD) {
+        Parallelism::None
+    } else {
+        parallelism
+    };
+
+    let compute_u = u.is_some();
+    let compute_v = v.is_some();
+    let accumulate_v_nrows = v.rb().filter(|_| accumulate_v).map(|v| v.nrows());
+    let req = |subdiag: &[E]| {
+        alternate_blocks_req::<E>(
+            subdiag,
+            jacobi_fallback_threshold,
+            compute_u,
+            compute_v,
+            accumulate_v_nrows,
+            parallelism,
+        )
+        .and_then(|req| req.try_unaligned_bytes_required())
+    };
+
+    // the workspace is only guaranteed to be large enough for the largest block, so the two sides
+    // are solved one after the other when they don't fit in it together
+    let concurrent_bytes = match (
+        parallelism,
+        req(&subdiag[..mid_start]),
+        req(&subdiag[mid_end..]),
+    ) {
+        (Parallelism::None, _, _) => None,
+        (_, Ok(bytes1), Ok(bytes2)) => bytes1
+            .checked_add(bytes2)
+            .filter(|&bytes| bytes <= stack.len_bytes())
+            .map(|_| bytes1),
+        _ => None,
+    };
+
+    let (diag1, diag2) = diag.split_at_mut(mid_end);
+    let (subdiag1, subdiag2) = subdiag.split_at_mut(mid_end);
+    let diag1 = &mut diag1[..mid_start];
+    let subdiag1 = &mut subdiag1[..mid_start];
+    let (perm1, perm2) = match perm {
+        Some(perm) => {
+            let (perm1, perm2) = perm.split_at_mut(mid_end);
+            (Some(&mut perm1[..mid_start]), Some(perm2))
+        }
+        None => (None, None),
+    };
+    let (u1, u2) = match u {
+        Some(u) => {
+            let (u1, _, _, u2) = u.split_at_mut(mid_end, mid_end);
+            (
+                Some(u1.submatrix_mut(0, 0, mid_start + 1, mid_start + 1)),
+                Some(u2),
+            )
+        }
+        None => (None, None),
+    };
+    let (v1, v2) = match v {
+        Some(v) if accumulate_v => {
+            let (v1, v2) = v.split_at_col_mut(mid_end);
+            (Some(v1.subcols_mut(0, mid_start)), Some(v2))
+        }
+        Some(v) => {
+            let (v1, _, _, v2) = v.split_at_mut(mid_end, mid_end);
+            (Some(v1.submatrix_mut(0, 0, mid_start, mid_start)), Some(v2))
+        }
+        None => (None, None),
+    };
+
+    let mut stats1 = BidiagSvdStats::default();
+    let mut stats2 = BidiagSvdStats::default();
+
+    let solve_1 = |parallelism, stack| {
+        bidiag_svd_alternate_blocks(
+            diag1,
+            subdiag1,
+            u1,
+            v1,
+            perm1,
+            &mut stats1,
+            offset,
+            false,
+            accumulate_v,
+            jacobi_fallback_threshold,
+            bidiag_qr_fallback_threshold,
+            epsilon,
+            consider_zero_threshold,
+            parallelism,
+            stack,
+            params,
+        )
+    };
+    let solve_2 = |parallelism, stack| {
+        if !diag2.is_empty() {
+            bidiag_svd_alternate_blocks(
+                diag2,
+                subdiag2,
+                u2,
+                v2,
+                perm2,
+                &mut stats2,
+                offset + mid_end,
+                contains_last,
+                accumulate_v,
+                jacobi_fallback_threshold,
+                bidiag_qr_fallback_threshold,
+                epsilon,
+                consider_zero_threshold,
+                parallelism,
+                stack,
+                params,
+            )
+        }
+    };
+
+    match concurrent_bytes {
+        Some(bytes1) => {
+            let (mem1, stack2) = stack.make_raw::<u8>(bytes1);
+            let stack1 = PodStack::new(mem1);
+            join_raw(
+                |parallelism| solve_1(parallelism, stack1),
+                |parallelism| solve_2(parallelism, stack2),
+                parallelism,
+            );
+        }
+        None => {
+            solve_1(parallelism, stack.rb_mut());
+            solve_2(parallelism, stack.rb_mut());
+        }
+    }
+
+    *stats += stats1;
+    *stats += stats2;
+}
+
+/// svd of a single block of the bidiagonal matrix, with no exact zeros in its subdiagonal except
+/// possibly the last element
+fn bidiag_svd_single_block<E: RealField>(
+    diag: &mut [E],
+    subdiag: &mut [E],
+    mut u: Option<MatMut<'_, E>>,
+    v: Option<MatMut<'_, E>>,
+    mut perm: Option<&mut [usize]>,
+    stats: &mut BidiagSvdStats,
+    offset: usize,
+    contains_last: bool,
+    accumulate_v: bool,
+    jacobi_fallback_threshold: usize,
+    bidiag_qr_fallback_threshold: usize,
+    epsilon: E,
+    consider_zero_threshold: E,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+    params: SvdParams,
+) {
+    let n = diag.len();
+
+    // the corner of `u` is the top left corner of the next block, which may have already been
+    // solved
+    let corner = match (u.rb(), contains_last) {
+        (Some(u), false) => Some(u.read(n, n)),
+        _ => None,
+    };
+
+    let mut local_stats = BidiagSvdStats::default();
+    compute_bidiag_real_svd_impl(
+        diag,
+        subdiag,
+        u.rb_mut(),
+        v,
+        perm.as_deref_mut(),
+        Some(&mut local_stats),
+        None,
+        accumulate_v,
+        jacobi_fallback_threshold,
+        bidiag_qr_fallback_threshold,
+        epsilon,
+        consider_zero_threshold,
+        parallelism,
+        stack,
+        params,
+    );
+    *stats += local_stats;
+
+    if let Some(perm) = perm {
+        for p in perm {
+            *p += offset;
+        }
+    }
+
+    // the blocks are found again by looking for the zeros of the subdiagonal after this one is
+    // solved, so its layout is restored
+    for x in &mut subdiag[..n - 1] {
+        *x = E::faer_one();
+    }
+    if !contains_last {
+        subdiag[n - 1] = E::faer_zero();
+    }
+
+    if let (Some(u), Some(corner), false) = (u, corner, contains_last) {
+        // the last row of the block is zero, so it is orthogonal to the left singular vectors of
+        // the nonzero singular values. the ones of the zero singular values may still have a
+        // component along it, which is rotated into the last column
+        let (mut u, mut u_last) = u.split_at_col_mut(n);
+        for j in 0..n {
+            let rot = JacobiRotation::make_givens(u_last.read(n, 0), u.read(n, j));
+            rot.apply_on_the_right_in_place(u_last.rb_mut(), u.rb_mut().col_mut(j).as_2d_mut());
+        }
+
+        u.row_mut(n).fill_zero();
+        u_last.fill_zero();
+        u_last.write(n, 0, corner);
+    }
+}
+
+/// returns the column `k` at which the problem is split, along with the number of bytes of
+/// workspace given to the first subproblem.
+///
+/// the split is moved away from the middle only to a column of the middle half whose coupling
+/// `max(|subdiag[k - 1]|, |subdiag[k]|)` with the rest is negligible. the rows of the subproblems
+/// that touch the removed column are then decoupled from the rest, so the merge deflates almost
+/// entirely, which makes up for the unbalanced subproblems. among those, the one closest to the
+/// middle is chosen.
+///
+/// falls back to splitting in halves if there is no such column, or if the workspace of its
+/// subproblems doesn't fit in `stack_bytes`
+fn split_by_mass<E: RealField>(
+    subdiag: &[E],
+    jacobi_fallback_threshold: usize,
+    compute_u: bool,
+    compute_v: bool,
+    accumulate_v_nrows: Option<usize>,
+    secular_window: Option<usize>,
+    epsilon: E,
+    stack_bytes: usize,
+    parallelism: Parallelism,
+) -> (usize, usize) {
+    let n = subdiag.len();
+    let half = (n / 2, stack_bytes / 2);
+    let req_bytes = |n| {
+        // the subproblems of a windowed merge step are windowed too
+        let req = match secular_window {
+            Some(window) => {
+                divide_conquer_windowed_req::<E>(n, jacobi_fallback_threshold, window, true)
+            }
+            None => bidiag_real_svd_req_impl::<E>(
+                n,
+                jacobi_fallback_threshold,
+                compute_u,
+                compute_v,
+                accumulate_v_nrows,
+                parallelism,
+            ),
+        };
+        req.and_then(|req| req.try_unaligned_bytes_required())
+            .unwrap_or(usize::MAX)
+    };
+
+    // both subproblems must stay above the jacobi threshold, since the jacobi fallback of the
+    // split handles them both at once
+    let lo = Ord::max(n / 4, jacobi_fallback_threshold + 1);
+    let hi = Ord::min(n - n / 4, n.saturating_sub(jacobi_fallback_threshold + 2));
+    if lo > hi {
+        return half;
+    }
+
+    // the matrix is normalized so that its largest entry is close to one
+    let mid = n / 2;
+    for offset in 0..=Ord::max(mid.saturating_sub(lo), hi.saturating_sub(mid)) {
+        for k in [mid.wrapping_sub(offset), mid + offset] {
+            if k < lo || k > hi {
+                continue;
+            }
+            let (left, right) = (subdiag[k - 1].faer_abs(), subdiag[k].faer_abs());
+            if left > epsilon || right > epsilon {
+                continue;
+            }
+            let bytes1 = req_bytes(k);
+            let bytes2 = req_bytes(n - 1 - k);
+            if bytes1.saturating_add(bytes2) <= stack_bytes {
+                return (k, bytes1);
+            }
+        }
+    }
+
+    half
+}
+
+/// returns the smallest power of two greater than or equal to the positive value `x`, or the largest
+/// finite one if that would overflow
+fn power_of_two_above<E: RealField>(x: E) -> E {
+    let two = E::faer_one().faer_add(E::faer_one());
+    let one_half = two.faer_inv();
+
+    let mut p = E::faer_one();
+    while p < x {
+        let next = p.faer_mul(two);
+        if !next.faer_is_finite() {
+            break;
+        }
+        p = next;
+    }
+    while p.faer_mul(one_half) >= x {
+        p = p.faer_mul(one_half);
+    }
+    p
+}
+
+/// returns the largest magnitude of the entries of the bidiagonal matrix, which is only found by
+/// comparing them, so it can't overflow, unlike a norm
+fn bidiagonal_max_abs<E: RealField>(diag: &[E], subdiag: &[E]) -> E {
+    let mut max_val = E::faer_zero();
+    for x in diag.iter().chain(subdiag) {
+        let val = x.faer_abs();
+        if val > max_val {
+            max_val = val;
+        }
+    }
+    max_val
+}
+
+/// divides the entries of the bidiagonal matrix by the smallest power of two above the largest of
+/// their magnitudes, and returns it, or returns zero and leaves them unchanged if they're all
+/// zero
+///
+/// the scaling is exact, barring underflow, so nested normalizations of the subproblems are exact
+/// as well, instead of accumulating rounding errors with the recursion depth. the subnormal
+/// entries, and the ones that underflow to subnormal values, are flushed to zero, see
+/// [`flush_subnormals`]
+fn normalize_bidiagonal<E: RealField>(diag: &mut [E], subdiag: &mut [E]) -> E {
+    flush_subnormals(diag, subdiag);
+    let max_val = bidiagonal_max_abs(diag, subdiag);
+    // the entries may still be negative zeros
+    if max_val == E::faer_zero() {
+        return max_val;
+    }
+
+    let scale = power_of_two_above(max_val);
+    for x in diag.iter_mut().chain(subdiag.iter_mut()) {
+        *x = (*x).faer_div(scale);
+    }
+    if scale > E::faer_one() {
+        flush_subnormals(diag, subdiag);
+    }
+    scale
+}
+
+/// replaces the subnormal entries of the bidiagonal matrix by zeros
+///
+/// cpus that flush subnormal inputs or outputs to zero (the daz and ftz modes of x86) would
+/// otherwise see a different matrix than the others, and the singular values of the blocks split
+/// by a subnormal subdiagonal entry could differ in more than its magnitude. the comparison gives
+/// the same result in every mode, since a subnormal is below the threshold either way
+fn flush_subnormals<E: RealField>(diag: &mut [E], subdiag: &mut [E]) {
+    if let Some(min_positive) = E::faer_zero_threshold() {
+        for x in diag.iter_mut().chain(subdiag.iter_mut()) {
+            if x.faer_abs() < min_positive {
+                *x = E::faer_zero();
+            }
+        }
+    }
+}
+
+/// undoes [`normalize_bidiagonal`] on the singular values of the normalized matrix
+fn denormalize<E: RealField>(singular_values: &mut [E], scale: E) {
+    if scale == E::faer_zero() {
+        return;
+    }
+    for x in singular_values {
+        *x = (*x).faer_mul(scale);
+    }
+}
+
+/// svd of the `(n + 1, n)` bidiagonal matrix by the qr algorithm, with the same outputs as a
+/// subproblem of `bidiag_svd_impl`, for the subproblems past `params.max_depth`. `u` has
+/// `n + 1` columns, and either `n + 1` rows, or the two rows that are the first and last ones of
+/// the full matrix
+///
+/// the last row is rotated away first, so that the left singular vectors are the rows of the
+/// product of these rotations, multiplied by those of the upper `(n, n)` block. only the rows
+/// that are stored in `u` are formed, so this doesn't need any workspace
+///
+/// if `accumulate_v` is true, the rotations are applied to the starting matrix in `v` instead of
+/// the identity
+fn bidiag_svd_qr_subproblem<E: RealField>(
+    diag: &mut [E],
+    subdiag: &mut [E],
+    mut u: MatMut<'_, E>,
+    v: Option<MatMut<'_, E>>,
+    perm: Option<&mut [usize]>,
+    accumulate_v: bool,
+    epsilon: E,
+    consider_zero_threshold: E,
+    high_relative_accuracy: bool,
+    shift_strategy: ShiftStrategy,
+    convergence_criterion: ConvergenceCriterion,
+) -> QrStats {
+    let n = diag.len();
+
+    u.fill_zero();
+    if u.nrows() == n + 1 {
+        u.rb_mut()
+            .diagonal_mut()
+            .column_vector_mut()
+            .fill(E::faer_one());
+    } else {
+        u.write(0, 0, E::faer_one());
+        u.write(1, n, E::faer_one());
+    }
+    bidiag_zero_last_row(diag, subdiag, |j, rot| {
+        let (left, right) = u.rb_mut().split_at_col_mut(n);
+        rot.transpose()
+            .apply_on_the_right_in_place(left.col_mut(j).as_2d_mut(), right.col_mut(0).as_2d_mut());
+    });
+
+    let v = v.map(|mut v| {
+        if !accumulate_v {
+            v.fill_zero();
+            v.rb_mut()
+                .diagonal_mut()
+                .column_vector_mut()
+                .fill(E::faer_one());
+        }
+        v
+    });
+    let nrows = u.nrows();
+    bidiag_svd_qr_algorithm_accumulate(
+        diag,
+        subdiag,
+        Some(u.submatrix_mut(0, 0, nrows, n)),
+        v,
+        perm,
+        epsilon,
+        consider_zero_threshold,
+        high_relative_accuracy,
+        shift_strategy,
+        convergence_criterion,
+    )
+}
+
+/// svd of bidiagonal lower matrix
+///
+/// if `accumulate_v` is true, `v` holds a starting matrix with `n` columns and any number of rows,
+/// which is multiplied by the right singular vectors instead of being overwritten by them
+fn bidiag_svd_impl<E: RealField>(
+    diag: &mut [E],
+    subdiag: &mut [E],
+    mut u: MatMut<'_, E>,
+    v: Option<MatMut<'_, E>>,
+    mut perm: Option<&mut [usize]>,
+    stats: &mut BidiagSvdStats,
+    fill_u: bool,
+    smallest_only: bool,
+    accumulate_v: bool,
+    mut initial_guess: Option<&mut [E]>,
+    depth: usize,
+    jacobi_fallback_threshold: usize,
+    epsilon: E,
+    consider_zero_threshold: E,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+    params: SvdParams,
+) {
+    let n = diag.len();
+
+    // past the maximum depth, the subproblems are no longer split. the top level problem is
+    // always split, since it may request outputs that the qr algorithm can't provide
+    if depth > 0 && matches!(params.max_depth, Some(max_depth) if depth >= max_depth) {
+        stats.qr += bidiag_svd_qr_subproblem(
+            diag,
+            subdiag,
+            u,
+            v,
+            perm,
+            accumulate_v,
+            epsilon,
+            consider_zero_threshold,
+            params.high_relative_accuracy,
+            params.shift_strategy,
+            params.convergence_criterion,
+        );
+        return;
+    }
+
+    let scale = normalize_bidiagonal(diag, subdiag);
+
+    // the svd of the zero matrix has identity singular vectors. the single columns requested by
+    // `smallest_only` are the last ones of the `n` first columns
+    if scale == E::faer_zero() {
+        u.fill_zero();
+        if smallest_only {
+            u.write(n - 1, 0, E::faer_one());
+        } else if u.nrows() == n + 1 {
+            u.diagonal_mut().column_vector_mut().fill(E::faer_one());
+        } else {
+            u.write(0, 0, E::faer_one());
+            u.write(1, n, E::faer_one());
+        }
+        if let (Some(mut v), false) = (v, accumulate_v) {
+            v.fill_zero();
+            if smallest_only {
+                v.write(n - 1, 0, E::faer_one());
+            } else {
+                v.diagonal_mut().column_vector_mut().fill(E::faer_one());
+            }
+        };
+        if let Some(perm) = perm {
+            for (i, p) in perm.iter_mut().enumerate() {
+                *p = i;
+            }
+        }
+        return;
+    }
+
+    // the guess of the singular values is scaled along with the matrix
+    if let Some(guess) = initial_guess.as_deref_mut() {
+        for x in guess {
+            *x = (*x).faer_div(scale);
+        }
+    }
+
+    assert!(
+        subdiag.len() == n,
+        "`subdiag` must have the same length as `diag`, with its last element in the last row",
+    );
+    assert!(n > jacobi_fallback_threshold);
+    assert!(!(accumulate_v && smallest_only));
+
+    // an economy `u` only holds the first `n` left singular vectors. the ones of the subproblems
+    // still need `n + 1` columns, so they're stored in a temporary matrix, and the final product
+    // is written directly to `u` without computing its last column. the same goes for `u` and `v`
+    // when only the vectors of the smallest singular value are requested, in which case they hold
+    // a single column
+    let economy = u.ncols() == n || smallest_only;
+    let (mut u_full, stack) = temp_mat_uninit::<E>(n + 1, economy as usize * (n + 1), stack);
+    let (mut u, mut u_out) = if economy {
+        (u_full.as_mut(), Some(u))
+    } else {
+        (u, None)
+    };
+    let (mut v_full, mut stack) = temp_mat_uninit::<E>(n, smallest_only as usize * n, stack);
+    let (mut v, mut v_out) = if smallest_only {
+        (v.is_some().then_some(v_full.as_mut()), v)
+    } else {
+        (v, None)
+    };
+
+    // the singular vectors depend on all the singular values, so the range can only be used when
+    // they are not requested. this is never the case for the subproblems
+    let range = if fill_u || v.is_some() {
+        None
+    } else {
+        params.singular_value_range.map(|(lo, hi)| {
+            (
+                E::faer_from_f64(lo).faer_div(scale),
+                E::faer_from_f64(hi).faer_div(scale),
+            )
+        })
+    };
+
+    // without `v`, the merge steps can solve their secular equations a window at a time, as long
+    // as `u` is compact. the subproblems of a compact `u` are compact too, so this holds at every
+    // depth below the top level
+    let secular_window = params.secular_window.filter(|_| {
+        u.nrows() != n + 1
+            && v.is_none()
+            && perm.is_none()
+            && range.is_none()
+            && initial_guess.is_none()
+    });
+
+    let v_nrows = v.rb().map_or(n, |v| v.nrows());
+    let stack_bytes = stack.len_bytes();
+    let (k, stack1_bytes) = match params.split_strategy {
+        SplitStrategy::Half => (n / 2, stack_bytes / 2),
+        SplitStrategy::ByMass => split_by_mass::<E>(
+            subdiag,
+            jacobi_fallback_threshold,
+            u.nrows() == n + 1,
+            v.is_some(),
+            accumulate_v.then_some(v_nrows),
+            secular_window,
+            epsilon,
+            stack_bytes,
+            parallelism,
+        ),
+    };
+    let rem = n - 1 - k;
+
+    // the right singular vectors are multiplied by the block structure of the subproblems, whose
+    // column `0` is row `k` of the identity, so column `k` of the starting matrix is moved to the
+    // front, and the columns of each subproblem follow
+    if let (Some(mut v), true) = (v.rb_mut(), accumulate_v) {
+        for i in (0..k).rev() {
+            faer_core::permutation::swap_cols(v.rb_mut(), i, i + 1);
+        }
+    }
+
+    let (d1, alpha_d2) = diag.split_at_mut(k);
+    let (sub_d1, beta_sub_d2) = subdiag.split_at_mut(k);
+    let (alpha, d2) = alpha_d2.split_first_mut().unwrap();
+    let (beta, sub_d2) = beta_sub_d2.split_first_mut().unwrap();
+    let alpha = *alpha;
+    let beta = *beta;
+
+    let compact_u = (u.nrows() != n + 1) as usize;
+
+    // the permutations of the two subproblems are stored in `perm[..k]` and `perm[k + 1..]`
+    let (perm1, perm2) = match perm.as_deref_mut() {
+        Some(perm) => {
+            let (perm1, perm2) = perm.split_at_mut(k);
+            (Some(perm1), Some(&mut perm2[1..]))
+        }
+        None => (None, None),
+    };
+
+    if k <= jacobi_fallback_threshold || rem <= jacobi_fallback_threshold {
+        let (mut u1_alloc, stack) =
+            temp_mat_uninit::<E>(k + 1, compact_u * (k + 1), stack.rb_mut());
+        let mut u1_alloc = u1_alloc.as_mut();
+        let (mut u2_alloc, stack) = temp_mat_uninit::<E>(rem + 1, compact_u * (rem + 1), stack);
+        let mut u2_alloc = u2_alloc.as_mut();
+
+        // when accumulating, the right singular vectors of the subproblems are computed on their
+        // own, then multiplied into the starting matrix
+        let accumulate = accumulate_v as usize;
+        let (mut v1_alloc, stack) = temp_mat_uninit::<E>(k, accumulate * (k + 1), stack);
+        let (mut v2_alloc, stack) = temp_mat_uninit::<E>(rem, accumulate * (rem + 1), stack);
+        let (mut v_prod, stack) =
+            temp_mat_uninit::<E>(v_nrows, accumulate * Ord::max(k, rem), stack);
+        let mut v1_alloc = v1_alloc.as_mut();
+        let mut v2_alloc = v2_alloc.as_mut();
+        let mut v_prod = v_prod.as_mut();
+
+        let (_u0, mut u1, mut u2) = if compact_u == 0 {
+            let (u1, u2) = u.rb_mut().split_at_row_mut(k + 1);
+            let (u0, u1) = u1.split_at_col_mut(1);
+            (
+                u0,
+                u1.submatrix_mut(0, 0, k + 1, k + 1),
+                u2.submatrix_mut(0, k, rem + 1, rem + 1),
+            )
+        } else {
+            (
+                u.rb_mut().col_mut(0).as_2d_mut(),
+                u1_alloc.rb_mut(),
+                u2_alloc.rb_mut(),
+            )
+        };
+
+        let (mut v1, mut v2) = match v.rb_mut() {
+            Some(_) if accumulate_v => (Some(v1_alloc.rb_mut()), Some(v2_alloc.rb_mut())),
+            Some(v) => {
+                let (v1, v2) = v.split_at_row_mut(k);
+                (
+                    Some(v1.submatrix_mut(0, 1, k, k + 1)),
+                    Some(v2.submatrix_mut(1, k, rem, rem + 1)),
+                )
+            }
+            None => (None, None),
+        };
+
+        let (mut matrix1, stack) = temp_mat_zeroed::<E>(k + 1, k + 1, stack);
+        let (mut matrix2, _) = temp_mat_zeroed::<E>(rem + 1, rem + 1, stack);
+        let mut matrix1 = matrix1.as_mut();
+        let mut matrix2 = matrix2.as_mut();
+
+        for j in 0..k {
+            matrix1.write(j, j, d1[j]);
+            matrix1.write(j + 1, j, sub_d1[j]);
+        }
+        for j in 0..rem {
+            matrix2.write(j, j + 1, d2[j]);
+            matrix2.write(j + 1, j + 1, sub_d2[j]);
+        }
+
+        jacobi_svd_with_perm(
+            matrix1.rb_mut(),
+            Some(u1.rb_mut()),
+            v1.rb_mut(),
+            perm1,
+            Skip::Last,
+            false,
+            epsilon,
+            consider_zero_threshold,
+        );
+        for j in 0..matrix1.ncols() {
+            for i in 0..matrix1.nrows() {
+                if i != j {
+                    matrix1.write(i, j, E::faer_zero());
+                }
+            }
+        }
+        jacobi_svd_with_perm(
+            matrix2.rb_mut(),
+            Some(u2.rb_mut()),
+            v2.rb_mut(),
+            perm2,
+            Skip::First,
+            false,
+            epsilon,
+            consider_zero_threshold,
+        );
+        for j in 0..matrix2.ncols() {
+            for i in 0..matrix1.nrows() {
+                if i != j {
+                    matrix1.write(i, j, E::faer_zero());
+                }
+            }
+        }
+
+        // the columns that are consumed by the merge step are overwritten with nan, so that stale
+        // reads show up in the output. this is compiled out unless the `svd-debug-poison` feature
+        // is enabled, or in the tests of this crate
+        #[cfg(any(test, feature = "svd-debug-poison"))]
+        {
+            if let Some(v1) = v1 {
+                zipped!(v1.col_mut(k).as_2d_mut())
+                    .for_each(|unzipped!(mut x)| x.write(E::faer_nan()));
+            }
+            if let Some(v2) = v2 {
+                zipped!(v2.col_mut(0).as_2d_mut())
+                    .for_each(|unzipped!(mut x)| x.write(E::faer_nan()));
+            }
+        }
+
+        if let (Some(mut v), true) = (v.rb_mut(), accumulate_v) {
+            for (start, len, v_rhs) in [
+                (1, k, v1_alloc.rb().subcols(0, k)),
+                (k + 1, rem, v2_alloc.rb().subcols(1, rem)),
+            ] {
+                let mut v_prod = v_prod.rb_mut().subcols_mut(0, len);
+                faer_core::mul::matmul(
+                    v_prod.rb_mut(),
+                    v.rb().subcols(start, len),
+                    v_rhs,
+                    None,
+                    E::faer_one(),
+                    parallelism,
+                );
+                zipped!(v.rb_mut().subcols_mut(start, len), v_prod.rb())
+                    .for_each(|unzipped!(mut dst, src)| dst.write(src.read()));
+            }
+        }
+
+        for j in 0..k {
+            diag[j + 1] = matrix1.read(j, j);
+        }
+        for j in 0..rem {
+            diag[j + k + 1] = matrix2.read(j + 1, j + 1);
+        }
+
+        if compact_u == 1 {
+            // need to copy the first and last rows
             //
             // NOTE: we handle the rotation of (Q1, q1) here, so no need to handle it later when
             // compact_u == 1
@@ -1750,566 +4926,4375 @@ fn bidiag_svd_impl<E: RealField>(
                     u1_alloc.rb().row(row1).subcols(0, k).as_2d(),
                 )
                 .for_each(|unzipped!(mut dst, src)| dst.write(src.read()));
-                u.write(row, 0, u1_alloc.read(row1, k));
+                u.write(row, 0, u1_alloc.read(row1, k));
+
+                zipped!(
+                    u.rb_mut().row_mut(row).subcols_mut(k + 1, rem).as_2d_mut(),
+                    u2_alloc.rb().row(row2).subcols(1, rem).as_2d(),
+                )
+                .for_each(|unzipped!(mut dst, src)| dst.write(src.read()));
+                u.write(row, n, u2_alloc.read(row2, 0));
+            }
+        } else {
+            let (_, u2) = if compact_u == 0 {
+                let (u1, u2) = u.rb_mut().split_at_row_mut(k + 1);
+                (u1, u2)
+            } else {
+                let (u1, u2) = u.rb_mut().split_at_row_mut(1);
+                (u1, u2)
+            };
+
+            let (left, right) = u2.split_at_col_mut(k + 1);
+            let left = left.col_mut(k);
+            let right = right.col_mut(rem);
+            zipped!(right.as_2d_mut(), left.as_2d_mut()).for_each(|unzipped!(mut right, left)| {
+                right.write(left.read());
+
+                #[cfg(any(test, feature = "svd-debug-poison"))]
+                {
+                    let mut left = left;
+                    left.write(E::faer_nan());
+                }
+            });
+        }
+    } else {
+        let (mut u1, mut u2) = if compact_u == 0 {
+            let (u1, u2) = u.rb_mut().split_at_row_mut(k + 1);
+            (
+                u1.submatrix_mut(0, 1, k + 1, k + 1),
+                u2.submatrix_mut(0, k + 1, rem + 1, rem + 1),
+            )
+        } else {
+            // NOTE: need to handle rotation of Q1, q1
+            let (u1, u2) = u.rb_mut().split_at_col_mut(k + 1);
+            (u1, u2)
+        };
+
+        let (mut v1, mut v2) = match v.rb_mut() {
+            Some(v) if accumulate_v => {
+                let (v1, v2) = v.split_at_col_mut(k + 1);
+                (Some(v1.subcols_mut(1, k)), Some(v2))
+            }
+            Some(v) => {
+                let (v1, v2) = v.split_at_row_mut(k);
+                (
+                    Some(v1.submatrix_mut(0, 1, k, k)),
+                    Some(v2.submatrix_mut(1, k + 1, rem, rem)),
+                )
+            }
+            None => (None, None),
+        };
+
+        let (mem1, stack2) = stack.rb_mut().make_raw::<u8>(stack1_bytes);
+        let stack1 = PodStack::new(mem1);
+
+        // the subproblems are too small for the task overhead to pay off
+        let parallelism = if n < params.parallel_threshold.unwrap_or(PARALLEL_THRESHOLD) {
+            Parallelism::None
+        } else {
+            parallelism
+        };
+
+        let mut stats1 = BidiagSvdStats::default();
+        let mut stats2 = BidiagSvdStats::default();
+        join_raw(
+            |parallelism| {
+                bidiag_svd_impl(
+                    d1,
+                    sub_d1,
+                    u1.rb_mut(),
+                    v1.rb_mut(),
+                    perm1,
+                    &mut stats1,
+                    true,
+                    false,
+                    accumulate_v,
+                    None,
+                    depth + 1,
+                    jacobi_fallback_threshold,
+                    epsilon,
+                    consider_zero_threshold,
+                    parallelism,
+                    stack1,
+                    params,
+                );
+            },
+            |parallelism| {
+                bidiag_svd_impl(
+                    d2,
+                    sub_d2,
+                    u2.rb_mut(),
+                    v2.rb_mut(),
+                    perm2,
+                    &mut stats2,
+                    true,
+                    false,
+                    accumulate_v,
+                    None,
+                    depth + 1,
+                    jacobi_fallback_threshold,
+                    epsilon,
+                    consider_zero_threshold,
+                    parallelism,
+                    stack2,
+                    params,
+                );
+            },
+            parallelism,
+        );
+        *stats += stats1;
+        *stats += stats2;
+
+        if compact_u == 1 {
+            // handle rotation of Q1, q1
+            for i in (0..k).rev() {
+                faer_core::permutation::swap_cols(u1.rb_mut(), i, i + 1);
+            }
+        }
+
+        for i in (0..k).rev() {
+            diag[i + 1] = diag[i];
+        }
+    }
+
+    if let (Some(mut v), false) = (v.rb_mut(), accumulate_v) {
+        v.write(k, 0, E::faer_one());
+    };
+
+    // column `0` of the merged problem comes from the removed row `k`, and the remaining columns
+    // come from the two subproblems
+    if let Some(perm) = perm.as_deref_mut() {
+        for i in (0..k).rev() {
+            perm[i + 1] = perm[i];
+        }
+        perm[0] = k;
+        for p in &mut perm[k + 1..] {
+            *p += k + 1;
+        }
+    }
+
+    let lambda = if compact_u == 0 {
+        u.read(k, k + 1)
+    } else {
+        // we already rotated u
+        u.read(1, 0)
+    };
+    let phi = if compact_u == 0 {
+        u.read(k + 1, n)
+    } else {
+        u.read(0, n)
+    };
+
+    let al = alpha.faer_mul(lambda);
+    let bp = beta.faer_mul(phi);
+
+    let r0 = ((al.faer_mul(al)).faer_add(bp.faer_mul(bp))).faer_sqrt();
+    let (c0, s0) = if r0 == E::faer_zero() {
+        (E::faer_one(), E::faer_zero())
+    } else {
+        (al.faer_div(r0), bp.faer_div(r0))
+    };
+
+    let col0 = subdiag;
+    diag[0] = r0;
+    col0[0] = r0;
+
+    if compact_u == 0 {
+        let (u1, u2) = if compact_u == 0 {
+            let (u1, u2) = u.rb_mut().split_at_row_mut(k + 1);
+            (u1, u2)
+        } else {
+            let (u1, u2) = u.rb_mut().split_at_row_mut(1);
+            (u1, u2)
+        };
+
+        let (mut u0_top, u1) = u1.split_at_col_mut(1);
+        let (u1, mut un_top) = u1.split_at_col_mut(n - 1);
+        let (mut u0_bot, u2) = u2.split_at_col_mut(1);
+        let (u2, mut un_bot) = u2.split_at_col_mut(n - 1);
+
+        for j in 0..k {
+            col0[j + 1] = alpha.faer_mul(u1.read(k, j));
+        }
+        for j in 0..rem {
+            col0[j + 1 + k] = beta.faer_mul(u2.read(0, j + k));
+        }
+
+        zipped!(
+            u0_top.rb_mut().col_mut(0).as_2d_mut(),
+            un_top.rb_mut().col_mut(0).as_2d_mut(),
+            u1.col_mut(k).as_2d_mut(),
+        )
+        .for_each(|unzipped!(mut dst0, mut dstn, src)| {
+            let src_ = src.read();
+            dst0.write(c0.faer_mul(src_));
+            dstn.write(s0.faer_neg().faer_mul(src_));
+            #[cfg(any(test, feature = "svd-debug-poison"))]
+            {
+                let mut src = src;
+                src.write(E::faer_nan());
+            }
+        });
+
+        zipped!(
+            u0_bot.rb_mut().col_mut(0).as_2d_mut(),
+            un_bot.rb_mut().col_mut(0).as_2d_mut(),
+        )
+        .for_each(|unzipped!(mut dst0, mut dstn)| {
+            let src_ = dstn.read();
+            dst0.write(s0.faer_mul(src_));
+            dstn.write(c0.faer_mul(src_));
+        });
+    } else {
+        for j in 0..k {
+            col0[j + 1] = alpha.faer_mul(u.read(1, j + 1));
+            u.write(1, j + 1, E::faer_zero());
+        }
+        for j in 0..rem {
+            col0[j + 1 + k] = beta.faer_mul(u.read(0, j + k + 1));
+            u.write(0, j + k + 1, E::faer_zero());
+        }
+
+        let q10 = u.read(0, 0);
+        let q21 = u.read(1, n);
+
+        u.write(0, 0, c0.faer_mul(q10));
+        u.write(0, n, s0.faer_neg().faer_mul(q10));
+        u.write(1, 0, s0.faer_mul(q21));
+        u.write(1, n, c0.faer_mul(q21));
+    }
+
+    // the windowed merge step only updates the first and last rows of `u`, and computes the
+    // singular vectors of the arrow matrix one at a time
+    if let Some(window) = secular_window {
+        *stats += merge_singular_values_windowed(
+            diag,
+            col0,
+            fill_u.then_some(u.rb_mut()),
+            window,
+            epsilon,
+            consider_zero_threshold,
+            parallelism,
+            stack.rb_mut(),
+            params,
+        );
+        denormalize(diag, scale);
+        return;
+    }
+
+    let col_perm = perm;
+    let (perm, stack) = stack.rb_mut().make_with(n, |_| 0usize);
+    let (jacobi_coeffs, stack) = stack.make_with(n, |_| JacobiRotation {
+        c: E::faer_zero(),
+        s: E::faer_zero(),
+    });
+    let (jacobi_indices, mut stack) = stack.make_with(n, |_| 0);
+
+    let (jacobi_0i, jacobi_ij) = {
+        let (transpositions, stack) = stack.rb_mut().make_with(n, |_| 0usize);
+        deflate(
+            diag,
+            col0,
+            jacobi_coeffs,
+            jacobi_indices,
+            u.rb_mut(),
+            v.rb_mut(),
+            transpositions,
+            perm,
+            k,
+            epsilon,
+            col0_flush(epsilon, &params),
+            consider_zero_threshold,
+            stack,
+        )
+    };
+
+    let allocate_vm = v.is_some() as usize;
+    let allocate_um = fill_u as usize;
+    let um_ncols = if smallest_only {
+        1
+    } else if economy {
+        n
+    } else {
+        n + 1
+    };
+    let vm_ncols = if smallest_only { 1 } else { n };
+    let (mut um, stack) = temp_mat_zeroed::<E>(n + 1, allocate_um * um_ncols, stack);
+    let (mut vm, mut stack) = temp_mat_zeroed::<E>(n, allocate_vm * vm_ncols, stack);
+    let mut um = um.as_mut();
+    let mut vm = vm.as_mut();
+
+    // the secular equation solves are too cheap for the task overhead to pay off on small
+    // problems
+    let secular_parallelism = if n < params.parallel_threshold.unwrap_or(PARALLEL_THRESHOLD) {
+        Parallelism::None
+    } else {
+        parallelism
+    };
+    *stats += compute_svd_of_m(
+        fill_u.then_some(um.rb_mut()),
+        v.is_some().then_some(vm.rb_mut()),
+        smallest_only,
+        diag,
+        col0,
+        perm,
+        col_perm,
+        range,
+        epsilon,
+        params.refine_iters,
+        params.secular_bracket,
+        initial_guess.as_deref(),
+        consider_zero_threshold,
+        secular_parallelism,
+        stack.rb_mut(),
+    );
+
+    if fill_u {
+        for (rot, &i) in jacobi_coeffs[..jacobi_0i]
+            .iter()
+            .zip(&jacobi_indices[..jacobi_0i])
+            .rev()
+        {
+            let (um_top, um_bot) = um.rb_mut().split_at_row_mut(i);
+            rot.apply_on_the_left_in_place(
+                um_top.row_mut(0).as_2d_mut(),
+                um_bot.row_mut(0).as_2d_mut(),
+            );
+        }
+    }
+
+    for (rot, &i) in jacobi_coeffs[jacobi_0i..][..jacobi_ij]
+        .iter()
+        .zip(&jacobi_indices[jacobi_0i..][..jacobi_ij])
+        .rev()
+    {
+        // the rotation was computed after the permutation, between the entries at positions
+        // `i - 1` and `i`, whose singular vector rows are written to `perm[i - 1]` and `perm[i]`
+        let actual_i = perm[i - 1];
+        let actual_j = perm[i];
+
+        if fill_u {
+            let (row_i, row_j) = if actual_i < actual_j {
+                let (um_top, um_bot) = um.rb_mut().split_at_row_mut(actual_j);
+                (um_top.row_mut(actual_i), um_bot.row_mut(0))
+            } else {
+                let (um_top, um_bot) = um.rb_mut().split_at_row_mut(actual_i);
+                (um_bot.row_mut(0), um_top.row_mut(actual_j))
+            };
+            rot.apply_on_the_left_in_place(row_i.as_2d_mut(), row_j.as_2d_mut());
+        }
+
+        if v.is_some() {
+            let (row_i, row_j) = if actual_i < actual_j {
+                let (vm_top, vm_bot) = vm.rb_mut().split_at_row_mut(actual_j);
+                (vm_top.row_mut(actual_i), vm_bot.row_mut(0))
+            } else {
+                let (vm_top, vm_bot) = vm.rb_mut().split_at_row_mut(actual_i);
+                (vm_bot.row_mut(0), vm_top.row_mut(actual_j))
+            };
+            rot.apply_on_the_left_in_place(row_i.as_2d_mut(), row_j.as_2d_mut());
+        }
+    }
+
+    let _v_is_none = v.is_none();
+
+    let mut update_v = |parallelism, stack: PodStack<'_>| {
+        // the starting matrix already holds the right singular vectors of the subproblems
+        if let (Some(mut v), true) = (v.rb_mut(), accumulate_v) {
+            let (mut combined_v, _) = temp_mat_uninit::<E>(v_nrows, n, stack);
+            let mut combined_v = combined_v.as_mut();
+            faer_core::mul::matmul(
+                combined_v.rb_mut(),
+                v.rb(),
+                vm.rb(),
+                None,
+                E::faer_one(),
+                parallelism,
+            );
+            zipped!(v.rb_mut(), combined_v.rb())
+                .for_each(|unzipped!(mut dst, src)| dst.write(src.read()));
+            return;
+        }
+
+        let allocate_combined_v = (v_out.is_none()) as usize * allocate_vm;
+        let (mut combined_v, _) = temp_mat_uninit::<E>(n, allocate_combined_v * n, stack);
+        let mut combined_v = match v_out.rb_mut() {
+            Some(v_out) => v_out,
+            None => combined_v.as_mut(),
+        };
+        let v_rhs = vm.rb();
+
+        if let Some(mut v) = v.rb_mut() {
+            let mut combined_v = combined_v.rb_mut();
+            let (mut combined_v1, combined_v2) = combined_v.rb_mut().split_at_row_mut(k);
+            let mut combined_v2 = combined_v2.submatrix_mut(1, 0, rem, vm_ncols);
+
+            let v_lhs = v.rb();
+            let v_lhs1 = v_lhs.submatrix(0, 1, k, k);
+            let v_lhs2 = v_lhs.submatrix(k + 1, k + 1, rem, rem);
+            let (v_rhs1, v_rhs2) = v_rhs.split_at_row(1).1.split_at_row(k);
+
+            join_raw(
+                |parallelism| {
+                    faer_core::mul::matmul(
+                        combined_v1.rb_mut(),
+                        v_lhs1,
+                        v_rhs1,
+                        None,
+                        E::faer_one(),
+                        parallelism,
+                    )
+                },
+                |parallelism| {
+                    faer_core::mul::matmul(
+                        combined_v2.rb_mut(),
+                        v_lhs2,
+                        v_rhs2,
+                        None,
+                        E::faer_one(),
+                        parallelism,
+                    )
+                },
+                parallelism,
+            );
+
+            faer_core::mul::matmul(
+                combined_v.rb_mut().submatrix_mut(k, 0, 1, vm_ncols),
+                v_lhs.submatrix(k, 0, 1, 1),
+                v_rhs.submatrix(0, 0, 1, vm_ncols),
+                None,
+                E::faer_one(),
+                parallelism,
+            );
+
+            if !smallest_only {
+                zipped!(v.rb_mut(), combined_v.rb())
+                    .for_each(|unzipped!(mut dst, src)| dst.write(src.read()));
+            }
+        }
+    };
+
+    let mut update_u = |parallelism, stack: PodStack<'_>| {
+        let allocate_combined_u = (fill_u && !economy) as usize;
+        let (mut combined_u, _) = temp_mat_uninit::<E>(n + 1, allocate_combined_u * (n + 1), stack);
+        let mut combined_u = match u_out.rb_mut() {
+            Some(u_out) => u_out,
+            None => combined_u.as_mut(),
+        };
+
+        if fill_u {
+            let (mut combined_u1, mut combined_u2) = combined_u.rb_mut().split_at_row_mut(k + 1);
+            let u_lhs = u.rb();
+            let u_rhs = um.rb();
+            let (u_lhs1, u_lhs2) = (
+                u_lhs.submatrix(0, 0, k + 1, k + 1),
+                u_lhs.submatrix(k + 1, k + 1, rem + 1, rem + 1),
+            );
+            let (u_rhs1, u_rhs2) = u_rhs.split_at_row(k + 1);
+
+            join_raw(
+                |parallelism| {
+                    // matrix matrix
+                    faer_core::mul::matmul(
+                        combined_u1.rb_mut(),
+                        u_lhs1,
+                        u_rhs1,
+                        None,
+                        E::faer_one(),
+                        parallelism,
+                    );
+                    // rank 1 update
+                    faer_core::mul::matmul(
+                        combined_u1.rb_mut(),
+                        u_lhs.col(n).subrows(0, k + 1).as_2d(),
+                        u_rhs2.row(rem).as_2d(),
+                        Some(E::faer_one()),
+                        E::faer_one(),
+                        parallelism,
+                    );
+                },
+                |parallelism| {
+                    // matrix matrix
+                    faer_core::mul::matmul(
+                        combined_u2.rb_mut(),
+                        u_lhs2,
+                        u_rhs2,
+                        None,
+                        E::faer_one(),
+                        parallelism,
+                    );
+                    // rank 1 update
+                    faer_core::mul::matmul(
+                        combined_u2.rb_mut(),
+                        u_lhs.col(0).subrows(k + 1, rem + 1).as_2d(),
+                        u_rhs1.row(0).as_2d(),
+                        Some(E::faer_one()),
+                        E::faer_one(),
+                        parallelism,
+                    );
+                },
+                parallelism,
+            );
+
+            if !economy {
+                zipped!(u.rb_mut(), combined_u.rb())
+                    .for_each(|unzipped!(mut dst, src)| dst.write(src.read()));
+            }
+        }
+    };
+
+    let parallelism = match params.merge_parallelism {
+        MergeParallelism::Nested => parallelism,
+        MergeParallelism::Sequential => Parallelism::None,
+    };
+
+    if compact_u == 1 {
+        update_v(parallelism, stack.rb_mut());
+        if fill_u {
+            let (mut combined_u, _) = temp_mat_uninit::<E>(2, n + 1, stack);
+            let mut combined_u = combined_u.as_mut();
+            faer_core::mul::matmul(
+                combined_u.rb_mut(),
+                u.rb(),
+                um.rb(),
+                None,
+                E::faer_one(),
+                parallelism,
+            );
+            zipped!(u.rb_mut(), combined_u.rb())
+                .for_each(|unzipped!(mut dst, src)| dst.write(src.read()));
+        }
+    } else {
+        match parallelism {
+            #[cfg(feature = "rayon")]
+            Parallelism::Rayon(_) if !_v_is_none => {
+                let req_v = faer_core::temp_mat_req::<E>(v_nrows, n).unwrap();
+                let (mem_v, stack_u) =
+                    stack.make_aligned_raw::<u8>(req_v.size_bytes(), req_v.align_bytes());
+                let stack_v = PodStack::new(mem_v);
+                faer_core::join_raw(
+                    |parallelism| update_v(parallelism, stack_v),
+                    |parallelism| update_u(parallelism, stack_u),
+                    parallelism,
+                );
+            }
+            _ => {
+                update_v(parallelism, stack.rb_mut());
+                update_u(parallelism, stack);
+            }
+        }
+    }
+
+    denormalize(diag, scale);
+}
+
+/// merge step of `bidiag_svd_impl` when `v` is not requested, which overwrites `diag` with the
+/// singular values of the arrow matrix with first column `col0`, in nonincreasing order
+///
+/// the singular values of the two subproblems in `diag[1..]` are each sorted, but not with
+/// respect to each other, so they're sorted along with `col0` first. if `u` is provided, it holds
+/// the first and last rows of the left singular vectors, whose first `n` columns are multiplied by
+/// the ones of the arrow matrix
+fn merge_singular_values_windowed<E: RealField>(
+    diag: &mut [E],
+    col0: &[E],
+    u: Option<MatMut<'_, E>>,
+    window: usize,
+    epsilon: E,
+    consider_zero_threshold: E,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+    params: SvdParams,
+) -> BidiagSvdStats {
+    let n = diag.len();
+
+    let (perm, stack) = stack.make_with(n - 1, |i| i + 1);
+    perm.sort_unstable_by(|&i, &j| {
+        diag[i]
+            .partial_cmp(&diag[j])
+            .unwrap_or(core::cmp::Ordering::Equal)
+            .then(i.cmp(&j))
+    });
+    let (diag_sorted, stack) =
+        stack.collect(core::iter::once(E::faer_zero()).chain(perm.iter().map(|&p| diag[p])));
+    let (col0_sorted, stack) =
+        stack.collect(core::iter::once(col0[0]).chain(perm.iter().map(|&p| col0[p])));
+
+    // the rows are stored contiguously, as in `compute_bidiag_real_svd_impl`
+    let (mut u_sorted, stack) = temp_mat_uninit::<E>(n, u.rb().map_or(0, |u| u.nrows()), stack);
+    let mut u_sorted = u_sorted.as_mut().transpose_mut();
+    if let Some(u) = u.rb() {
+        for (j, p) in core::iter::once(0).chain(perm.iter().copied()).enumerate() {
+            zipped!(u_sorted.rb_mut().col_mut(j).as_2d_mut(), u.col(p).as_2d())
+                .for_each(|unzipped!(mut dst, src)| dst.write(src.read()));
+        }
+    }
+
+    arrow_singular_values_windowed_impl(
+        diag,
+        diag_sorted,
+        col0_sorted,
+        u.map(|u| (u_sorted, u.subcols_mut(0, n))),
+        Ord::max(window, 1),
+        epsilon,
+        consider_zero_threshold,
+        parallelism,
+        stack,
+        params,
+    )
+}
+
+/// Computes the size and alignment of required workspace for computing the svd of a bidiagonal
+/// matrix of size `n` with [`compute_bidiag_real_svd`] and its variants.
+///
+/// The result only depends on the arguments, and is cheap to compute, so it can be memoized if
+/// needed. Additionally, the requirement is nondecreasing in `n` and in `compute_u`/`compute_v`,
+/// so a single workspace allocated for the largest problem can be reused for all smaller ones
+/// with the same `jacobi_fallback_threshold`.
+pub fn bidiag_real_svd_req<E: Entity>(
+    n: usize,
+    jacobi_fallback_threshold: usize,
+    compute_u: bool,
+    compute_v: bool,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    bidiag_real_svd_req_impl::<E>(
+        n,
+        jacobi_fallback_threshold,
+        compute_u,
+        compute_v,
+        None,
+        parallelism,
+    )
+}
+
+/// workspace of `compute_bidiag_real_svd_impl`, where `accumulate_v_nrows` is the number of rows
+/// of `v` when the right singular vectors are accumulated into it
+fn bidiag_real_svd_req_impl<E: Entity>(
+    n: usize,
+    jacobi_fallback_threshold: usize,
+    compute_u: bool,
+    compute_v: bool,
+    accumulate_v_nrows: Option<usize>,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    let _ = parallelism;
+
+    // the rotations that zero the last row, followed by the jacobi fallback. this also covers
+    // the qr fallback
+    let fallback = StackReq::try_all_of([
+        StackReq::try_new::<JacobiRotation<E>>(n)?,
+        temp_mat_req::<E>(n, n)?,
+    ])?;
+
+    // the divide and conquer algorithm can also be forced with `SvdAlgorithm::DivideConquer` for
+    // small matrices, in which case its leaves are smaller than `n`
+    let divide_conquer = if n > jacobi_fallback_threshold || n >= 3 {
+        StackReq::try_all_of([
+            // first and last rows of `u` when it's not requested. this is also counted when it is,
+            // so that the workspace stays nondecreasing in `compute_u`
+            temp_mat_req::<E>(n + 1, 2)?,
+            divide_conquer_req::<E>(
+                n,
+                Ord::min(jacobi_fallback_threshold, n - 1),
+                compute_u,
+                compute_v,
+                accumulate_v_nrows,
+            )?,
+        ])?
+    } else {
+        StackReq::empty()
+    };
+
+    StackReq::try_any_of([fallback, divide_conquer])
+}
+
+/// Computes the size and alignment of required workspace for computing the svd of a bidiagonal
+/// matrix of size `n` with [`compute_bidiag_real_svd_accumulate_v`], where `v` has `v_nrows`
+/// rows.
+pub fn bidiag_real_svd_accumulate_v_req<E: Entity>(
+    n: usize,
+    v_nrows: usize,
+    jacobi_fallback_threshold: usize,
+    compute_u: bool,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    bidiag_real_svd_req_impl::<E>(
+        n,
+        jacobi_fallback_threshold,
+        compute_u,
+        true,
+        Some(v_nrows),
+        parallelism,
+    )
+}
+
+/// Computes the size and alignment of required workspace for computing the singular values of a
+/// bidiagonal matrix of size `n` with [`compute_bidiag_real_singular_values`] or
+/// [`compute_bidiag_real_singular_values_interleaved`].
+pub fn bidiag_real_singular_values_req<E: Entity>(
+    n: usize,
+    jacobi_fallback_threshold: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    bidiag_real_svd_out_of_place_req::<E>(n, jacobi_fallback_threshold, false, false, parallelism)
+}
+
+/// Computes the size and alignment of required workspace for computing the svd of a bidiagonal
+/// matrix of size `n` with [`compute_bidiag_real_svd_out_of_place`].
+pub fn bidiag_real_svd_out_of_place_req<E: Entity>(
+    n: usize,
+    jacobi_fallback_threshold: usize,
+    compute_u: bool,
+    compute_v: bool,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    StackReq::try_all_of([
+        StackReq::try_new::<E>(n)?,
+        bidiag_real_svd_req::<E>(
+            n,
+            jacobi_fallback_threshold,
+            compute_u,
+            compute_v,
+            parallelism,
+        )?,
+    ])
+}
+
+/// Computes the size and alignment of required workspace for computing the smallest singular
+/// triplet of a bidiagonal matrix of size `n` with
+/// [`compute_bidiag_real_smallest_singular_triplet`].
+pub fn bidiag_real_smallest_singular_triplet_req<E: Entity>(
+    n: usize,
+    jacobi_fallback_threshold: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    StackReq::try_all_of([
+        StackReq::try_new::<E>(n)?,
+        StackReq::try_new::<E>(n)?,
+        // the full singular vectors, which are only needed by the subproblems when the divide and
+        // conquer algorithm is used
+        temp_mat_req::<E>(n + 1, n + 1)?,
+        temp_mat_req::<E>(n, n)?,
+        bidiag_real_svd_req::<E>(n, jacobi_fallback_threshold, true, true, parallelism)?,
+    ])
+}
+
+/// Computes the size and alignment of required workspace for computing the singular values of an
+/// arrow matrix `window` at a time with [`compute_arrow_real_singular_values_windowed`].
+///
+/// This doesn't depend on the size of the matrix.
+pub fn arrow_real_singular_values_windowed_req<E: Entity>(
+    window: usize,
+) -> Result<StackReq, SizeOverflow> {
+    arrow_windowed_req::<E>(0, window, false)
+}
+
+/// Computes the size and alignment of required workspace for computing the singular values of a
+/// bidiagonal matrix of size `n` with [`compute_bidiag_real_svd`], without singular vectors, when
+/// [`SvdParams::secular_window`] is `Some(window)`.
+///
+/// Apart from the subproblems that are small enough to be solved by the Jacobi algorithm, this is
+/// about `11n` scalars and indices, plus a workspace proportional to `window` for the secular
+/// equations. The first and last rows of the left singular vectors take `2n` of them. The merge
+/// step of a subproblem of size `m` takes `9m`: its sorted arrow matrix and the permutation that
+/// sorts it, a sorted copy of its rows of the left singular vectors, the entries of the first
+/// column that are consistent with the computed singular values, and three more permutations.
+/// The two subproblems of a split each get half of the workspace, so the largest requirement is
+/// the one of the two subproblems of the top level split.
+pub fn bidiag_real_singular_values_windowed_req<E: Entity>(
+    n: usize,
+    jacobi_fallback_threshold: usize,
+    window: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    let _ = parallelism;
+
+    // the rotations that zero the last row, followed by the jacobi fallback. this also covers
+    // the qr fallback
+    let fallback = StackReq::try_all_of([
+        StackReq::try_new::<JacobiRotation<E>>(n)?,
+        temp_mat_req::<E>(
+            Ord::min(n, jacobi_fallback_threshold),
+            Ord::min(n, jacobi_fallback_threshold),
+        )?,
+    ])?;
+
+    let divide_conquer = if n > jacobi_fallback_threshold || n >= 3 {
+        let jacobi_fallback_threshold = Ord::min(jacobi_fallback_threshold, n - 1);
+        // the largest jacobi subproblems are counted separately, since they can exceed the
+        // workspace of larger matrices, whose subproblems are split again. this keeps the
+        // workspace nondecreasing in `n`, which is needed by the blocks of a matrix whose
+        // subdiagonal has exact zeros
+        let leaf = Ord::min(n, 2 * jacobi_fallback_threshold + 2);
+        StackReq::try_all_of([
+            temp_mat_req::<E>(n + 1, 2)?,
+            StackReq::try_any_of([
+                divide_conquer_windowed_req::<E>(n, jacobi_fallback_threshold, window, false)?,
+                divide_conquer_windowed_req::<E>(leaf, jacobi_fallback_threshold, window, false)?,
+            ])?,
+        ])?
+    } else {
+        StackReq::empty()
+    };
+
+    StackReq::try_any_of([fallback, divide_conquer])
+}
+
+/// Computes the size and alignment of required workspace for computing the svd of a bidiagonal
+/// matrix of size `n` with [`compute_bidiag_real_svd_with_guess`].
+pub fn bidiag_real_svd_with_guess_req<E: Entity>(
+    n: usize,
+    jacobi_fallback_threshold: usize,
+    compute_u: bool,
+    compute_v: bool,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    StackReq::try_all_of([
+        StackReq::try_new::<E>(n)?,
+        bidiag_real_svd_req::<E>(
+            n,
+            jacobi_fallback_threshold,
+            compute_u,
+            compute_v,
+            parallelism,
+        )?,
+    ])
+}
+
+/// Computes the size and alignment of required workspace for computing the svd of a bidiagonal
+/// matrix of size `n` with [`compute_bidiag_real_svd_refined`].
+pub fn bidiag_real_svd_refined_req<E: Entity>(
+    n: usize,
+    jacobi_fallback_threshold: usize,
+    compute_u: bool,
+    compute_v: bool,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    if !coe::is_same::<f32, E>() {
+        return bidiag_real_svd_req::<E>(
+            n,
+            jacobi_fallback_threshold,
+            compute_u,
+            compute_v,
+            parallelism,
+        );
+    }
+
+    StackReq::try_all_of([
+        StackReq::try_new::<f64>(n)?,
+        StackReq::try_new::<f64>(n)?,
+        temp_mat_req::<f64>(n + 1, if compute_u { n + 1 } else { 0 })?,
+        temp_mat_req::<f64>(n, if compute_v { n } else { 0 })?,
+        bidiag_real_svd_req::<f64>(
+            n,
+            jacobi_fallback_threshold,
+            compute_u,
+            compute_v,
+            parallelism,
+        )?,
+    ])
+}
+
+/// workspace of `bidiag_svd_impl` for a matrix of size `n > jacobi_fallback_threshold`, where
+/// `accumulate_v_nrows` is the number of rows of `v` when the right singular vectors are
+/// accumulated into it
+fn divide_conquer_req<E: Entity>(
+    n: usize,
+    jacobi_fallback_threshold: usize,
+    compute_u: bool,
+    compute_v: bool,
+    accumulate_v_nrows: Option<usize>,
+) -> Result<StackReq, SizeOverflow> {
+    let perm = StackReq::try_new::<usize>(n)?;
+    let jacobi_coeffs = StackReq::try_new::<JacobiRotation<E>>(n)?;
+    let jacobi_indices = perm;
+    let transpositions = perm;
+    let real_ind = perm;
+    let real_col = perm;
+
+    let um = temp_mat_req::<E>(n + 1, n + 1)?;
+    let vm = temp_mat_req::<E>(n, if compute_v { n } else { 0 })?;
+
+    let combined_u = temp_mat_req::<E>(if compute_u { n + 1 } else { 2 }, n + 1)?;
+    let combined_v = match accumulate_v_nrows {
+        Some(v_nrows) => temp_mat_req::<E>(v_nrows, n)?,
+        None => vm,
+    };
+
+    let prologue = StackReq::try_all_of([perm, jacobi_coeffs, jacobi_indices])?;
+
+    let merge = StackReq::try_all_of([
+        prologue,
+        um,
+        vm,
+        combined_u,
+        combined_v,
+        transpositions,
+        real_ind,
+        real_col,
+    ])?;
+
+    // the subproblems are solved before the merge, and release their workspace
+    let k = n / 2;
+    let rem = n - 1 - k;
+    let split = if k <= jacobi_fallback_threshold || rem <= jacobi_fallback_threshold {
+        // the singular vectors of the subproblems are only allocated when `u` is not requested,
+        // but they are always counted for the same reason
+        let leaves = StackReq::try_all_of([
+            temp_mat_req::<E>(k + 1, k + 1)?,
+            temp_mat_req::<E>(rem + 1, rem + 1)?,
+            temp_mat_req::<E>(k + 1, k + 1)?,
+            temp_mat_req::<E>(rem + 1, rem + 1)?,
+        ])?;
+        match accumulate_v_nrows {
+            // the right singular vectors of the subproblems, and their product with the starting
+            // matrix
+            Some(v_nrows) => StackReq::try_all_of([
+                leaves,
+                temp_mat_req::<E>(k, k + 1)?,
+                temp_mat_req::<E>(rem, rem + 1)?,
+                temp_mat_req::<E>(v_nrows, Ord::max(k, rem))?,
+            ])?,
+            None => leaves,
+        }
+    } else {
+        // each subproblem gets half of the workspace, and the first one is the largest. the
+        // padding of small matrices means that this can exceed the workspace of the merge
+        let bytes = divide_conquer_req::<E>(
+            k,
+            jacobi_fallback_threshold,
+            compute_u,
+            compute_v,
+            accumulate_v_nrows,
+        )?
+        .try_unaligned_bytes_required()?;
+        StackReq::try_new::<u8>(bytes.checked_mul(2).ok_or(SizeOverflow)?)?
+    };
+
+    StackReq::try_any_of([merge, split])
+}
+
+/// workspace of `arrow_singular_values_windowed_impl` for an arrow matrix of size `n`, where
+/// `compute_u` is true when it also multiplies `u` by the left singular vectors
+fn arrow_windowed_req<E: Entity>(
+    n: usize,
+    window: usize,
+    compute_u: bool,
+) -> Result<StackReq, SizeOverflow> {
+    let n = if compute_u { n } else { 0 };
+    let src = StackReq::try_new::<usize>(n)?;
+    let zhat = StackReq::try_new::<E>(n)?;
+    let order = src;
+    let pos = src;
+
+    StackReq::try_all_of([
+        src,
+        temp_mat_req::<E>(window, 1)?,
+        temp_mat_req::<E>(window, 1)?,
+        temp_mat_req::<E>(window, 1)?,
+        zhat,
+        order,
+        pos,
+    ])
+}
+
+/// workspace of `bidiag_svd_impl` for a matrix of size `n > jacobi_fallback_threshold` with a
+/// compact `u`, when its merge steps are windowed. `compute_u` is false at the top level, where the
+/// rows of `u` aren't needed
+fn divide_conquer_windowed_req<E: Entity>(
+    n: usize,
+    jacobi_fallback_threshold: usize,
+    window: usize,
+    compute_u: bool,
+) -> Result<StackReq, SizeOverflow> {
+    // the sorted arrow matrix and its permutation, and the sorted rows of `u`
+    let merge = StackReq::try_all_of([
+        StackReq::try_new::<usize>(n)?,
+        StackReq::try_new::<E>(n)?,
+        StackReq::try_new::<E>(n)?,
+        temp_mat_req::<E>(n, if compute_u { 2 } else { 0 })?,
+        arrow_windowed_req::<E>(n, window, compute_u)?,
+    ])?;
+
+    // the subproblems are solved before the merge, and release their workspace
+    let k = n / 2;
+    let rem = n - 1 - k;
+    let split = if k <= jacobi_fallback_threshold || rem <= jacobi_fallback_threshold {
+        StackReq::try_all_of([
+            temp_mat_req::<E>(k + 1, k + 1)?,
+            temp_mat_req::<E>(rem + 1, rem + 1)?,
+            temp_mat_req::<E>(k + 1, k + 1)?,
+            temp_mat_req::<E>(rem + 1, rem + 1)?,
+        ])?
+    } else {
+        // each subproblem gets half of the workspace, and the first one is the largest
+        let bytes = divide_conquer_windowed_req::<E>(k, jacobi_fallback_threshold, window, true)?
+            .try_unaligned_bytes_required()?;
+        StackReq::try_new::<u8>(bytes.checked_mul(2).ok_or(SizeOverflow)?)?
+    };
+
+    StackReq::try_any_of([merge, split])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_approx_eq::assert_approx_eq;
+    use faer_core::{assert, Mat};
+
+    macro_rules! make_stack {
+        ($req: expr) => {
+            ::dyn_stack::PodStack::new(&mut ::dyn_stack::GlobalPodBuffer::new($req.unwrap()))
+        };
+    }
+
+    // to avoid overflowing the stack
+    macro_rules! vec_static {
+    ($($x:expr),+ $(,)?) => (
+        {
+            static ARRAY: &[f64] = &[$($x),+];
+            ARRAY.to_vec()
+        }
+    );
+    }
+
+    #[test]
+    fn test_svd_req_monotonic() {
+        let fits = |small: StackReq, large: StackReq| {
+            small.size_bytes() <= large.size_bytes() && small.align_bytes() <= large.align_bytes()
+        };
+        let req = |n, compute_u, compute_v| {
+            bidiag_real_svd_req::<f64>(n, 40, compute_u, compute_v, Parallelism::None).unwrap()
+        };
+
+        for n in 0..300 {
+            let full = req(n, true, true);
+            for (compute_u, compute_v) in [(false, false), (false, true), (true, false)] {
+                assert!(fits(req(n, compute_u, compute_v), full));
+            }
+            for (compute_u, compute_v) in
+                [(false, false), (false, true), (true, false), (true, true)]
+            {
+                assert!(fits(
+                    req(n, compute_u, compute_v),
+                    req(n + 1, compute_u, compute_v)
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn test_svd_n() {
+        for n in [9, 16, 32, 64, 128, 256, 512, 1024] {
+            let diag = (0..n).map(|_| rand::random::<f64>()).collect::<Vec<_>>();
+            let subdiag = (0..n).map(|_| rand::random::<f64>()).collect::<Vec<_>>();
+            #[cfg(feature = "std")]
+            dbg!(&diag, &subdiag);
+
+            let n = diag.len();
+            let mut u = Mat::from_fn(n + 1, n + 1, |_, _| f64::NAN);
+            let mut v = Mat::from_fn(n, n, |_, _| f64::NAN);
+            let s = {
+                let mut diag = diag.clone();
+                let mut subdiag = subdiag.clone();
+                compute_bidiag_real_svd(
+                    &mut diag,
+                    &mut subdiag,
+                    Some(u.as_mut()),
+                    Some(v.as_mut()),
+                    5,
+                    0,
+                    f64::EPSILON,
+                    f64::MIN_POSITIVE,
+                    Parallelism::None,
+                    make_stack!(bidiag_real_svd_req::<f64>(
+                        n,
+                        5,
+                        true,
+                        true,
+                        Parallelism::None
+                    )),
+                    SvdParams::default(),
+                );
+                Mat::from_fn(n + 1, n, |i, j| if i == j { diag[i] } else { 0.0 })
+            };
+
+            let reconstructed = &u * &s * v.transpose();
+            for j in 0..n {
+                for i in 0..n + 1 {
+                    let target = if i == j {
+                        diag[j]
+                    } else if i == j + 1 {
+                        subdiag[j]
+                    } else {
+                        0.0
+                    };
+
+                    assert_approx_eq!(reconstructed.read(i, j), target, 1e-10);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_svd_seeded() {
+        for (n, seed) in [
+            (64, 0),
+            (256, 1),
+            (1024, 0),
+            (1024, 1),
+            (1024, 2),
+            (1024, 3),
+        ] {
+            let (diag, subdiag) = crate::testing::test_bidiag::<f64>(n, seed);
+
+            let mut u = Mat::from_fn(n + 1, n + 1, |_, _| f64::NAN);
+            let mut v = Mat::from_fn(n, n, |_, _| f64::NAN);
+            let s = {
+                let mut diag = diag.clone();
+                let mut subdiag = subdiag.clone();
+                compute_bidiag_real_svd(
+                    &mut diag,
+                    &mut subdiag,
+                    Some(u.as_mut()),
+                    Some(v.as_mut()),
+                    40,
+                    0,
+                    f64::EPSILON,
+                    f64::MIN_POSITIVE,
+                    Parallelism::None,
+                    make_stack!(bidiag_real_svd_req::<f64>(
+                        n,
+                        40,
+                        true,
+                        true,
+                        Parallelism::None
+                    )),
+                    SvdParams::default(),
+                );
+                Mat::from_fn(n + 1, n, |i, j| if i == j { diag[i] } else { 0.0 })
+            };
+
+            let reconstructed = &u * &s * v.transpose();
+            for j in 0..n {
+                for i in 0..n + 1 {
+                    let target = if i == j {
+                        diag[j]
+                    } else if i == j + 1 {
+                        subdiag[j]
+                    } else {
+                        0.0
+                    };
+
+                    assert_approx_eq!(reconstructed.read(i, j), target, 1e-10);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn test_svd_parallel_secular_eq() {
+        let n = 1024;
+        let diag = (0..n).map(|_| rand::random::<f64>()).collect::<Vec<_>>();
+        let subdiag = (0..n).map(|_| rand::random::<f64>()).collect::<Vec<_>>();
+
+        // each singular value is computed independently, so splitting them between tasks must
+        // not change the result
+        let mut singular_values = Vec::new();
+        for parallelism in [Parallelism::None, Parallelism::Rayon(4)] {
+            let mut s = diag.clone();
+            let mut e = subdiag.clone();
+            compute_bidiag_real_svd(
+                &mut s,
+                &mut e,
+                None,
+                None,
+                5,
+                0,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                parallelism,
+                make_stack!(bidiag_real_svd_req::<f64>(n, 5, false, false, parallelism)),
+                SvdParams {
+                    parallel_threshold: Some(0),
+                    ..Default::default()
+                },
+            );
+            singular_values.push(s);
+        }
+        assert!(singular_values[0] == singular_values[1]);
+    }
+
+    #[test]
+    fn test_secular_eq_refinement() {
+        // arrow matrix with well separated poles and no deflated entries
+        let n = 48;
+        let diag = (0..n)
+            .map(|i| i as f64 + 0.25 * (i % 3) as f64)
+            .collect::<Vec<_>>();
+        let col0 = (0..n)
+            .map(|i| 0.5 + 0.125 * (i % 5) as f64)
+            .collect::<Vec<_>>();
+
+        let residuals = |epsilon: f64, refine_iters: usize| {
+            let mut shifts = Mat::<f64>::zeros(n, 1);
+            let mut mus = Mat::<f64>::zeros(n, 1);
+            let mut s = Mat::<f64>::zeros(n, 1);
+            compute_singular_values(
+                shifts.as_mut(),
+                mus.as_mut(),
+                s.as_mut(),
+                0,
+                &diag,
+                &diag,
+                &col0,
+                &col0,
+                None,
+                epsilon,
+                refine_iters,
+                SecularBracketMode::Strict,
+                None,
+                Parallelism::None,
+            );
+            (0..n)
+                .map(|k| secular_eq(mus.read(k, 0), &col0, &diag, shifts.read(k, 0)).abs())
+                .collect::<Vec<_>>()
+        };
+
+        let max = |r: &[f64]| r.iter().copied().fold(0.0, f64::max);
+        for epsilon in [1e-2, 1e-6, f64::EPSILON] {
+            let coarse = residuals(epsilon, 0);
+            let refined = residuals(epsilon, 2);
+            for k in 0..n {
+                assert!(refined[k] <= coarse[k]);
+            }
+            // the secant iterations already reach the residual floor at full precision
+            if epsilon > f64::EPSILON {
+                assert!(max(&refined) < 1e-3 * max(&coarse));
+            }
+        }
+    }
+
+    #[test]
+    fn test_wilkinson_shift_2x2() {
+        let sqrt2 = 2.0f64.sqrt();
+        // eigenvalues 2 ± sqrt(2), the closest to `t11` is picked on either side
+        assert_approx_eq!(wilkinson_shift_2x2(3.0, 1.0, 1.0, 0.0), 2.0 - sqrt2);
+        assert_approx_eq!(wilkinson_shift_2x2(1.0, 3.0, 1.0, 0.0), 2.0 + sqrt2);
+        // the sign of `t01` doesn't matter
+        assert_approx_eq!(wilkinson_shift_2x2(3.0, 1.0, -1.0, 0.0), 2.0 - sqrt2);
+        // equidistant eigenvalues 1 and 3
+        assert_approx_eq!(wilkinson_shift_2x2::<f64>(2.0, 2.0, 1.0, 0.0), 1.0);
+        // diagonal, or treated as diagonal
+        assert!(wilkinson_shift_2x2(2.0, 2.0, 0.0, 0.0) == 2.0);
+        assert!(wilkinson_shift_2x2(5.0, 2.0, 1e-10, 1e-18) == 2.0);
+        // the eigenvalue `(1 - sqrt(1 + 4e-20)) / 2`, which is about `-1e-20`, is computed
+        // without the cancellation of the textbook formula
+        let shift = wilkinson_shift_2x2::<f64>(1.0, 0.0, 1e-10, 0.0);
+        assert!((shift / -1e-20 - 1.0).abs() < 1e-14);
+
+        for _ in 0..100 {
+            let t00 = rand::random::<f64>();
+            let t11 = rand::random::<f64>();
+            let t01 = rand::random::<f64>() - 0.5;
+            let mu = wilkinson_shift_2x2(t00, t11, t01, 0.0);
+            let mean = 0.5 * (t00 + t11);
+            let radius = (0.25 * (t00 - t11) * (t00 - t11) + t01 * t01).sqrt();
+            let (lambda0, lambda1) = (mean - radius, mean + radius);
+            let expected = if (lambda0 - t11).abs() < (lambda1 - t11).abs() {
+                lambda0
+            } else {
+                lambda1
+            };
+            assert_approx_eq!(mu, expected, 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_reconstruct_lowner_vector() {
+        // arrow matrix with a few deflated entries, whose value of `diag[0]` is ignored
+        let n = 24;
+        let diag = (0..n)
+            .map(|i| {
+                if i == 0 {
+                    3.0
+                } else {
+                    i as f64 + 0.25 * (i % 3) as f64
+                }
+            })
+            .collect::<Vec<_>>();
+        let col0 = (0..n)
+            .map(|i| {
+                if i % 7 == 3 {
+                    0.0
+                } else if i % 2 == 0 {
+                    0.5 + 0.125 * (i % 5) as f64
+                } else {
+                    -0.75
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // the setup of the merge step
+        let mut diag_zero = diag.clone();
+        diag_zero[0] = 0.0;
+        let perm = (0..n).filter(|&i| col0[i] != 0.0).collect::<Vec<_>>();
+        let col0_perm = perm.iter().map(|&p| col0[p]).collect::<Vec<_>>();
+        let diag_perm = perm.iter().map(|&p| diag_zero[p]).collect::<Vec<_>>();
+
+        let mut shifts = Mat::<f64>::zeros(n, 1);
+        let mut mus = Mat::<f64>::zeros(n, 1);
+        let mut s = Mat::<f64>::zeros(n, 1);
+        compute_singular_values(
+            shifts.as_mut(),
+            mus.as_mut(),
+            s.as_mut(),
+            0,
+            &diag_zero,
+            &diag_perm,
+            &col0,
+            &col0_perm,
+            None,
+            f64::EPSILON,
+            0,
+            SecularBracketMode::Strict,
+            None,
+            Parallelism::None,
+        );
+        let mut expected = Mat::<f64>::zeros(n, 1);
+        perturb_col0(
+            expected.as_mut(),
+            &col0,
+            &diag_zero,
+            &perm,
+            s.as_ref(),
+            shifts.as_ref(),
+            mus.as_ref(),
+        );
+
+        let col = |x: &[f64]| Col::<f64>::from_fn(x.len(), |i| x[i]);
+        let zhat = reconstruct_lowner_vector(
+            col(&col0).as_ref(),
+            col(&diag).as_ref(),
+            s.as_ref().col(0),
+            shifts.as_ref().col(0),
+            mus.as_ref().col(0),
+        );
+        for (i, &z) in col0.iter().enumerate() {
+            assert!(zhat.read(i) == expected.read(i, 0));
+            if z == 0.0 {
+                assert!(zhat.read(i) == 0.0);
+            } else {
+                assert!(zhat.read(i).signum() == z.signum());
+                assert_approx_eq!(zhat.read(i), z, 1e-12);
+            }
+        }
+
+        // the singular values of the reconstructed arrow matrix are the computed ones
+        let arrow = Mat::<f64>::from_fn(n, n, |i, j| {
+            if j == 0 {
+                zhat.read(i)
+            } else if i == j {
+                diag_zero[i]
+            } else {
+                0.0
+            }
+        });
+        let mut expected_s = (0..n).map(|i| s.read(i, 0)).collect::<Vec<_>>();
+        expected_s.sort_by(|a, b| b.total_cmp(a));
+        let svd = crate::SvdDecomposition::new(arrow.as_ref());
+        for (actual, &expected) in zip(svd.singular_values(), &expected_s) {
+            assert_approx_eq!(actual, expected, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_secular_eq_initial_guess() {
+        let n = 128;
+        let diag = (0..n)
+            .map(|i| i as f64 + 0.25 * (i % 3) as f64)
+            .collect::<Vec<_>>();
+        let col0 = (0..n)
+            .map(|i| 0.5 + 0.125 * (i % 5) as f64)
+            .collect::<Vec<_>>();
+
+        let solve = |col0: &[f64], initial_guess: Option<&[f64]>| {
+            let mut shifts = Mat::<f64>::zeros(n, 1);
+            let mut mus = Mat::<f64>::zeros(n, 1);
+            let mut s = Mat::<f64>::zeros(n, 1);
+            let stats = compute_singular_values(
+                shifts.as_mut(),
+                mus.as_mut(),
+                s.as_mut(),
+                0,
+                &diag,
+                &diag,
+                col0,
+                col0,
+                None,
+                f64::EPSILON,
+                0,
+                SecularBracketMode::Strict,
+                initial_guess,
+                Parallelism::None,
+            );
+            ((0..n).map(|k| s.read(k, 0)).collect::<Vec<_>>(), stats)
+        };
+
+        let (s, _) = solve(&col0, None);
+
+        // the same arrow matrix after a small perturbation
+        let perturbed_col0 = col0
+            .iter()
+            .enumerate()
+            .map(|(i, &x)| x + 1e-6 * ((i % 7) as f64 - 3.0))
+            .collect::<Vec<_>>();
+        let (cold, cold_stats) = solve(&perturbed_col0, None);
+        let (warm, warm_stats) = solve(&perturbed_col0, Some(&s));
+        for (&cold, &warm) in zip(&cold, &warm) {
+            assert!((cold - warm).abs() <= 4.0 * f64::EPSILON * cold.max(1.0));
+        }
+        assert!(warm_stats.secular_eq_solves == cold_stats.secular_eq_solves);
+        assert!(2 * warm_stats.secular_eq_evaluations < cold_stats.secular_eq_evaluations);
+
+        // guesses that are outside of the brackets, or far from the roots
+        let far = s.iter().map(|&x| x + 0.1).collect::<Vec<_>>();
+        for guess in [vec![0.0; n], far] {
+            let (warm, _) = solve(&perturbed_col0, Some(&guess));
+            for (&cold, &warm) in zip(&cold, &warm) {
+                assert!((cold - warm).abs() <= 4.0 * f64::EPSILON * cold.max(1.0));
+            }
+        }
+    }
+
+    #[test]
+    fn test_secular_bracket_repair() {
+        // the entries of `col0` are far too small to survive deflation, so the evaluations of the
+        // secular equation that choose the shift of the smallest singular value contradict each
+        // other and the initial bracket doesn't contain the root
+        let diag = [0.0, 0.6068800537027215];
+        let col0 = [-3.3967600495460024e-113, 1.4184353970784668e-168];
+
+        let solve = |bracket_mode| {
+            let mut shifts = Mat::<f64>::zeros(2, 1);
+            let mut mus = Mat::<f64>::zeros(2, 1);
+            let mut s = Mat::<f64>::zeros(2, 1);
+            let stats = compute_singular_values(
+                shifts.as_mut(),
+                mus.as_mut(),
+                s.as_mut(),
+                0,
+                &diag,
+                &diag,
+                &col0,
+                &col0,
+                None,
+                f64::EPSILON,
+                0,
+                bracket_mode,
+                None,
+                Parallelism::None,
+            );
+            (s, stats)
+        };
+
+        #[cfg(feature = "std")]
+        assert!(std::panic::catch_unwind(|| solve(SecularBracketMode::Strict)).is_err());
+
+        let (s, stats) = solve(SecularBracketMode::Permissive);
+        assert!(stats.bracket_repairs == 1);
+        assert!(stats.secular_divergences == 0);
+
+        // the singular values of [[col0[0], 0], [col0[1], diag[1]]] are about |col0[0]| and
+        // diag[1], and are computed to high absolute accuracy
+        let norm = diag[1];
+        assert!((s.read(0, 0) - col0[0].abs()).abs() <= 4.0 * f64::EPSILON * norm);
+        assert!((s.read(1, 0) - diag[1]).abs() <= 4.0 * f64::EPSILON * norm);
+    }
+
+    #[test]
+    fn test_norm_l2_scaled() {
+        assert!(norm_l2_scaled::<f64>(&[]) == 0.0);
+        assert!(norm_l2_scaled(&[0.0f64, 0.0]) == 0.0);
+        assert_approx_eq!(norm_l2_scaled(&[3.0f64, -4.0]), 5.0);
+
+        // the naive sum of squares overflows and underflows for these
+        let big = f64::MAX / 4.0;
+        assert_approx_eq!(norm_l2_scaled(&[big, big, big]) / big, 3.0f64.sqrt(), 1e-15);
+        let small = f64::MIN_POSITIVE * 2.0f64.powi(10);
+        assert_approx_eq!(
+            norm_l2_scaled(&[small, -small]) / small,
+            2.0f64.sqrt(),
+            1e-15
+        );
+    }
+
+    #[test]
+    fn test_norm_l2_compensated() {
+        // the squares of the small entries are each below half an ulp of the first one, so a naive
+        // sum drops all of them, even though together they shift the norm by `2^-43`
+        let n = 4097;
+        let small = 2.0f64.powi(-27);
+        let col0 = (0..n)
+            .map(|i| if i == 0 { 1.0 } else { small })
+            .collect::<Vec<_>>();
+        let expected = (1.0 + 4096.0 * (small * small)).sqrt();
+        assert!(expected > 1.0);
+        assert!((norm_l2_scaled(&col0) - expected).abs() <= f64::EPSILON);
+
+        // the largest singular value of the arrow matrix with a tiny diagonal is only slightly
+        // larger than `|col0|`, so it lies outside of the initial bracket if the norm is
+        // underestimated
+        let diag = (0..n)
+            .map(|i| i as f64 * 2.0f64.powi(-70))
+            .collect::<Vec<_>>();
+        let mut shifts = Mat::<f64>::zeros(n, 1);
+        let mut mus = Mat::<f64>::zeros(n, 1);
+        let mut s = Mat::<f64>::zeros(n, 1);
+        let stats = compute_singular_values(
+            shifts.as_mut(),
+            mus.as_mut(),
+            s.as_mut(),
+            0,
+            &diag,
+            &diag,
+            &col0,
+            &col0,
+            None,
+            f64::EPSILON,
+            0,
+            SecularBracketMode::Permissive,
+            None,
+            Parallelism::None,
+        );
+        assert!(stats.bracket_repairs == 0);
+        assert!((s.read(n - 1, 0) - expected).abs() <= 2.0 * f64::EPSILON);
+    }
+
+    #[test]
+    fn test_singular_value_bracket_near_overflow() {
+        // the upper end of the bracket of the largest singular value of the arrow matrix with
+        // first column `col0` and diagonal `diag` is `diag[n - 1] + |col0|`, and must scale
+        // linearly with the input instead of overflowing
+        let n = 8;
+        let bound = |scale: f64| {
+            let col0 = (0..n)
+                .map(|i| scale * (1.0 + i as f64) / 8.0)
+                .collect::<Vec<_>>();
+            let diag = (0..n).map(|i| scale * i as f64 / 16.0).collect::<Vec<_>>();
+            diag[n - 1] + norm_l2_scaled(&col0)
+        };
+
+        let reference = bound(1.0);
+        for scale in [1e150, 1e300] {
+            let scaled = bound(scale);
+            assert!(scaled.is_finite());
+            assert_approx_eq!(scaled / scale, reference, 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_deflate_large_col0() {
+        // `deflate` can be called on an unnormalized arrow matrix, where squaring the entries of
+        // `col0` overflows. the rotations must stay well formed and preserve the norm of `col0`
+        let n = 4;
+        let k = 1;
+        let big = 1e300;
+        let mut diag = vec![big, 0.0, big, big];
+        let mut col0 = vec![big, big, big, big];
+        let norm = norm_l2_scaled(&col0);
+
+        let mut jacobi_coeffs = vec![
+            JacobiRotation {
+                c: 0.0f64,
+                s: 0.0f64,
+            };
+            n
+        ];
+        let mut jacobi_indices = vec![0usize; n];
+        let mut transpositions = vec![0usize; n];
+        let mut perm = vec![0usize; n];
+        let mut u = Mat::<f64>::zeros(n + 1, n + 1);
+        let mut v = Mat::<f64>::zeros(n, n);
+
+        let (jacobi_0i, jacobi_ij) = deflate(
+            &mut diag,
+            &mut col0,
+            &mut jacobi_coeffs,
+            &mut jacobi_indices,
+            u.as_mut(),
+            Some(v.as_mut()),
+            &mut transpositions,
+            &mut perm,
+            k,
+            f64::EPSILON,
+            f64::EPSILON,
+            f64::MIN_POSITIVE,
+            make_stack!(StackReq::try_new::<usize>(2 * n)),
+        );
+
+        // the zero diagonal entry triggers 4.3, and the two equal ones trigger 4.4
+        assert!(jacobi_0i == 1);
+        assert!(jacobi_ij == 1);
+        for rot in &jacobi_coeffs[..jacobi_0i + jacobi_ij] {
+            assert!(rot.c.is_finite() && rot.s.is_finite());
+            assert_approx_eq!(rot.c * rot.c + rot.s * rot.s, 1.0, 1e-15);
+        }
+        assert!(diag.iter().chain(&col0).all(|x| x.is_finite()));
+        assert_approx_eq!(norm_l2_scaled(&col0) / norm, 1.0, 1e-15);
+    }
+
+    #[test]
+    fn test_power_of_two_above() {
+        assert!(power_of_two_above(3.0f64) == 4.0);
+        assert!(power_of_two_above(4.0f64) == 4.0);
+        assert!(power_of_two_above(0.3f64) == 0.5);
+        assert!(power_of_two_above(1e-310f64) == 2.0f64.powi(-1029));
+        assert!(power_of_two_above(f64::MAX) == 2.0f64.powi(1023));
+    }
+
+    #[test]
+    fn test_svd_scaling_applied_once() {
+        let n = 1024;
+        let (diag, subdiag) = crate::testing::test_bidiag::<f64>(n, 1);
+
+        let svd = |factor: f64, qr_threshold: usize| {
+            let mut s = diag.iter().map(|x| x * factor).collect::<Vec<_>>();
+            let mut e = subdiag.iter().map(|x| x * factor).collect::<Vec<_>>();
+            let mut u = Mat::from_fn(n + 1, n + 1, |_, _| f64::NAN);
+            let mut v = Mat::from_fn(n, n, |_, _| f64::NAN);
+            compute_bidiag_real_svd(
+                &mut s,
+                &mut e,
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                4,
+                qr_threshold,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(bidiag_real_svd_req::<f64>(
+                    n,
+                    4,
+                    true,
+                    true,
+                    Parallelism::None
+                )),
+                SvdParams::default(),
+            );
+            (s, u, v)
+        };
+
+        // scaling the input by a power of two goes through every level of the recursion without
+        // rounding, so the output must be scaled by exactly the same factor
+        let (s, u, v) = svd(1.0, 0);
+        for factor in [2.0f64.powi(-300), 2.0f64.powi(300)] {
+            let (s_scaled, u_scaled, v_scaled) = svd(factor, 0);
+            assert!(s_scaled == s.iter().map(|x| x * factor).collect::<Vec<_>>());
+            assert!(u_scaled == u);
+            assert!(v_scaled == v);
+        }
+
+        // the qr algorithm only scales the input once, so it serves as a reference for inputs far
+        // from unit scale
+        let n = 128;
+        let (diag, mut subdiag) = crate::testing::test_bidiag::<f64>(n, 2);
+        subdiag[n - 1] = 0.0;
+        for factor in [1e-200, 1.0, 1e200] {
+            let mut singular_values = Vec::new();
+            for qr_threshold in [0, n] {
+                let mut s = diag.iter().map(|x| x * factor).collect::<Vec<_>>();
+                let mut e = subdiag.iter().map(|x| x * factor).collect::<Vec<_>>();
+                compute_bidiag_real_svd(
+                    &mut s,
+                    &mut e,
+                    None,
+                    None,
+                    4,
+                    qr_threshold,
+                    f64::EPSILON,
+                    f64::MIN_POSITIVE,
+                    Parallelism::None,
+                    make_stack!(bidiag_real_svd_req::<f64>(
+                        n,
+                        4,
+                        false,
+                        false,
+                        Parallelism::None
+                    )),
+                    SvdParams::default(),
+                );
+                s.sort_by(|a, b| b.partial_cmp(a).unwrap());
+                singular_values.push(s);
+            }
+            for (&x, &y) in zip(&singular_values[0], &singular_values[1]) {
+                assert_approx_eq!(x / factor, y / factor, 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sort_singular_values() {
+        // a single bubble pass leaves `1.0` one position away from its place
+        let s = Mat::from_fn(3, 1, |i, _| [2.0, 3.0, 1.0][i]);
+        let mut col_perm = [0, 1, 2];
+        sort_singular_values(s.as_ref(), &mut col_perm);
+        assert!(col_perm == [2, 0, 1]);
+
+        let s = Mat::from_fn(5, 1, |i, _| [4.0, 3.0, 2.0, 1.0, 0.0][i]);
+        let mut col_perm = [0, 1, 2, 3, 4];
+        sort_singular_values(s.as_ref(), &mut col_perm);
+        assert!(col_perm == [4, 3, 2, 1, 0]);
+
+        // equal values keep their original order
+        let s = Mat::from_fn(5, 1, |i, _| [1.0, 0.5, 1.0, 0.5, 0.0][i]);
+        let mut col_perm = [0, 1, 2, 3, 4];
+        sort_singular_values(s.as_ref(), &mut col_perm);
+        assert!(col_perm == [4, 1, 3, 0, 2]);
+
+        // the roots that diverged come last
+        let s = Mat::from_fn(4, 1, |i, _| [1.0, f64::NAN, 0.5, f64::NAN][i]);
+        let mut col_perm = [0, 1, 2, 3];
+        sort_singular_values(s.as_ref(), &mut col_perm);
+        assert!(col_perm == [2, 0, 1, 3]);
+
+        // the sort isn't quadratic on reversed inputs
+        let n = 100_000;
+        let s = Mat::from_fn(n, 1, |i, _| (n - i) as f64);
+        let mut col_perm = (0..n).collect::<Vec<_>>();
+        sort_singular_values(s.as_ref(), &mut col_perm);
+        assert!(col_perm.iter().copied().eq((0..n).rev()));
+    }
+
+    #[test]
+    fn test_singular_vectors_clustered() {
+        // the middle singular value coincides with `diag[1]` up to rounding, so the denominators of
+        // the middle entries of its singular vectors underflow, or vanish
+        let n = 3;
+        let diag = [0.0, 1.0, 2.0];
+        let zhat = Mat::from_fn(n, 1, |_, _| 0.5);
+        let perm = [0, 1, 2];
+        let shifts = Mat::from_fn(n, 1, |i, _| diag[i]);
+        for mu in [0.0, 1e-320, -1e-320, 1e-300] {
+            let mus = Mat::from_fn(n, 1, |i, _| if i == 1 { mu } else { 0.25 });
+            let mut um = Mat::<f64>::zeros(n + 1, n + 1);
+            let mut vm = Mat::<f64>::zeros(n, n);
+            compute_singular_vectors(
+                Some(um.as_mut()),
+                Some(vm.as_mut()),
+                false,
+                zhat.as_ref(),
+                &diag,
+                &perm,
+                &perm,
+                &perm,
+                n,
+                shifts.as_ref(),
+                mus.as_ref(),
+                f64::MIN_POSITIVE,
+            );
+
+            // the vectors are the middle columns of the identity, up to their sign
+            let u = um.col_as_slice(1);
+            let v = vm.col_as_slice(1);
+            assert!(u.iter().chain(v).all(|x| x.is_finite()));
+            assert!((u[1].abs() - 1.0).abs() <= f64::EPSILON);
+            assert!((v[1].abs() - 1.0).abs() <= f64::EPSILON);
+            for i in [0, 2, 3] {
+                assert!(u[i].abs() <= 1e-250);
+            }
+            for i in [0, 2] {
+                assert!(v[i].abs() <= 1e-250);
+            }
+        }
+    }
+
+    #[test]
+    fn test_svd_of_m_deflated() {
+        // every entry of `col0` is deflated, so `m` is already diagonal
+        let n = 5;
+        let mut diag = vec![0.0, 1.0, 2.0, 3.0, 4.0];
+        let col0 = vec![0.0; n];
+        let outer_perm = (0..n).collect::<Vec<_>>();
+        let mut um = Mat::<f64>::zeros(n + 1, n + 1);
+        let mut vm = Mat::<f64>::zeros(n, n);
+
+        compute_svd_of_m(
+            Some(um.as_mut()),
+            Some(vm.as_mut()),
+            false,
+            &mut diag,
+            &col0,
+            &outer_perm,
+            None,
+            None,
+            f64::EPSILON,
+            0,
+            SecularBracketMode::Strict,
+            None,
+            f64::MIN_POSITIVE,
+            Parallelism::None,
+            make_stack!(StackReq::try_new::<f64>(1024)),
+        );
+
+        assert!(diag == [4.0, 3.0, 2.0, 1.0, 0.0]);
+        assert!(um.read(n, n) == 1.0);
+        for j in 0..n {
+            for i in 0..n {
+                // the singular vectors are the columns of the identity, sorted by singular value
+                let target = if i + j == n - 1 { 1.0 } else { 0.0 };
+                assert!(um.read(i, j) == target);
+                assert!(vm.read(i, j) == target);
+            }
+        }
+    }
+
+    #[test]
+    fn test_svd_of_m_inconsistent() {
+        // the last entry of `diag` is zero, but not the corresponding entry of `col0`
+        let n = 4;
+        let result = std::panic::catch_unwind(|| {
+            let mut diag = vec![0.0, 1.0, 2.0, 0.0];
+            let col0 = vec![0.5; n];
+            let outer_perm = (0..n).collect::<Vec<_>>();
+            compute_svd_of_m(
+                None,
+                None,
+                false,
+                &mut diag,
+                &col0,
+                &outer_perm,
+                None,
+                None,
+                f64::EPSILON,
+                0,
+                SecularBracketMode::Strict,
+                None,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(StackReq::try_new::<f64>(1024)),
+            )
+        });
+        if cfg!(any(debug_assertions, feature = "svd-invariant-checks")) {
+            assert!(result.is_err());
+        } else {
+            assert!(result.unwrap().inconsistent_inputs == 1);
+        }
+    }
+
+    #[test]
+    fn test_svd_stats() {
+        let n = 1024;
+        let (diag, subdiag) = crate::testing::test_bidiag::<f64>(n, 0);
+        // clustered diagonal with tiny off diagonal coupling
+        let clustered_diag = (0..n)
+            .map(|i| 1.0 + (i % 4) as f64 * f64::EPSILON)
+            .collect::<Vec<_>>();
+        let clustered_subdiag = vec![1e-8; n];
+
+        for (diag, subdiag) in [(&diag, &subdiag), (&clustered_diag, &clustered_subdiag)] {
+            let mut all_stats = Vec::new();
+            for parallelism in [Parallelism::None, Parallelism::Rayon(4)] {
+                let mut stats = BidiagSvdStats {
+                    secular_eq_solves: usize::MAX,
+                    bisection_fallbacks: usize::MAX,
+                    bracket_repairs: usize::MAX,
+                    secular_divergences: usize::MAX,
+                    secular_eq_evaluations: usize::MAX,
+                    inconsistent_inputs: usize::MAX,
+                    qr: QrStats {
+                        iterations: usize::MAX,
+                        deflations: usize::MAX,
+                        givens_applied: usize::MAX,
+                        unconverged: usize::MAX,
+                    },
+                    algorithm: SvdAlgorithm::OneSidedJacobi,
+                };
+                let mut s = diag.clone();
+                let mut e = subdiag.clone();
+                compute_bidiag_real_svd_with_stats(
+                    &mut s,
+                    &mut e,
+                    None,
+                    None,
+                    &mut stats,
+                    5,
+                    0,
+                    f64::EPSILON,
+                    f64::MIN_POSITIVE,
+                    parallelism,
+                    make_stack!(bidiag_real_svd_req::<f64>(n, 5, false, false, parallelism)),
+                    SvdParams {
+                        parallel_threshold: Some(0),
+                        ..Default::default()
+                    },
+                );
+                assert!(stats.secular_eq_solves > 0);
+                assert!(stats.secular_eq_evaluations > stats.secular_eq_solves);
+                assert!(stats.algorithm == SvdAlgorithm::DivideConquer);
+                assert!(stats.bisection_fallbacks <= stats.secular_eq_solves);
+                assert!(stats.secular_divergences == 0);
+                assert!(stats.qr == QrStats::default());
+                all_stats.push(stats);
+            }
+            assert!(all_stats[0] == all_stats[1]);
+        }
+
+        // the jacobi fallback doesn't solve any secular equation
+        let mut stats = BidiagSvdStats {
+            secular_eq_solves: 1,
+            bisection_fallbacks: 1,
+            bracket_repairs: 1,
+            secular_divergences: 1,
+            secular_eq_evaluations: 1,
+            inconsistent_inputs: 1,
+            qr: QrStats {
+                iterations: 1,
+                deflations: 1,
+                givens_applied: 1,
+                unconverged: 1,
+            },
+            algorithm: SvdAlgorithm::OneSidedJacobi,
+        };
+        let (mut s, mut e) = crate::testing::test_bidiag::<f64>(4, 0);
+        e[3] = 0.0;
+        compute_bidiag_real_svd_with_stats(
+            &mut s,
+            &mut e,
+            None,
+            None,
+            &mut stats,
+            5,
+            0,
+            f64::EPSILON,
+            f64::MIN_POSITIVE,
+            Parallelism::None,
+            make_stack!(bidiag_real_svd_req::<f64>(
+                4,
+                5,
+                false,
+                false,
+                Parallelism::None
+            )),
+            SvdParams::default(),
+        );
+        assert!(
+            stats
+                == BidiagSvdStats {
+                    algorithm: SvdAlgorithm::Jacobi,
+                    ..Default::default()
+                }
+        );
+    }
+
+    #[test]
+    fn test_svd_with_guess() {
+        let n = 256;
+        let (diag, subdiag) = crate::testing::test_bidiag::<f64>(n, 0);
+        let svd = |diag: &[f64], subdiag: &[f64], guess: Option<&[f64]>| {
+            let mut s = diag.to_vec();
+            let mut e = subdiag.to_vec();
+            let mut stats = BidiagSvdStats::default();
+            let req = bidiag_real_svd_with_guess_req::<f64>(n, 4, false, false, Parallelism::None);
+            match guess {
+                Some(guess) => compute_bidiag_real_svd_with_guess(
+                    &mut s,
+                    &mut e,
+                    None,
+                    None,
+                    guess,
+                    &mut stats,
+                    4,
+                    0,
+                    f64::EPSILON,
+                    f64::MIN_POSITIVE,
+                    Parallelism::None,
+                    make_stack!(req),
+                    SvdParams::default(),
+                ),
+                None => compute_bidiag_real_svd_with_stats(
+                    &mut s,
+                    &mut e,
+                    None,
+                    None,
+                    &mut stats,
+                    4,
+                    0,
+                    f64::EPSILON,
+                    f64::MIN_POSITIVE,
+                    Parallelism::None,
+                    make_stack!(req),
+                    SvdParams::default(),
+                ),
+            }
+            (s, stats)
+        };
+
+        let (s, _) = svd(&diag, &subdiag, None);
+
+        // the same matrix after a small perturbation
+        let perturbed_diag = diag
+            .iter()
+            .enumerate()
+            .map(|(i, &x)| x + 1e-6 * ((i % 7) as f64 - 3.0))
+            .collect::<Vec<_>>();
+        let (cold, cold_stats) = svd(&perturbed_diag, &subdiag, None);
+        let (warm, warm_stats) = svd(&perturbed_diag, &subdiag, Some(&s));
+        for (&cold, &warm) in zip(&cold, &warm) {
+            assert!((cold - warm).abs() <= 8.0 * f64::EPSILON * cold.max(1.0));
+        }
+        assert!(warm_stats.secular_eq_solves == cold_stats.secular_eq_solves);
+        // only the final merge step is seeded by the guess
+        assert!(warm_stats.secular_eq_evaluations < cold_stats.secular_eq_evaluations);
+
+        // a bad guess falls back to the usual search
+        for guess in [vec![0.0; n], s.iter().map(|&x| 0.5 * x).collect::<Vec<_>>()] {
+            let (warm, _) = svd(&perturbed_diag, &subdiag, Some(&guess));
+            for (&cold, &warm) in zip(&cold, &warm) {
+                assert!((cold - warm).abs() <= 8.0 * f64::EPSILON * cold.max(1.0));
+            }
+        }
+    }
+
+    #[test]
+    fn test_svd_qr_stats() {
+        let n = 64;
+        let svd_stats = |diag: &[f64], subdiag: &[f64], algorithm: SvdAlgorithm| {
+            let mut stats = BidiagSvdStats::default();
+            compute_bidiag_real_svd_with_stats(
+                &mut diag.to_vec(),
+                &mut subdiag.to_vec(),
+                None,
+                None,
+                &mut stats,
+                4,
+                128,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(bidiag_real_svd_req::<f64>(
+                    n,
+                    4,
+                    false,
+                    false,
+                    Parallelism::None
+                )),
+                SvdParams {
+                    algorithm,
+                    ..Default::default()
+                },
+            );
+            stats
+        };
+
+        let (mut diag, mut subdiag) = crate::testing::test_bidiag::<f64>(n, 5);
+
+        // every singular value but the first one converges at the end of the active block, and
+        // each sweep applies two rotations per subdiagonal entry of the active block
+        let stats = svd_stats(&diag, &subdiag, SvdAlgorithm::Auto);
+        assert!(stats.secular_eq_solves == 0);
+        assert!(stats == svd_stats(&diag, &subdiag, SvdAlgorithm::QrIteration));
+        let stats = stats.qr;
+        assert!(stats.iterations > 0);
+        assert!(stats.iterations < n * n);
+        assert!(stats.deflations == n - 1);
+        assert!(stats.givens_applied >= 2 * stats.iterations);
+        assert!(stats.unconverged == 0);
+
+        // the zero diagonal entry, along with the ones the iteration produces when the matrix is
+        // singular, are chased out of the active block. the last row is zero, so that it's not
+        // rotated into the diagonal first
+        diag[n / 2] = 0.0;
+        subdiag[n - 1] = 0.0;
+        let stats = svd_stats(&diag, &subdiag, SvdAlgorithm::QrIteration).qr;
+        assert!(stats.deflations > n - 1);
+
+        // the qr iteration isn't used
+        for algorithm in [SvdAlgorithm::Jacobi, SvdAlgorithm::DivideConquer] {
+            assert!(svd_stats(&diag, &subdiag, algorithm).qr == QrStats::default());
+        }
+
+        // a nan subdiagonal entry is never negligible, so the iteration runs out of sweeps with
+        // every singular value of the block containing it still active
+        let (diag, mut subdiag) = crate::testing::test_bidiag::<f64>(8, 5);
+        subdiag[3] = f64::NAN;
+        let stats = svd_stats(&diag, &subdiag, SvdAlgorithm::QrIteration).qr;
+        assert!(stats.unconverged > 0);
+        assert!(stats.iterations == 30 * 8 * 8);
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn test_svd_parallel_threshold() {
+        let n = 512;
+        let diag = (0..n).map(|_| rand::random::<f64>()).collect::<Vec<_>>();
+        let subdiag = (0..n).map(|_| rand::random::<f64>()).collect::<Vec<_>>();
+
+        let mut singular_values = Vec::new();
+        for parallel_threshold in [0, 64, usize::MAX] {
+            let params = SvdParams {
+                parallel_threshold: Some(parallel_threshold),
+                ..Default::default()
+            };
+
+            let mut u = Mat::from_fn(n + 1, n + 1, |_, _| f64::NAN);
+            let mut v = Mat::from_fn(n, n, |_, _| f64::NAN);
+            let mut s = diag.clone();
+            let mut e = subdiag.clone();
+            compute_bidiag_real_svd(
+                &mut s,
+                &mut e,
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                5,
+                0,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::Rayon(4),
+                make_stack!(bidiag_real_svd_req::<f64>(
+                    n,
+                    5,
+                    true,
+                    true,
+                    Parallelism::Rayon(4)
+                )),
+                params,
+            );
+            let s_mat = Mat::from_fn(n + 1, n, |i, j| if i == j { s[i] } else { 0.0 });
+
+            let reconstructed = &u * &s_mat * v.transpose();
+            for j in 0..n {
+                for i in 0..n + 1 {
+                    let target = if i == j {
+                        diag[j]
+                    } else if i == j + 1 {
+                        subdiag[j]
+                    } else {
+                        0.0
+                    };
+
+                    assert_approx_eq!(reconstructed.read(i, j), target, 1e-10);
+                }
+            }
+            singular_values.push(s);
+        }
+
+        for s in &singular_values[1..] {
+            for (x, y) in zip(s, &singular_values[0]) {
+                assert_approx_eq!(x, y, 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_svd_merge_parallelism() {
+        let n = 512;
+        let (diag, subdiag) = crate::testing::test_bidiag::<f64>(n, 13);
+
+        let mut results = Vec::new();
+        for merge_parallelism in [MergeParallelism::Nested, MergeParallelism::Sequential] {
+            let params = SvdParams {
+                parallel_threshold: Some(0),
+                merge_parallelism,
+                ..Default::default()
+            };
+
+            let mut u = Mat::from_fn(n + 1, n + 1, |_, _| f64::NAN);
+            let mut v = Mat::from_fn(n, n, |_, _| f64::NAN);
+            let mut s = diag.clone();
+            let mut e = subdiag.clone();
+            compute_bidiag_real_svd(
+                &mut s,
+                &mut e,
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                5,
+                0,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::Rayon(4),
+                make_stack!(bidiag_real_svd_req::<f64>(
+                    n,
+                    5,
+                    true,
+                    true,
+                    Parallelism::Rayon(4)
+                )),
+                params,
+            );
+            crate::testing::assert_orthogonal(u.as_ref(), 1e-10);
+            crate::testing::assert_orthogonal(v.as_ref(), 1e-10);
+            results.push((s, u, v));
+        }
+
+        // only the number of threads used by the products changes, not their result
+        let (s0, u0, v0) = &results[0];
+        let (s1, u1, v1) = &results[1];
+        for (x, y) in zip(s0, s1) {
+            assert_approx_eq!(x, y, 1e-12);
+        }
+        for (a, b) in [(u0, u1), (v0, v1)] {
+            for j in 0..a.ncols() {
+                for i in 0..a.nrows() {
+                    assert_approx_eq!(a.read(i, j), b.read(i, j), 1e-10);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_svd_blocks() {
+        let n = 300;
+        let (mut diag, mut subdiag) = crate::testing::test_bidiag::<f64>(n, 3);
+        // blocks of sizes 1, 2, 1, 45, 1, 120, 130, with a zero singular value in the 45 block
+        for i in [0, 2, 3, 48, 49, 169] {
+            subdiag[i] = 0.0;
+        }
+        diag[20] = 0.0;
+
+        let svd =
+            |subdiag: &[f64], parallelism: Parallelism, bidiag_qr_fallback_threshold: usize| {
+                let params = SvdParams {
+                    parallel_threshold: Some(0),
+                    ..Default::default()
+                };
+                let mut u = Mat::from_fn(n + 1, n + 1, |_, _| f64::NAN);
+                let mut v = Mat::from_fn(n, n, |_, _| f64::NAN);
+                let mut perm = vec![usize::MAX; n];
+                let mut s = diag.clone();
+                let mut e = subdiag.to_vec();
+                compute_bidiag_real_svd_with_perm(
+                    &mut s,
+                    &mut e,
+                    Some(u.as_mut()),
+                    Some(v.as_mut()),
+                    &mut perm,
+                    5,
+                    bidiag_qr_fallback_threshold,
+                    f64::EPSILON,
+                    f64::MIN_POSITIVE,
+                    parallelism,
+                    make_stack!(bidiag_real_svd_req::<f64>(n, 5, true, true, parallelism)),
+                    params,
+                );
+                (s, u, v, perm)
+            };
+
+        let (s, u, v, perm) = svd(&subdiag, Parallelism::None, 0);
+
+        assert!(s.windows(2).all(|s| s[0] >= s[1]));
+        let mut seen = vec![false; n];
+        for &p in &perm {
+            assert!(!seen[p]);
+            seen[p] = true;
+        }
+
+        let s_mat = Mat::from_fn(n + 1, n, |i, j| if i == j { s[i] } else { 0.0 });
+        let reconstructed = &u * &s_mat * v.transpose();
+        let uu = u.transpose() * &u;
+        let vv = v.transpose() * &v;
+        for j in 0..n {
+            for i in 0..n + 1 {
+                let target = if i == j {
+                    diag[j]
+                } else if i == j + 1 {
+                    subdiag[j]
+                } else {
+                    0.0
+                };
+                assert_approx_eq!(reconstructed.read(i, j), target, 1e-10);
+            }
+        }
+        for j in 0..n + 1 {
+            for i in 0..n + 1 {
+                let target = if i == j { 1.0 } else { 0.0 };
+                assert_approx_eq!(uu.read(i, j), target, 1e-10);
+                if i < n && j < n {
+                    assert_approx_eq!(vv.read(i, j), target, 1e-10);
+                }
+            }
+        }
+
+        // the blocks are solved the same way whether they run concurrently or not
+        let (s_par, u_par, v_par, perm_par) = svd(&subdiag, Parallelism::Rayon(4), 0);
+        assert!(s_par == s);
+        assert!(u_par == u);
+        assert!(v_par == v);
+        assert!(perm_par == perm);
+
+        // the singular values match the ones computed by the qr algorithm on the full matrix,
+        // which requires the last element of the subdiagonal to be zero
+        subdiag[n - 1] = 0.0;
+        let (s, _, _, _) = svd(&subdiag, Parallelism::None, 0);
+        let (s_qr, _, _, _) = svd(&subdiag, Parallelism::None, n);
+        for (x, y) in zip(&s, &s_qr) {
+            assert_approx_eq!(x, y, 1e-12);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_svd_split_by_mass() {
+        let n = 400;
+        let (diag, mut subdiag) = crate::testing::test_bidiag::<f64>(n, 5);
+        // the coupling is concentrated in a region that the halves would cut through
+        for (i, x) in subdiag.iter_mut().enumerate() {
+            if !(150..250).contains(&i) {
+                *x *= 1e-20;
+            }
+        }
+
+        let svd = |split_strategy: SplitStrategy, compute_vectors: bool| {
+            let params = SvdParams {
+                split_strategy,
+                ..Default::default()
+            };
+            let mut diag = diag.clone();
+            let mut subdiag = subdiag.clone();
+            let mut u = Mat::from_fn(n + 1, n + 1, |_, _| f64::NAN);
+            let mut v = Mat::from_fn(n, n, |_, _| f64::NAN);
+            compute_bidiag_real_svd(
+                &mut diag,
+                &mut subdiag,
+                compute_vectors.then_some(u.as_mut()),
+                compute_vectors.then_some(v.as_mut()),
+                5,
+                0,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(bidiag_real_svd_req::<f64>(
+                    n,
+                    5,
+                    compute_vectors,
+                    compute_vectors,
+                    Parallelism::None
+                )),
+                params,
+            );
+            (diag, u, v)
+        };
+
+        let (s, u, v) = svd(SplitStrategy::ByMass, true);
+        let reconstructed =
+            &u * Mat::from_fn(n + 1, n, |i, j| if i == j { s[i] } else { 0.0 }) * v.transpose();
+        for j in 0..n {
+            for i in 0..n + 1 {
+                let target = if i == j {
+                    diag[j]
+                } else if i == j + 1 {
+                    subdiag[j]
+                } else {
+                    0.0
+                };
+                assert_approx_eq!(reconstructed.read(i, j), target, 1e-10);
+            }
+        }
+
+        let (s_half, _, _) = svd(SplitStrategy::Half, true);
+        let (s_values, _, _) = svd(SplitStrategy::ByMass, false);
+        for ((x, y), z) in zip(&s, &s_half).zip(&s_values) {
+            assert_approx_eq!(x, y, 1e-12);
+            assert_approx_eq!(x, z, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_svd_range() {
+        let n = 1024;
+        let diag = (0..n).map(|_| rand::random::<f64>()).collect::<Vec<_>>();
+        let subdiag = (0..n).map(|_| rand::random::<f64>()).collect::<Vec<_>>();
+
+        let singular_values = |params: SvdParams| {
+            let mut s = diag.clone();
+            let mut e = subdiag.clone();
+            compute_bidiag_real_svd(
+                &mut s,
+                &mut e,
+                None,
+                None,
+                5,
+                0,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(bidiag_real_svd_req::<f64>(
+                    n,
+                    5,
+                    false,
+                    false,
+                    Parallelism::None
+                )),
+                params,
+            );
+            s
+        };
+
+        let full = singular_values(SvdParams::default());
+        let (lo, hi) = (full[20], full[10]);
+        let partial = singular_values(SvdParams {
+            singular_value_range: Some((lo, hi)),
+            ..Default::default()
+        });
+
+        let mut count = 0;
+        for (x, y) in zip(&full, &partial) {
+            if lo <= *x && *x <= hi {
+                assert_approx_eq!(x, y, 1e-12);
+                count += 1;
+            } else {
+                assert!(*y < lo || *y > hi);
+            }
+        }
+        assert!(count == 11);
+    }
+
+    fn bidiag_svd_generic<E: RealField>(diag: &[E], subdiag: &[E]) -> (Vec<E>, Mat<E>, Mat<E>) {
+        let n = diag.len();
+        let mut u = Mat::<E>::zeros(n + 1, n + 1);
+        let mut v = Mat::<E>::zeros(n, n);
+        let mut s = diag.to_vec();
+        let mut e = subdiag.to_vec();
+        compute_bidiag_real_svd_auto(
+            &mut s,
+            &mut e,
+            Some(u.as_mut()),
+            Some(v.as_mut()),
+            5,
+            0,
+            Parallelism::None,
+            make_stack!(bidiag_real_svd_req::<E>(
+                n,
+                5,
+                true,
+                true,
+                Parallelism::None
+            )),
+            SvdParams::default(),
+        );
+        (s, u, v)
+    }
+
+    #[test]
+    fn test_svd_auto_epsilon() {
+        let n = 64;
+        let diag = (0..n).map(|_| rand::random::<f64>()).collect::<Vec<_>>();
+        let subdiag = (0..n).map(|_| rand::random::<f64>()).collect::<Vec<_>>();
+
+        let (s, u, v) = bidiag_svd_generic(&diag, &subdiag);
+        let s_mat = Mat::from_fn(n + 1, n, |i, j| if i == j { s[i] } else { 0.0 });
+        let reconstructed = &u * &s_mat * v.transpose();
+        for j in 0..n {
+            for i in 0..n + 1 {
+                let target = if i == j {
+                    diag[j]
+                } else if i == j + 1 {
+                    subdiag[j]
+                } else {
+                    0.0
+                };
+                assert_approx_eq!(reconstructed.read(i, j), target, 1e-10);
+            }
+        }
+
+        let diag = diag.iter().map(|&x| x as f32).collect::<Vec<_>>();
+        let subdiag = subdiag.iter().map(|&x| x as f32).collect::<Vec<_>>();
+        let (s, u, v) = bidiag_svd_generic(&diag, &subdiag);
+        let s_mat = Mat::from_fn(n + 1, n, |i, j| if i == j { s[i] } else { 0.0 });
+        let reconstructed = &u * &s_mat * v.transpose();
+        for j in 0..n {
+            for i in 0..n + 1 {
+                let target = if i == j {
+                    diag[j]
+                } else if i == j + 1 {
+                    subdiag[j]
+                } else {
+                    0.0
+                };
+                assert_approx_eq!(reconstructed.read(i, j), target, 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn test_svd_perm() {
+        // jacobi, qr and divide and conquer paths
+        for (n, jacobi_fallback_threshold, bidiag_qr_fallback_threshold) in
+            [(4, 5, 0), (32, 5, 64), (300, 5, 0)]
+        {
+            // with a zero subdiagonal, the singular values are the diagonal entries, and each one
+            // comes from a single index of the bidiagonal matrix
+            let diag = (0..n)
+                .map(|_| rand::random::<f64>() + 1.0)
+                .collect::<Vec<_>>();
+            let subdiag = vec![0.0; n];
+
+            let mut u = Mat::from_fn(n + 1, n + 1, |_, _| f64::NAN);
+            let mut v = Mat::from_fn(n, n, |_, _| f64::NAN);
+            let mut perm = vec![usize::MAX; n];
+            let mut s = diag.clone();
+            let mut e = subdiag.clone();
+            compute_bidiag_real_svd_with_perm(
+                &mut s,
+                &mut e,
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                &mut perm,
+                jacobi_fallback_threshold,
+                bidiag_qr_fallback_threshold,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(bidiag_real_svd_req::<f64>(
+                    n,
+                    jacobi_fallback_threshold,
+                    true,
+                    true,
+                    Parallelism::None
+                )),
+                SvdParams::default(),
+            );
+
+            let mut seen = vec![false; n];
+            for j in 0..n {
+                let p = perm[j];
+                assert!(!seen[p]);
+                seen[p] = true;
+
+                assert_approx_eq!(s[j], diag[p], 1e-12);
+                assert_approx_eq!(v.read(p, j).abs(), 1.0, 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_self_overlap() {
+        let data = [0.0f64; 64];
+        let ptr = data.as_ptr();
+        let overlap = |m, n, rs, cs| {
+            has_self_overlap(unsafe { faer_core::mat::from_raw_parts::<f64>(ptr, m, n, rs, cs) })
+        };
+
+        assert!(!overlap(4, 4, 1, 4));
+        assert!(!overlap(4, 4, 4, 1));
+        assert!(!overlap(4, 4, -1, 4));
+        assert!(!overlap(4, 4, 1, -4));
+        assert!(!overlap(4, 3, 1, 5));
+        assert!(!overlap(1, 4, 0, 1));
+        assert!(!overlap(0, 4, 1, 0));
+
+        assert!(overlap(4, 4, 1, 0));
+        assert!(overlap(4, 4, 0, 1));
+        assert!(overlap(4, 4, 1, 3));
+        assert!(overlap(4, 4, 2, 2));
+        assert!(overlap(4, 4, -1, 2));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "the columns of `v` must not overlap in memory")]
+    fn test_svd_aliasing_v() {
+        let n = 8;
+        let mut diag = (0..n).map(|_| rand::random::<f64>()).collect::<Vec<_>>();
+        let mut subdiag = (0..n).map(|_| rand::random::<f64>()).collect::<Vec<_>>();
+
+        let mut u = Mat::from_fn(n + 1, n + 1, |_, _| f64::NAN);
+        let mut v_data = Mat::from_fn(n, 1, |_, _| f64::NAN);
+        // every column of `v` refers to the same memory
+        let v =
+            unsafe { faer_core::mat::from_raw_parts_mut::<f64>(v_data.as_ptr_mut(), n, n, 1, 0) };
+
+        compute_bidiag_real_svd(
+            &mut diag,
+            &mut subdiag,
+            Some(u.as_mut()),
+            Some(v),
+            4,
+            128,
+            f64::EPSILON,
+            f64::MIN_POSITIVE,
+            Parallelism::None,
+            make_stack!(bidiag_real_svd_req::<f64>(
+                n,
+                4,
+                true,
+                true,
+                Parallelism::None
+            )),
+            SvdParams::default(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "`subdiag` must have the same length as `diag`")]
+    fn test_svd_subdiag_len() {
+        let n = 8;
+        let mut diag = (0..n).map(|_| rand::random::<f64>()).collect::<Vec<_>>();
+        let mut subdiag = (0..n - 1)
+            .map(|_| rand::random::<f64>())
+            .collect::<Vec<_>>();
+
+        compute_bidiag_real_svd(
+            &mut diag,
+            &mut subdiag,
+            None,
+            None,
+            4,
+            128,
+            f64::EPSILON,
+            f64::MIN_POSITIVE,
+            Parallelism::None,
+            make_stack!(bidiag_real_svd_req::<f64>(
+                n,
+                4,
+                false,
+                false,
+                Parallelism::None
+            )),
+            SvdParams::default(),
+        );
+    }
+
+    #[test]
+    fn test_svd_fallback_last_row() {
+        let n = 12;
+        let diag = (0..n).map(|_| rand::random::<f64>()).collect::<Vec<_>>();
+        let subdiag = (0..n).map(|_| rand::random::<f64>()).collect::<Vec<_>>();
+
+        // jacobi fallback, then qr fallback
+        for (jacobi_fallback_threshold, bidiag_qr_fallback_threshold) in [(16, 0), (4, 16)] {
+            let mut u = Mat::from_fn(n + 1, n + 1, |_, _| f64::NAN);
+            let mut v = Mat::from_fn(n, n, |_, _| f64::NAN);
+            let s = {
+                let mut diag = diag.clone();
+                let mut subdiag = subdiag.clone();
+                compute_bidiag_real_svd(
+                    &mut diag,
+                    &mut subdiag,
+                    Some(u.as_mut()),
+                    Some(v.as_mut()),
+                    jacobi_fallback_threshold,
+                    bidiag_qr_fallback_threshold,
+                    f64::EPSILON,
+                    f64::MIN_POSITIVE,
+                    Parallelism::None,
+                    make_stack!(bidiag_real_svd_req::<f64>(
+                        n,
+                        jacobi_fallback_threshold,
+                        true,
+                        true,
+                        Parallelism::None
+                    )),
+                    SvdParams::default(),
+                );
+                Mat::from_fn(n + 1, n, |i, j| if i == j { diag[i] } else { 0.0 })
+            };
+
+            let reconstructed = &u * &s * v.transpose();
+            for j in 0..n {
+                for i in 0..n + 1 {
+                    let target = if i == j {
+                        diag[j]
+                    } else if i == j + 1 {
+                        subdiag[j]
+                    } else {
+                        0.0
+                    };
+
+                    assert_approx_eq!(reconstructed.read(i, j), target, 1e-10);
+                }
+            }
+
+            let utu = u.transpose() * &u;
+            for j in 0..n + 1 {
+                for i in 0..n + 1 {
+                    let target = if i == j { 1.0 } else { 0.0 };
+                    assert_approx_eq!(utu.read(i, j), target, 1e-10);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_svd_selected_algorithm() {
+        let svd = |diag: &[f64], subdiag: &[f64]| {
+            let n = diag.len();
+            let mut diag = diag.to_vec();
+            let mut subdiag = subdiag.to_vec();
+            let mut stats = BidiagSvdStats::default();
+            compute_bidiag_real_svd_with_stats(
+                &mut diag,
+                &mut subdiag,
+                None,
+                None,
+                &mut stats,
+                4,
+                128,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(bidiag_real_svd_req::<f64>(
+                    n,
+                    4,
+                    false,
+                    false,
+                    Parallelism::None
+                )),
+                SvdParams::default(),
+            );
+            stats.algorithm
+        };
+
+        for (n, expected) in [
+            (0, SvdAlgorithm::Jacobi),
+            (1, SvdAlgorithm::Jacobi),
+            (2, SvdAlgorithm::ClosedForm),
+            (4, SvdAlgorithm::Jacobi),
+            (5, SvdAlgorithm::QrIteration),
+            (37, SvdAlgorithm::QrIteration),
+            (128, SvdAlgorithm::QrIteration),
+            (129, SvdAlgorithm::DivideConquer),
+            (300, SvdAlgorithm::DivideConquer),
+        ] {
+            let (diag, subdiag) = crate::testing::test_bidiag::<f64>(n, 3);
+            assert!(svd(&diag, &subdiag) == expected);
+        }
+
+        // the blocks are solved independently, and the largest one decides the algorithm
+        let n = 300;
+        let (diag, mut subdiag) = crate::testing::test_bidiag::<f64>(n, 3);
+        subdiag[9] = 0.0;
+        assert!(svd(&diag, &subdiag) == SvdAlgorithm::DivideConquer);
+        subdiag[109] = 0.0;
+        subdiag[209] = 0.0;
+        assert!(svd(&diag, &subdiag) == SvdAlgorithm::QrIteration);
+    }
+
+    #[test]
+    fn test_svd_zero_matrix() {
+        for n in [1, 2, 3, 5, 8, 40, 200] {
+            for algorithm in [
+                SvdAlgorithm::Auto,
+                SvdAlgorithm::Jacobi,
+                SvdAlgorithm::QrIteration,
+                SvdAlgorithm::DivideConquer,
+            ] {
+                let params = SvdParams {
+                    algorithm,
+                    ..Default::default()
+                };
+                let mut diag = vec![0.0; n];
+                let mut subdiag = vec![0.0; n];
+                let mut u = Mat::from_fn(n + 1, n + 1, |_, _| f64::NAN);
+                let mut v = Mat::from_fn(n, n, |_, _| f64::NAN);
+                compute_bidiag_real_svd(
+                    &mut diag,
+                    &mut subdiag,
+                    Some(u.as_mut()),
+                    Some(v.as_mut()),
+                    4,
+                    8,
+                    f64::EPSILON,
+                    f64::MIN_POSITIVE,
+                    Parallelism::None,
+                    make_stack!(bidiag_real_svd_req::<f64>(
+                        n,
+                        4,
+                        true,
+                        true,
+                        Parallelism::None
+                    )),
+                    params,
+                );
+
+                assert!(diag.iter().all(|&s| s == 0.0));
+                for (x, dim) in [(&u, n + 1), (&v, n)] {
+                    for j in 0..dim {
+                        for i in 0..dim {
+                            let target = if i == j { 1.0 } else { 0.0 };
+                            assert!(x.read(i, j) == target);
+                        }
+                    }
+                }
+            }
+        }
+
+        // the zero blocks never reach the divide and conquer algorithm through the dispatch, since
+        // their subdiagonal is zero, so it's called directly
+        let n = 40;
+        let stack_req =
+            || bidiag_real_smallest_singular_triplet_req::<f64>(n, 4, Parallelism::None);
+        for (u_nrows, u_ncols, fill_u, smallest_only) in [
+            (n + 1, n + 1, true, false),
+            (n + 1, n, true, false),
+            (2, n + 1, false, false),
+            (n + 1, 1, true, true),
+        ] {
+            let v_ncols = if smallest_only { 1 } else { n };
+            let mut diag = vec![0.0; n];
+            let mut subdiag = vec![0.0; n];
+            let mut u = Mat::from_fn(u_nrows, u_ncols, |_, _| f64::NAN);
+            let mut v = Mat::from_fn(n, v_ncols, |_, _| f64::NAN);
+            bidiag_svd_impl(
+                &mut diag,
+                &mut subdiag,
+                u.as_mut(),
+                Some(v.as_mut()),
+                None,
+                &mut BidiagSvdStats::default(),
+                fill_u,
+                smallest_only,
+                false,
+                None,
+                0,
+                4,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(stack_req()),
+                SvdParams::default(),
+            );
+
+            assert!(diag.iter().all(|&s| s == 0.0));
+            // row `i` of `u` holds row `row_index[i]` of the identity
+            let row_index = |i: usize| if u_nrows == 2 && i == 1 { n } else { i };
+            let col_index = |j: usize| if smallest_only { n - 1 } else { j };
+            for j in 0..u_ncols {
+                for i in 0..u_nrows {
+                    let target = if row_index(i) == col_index(j) {
+                        1.0
+                    } else {
+                        0.0
+                    };
+                    assert!(u.read(i, j) == target);
+                }
+            }
+            for j in 0..v_ncols {
+                for i in 0..n {
+                    let target = if i == col_index(j) { 1.0 } else { 0.0 };
+                    assert!(v.read(i, j) == target);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_svd_forced_algorithm() {
+        for n in [1, 2, 3, 5, 8, 12, 40, 200] {
+            let (diag, subdiag) = crate::testing::test_bidiag::<f64>(n, 7);
+
+            let svd = |algorithm: SvdAlgorithm, compute_vectors: bool| {
+                let params = SvdParams {
+                    algorithm,
+                    ..Default::default()
+                };
+                let mut diag = diag.clone();
+                let mut subdiag = subdiag.clone();
+                let mut u = Mat::from_fn(n + 1, n + 1, |_, _| f64::NAN);
+                let mut v = Mat::from_fn(n, n, |_, _| f64::NAN);
+                let mut stats = BidiagSvdStats::default();
+                compute_bidiag_real_svd_with_stats(
+                    &mut diag,
+                    &mut subdiag,
+                    compute_vectors.then_some(u.as_mut()),
+                    compute_vectors.then_some(v.as_mut()),
+                    &mut stats,
+                    4,
+                    128,
+                    f64::EPSILON,
+                    f64::MIN_POSITIVE,
+                    Parallelism::None,
+                    make_stack!(bidiag_real_svd_req::<f64>(
+                        n,
+                        4,
+                        compute_vectors,
+                        compute_vectors,
+                        Parallelism::None
+                    )),
+                    params,
+                );
+                (diag, u, v, stats)
+            };
+
+            let (expected, ..) = svd(SvdAlgorithm::Auto, true);
+            for algorithm in [
+                SvdAlgorithm::Jacobi,
+                SvdAlgorithm::QrIteration,
+                SvdAlgorithm::DivideConquer,
+            ] {
+                for compute_vectors in [true, false] {
+                    let (s, u, v, stats) = svd(algorithm, compute_vectors);
+                    // only the divide and conquer algorithm solves secular equations
+                    assert!(
+                        (stats.secular_eq_solves > 0)
+                            == (algorithm == SvdAlgorithm::DivideConquer && n >= 3)
+                    );
+                    if algorithm == SvdAlgorithm::DivideConquer && n < 3 {
+                        assert!(stats.algorithm == SvdAlgorithm::Jacobi);
+                    } else {
+                        assert!(stats.algorithm == algorithm);
+                    }
+                    for (&s, &expected) in s.iter().zip(&expected) {
+                        assert_approx_eq!(s, expected, 1e-10);
+                    }
+
+                    if compute_vectors {
+                        let s = Mat::from_fn(n + 1, n, |i, j| if i == j { s[i] } else { 0.0 });
+                        let reconstructed = &u * &s * v.transpose();
+                        for j in 0..n {
+                            for i in 0..n + 1 {
+                                let target = if i == j {
+                                    diag[j]
+                                } else if i == j + 1 {
+                                    subdiag[j]
+                                } else {
+                                    0.0
+                                };
+                                assert_approx_eq!(reconstructed.read(i, j), target, 1e-10);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_svd_2x2_closed_form() {
+        let n = 2;
+        let mut cases = vec![
+            ([1.0, 2.0], [3.0, 0.0]),
+            ([1.0, 2.0], [3.0, 4.0]),
+            ([-1.0, 2.0], [3.0, -4.0]),
+            ([2.0, 2.0], [0.0, 0.0]),
+            ([0.0, 2.0], [3.0, 0.0]),
+            ([1.0, 0.0], [3.0, 0.0]),
+            ([0.0, 0.0], [0.0, 0.0]),
+            ([1.0, 1.0], [1e20, 0.0]),
+            ([1e-300, 1e300], [1.0, 0.0]),
+            ([1e300, -1e-300], [1e-10, 0.0]),
+            ([3.0, 3.0 * (1.0 + f64::EPSILON)], [1e-8, 0.0]),
+        ];
+        for _ in 0..100 {
+            cases.push((
+                [rand::random::<f64>() - 0.5, rand::random::<f64>() - 0.5],
+                [rand::random::<f64>() - 0.5, rand::random::<f64>() - 0.5],
+            ));
+        }
+
+        for (diag, subdiag) in cases {
+            let svd = |algorithm: SvdAlgorithm, u_ncols: usize| {
+                let mut s = diag;
+                let mut e = subdiag;
+                let mut u = Mat::from_fn(n + 1, u_ncols, |_, _| f64::NAN);
+                let mut v = Mat::from_fn(n, n, |_, _| f64::NAN);
+                let mut perm = [usize::MAX; 2];
+                let mut stats = BidiagSvdStats::default();
+                compute_bidiag_real_svd_impl(
+                    &mut s,
+                    &mut e,
+                    Some(u.as_mut()),
+                    Some(v.as_mut()),
+                    Some(&mut perm),
+                    Some(&mut stats),
+                    None,
+                    false,
+                    4,
+                    128,
+                    f64::EPSILON,
+                    f64::MIN_POSITIVE,
+                    Parallelism::None,
+                    make_stack!(bidiag_real_svd_req::<f64>(
+                        n,
+                        4,
+                        true,
+                        true,
+                        Parallelism::None
+                    )),
+                    SvdParams {
+                        algorithm,
+                        ..Default::default()
+                    },
+                );
+                (s, u, v, perm, stats)
+            };
+
+            let (expected, ..) = svd(SvdAlgorithm::Jacobi, n + 1);
+            let norm = expected[0];
+            for u_ncols in [n + 1, n] {
+                let (s, u, v, perm, stats) = svd(SvdAlgorithm::Auto, u_ncols);
+                // no iteration was needed
+                assert!(
+                    stats
+                        == BidiagSvdStats {
+                            algorithm: SvdAlgorithm::ClosedForm,
+                            ..Default::default()
+                        }
+                );
+                assert!(perm == [0, 1]);
+                assert!(s[0] >= s[1]);
+                assert!(s[1] >= 0.0);
+                for (&s, &expected) in s.iter().zip(&expected) {
+                    assert!((s - expected).abs() <= 4.0 * f64::EPSILON * norm);
+                }
+
+                let s = Mat::from_fn(u_ncols, n, |i, j| if i == j { s[i] } else { 0.0 });
+                let reconstructed = &u * &s * v.transpose();
+                for j in 0..n {
+                    for i in 0..n + 1 {
+                        let target = if i == j {
+                            diag[j]
+                        } else if i == j + 1 {
+                            subdiag[j]
+                        } else {
+                            0.0
+                        };
+                        assert!(
+                            (reconstructed.read(i, j) - target).abs() <= 8.0 * f64::EPSILON * norm
+                        );
+                    }
+                }
+
+                let utu = u.transpose() * &u;
+                let vtv = v.transpose() * &v;
+                for j in 0..n {
+                    for i in 0..n {
+                        let target = if i == j { 1.0 } else { 0.0 };
+                        assert_approx_eq!(utu.read(i, j), target, 8.0 * f64::EPSILON);
+                        assert_approx_eq!(vtv.read(i, j), target, 8.0 * f64::EPSILON);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_svd_economy_u() {
+        for n in [1, 2, 3, 5, 12, 40, 200] {
+            for with_blocks in [false, true] {
+                let (diag, mut subdiag) = crate::testing::test_bidiag::<f64>(n, 13);
+                if with_blocks && n > 2 {
+                    subdiag[n / 2] = 0.0;
+                }
+
+                for algorithm in [
+                    SvdAlgorithm::Auto,
+                    SvdAlgorithm::Jacobi,
+                    SvdAlgorithm::QrIteration,
+                    SvdAlgorithm::DivideConquer,
+                ] {
+                    let params = SvdParams {
+                        algorithm,
+                        ..Default::default()
+                    };
+                    let svd = |ncols: usize| {
+                        let mut s = diag.clone();
+                        let mut u = Mat::from_fn(n + 1, ncols, |_, _| f64::NAN);
+                        let mut v = Mat::from_fn(n, n, |_, _| f64::NAN);
+                        compute_bidiag_real_svd(
+                            &mut s,
+                            &mut subdiag.clone(),
+                            Some(u.as_mut()),
+                            Some(v.as_mut()),
+                            4,
+                            128,
+                            f64::EPSILON,
+                            f64::MIN_POSITIVE,
+                            Parallelism::None,
+                            make_stack!(bidiag_real_svd_req::<f64>(
+                                n,
+                                4,
+                                true,
+                                true,
+                                Parallelism::None
+                            )),
+                            params,
+                        );
+                        (s, u, v)
+                    };
+
+                    let (s_full, u_full, _) = svd(n + 1);
+                    let (s, u, v) = svd(n);
+
+                    // the economy `u` is made of the first `n` columns of the full one
+                    for (&s, &s_full) in s.iter().zip(&s_full) {
+                        assert_approx_eq!(s, s_full, 1e-10);
+                    }
+                    for j in 0..n {
+                        for i in 0..n + 1 {
+                            assert_approx_eq!(u.read(i, j), u_full.read(i, j), 1e-10);
+                        }
+                    }
+
+                    let utu = u.transpose() * &u;
+                    let s = Mat::from_fn(n, n, |i, j| if i == j { s[i] } else { 0.0 });
+                    let reconstructed = &u * &s * v.transpose();
+                    for j in 0..n {
+                        for i in 0..n {
+                            let target = if i == j { 1.0 } else { 0.0 };
+                            assert_approx_eq!(utu.read(i, j), target, 1e-10);
+                        }
+                        for i in 0..n + 1 {
+                            let target = if i == j {
+                                diag[j]
+                            } else if i == j + 1 {
+                                subdiag[j]
+                            } else {
+                                0.0
+                            };
+                            assert_approx_eq!(reconstructed.read(i, j), target, 1e-10);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_svd_out_of_place() {
+        for n in [1, 2, 3, 8, 40, 200] {
+            let (diag, subdiag) = crate::testing::test_bidiag::<f64>(n, 5);
+            let (diag_copy, subdiag_copy) = (diag.clone(), subdiag.clone());
+
+            let mut out = vec![f64::NAN; n];
+            let mut u = Mat::from_fn(n + 1, n + 1, |_, _| f64::NAN);
+            let mut v = Mat::from_fn(n, n, |_, _| f64::NAN);
+            compute_bidiag_real_svd_out_of_place(
+                &mut out,
+                &diag,
+                &subdiag,
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                4,
+                128,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(bidiag_real_svd_out_of_place_req::<f64>(
+                    n,
+                    4,
+                    true,
+                    true,
+                    Parallelism::None
+                )),
+                SvdParams::default(),
+            );
+            assert!(diag == diag_copy);
+            assert!(subdiag == subdiag_copy);
+
+            let mut expected = diag.clone();
+            let mut expected_u = Mat::from_fn(n + 1, n + 1, |_, _| f64::NAN);
+            let mut expected_v = Mat::from_fn(n, n, |_, _| f64::NAN);
+            compute_bidiag_real_svd(
+                &mut expected,
+                &mut subdiag.clone(),
+                Some(expected_u.as_mut()),
+                Some(expected_v.as_mut()),
+                4,
+                128,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(bidiag_real_svd_req::<f64>(
+                    n,
+                    4,
+                    true,
+                    true,
+                    Parallelism::None
+                )),
+                SvdParams::default(),
+            );
+            assert!(out == expected);
+            assert!(u == expected_u);
+            assert!(v == expected_v);
+        }
+    }
+
+    #[test]
+    fn test_svd_smallest_singular_triplet() {
+        for n in [1, 2, 3, 8, 40, 200] {
+            for with_zero_subdiag in [false, true] {
+                let (diag, mut subdiag) = crate::testing::test_bidiag::<f64>(n, 7);
+                if with_zero_subdiag && n > 2 {
+                    // splits the matrix into independent blocks
+                    subdiag[n / 2] = 0.0;
+                }
+
+                let mut u = Mat::from_fn(n + 1, 1, |_, _| f64::NAN);
+                let mut v = Mat::from_fn(n, 1, |_, _| f64::NAN);
+                let sigma = compute_bidiag_real_smallest_singular_triplet(
+                    &diag,
+                    &subdiag,
+                    u.as_mut(),
+                    v.as_mut(),
+                    4,
+                    8,
+                    f64::EPSILON,
+                    f64::MIN_POSITIVE,
+                    Parallelism::None,
+                    make_stack!(bidiag_real_smallest_singular_triplet_req::<f64>(
+                        n,
+                        4,
+                        Parallelism::None
+                    )),
+                    SvdParams::default(),
+                );
 
-                zipped!(
-                    u.rb_mut().row_mut(row).subcols_mut(k + 1, rem).as_2d_mut(),
-                    u2_alloc.rb().row(row2).subcols(1, rem).as_2d(),
-                )
-                .for_each(|unzipped!(mut dst, src)| dst.write(src.read()));
-                u.write(row, n, u2_alloc.read(row2, 0));
+                let mut expected = diag.clone();
+                let mut expected_u = Mat::from_fn(n + 1, n + 1, |_, _| f64::NAN);
+                let mut expected_v = Mat::from_fn(n, n, |_, _| f64::NAN);
+                compute_bidiag_real_svd(
+                    &mut expected,
+                    &mut subdiag.clone(),
+                    Some(expected_u.as_mut()),
+                    Some(expected_v.as_mut()),
+                    4,
+                    8,
+                    f64::EPSILON,
+                    f64::MIN_POSITIVE,
+                    Parallelism::None,
+                    make_stack!(bidiag_real_svd_req::<f64>(
+                        n,
+                        4,
+                        true,
+                        true,
+                        Parallelism::None
+                    )),
+                    SvdParams::default(),
+                );
+
+                // the singular value is computed identically, and the vectors only differ by the
+                // rounding of the final matrix product
+                assert!(sigma == expected[n - 1]);
+                for i in 0..n + 1 {
+                    assert_approx_eq!(u.read(i, 0), expected_u.read(i, n - 1), 1e-13);
+                }
+                for i in 0..n {
+                    assert_approx_eq!(v.read(i, 0), expected_v.read(i, n - 1), 1e-13);
+                }
+
+                // the triplet satisfies `B v = sigma u`
+                let mat = bidiag_to_mat(&diag, &subdiag);
+                let residual = &mat * &v - &u * faer_core::scale(sigma);
+                let norm = expected[0];
+                for i in 0..n + 1 {
+                    assert!(residual.read(i, 0).abs() <= 32.0 * f64::EPSILON * norm);
+                }
             }
-        } else {
-            let (_, u2) = if compact_u == 0 {
-                let (u1, u2) = u.rb_mut().split_at_row_mut(k + 1);
-                (u1, u2)
-            } else {
-                let (u1, u2) = u.rb_mut().split_at_row_mut(1);
-                (u1, u2)
-            };
+        }
+    }
 
-            let (left, right) = u2.split_at_col_mut(k + 1);
-            let left = left.col_mut(k);
-            let right = right.col_mut(rem);
-            zipped!(right.as_2d_mut(), left.as_2d_mut()).for_each(
-                |unzipped!(mut right, mut left)| {
-                    right.write(left.read());
+    #[test]
+    fn test_svd_accumulate_v() {
+        for n in [1, 2, 8, 40, 200] {
+            let (diag, subdiag) = crate::testing::test_bidiag::<f64>(n, 11);
+            // a zero in the subdiagonal splits the matrix into blocks
+            let mut split_subdiag = subdiag.clone();
+            if n >= 3 {
+                split_subdiag[n / 3] = 0.0;
+            }
+            let v_init = Mat::from_fn(n + 3, n, |_, _| rand::random::<f64>());
+
+            for subdiag in [&subdiag, &split_subdiag] {
+                for (algorithm, max_depth) in [
+                    (SvdAlgorithm::Auto, None),
+                    (SvdAlgorithm::Auto, Some(2)),
+                    (SvdAlgorithm::Jacobi, None),
+                    (SvdAlgorithm::QrIteration, None),
+                    (SvdAlgorithm::DivideConquer, None),
+                ] {
+                    let params = SvdParams {
+                        algorithm,
+                        max_depth,
+                        ..Default::default()
+                    };
+
+                    let mut s = diag.clone();
+                    let mut u = Mat::from_fn(n + 1, n + 1, |_, _| f64::NAN);
+                    let mut v = v_init.clone();
+                    compute_bidiag_real_svd_accumulate_v(
+                        &mut s,
+                        &mut subdiag.clone(),
+                        Some(u.as_mut()),
+                        v.as_mut(),
+                        4,
+                        128,
+                        f64::EPSILON,
+                        f64::MIN_POSITIVE,
+                        Parallelism::None,
+                        make_stack!(bidiag_real_svd_accumulate_v_req::<f64>(
+                            n,
+                            n + 3,
+                            4,
+                            true,
+                            Parallelism::None
+                        )),
+                        params,
+                    );
+
+                    let mut expected_s = diag.clone();
+                    let mut expected_u = Mat::from_fn(n + 1, n + 1, |_, _| f64::NAN);
+                    let mut expected_v = Mat::from_fn(n, n, |_, _| f64::NAN);
+                    compute_bidiag_real_svd(
+                        &mut expected_s,
+                        &mut subdiag.clone(),
+                        Some(expected_u.as_mut()),
+                        Some(expected_v.as_mut()),
+                        4,
+                        128,
+                        f64::EPSILON,
+                        f64::MIN_POSITIVE,
+                        Parallelism::None,
+                        make_stack!(bidiag_real_svd_req::<f64>(
+                            n,
+                            4,
+                            true,
+                            true,
+                            Parallelism::None
+                        )),
+                        params,
+                    );
+                    assert!(s == expected_s);
+                    assert!(u == expected_u);
 
-                    if cfg!(debug_assertions) {
-                        left.write(E::faer_nan());
+                    let expected_v = &v_init * &expected_v;
+                    for j in 0..n {
+                        for i in 0..n + 3 {
+                            assert_approx_eq!(v.read(i, j), expected_v.read(i, j), 1e-12);
+                        }
                     }
-                },
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_svd_singular_values_out() {
+        for n in [1, 3, 8, 40, 200] {
+            let (mut diag, mut subdiag) = crate::testing::test_bidiag::<f64>(n, 11);
+            // a zero column makes the matrix rank deficient
+            diag[n / 2] = 0.0;
+            subdiag[n / 2] = 0.0;
+
+            let mut out = vec![f64::NAN; n];
+            compute_bidiag_real_singular_values(
+                &mut out,
+                &diag,
+                &subdiag,
+                4,
+                128,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(bidiag_real_singular_values_req::<f64>(
+                    n,
+                    4,
+                    Parallelism::None
+                )),
+                SvdParams::default(),
+            );
+
+            let mut expected = diag.clone();
+            compute_bidiag_real_svd(
+                &mut expected,
+                &mut subdiag.clone(),
+                None,
+                None,
+                4,
+                128,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(bidiag_real_svd_req::<f64>(
+                    n,
+                    4,
+                    false,
+                    false,
+                    Parallelism::None
+                )),
+                SvdParams::default(),
             );
+            assert!(out == expected);
+
+            // the nonzero singular values come first, in nonincreasing order
+            for i in 0..n - 1 {
+                assert!(out[i] >= out[i + 1]);
+                assert!(out[i] > 1e-10);
+            }
+            assert!(out[n - 1] <= 1e-14 * out[0]);
         }
-    } else {
-        let (mut u1, mut u2) = if compact_u == 0 {
-            let (u1, u2) = u.rb_mut().split_at_row_mut(k + 1);
-            (
-                u1.submatrix_mut(0, 1, k + 1, k + 1),
-                u2.submatrix_mut(0, k + 1, rem + 1, rem + 1),
-            )
-        } else {
-            // NOTE: need to handle rotation of Q1, q1
-            let (u1, u2) = u.rb_mut().split_at_col_mut(k + 1);
-            (u1, u2)
+    }
+
+    #[test]
+    fn test_svd_singular_values_interleaved() {
+        for n in [1, 3, 8, 40, 200] {
+            let (diag, subdiag) = crate::testing::test_bidiag::<f64>(n, 11);
+            let mut expected = vec![f64::NAN; n];
+            compute_bidiag_real_singular_values(
+                &mut expected,
+                &diag,
+                &subdiag,
+                4,
+                128,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(bidiag_real_singular_values_req::<f64>(
+                    n,
+                    4,
+                    Parallelism::None
+                )),
+                SvdParams::default(),
+            );
+
+            for stride in [2, 3] {
+                // the padding between the pairs must be ignored
+                let mut bidiag = vec![f64::NAN; (n - 1) * stride + 2];
+                for i in 0..n {
+                    bidiag[i * stride] = diag[i];
+                    bidiag[i * stride + 1] = subdiag[i];
+                }
+
+                let mut out = vec![f64::NAN; n];
+                compute_bidiag_real_singular_values_interleaved(
+                    &mut out,
+                    &bidiag,
+                    stride,
+                    4,
+                    128,
+                    f64::EPSILON,
+                    f64::MIN_POSITIVE,
+                    Parallelism::None,
+                    make_stack!(bidiag_real_singular_values_req::<f64>(
+                        n,
+                        4,
+                        Parallelism::None
+                    )),
+                    SvdParams::default(),
+                );
+                assert!(out == expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_svd_refined() {
+        let n = 200;
+        let (diag, subdiag) = crate::testing::test_bidiag::<f32>(n, 11);
+
+        let native = {
+            let mut diag = diag.clone();
+            let mut subdiag = subdiag.clone();
+            compute_bidiag_real_svd(
+                &mut diag,
+                &mut subdiag,
+                None,
+                None,
+                4,
+                128,
+                f32::EPSILON,
+                f32::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(bidiag_real_svd_req::<f32>(
+                    n,
+                    4,
+                    false,
+                    false,
+                    Parallelism::None
+                )),
+                SvdParams::default(),
+            );
+            diag
         };
 
-        let (mut v1, mut v2) = match v.rb_mut() {
-            Some(v) => {
-                let (v1, v2) = v.split_at_row_mut(k);
-                (
-                    Some(v1.submatrix_mut(0, 1, k, k)),
-                    Some(v2.submatrix_mut(1, k + 1, rem, rem)),
-                )
+        let mut u = Mat::from_fn(n + 1, n + 1, |_, _| f32::NAN);
+        let mut v = Mat::from_fn(n, n, |_, _| f32::NAN);
+        let refined = {
+            let mut diag = diag.clone();
+            let mut subdiag = subdiag.clone();
+            compute_bidiag_real_svd_refined(
+                &mut diag,
+                &mut subdiag,
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                4,
+                128,
+                Parallelism::None,
+                make_stack!(bidiag_real_svd_refined_req::<f32>(
+                    n,
+                    4,
+                    true,
+                    true,
+                    Parallelism::None
+                )),
+                SvdParams::default(),
+            );
+            diag
+        };
+
+        // the reference is the f64 decomposition, rounded to f32
+        let reference = {
+            let mut diag = diag.iter().map(|&x| x as f64).collect::<Vec<_>>();
+            let mut subdiag = subdiag.iter().map(|&x| x as f64).collect::<Vec<_>>();
+            compute_bidiag_real_svd(
+                &mut diag,
+                &mut subdiag,
+                None,
+                None,
+                4,
+                128,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(bidiag_real_svd_req::<f64>(
+                    n,
+                    4,
+                    false,
+                    false,
+                    Parallelism::None
+                )),
+                SvdParams::default(),
+            );
+            diag
+        };
+        for (&refined, &reference) in zip(&refined, &reference) {
+            assert!((refined - reference as f32).abs() <= f32::EPSILON * refined);
+        }
+
+        // the gap between the native path and the reference is within the backward error of the
+        // native path
+        let s_max = refined[0];
+        let gap = zip(&native, &refined)
+            .map(|(&native, &refined)| (native - refined).abs() / s_max)
+            .fold(0.0f32, f32::max);
+        assert!(gap <= 8.0 * n as f32 * f32::EPSILON);
+
+        let s = Mat::from_fn(n + 1, n, |i, j| if i == j { refined[i] } else { 0.0 });
+        let reconstructed = &u * &s * v.transpose();
+        for j in 0..n {
+            for i in 0..n + 1 {
+                let target = if i == j {
+                    diag[j]
+                } else if i == j + 1 {
+                    subdiag[j]
+                } else {
+                    0.0
+                };
+                assert!((reconstructed.read(i, j) - target).abs() <= 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_svd_qr_values_only() {
+        let n = 32;
+        let diag = (0..n).map(|_| rand::random::<f64>()).collect::<Vec<_>>();
+        let mut subdiag = (0..n).map(|_| rand::random::<f64>()).collect::<Vec<_>>();
+        subdiag[n - 1] = 0.0;
+
+        let mut singular_values = Vec::new();
+        for (compute_u, compute_v) in [(true, true), (false, true), (true, false), (false, false)] {
+            let mut u = Mat::from_fn(n + 1, n + 1, |_, _| f64::NAN);
+            let mut v = Mat::from_fn(n, n, |_, _| f64::NAN);
+            let mut diag = diag.clone();
+            let mut subdiag = subdiag.clone();
+            compute_bidiag_real_svd(
+                &mut diag,
+                &mut subdiag,
+                compute_u.then_some(u.as_mut()),
+                compute_v.then_some(v.as_mut()),
+                5,
+                64,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(bidiag_real_svd_req::<f64>(
+                    n,
+                    5,
+                    compute_u,
+                    compute_v,
+                    Parallelism::None
+                )),
+                SvdParams::default(),
+            );
+            singular_values.push(diag);
+        }
+
+        for s in &singular_values[1..] {
+            for i in 0..n {
+                assert_approx_eq!(s[i], singular_values[0][i], 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_svd_qr_high_relative_accuracy() {
+        // graded matrix with singular values ranging from about 1 to 1e-22
+        let graded = |n: usize| {
+            let diag = (0..n)
+                .map(|i| 10.0f64.powi(-2 * i as i32) * (1.0 + rand::random::<f64>()))
+                .collect::<Vec<_>>();
+            let subdiag = (0..n)
+                .map(|i| 10.0f64.powi(-2 * i as i32 - 1) * rand::random::<f64>())
+                .collect::<Vec<_>>();
+            (diag, subdiag)
+        };
+        // entries of order one, with a smallest singular value of about 2^-n
+        let ill_conditioned = |n: usize| {
+            let diag = (0..n)
+                .map(|_| 1.0 + 0.1 * rand::random::<f64>())
+                .collect::<Vec<_>>();
+            let subdiag = (0..n)
+                .map(|_| 2.0 + 0.1 * rand::random::<f64>())
+                .collect::<Vec<_>>();
+            (diag, subdiag)
+        };
+
+        for (n, (diag, mut subdiag)) in [(12, graded(12)), (100, ill_conditioned(100))] {
+            subdiag[n - 1] = 0.0;
+
+            // the inverse of a bidiagonal matrix can be computed without cancellation, and its
+            // largest singular value is the inverse of the smallest one of the original matrix
+            let mut inv = Mat::<f64>::zeros(n, n);
+            for j in 0..n {
+                inv.write(j, j, 1.0 / diag[j]);
+                for i in j..n - 1 {
+                    inv.write(i + 1, j, -subdiag[i] * inv.read(i, j) / diag[i + 1]);
+                }
+            }
+            let mut inv_s = Mat::<f64>::zeros(n, 1);
+            crate::compute_svd(
+                inv.as_ref(),
+                inv_s.as_mut(),
+                None,
+                None,
+                Parallelism::None,
+                make_stack!(crate::compute_svd_req::<f64>(
+                    n,
+                    n,
+                    crate::ComputeVectors::No,
+                    crate::ComputeVectors::No,
+                    Parallelism::None,
+                    SvdParams::default(),
+                )),
+                SvdParams::default(),
+            );
+            let s_min = 1.0 / inv_s.read(0, 0);
+            let det = diag.iter().product::<f64>();
+
+            let mut u = Mat::from_fn(n + 1, n + 1, |_, _| f64::NAN);
+            let mut v = Mat::from_fn(n, n, |_, _| f64::NAN);
+            let mut s = diag.clone();
+            let mut e = subdiag.clone();
+            compute_bidiag_real_svd(
+                &mut s,
+                &mut e,
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                4,
+                128,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(bidiag_real_svd_req::<f64>(
+                    n,
+                    4,
+                    true,
+                    true,
+                    Parallelism::None
+                )),
+                SvdParams {
+                    high_relative_accuracy: true,
+                    ..SvdParams::default()
+                },
+            );
+
+            assert!(((s[n - 1] - s_min) / s_min).abs() < 1e-12);
+            assert!(((s.iter().product::<f64>() - det) / det).abs() < 1e-12);
+
+            let s = Mat::from_fn(n + 1, n, |i, j| if i == j { s[i] } else { 0.0 });
+            let reconstructed = &u * &s * v.transpose();
+            for j in 0..n {
+                for i in 0..n + 1 {
+                    let target = if i == j {
+                        diag[j]
+                    } else if i == j + 1 {
+                        subdiag[j]
+                    } else {
+                        0.0
+                    };
+
+                    assert_approx_eq!(reconstructed.read(i, j), target, 1e-10);
+                }
             }
-            None => (None, None),
-        };
+        }
+    }
 
-        let stack_bytes = stack.len_bytes();
-        let (mem1, stack2) = stack.rb_mut().make_raw::<u8>(stack_bytes / 2);
-        let stack1 = PodStack::new(mem1);
+    #[test]
+    fn test_svd_qr_shift_strategy() {
+        let n = 100;
+        let (diag, subdiag) = crate::testing::test_bidiag::<f64>(n, 7);
 
-        join_raw(
-            |parallelism| {
-                bidiag_svd_impl(
-                    d1,
-                    sub_d1,
-                    u1.rb_mut(),
-                    v1.rb_mut(),
+        let mut values = Vec::new();
+        for shift_strategy in [ShiftStrategy::Wilkinson, ShiftStrategy::Discriminant] {
+            let mut u = Mat::from_fn(n + 1, n + 1, |_, _| f64::NAN);
+            let mut v = Mat::from_fn(n, n, |_, _| f64::NAN);
+            let mut s = diag.clone();
+            let mut e = subdiag.clone();
+            let mut stats = BidiagSvdStats::default();
+            compute_bidiag_real_svd_with_stats(
+                &mut s,
+                &mut e,
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                &mut stats,
+                4,
+                128,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(bidiag_real_svd_req::<f64>(
+                    n,
+                    4,
                     true,
-                    jacobi_fallback_threshold,
-                    epsilon,
-                    consider_zero_threshold,
-                    parallelism,
-                    stack1,
-                );
-            },
-            |parallelism| {
-                bidiag_svd_impl(
-                    d2,
-                    sub_d2,
-                    u2.rb_mut(),
-                    v2.rb_mut(),
                     true,
-                    jacobi_fallback_threshold,
-                    epsilon,
-                    consider_zero_threshold,
-                    parallelism,
-                    stack2,
-                );
-            },
-            parallelism,
-        );
+                    Parallelism::None
+                )),
+                SvdParams {
+                    shift_strategy,
+                    ..SvdParams::default()
+                },
+            );
+            assert!(stats.algorithm == SvdAlgorithm::QrIteration);
+            assert!(stats.qr.unconverged == 0);
 
-        if compact_u == 1 {
-            // handle rotation of Q1, q1
-            for i in (0..k).rev() {
-                faer_core::permutation::swap_cols(u1.rb_mut(), i, i + 1);
+            let s_mat = Mat::from_fn(n + 1, n, |i, j| if i == j { s[i] } else { 0.0 });
+            let reconstructed = &u * &s_mat * v.transpose();
+            let expected = bidiag_to_mat(&diag, &subdiag);
+            for j in 0..n {
+                for i in 0..n + 1 {
+                    assert_approx_eq!(reconstructed.read(i, j), expected.read(i, j), 1e-12);
+                }
             }
+            values.push(s);
         }
 
-        for i in (0..k).rev() {
-            diag[i + 1] = diag[i];
+        // the two shifts are rounded differently, so the iterates take different paths to the
+        // same singular values
+        assert!(values[0] != values[1]);
+        for (a, b) in zip(&values[0], &values[1]) {
+            assert_approx_eq!(a, b, 1e-12);
         }
     }
 
-    if let Some(mut v) = v.rb_mut() {
-        v.write(k, 0, E::faer_one());
-    };
-
-    let lambda = if compact_u == 0 {
-        u.read(k, k + 1)
-    } else {
-        // we already rotated u
-        u.read(1, 0)
-    };
-    let phi = if compact_u == 0 {
-        u.read(k + 1, n)
-    } else {
-        u.read(0, n)
-    };
-
-    let al = alpha.faer_mul(lambda);
-    let bp = beta.faer_mul(phi);
-
-    let r0 = ((al.faer_mul(al)).faer_add(bp.faer_mul(bp))).faer_sqrt();
-    let (c0, s0) = if r0 == E::faer_zero() {
-        (E::faer_one(), E::faer_zero())
-    } else {
-        (al.faer_div(r0), bp.faer_div(r0))
-    };
+    #[test]
+    fn test_svd_qr_convergence_criterion() {
+        // a block of order one, decoupled from a block of tiny entries, whose singular values are
+        // significant to full relative accuracy
+        let big_diag = [1.0, 0.5];
+        let big_subdiag = [0.3, 0.0];
+        let tiny_diag = [2.0, 1.0, 3.0, 1.5];
+        let tiny_subdiag = [1.0, 2.0, 0.5, 0.0];
+        let diag = big_diag
+            .iter()
+            .copied()
+            .chain(tiny_diag.iter().map(|x| 1e-20 * x))
+            .collect::<Vec<f64>>();
+        let subdiag = big_subdiag
+            .iter()
+            .copied()
+            .chain(tiny_subdiag.iter().map(|x| 1e-20 * x))
+            .collect::<Vec<f64>>();
+        let n = diag.len();
 
-    let col0 = subdiag;
-    diag[0] = r0;
-    col0[0] = r0;
+        let svd = |diag: &[f64], subdiag: &[f64], convergence_criterion| {
+            let n = diag.len();
+            let mut u = Mat::from_fn(n + 1, n + 1, |_, _| f64::NAN);
+            let mut v = Mat::from_fn(n, n, |_, _| f64::NAN);
+            let mut s = diag.to_vec();
+            let mut e = subdiag.to_vec();
+            let mut stats = BidiagSvdStats::default();
+            compute_bidiag_real_svd_with_stats(
+                &mut s,
+                &mut e,
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                &mut stats,
+                0,
+                128,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(bidiag_real_svd_req::<f64>(
+                    n,
+                    0,
+                    true,
+                    true,
+                    Parallelism::None
+                )),
+                SvdParams {
+                    convergence_criterion,
+                    ..SvdParams::default()
+                },
+            );
+            assert!(stats.algorithm == SvdAlgorithm::QrIteration);
+            assert!(stats.qr.unconverged == 0);
 
-    if compact_u == 0 {
-        let (u1, u2) = if compact_u == 0 {
-            let (u1, u2) = u.rb_mut().split_at_row_mut(k + 1);
-            (u1, u2)
-        } else {
-            let (u1, u2) = u.rb_mut().split_at_row_mut(1);
-            (u1, u2)
+            let s_mat = Mat::from_fn(n + 1, n, |i, j| if i == j { s[i] } else { 0.0 });
+            let reconstructed = &u * &s_mat * v.transpose();
+            let expected = bidiag_to_mat(diag, subdiag);
+            for j in 0..n {
+                for i in 0..n + 1 {
+                    assert_approx_eq!(reconstructed.read(i, j), expected.read(i, j), 1e-12);
+                }
+            }
+            s
         };
 
-        let (mut u0_top, u1) = u1.split_at_col_mut(1);
-        let (u1, mut un_top) = u1.split_at_col_mut(n - 1);
-        let (mut u0_bot, u2) = u2.split_at_col_mut(1);
-        let (u2, mut un_bot) = u2.split_at_col_mut(n - 1);
+        // the tiny block on its own, scaled up
+        let reference = svd(
+            &tiny_diag,
+            &tiny_subdiag,
+            ConvergenceCriterion::RelativeOrAbsolute,
+        );
 
-        for j in 0..k {
-            col0[j + 1] = alpha.faer_mul(u1.read(k, j));
+        // the tiny entries are negligible compared to the largest one, and are flushed to zero
+        let s = svd(&diag, &subdiag, ConvergenceCriterion::RelativeOrAbsolute);
+        for &s in &s[2..] {
+            assert!(s == 0.0);
         }
-        for j in 0..rem {
-            col0[j + 1 + k] = beta.faer_mul(u2.read(0, j + k));
+
+        let s = svd(&diag, &subdiag, ConvergenceCriterion::Relative);
+        for (&s, &expected) in zip(&s[2..], &reference) {
+            assert!((s / (1e-20 * expected) - 1.0).abs() < 1e-12);
         }
+        assert!(s[n - 1] > 0.0);
+    }
 
-        zipped!(
-            u0_top.rb_mut().col_mut(0).as_2d_mut(),
-            un_top.rb_mut().col_mut(0).as_2d_mut(),
-            u1.col_mut(k).as_2d_mut(),
-        )
-        .for_each(|unzipped!(mut dst0, mut dstn, mut src)| {
-            let src_ = src.read();
-            dst0.write(c0.faer_mul(src_));
-            dstn.write(s0.faer_neg().faer_mul(src_));
-            if cfg!(debug_assertions) {
-                src.write(E::faer_nan());
-            }
-        });
+    #[test]
+    fn test_svd_qr_extreme_scale() {
+        let n = 32;
+        for scale in [1e150, 1e-150] {
+            let diag = (0..n)
+                .map(|_| scale * rand::random::<f64>())
+                .collect::<Vec<_>>();
+            let mut subdiag = (0..n)
+                .map(|_| scale * rand::random::<f64>())
+                .collect::<Vec<_>>();
+            subdiag[n - 1] = 0.0;
 
-        zipped!(
-            u0_bot.rb_mut().col_mut(0).as_2d_mut(),
-            un_bot.rb_mut().col_mut(0).as_2d_mut(),
-        )
-        .for_each(|unzipped!(mut dst0, mut dstn)| {
-            let src_ = dstn.read();
-            dst0.write(s0.faer_mul(src_));
-            dstn.write(c0.faer_mul(src_));
-        });
-    } else {
-        for j in 0..k {
-            col0[j + 1] = alpha.faer_mul(u.read(1, j + 1));
-            u.write(1, j + 1, E::faer_zero());
-        }
-        for j in 0..rem {
-            col0[j + 1 + k] = beta.faer_mul(u.read(0, j + k + 1));
-            u.write(0, j + k + 1, E::faer_zero());
-        }
+            let mut u = Mat::from_fn(n, n, |_, _| f64::NAN);
+            let mut v = Mat::from_fn(n, n, |_, _| f64::NAN);
+            let s = {
+                let mut diag = diag.clone();
+                let mut subdiag = subdiag.clone();
+                bidiag_svd_qr_algorithm_impl(
+                    &mut diag,
+                    &mut subdiag,
+                    Some(u.as_mut()),
+                    Some(v.as_mut()),
+                    None,
+                    f64::EPSILON,
+                    f64::MIN_POSITIVE,
+                    false,
+                    ShiftStrategy::Wilkinson,
+                    ConvergenceCriterion::RelativeOrAbsolute,
+                );
+                Mat::from_fn(n, n, |i, j| if i == j { diag[i] } else { 0.0 })
+            };
 
-        let q10 = u.read(0, 0);
-        let q21 = u.read(1, n);
+            let reconstructed = &u * &s * v.transpose();
+            for j in 0..n {
+                for i in 0..n {
+                    let target = if i == j {
+                        diag[j]
+                    } else if i == j + 1 {
+                        subdiag[j]
+                    } else {
+                        0.0
+                    };
 
-        u.write(0, 0, c0.faer_mul(q10));
-        u.write(0, n, s0.faer_neg().faer_mul(q10));
-        u.write(1, 0, s0.faer_mul(q21));
-        u.write(1, n, c0.faer_mul(q21));
+                    assert_approx_eq!(reconstructed.read(i, j) / scale, target / scale, 1e-10);
+                }
+            }
+        }
     }
 
-    let (perm, stack) = stack.rb_mut().make_with(n, |_| 0usize);
-    let (jacobi_coeffs, stack) = stack.make_with(n, |_| JacobiRotation {
-        c: E::faer_zero(),
-        s: E::faer_zero(),
-    });
-    let (jacobi_indices, mut stack) = stack.make_with(n, |_| 0);
-
-    let (jacobi_0i, jacobi_ij) = {
-        let (transpositions, stack) = stack.rb_mut().make_with(n, |_| 0usize);
-        deflate(
-            diag,
-            col0,
-            jacobi_coeffs,
-            jacobi_indices,
-            u.rb_mut(),
-            v.rb_mut(),
-            transpositions,
-            perm,
-            k,
-            epsilon,
-            consider_zero_threshold,
-            stack,
-        )
-    };
+    #[test]
+    fn test_svd_qr_zero_diag() {
+        let n = 8;
+        for zeros in [&[0usize][..], &[3], &[3, 4], &[7]] {
+            let mut diag = (0..n).map(|_| rand::random::<f64>()).collect::<Vec<_>>();
+            let mut subdiag = (0..n).map(|_| rand::random::<f64>()).collect::<Vec<_>>();
+            subdiag[n - 1] = 0.0;
+            for &i in zeros {
+                diag[i] = 0.0;
+            }
 
-    let allocate_vm = v.is_some() as usize;
-    let allocate_um = fill_u as usize;
-    let (mut um, stack) = temp_mat_zeroed::<E>(n + 1, allocate_um * (n + 1), stack);
-    let (mut vm, mut stack) = temp_mat_zeroed::<E>(n, allocate_vm * n, stack);
-    let mut um = um.as_mut();
-    let mut vm = vm.as_mut();
+            let mut u = Mat::from_fn(n, n, |_, _| f64::NAN);
+            let mut v = Mat::from_fn(n, n, |_, _| f64::NAN);
+            let s = {
+                let mut diag = diag.clone();
+                let mut subdiag = subdiag.clone();
+                bidiag_svd_qr_algorithm_impl(
+                    &mut diag,
+                    &mut subdiag,
+                    Some(u.as_mut()),
+                    Some(v.as_mut()),
+                    None,
+                    f64::EPSILON,
+                    f64::MIN_POSITIVE,
+                    false,
+                    ShiftStrategy::Wilkinson,
+                    ConvergenceCriterion::RelativeOrAbsolute,
+                );
+                Mat::from_fn(n, n, |i, j| if i == j { diag[i] } else { 0.0 })
+            };
 
-    compute_svd_of_m(
-        fill_u.then_some(um.rb_mut()),
-        v.is_some().then_some(vm.rb_mut()),
-        diag,
-        col0,
-        perm,
-        epsilon,
-        consider_zero_threshold,
-        stack.rb_mut(),
-    );
+            let reconstructed = &u * &s * v.transpose();
+            for j in 0..n {
+                for i in 0..n {
+                    let target = if i == j {
+                        diag[j]
+                    } else if i == j + 1 {
+                        subdiag[j]
+                    } else {
+                        0.0
+                    };
 
-    if fill_u {
-        for (rot, &i) in jacobi_coeffs[..jacobi_0i]
-            .iter()
-            .zip(&jacobi_indices[..jacobi_0i])
-            .rev()
-        {
-            let (um_top, um_bot) = um.rb_mut().split_at_row_mut(i);
-            rot.apply_on_the_left_in_place(
-                um_top.row_mut(0).as_2d_mut(),
-                um_bot.row_mut(0).as_2d_mut(),
-            );
+                    assert_approx_eq!(reconstructed.read(i, j), target, 1e-10);
+                }
+            }
         }
     }
 
-    for (rot, &i) in jacobi_coeffs[jacobi_0i..][..jacobi_ij]
-        .iter()
-        .zip(&jacobi_indices[jacobi_0i..][..jacobi_ij])
-        .rev()
-    {
-        let (i, j) = (i - 1, i);
-        let mut actual_i = 0;
-        let mut actual_j = 0;
-        for (k, &p) in perm.iter().enumerate() {
-            if p == i {
-                actual_i = k;
-            }
-            if p == j {
-                actual_j = k;
+    #[test]
+    fn test_svd_qr_interior_zero_diag_vectors() {
+        let n = 10;
+        for zeros in [&[4usize][..], &[2, 6], &[3, 4, 5]] {
+            let mut diag = (0..n).map(|_| rand::random::<f64>()).collect::<Vec<_>>();
+            let mut subdiag = (0..n).map(|_| rand::random::<f64>()).collect::<Vec<_>>();
+            subdiag[n - 1] = 0.0;
+            for &i in zeros {
+                diag[i] = 0.0;
             }
-        }
 
-        if fill_u {
-            let (row_i, row_j) = if actual_i < actual_j {
-                let (um_top, um_bot) = um.rb_mut().split_at_row_mut(actual_j);
-                (um_top.row_mut(actual_i), um_bot.row_mut(0))
-            } else {
-                let (um_top, um_bot) = um.rb_mut().split_at_row_mut(actual_i);
-                (um_top.row_mut(actual_j), um_bot.row_mut(0))
-            };
-            rot.apply_on_the_left_in_place(row_i.as_2d_mut(), row_j.as_2d_mut());
-        }
+            let mut s = diag.clone();
+            let mut u = Mat::from_fn(n, n, |_, _| f64::NAN);
+            let mut v = Mat::from_fn(n, n, |_, _| f64::NAN);
+            bidiag_svd_qr_algorithm_impl(
+                &mut s,
+                &mut subdiag.clone(),
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                None,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                false,
+                ShiftStrategy::Wilkinson,
+                ConvergenceCriterion::RelativeOrAbsolute,
+            );
 
-        if v.is_some() {
-            let (row_i, row_j) = if actual_i < actual_j {
-                let (vm_top, vm_bot) = vm.rb_mut().split_at_row_mut(actual_j);
-                (vm_top.row_mut(actual_i), vm_bot.row_mut(0))
-            } else {
-                let (vm_top, vm_bot) = vm.rb_mut().split_at_row_mut(actual_i);
-                (vm_top.row_mut(actual_j), vm_bot.row_mut(0))
-            };
-            rot.apply_on_the_left_in_place(row_i.as_2d_mut(), row_j.as_2d_mut());
+            let s_mat = Mat::from_fn(n, n, |i, j| if i == j { s[i] } else { 0.0 });
+            let reconstructed = &u * &s_mat * v.transpose();
+            let utu = u.transpose() * &u;
+            let vtv = v.transpose() * &v;
+            for j in 0..n {
+                for i in 0..n {
+                    let target = if i == j {
+                        diag[j]
+                    } else if i == j + 1 {
+                        subdiag[j]
+                    } else {
+                        0.0
+                    };
+                    let id = if i == j { 1.0 } else { 0.0 };
+                    assert_approx_eq!(reconstructed.read(i, j), target, 1e-12);
+                    assert_approx_eq!(utu.read(i, j), id, 1e-12);
+                    assert_approx_eq!(vtv.read(i, j), id, 1e-12);
+                }
+            }
+
+            // the rotations applied to `v` don't depend on whether `u` is requested
+            let mut s_v_only = diag.clone();
+            let mut v_only = Mat::from_fn(n, n, |_, _| f64::NAN);
+            bidiag_svd_qr_algorithm_impl(
+                &mut s_v_only,
+                &mut subdiag.clone(),
+                None,
+                Some(v_only.as_mut()),
+                None,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                false,
+                ShiftStrategy::Wilkinson,
+                ConvergenceCriterion::RelativeOrAbsolute,
+            );
+            assert!(s_v_only == s);
+            assert!(v_only == v);
         }
     }
 
-    let _v_is_none = v.is_none();
-
-    let mut update_v = |parallelism, stack: PodStack<'_>| {
-        let (mut combined_v, _) = temp_mat_uninit::<E>(n, allocate_vm * n, stack);
-        let mut combined_v = combined_v.as_mut();
-        let v_rhs = vm.rb();
-
-        if let Some(mut v) = v.rb_mut() {
-            let mut combined_v = combined_v.rb_mut();
-            let (mut combined_v1, combined_v2) = combined_v.rb_mut().split_at_row_mut(k);
-            let mut combined_v2 = combined_v2.submatrix_mut(1, 0, rem, n);
-
-            let v_lhs = v.rb();
-            let v_lhs1 = v_lhs.submatrix(0, 1, k, k);
-            let v_lhs2 = v_lhs.submatrix(k + 1, k + 1, rem, rem);
-            let (v_rhs1, v_rhs2) = v_rhs.split_at_row(1).1.split_at_row(k);
+    #[test]
+    fn test_svd_max_depth() {
+        let n = 300;
+        let (diag, subdiag) = crate::testing::test_bidiag::<f64>(n, 11);
 
-            join_raw(
-                |parallelism| {
-                    faer_core::mul::matmul(
-                        combined_v1.rb_mut(),
-                        v_lhs1,
-                        v_rhs1,
-                        None,
-                        E::faer_one(),
-                        parallelism,
-                    )
-                },
-                |parallelism| {
-                    faer_core::mul::matmul(
-                        combined_v2.rb_mut(),
-                        v_lhs2,
-                        v_rhs2,
-                        None,
-                        E::faer_one(),
-                        parallelism,
-                    )
+        let decompose = |max_depth, compute_u: bool, compute_v: bool| {
+            let mut s = diag.clone();
+            let mut u = Mat::from_fn(n + 1, n + 1, |_, _| f64::NAN);
+            let mut v = Mat::from_fn(n, n, |_, _| f64::NAN);
+            let mut stats = BidiagSvdStats::default();
+            compute_bidiag_real_svd_with_stats(
+                &mut s,
+                &mut subdiag.clone(),
+                compute_u.then_some(u.as_mut()),
+                compute_v.then_some(v.as_mut()),
+                &mut stats,
+                4,
+                128,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(bidiag_real_svd_req::<f64>(
+                    n,
+                    4,
+                    compute_u,
+                    compute_v,
+                    Parallelism::None
+                )),
+                SvdParams {
+                    max_depth,
+                    ..SvdParams::default()
                 },
-                parallelism,
             );
+            (s, u, v, stats)
+        };
 
-            faer_core::mul::matmul(
-                combined_v.rb_mut().submatrix_mut(k, 0, 1, n),
-                v_lhs.submatrix(k, 0, 1, 1),
-                v_rhs.submatrix(0, 0, 1, n),
-                None,
-                E::faer_one(),
-                parallelism,
-            );
+        let (expected, _, _, stats) = decompose(None, false, false);
+        assert!(stats.qr.iterations == 0);
 
-            zipped!(v.rb_mut(), combined_v.rb())
-                .for_each(|unzipped!(mut dst, src)| dst.write(src.read()));
-        }
-    };
+        for max_depth in [0, 1, 2] {
+            // without `u`, only its first and last rows are computed
+            for (compute_u, compute_v) in [(true, true), (false, true), (false, false)] {
+                let (s, u, v, stats) = decompose(Some(max_depth), compute_u, compute_v);
+                assert!(stats.algorithm == SvdAlgorithm::DivideConquer);
+                assert!(stats.qr.iterations > 0);
+                for i in 0..n {
+                    assert_approx_eq!(s[i], expected[i], 1e-12);
+                }
 
-    let mut update_u = |parallelism, stack: PodStack<'_>| {
-        let (mut combined_u, _) = temp_mat_uninit::<E>(n + 1, allocate_um * (n + 1), stack);
-        let mut combined_u = combined_u.as_mut();
+                if compute_v {
+                    let vtv = v.transpose() * &v;
+                    for j in 0..n {
+                        for i in 0..n {
+                            let target = if i == j { 1.0 } else { 0.0 };
+                            assert_approx_eq!(vtv.read(i, j), target, 1e-12);
+                        }
+                    }
+                }
+                if compute_u {
+                    let us = Mat::from_fn(n + 1, n, |i, j| u.read(i, j) * s[j]);
+                    let reconstructed = &us * v.transpose();
+                    for j in 0..n {
+                        for i in 0..n + 1 {
+                            let target = if i == j {
+                                diag[j]
+                            } else if i == j + 1 {
+                                subdiag[j]
+                            } else {
+                                0.0
+                            };
+                            assert_approx_eq!(reconstructed.read(i, j), target, 1e-12);
+                        }
+                    }
+                }
+            }
+        }
+    }
 
-        if fill_u {
-            let (mut combined_u1, mut combined_u2) = combined_u.rb_mut().split_at_row_mut(k + 1);
-            let u_lhs = u.rb();
-            let u_rhs = um.rb();
-            let (u_lhs1, u_lhs2) = (
-                u_lhs.submatrix(0, 0, k + 1, k + 1),
-                u_lhs.submatrix(k + 1, k + 1, rem + 1, rem + 1),
-            );
-            let (u_rhs1, u_rhs2) = u_rhs.split_at_row(k + 1);
+    #[test]
+    fn test_svd_normalization() {
+        let n = 40;
+        let (diag, subdiag) = crate::testing::test_bidiag::<f64>(n, 11);
 
-            join_raw(
-                |parallelism| {
-                    // matrix matrix
-                    faer_core::mul::matmul(
-                        combined_u1.rb_mut(),
-                        u_lhs1,
-                        u_rhs1,
-                        None,
-                        E::faer_one(),
-                        parallelism,
-                    );
-                    // rank 1 update
-                    faer_core::mul::matmul(
-                        combined_u1.rb_mut(),
-                        u_lhs.col(n).subrows(0, k + 1).as_2d(),
-                        u_rhs2.row(rem).as_2d(),
-                        Some(E::faer_one()),
-                        E::faer_one(),
-                        parallelism,
-                    );
-                },
-                |parallelism| {
-                    // matrix matrix
-                    faer_core::mul::matmul(
-                        combined_u2.rb_mut(),
-                        u_lhs2,
-                        u_rhs2,
-                        None,
-                        E::faer_one(),
-                        parallelism,
-                    );
-                    // rank 1 update
-                    faer_core::mul::matmul(
-                        combined_u2.rb_mut(),
-                        u_lhs.col(0).subrows(k + 1, rem + 1).as_2d(),
-                        u_rhs1.row(0).as_2d(),
-                        Some(E::faer_one()),
-                        E::faer_one(),
-                        parallelism,
-                    );
+        let decompose = |algorithm, factor: f64| {
+            let mut s: Vec<f64> = diag.iter().map(|x| x * factor).collect();
+            let mut u = Mat::from_fn(n + 1, n + 1, |_, _| f64::NAN);
+            let mut v = Mat::from_fn(n, n, |_, _| f64::NAN);
+            compute_bidiag_real_svd(
+                &mut s,
+                &mut subdiag.iter().map(|x| x * factor).collect::<Vec<_>>(),
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                4,
+                128,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(bidiag_real_svd_req::<f64>(
+                    n,
+                    4,
+                    true,
+                    true,
+                    Parallelism::None
+                )),
+                SvdParams {
+                    algorithm,
+                    ..SvdParams::default()
                 },
-                parallelism,
             );
+            (s, u, v)
+        };
 
-            zipped!(u.rb_mut(), combined_u.rb())
-                .for_each(|unzipped!(mut dst, src)| dst.write(src.read()));
+        for algorithm in [
+            SvdAlgorithm::Jacobi,
+            SvdAlgorithm::QrIteration,
+            SvdAlgorithm::DivideConquer,
+        ] {
+            let (expected, expected_u, expected_v) = decompose(algorithm, 1.0);
+
+            // every path scales the matrix by a power of two, so the result is the same, up to
+            // the exact scaling of the singular values
+            for factor in [2.0f64.powi(-600), 2.0f64.powi(-40), 2.0f64.powi(500)] {
+                let (s, u, v) = decompose(algorithm, factor);
+                for i in 0..n {
+                    assert!(s[i] == expected[i] * factor);
+                }
+                assert!(u == expected_u);
+                assert!(v == expected_v);
+            }
+
+            // close to the overflow and underflow thresholds, where the norm of the matrix
+            // isn't representable
+            for factor in [1e-300, 1e300, 0.9 * f64::MAX / expected[0]] {
+                let (s, _, _) = decompose(algorithm, factor);
+                for i in 0..n {
+                    assert!(s[i].is_finite());
+                    assert_approx_eq!(s[i] / factor, expected[i], 1e-13);
+                }
+            }
         }
-    };
+    }
 
-    if compact_u == 1 {
-        update_v(parallelism, stack.rb_mut());
-        if fill_u {
-            let (mut combined_u, _) = temp_mat_uninit::<E>(2, n + 1, stack);
-            let mut combined_u = combined_u.as_mut();
-            faer_core::mul::matmul(
-                combined_u.rb_mut(),
-                u.rb(),
-                um.rb(),
-                None,
-                E::faer_one(),
-                parallelism,
+    #[test]
+    fn test_svd_col0_flush_threshold() {
+        let n = 300;
+        let (diag, subdiag) = crate::testing::test_bidiag::<f64>(n, 11);
+        let mat = bidiag_to_mat(&diag, &subdiag);
+
+        let decompose = |col0_flush_threshold| {
+            let mut s = diag.clone();
+            let mut u = Mat::from_fn(n + 1, n + 1, |_, _| f64::NAN);
+            let mut v = Mat::from_fn(n, n, |_, _| f64::NAN);
+            let mut stats = BidiagSvdStats::default();
+            compute_bidiag_real_svd_with_stats(
+                &mut s,
+                &mut subdiag.clone(),
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                &mut stats,
+                4,
+                0,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(bidiag_real_svd_req::<f64>(
+                    n,
+                    4,
+                    true,
+                    true,
+                    Parallelism::None
+                )),
+                SvdParams {
+                    col0_flush_threshold,
+                    ..SvdParams::default()
+                },
             );
-            zipped!(u.rb_mut(), combined_u.rb())
-                .for_each(|unzipped!(mut dst, src)| dst.write(src.read()));
-        }
-    } else {
-        match parallelism {
-            #[cfg(feature = "rayon")]
-            Parallelism::Rayon(_) if !_v_is_none => {
-                let req_v = faer_core::temp_mat_req::<E>(n, n).unwrap();
-                let (mem_v, stack_u) =
-                    stack.make_aligned_raw::<u8>(req_v.size_bytes(), req_v.align_bytes());
-                let stack_v = PodStack::new(mem_v);
-                faer_core::join_raw(
-                    |parallelism| update_v(parallelism, stack_v),
-                    |parallelism| update_u(parallelism, stack_u),
-                    parallelism,
-                );
+            (s, u, v, stats)
+        };
+
+        let (expected, _, _, expected_stats) = decompose(None);
+        // thresholds below the epsilon are raised to it
+        let (s, _, _, stats) = decompose(Some(0.0));
+        assert!(s == expected);
+        assert!(stats == expected_stats);
+
+        let mut prev_solves = expected_stats.secular_eq_solves;
+        for threshold in [1e-12, 1e-8, 1e-4] {
+            let (s, u, v, stats) = decompose(Some(threshold));
+            assert!(stats.secular_eq_solves <= prev_solves);
+            prev_solves = stats.secular_eq_solves;
+
+            // the accuracy degrades proportionally to the threshold
+            let norm = expected[0];
+            let tol = 16.0 * threshold * norm;
+            for i in 0..n {
+                assert!((s[i] - expected[i]).abs() <= tol);
             }
-            _ => {
-                update_v(parallelism, stack.rb_mut());
-                update_u(parallelism, stack);
+            let us = Mat::from_fn(n + 1, n, |i, j| u.read(i, j) * s[j]);
+            let reconstructed = &us * v.transpose();
+            for j in 0..n {
+                for i in 0..n + 1 {
+                    assert!((reconstructed.read(i, j) - mat.read(i, j)).abs() <= tol);
+                }
             }
         }
+        assert!(prev_solves < expected_stats.secular_eq_solves);
     }
 
-    for x in &mut *diag {
-        *x = (*x).faer_mul(max_val);
-    }
-}
-
-pub fn bidiag_real_svd_req<E: Entity>(
-    n: usize,
-    jacobi_fallback_threshold: usize,
-    compute_u: bool,
-    compute_v: bool,
-    parallelism: Parallelism,
-) -> Result<StackReq, SizeOverflow> {
-    if n <= jacobi_fallback_threshold {
-        temp_mat_req::<E>(n, n)
-    } else {
-        let _ = parallelism;
-        let perm = StackReq::try_new::<usize>(n)?;
-        let jacobi_coeffs = StackReq::try_new::<JacobiRotation<E>>(n)?;
-        let jacobi_indices = perm;
-        let transpositions = perm;
-        let real_ind = perm;
-        let real_col = perm;
-
-        let um = temp_mat_req::<E>(n + 1, n + 1)?;
-        let vm = temp_mat_req::<E>(n, if compute_v { n } else { 0 })?;
-
-        let combined_u = temp_mat_req::<E>(if compute_u { n + 1 } else { 2 }, n + 1)?;
-        let combined_v = vm;
+    #[test]
+    fn test_svd_subnormal() {
+        let n = 40;
+        let (diag, subdiag) = crate::testing::test_bidiag::<f64>(n, 13);
 
-        let prologue = StackReq::try_all_of([perm, jacobi_coeffs, jacobi_indices])?;
+        let decompose = |algorithm, diag: &[f64], subdiag: &[f64]| {
+            let mut s = diag.to_vec();
+            let mut u = Mat::from_fn(n + 1, n + 1, |_, _| f64::NAN);
+            let mut v = Mat::from_fn(n, n, |_, _| f64::NAN);
+            compute_bidiag_real_svd(
+                &mut s,
+                &mut subdiag.to_vec(),
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                4,
+                128,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(bidiag_real_svd_req::<f64>(
+                    n,
+                    4,
+                    true,
+                    true,
+                    Parallelism::None
+                )),
+                SvdParams {
+                    algorithm,
+                    ..SvdParams::default()
+                },
+            );
+            (s, u, v)
+        };
 
-        StackReq::try_all_of([
-            prologue,
-            um,
-            vm,
-            combined_u,
-            combined_v,
-            transpositions,
-            real_ind,
-            real_col,
-        ])
-    }
-}
+        // a cpu that flushes subnormal inputs to zero sees the matrix with zeros in their place
+        let subnormals = [
+            f64::MIN_POSITIVE / 2.0,
+            -1e-310,
+            5e-324,
+            -f64::MIN_POSITIVE * (1.0 - f64::EPSILON),
+        ];
+        for algorithm in [
+            SvdAlgorithm::Jacobi,
+            SvdAlgorithm::QrIteration,
+            SvdAlgorithm::DivideConquer,
+        ] {
+            for factor in [1.0, 1e300] {
+                let mut diag: Vec<f64> = diag.iter().map(|x| x * factor).collect();
+                let mut subdiag: Vec<f64> = subdiag.iter().map(|x| x * factor).collect();
+                let mut flushed_diag = diag.clone();
+                let mut flushed_subdiag = subdiag.clone();
+                for (k, &x) in subnormals.iter().enumerate() {
+                    // once the matrix is scaled by its largest entry, these entries become
+                    // subnormal
+                    let x = x * factor;
+                    diag[7 * k + 3] = x;
+                    subdiag[9 * k + 1] = x;
+                    flushed_diag[7 * k + 3] = 0.0;
+                    flushed_subdiag[9 * k + 1] = 0.0;
+                }
+                subdiag[n - 1] = subnormals[1] * factor;
+                flushed_subdiag[n - 1] = 0.0;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use assert_approx_eq::assert_approx_eq;
-    use faer_core::{assert, Mat};
+                let result = decompose(algorithm, &diag, &subdiag);
+                assert!(result.0.iter().all(|x| x.is_finite()));
+                if factor == 1.0 {
+                    assert!(result == decompose(algorithm, &flushed_diag, &flushed_subdiag));
+                }
 
-    macro_rules! make_stack {
-        ($req: expr) => {
-            ::dyn_stack::PodStack::new(&mut ::dyn_stack::GlobalPodBuffer::new($req.unwrap()))
-        };
-    }
+                // the same decomposition with the ftz and daz modes of the cpu
+                #[cfg(target_arch = "x86_64")]
+                #[allow(deprecated)]
+                {
+                    use core::arch::x86_64::{_mm_getcsr, _mm_setcsr};
+                    const FTZ_DAZ: u32 = (1 << 15) | (1 << 6);
 
-    // to avoid overflowing the stack
-    macro_rules! vec_static {
-    ($($x:expr),+ $(,)?) => (
-        {
-            static ARRAY: &[f64] = &[$($x),+];
-            ARRAY.to_vec()
+                    let csr = unsafe { _mm_getcsr() };
+                    unsafe { _mm_setcsr(csr | FTZ_DAZ) };
+                    let flushed = decompose(algorithm, &diag, &subdiag);
+                    unsafe { _mm_setcsr(csr) };
+                    assert!(flushed == result);
+                }
+            }
         }
-    );
     }
 
     #[test]
-    fn test_svd_n() {
-        for n in [9, 16, 32, 64, 128, 256, 512, 1024] {
-            let diag = (0..n).map(|_| rand::random::<f64>()).collect::<Vec<_>>();
-            let subdiag = (0..n).map(|_| rand::random::<f64>()).collect::<Vec<_>>();
-            dbg!(&diag, &subdiag);
-
-            let n = diag.len();
+    fn test_svd_deflation44_vectors() {
+        // the singular values of both halves of the top level merge are close to `3`, `2`, `1`
+        // and `0.5`, and deflated with condition 4.4. the rotations are then applied to rows of
+        // the singular vectors that aren't in the order of the permutation
+        let n = 10;
+        let subdiag = [
+            -2.9999122244173924,
+            -1.9992322967395249,
+            -1.000057756106471,
+            0.4538137562833485,
+            -0.2560975618863761,
+            -0.0003050839413210735,
+            0.00022458648384619885,
+            -0.0008186950977043161,
+            -0.0009466878073250307,
+            0.0,
+        ];
+        for first in [0.0, 1e-12] {
+            let diag = [
+                first,
+                0.01710813212283729,
+                -0.049778879404100335,
+                0.023646537147743958,
+                -0.17938563575828595,
+                -0.09454880939561537,
+                -2.999999969340035,
+                1.9999997866673036,
+                0.9999995150733195,
+                -0.49867893276931224,
+            ];
+            let mut s = diag;
             let mut u = Mat::from_fn(n + 1, n + 1, |_, _| f64::NAN);
             let mut v = Mat::from_fn(n, n, |_, _| f64::NAN);
-            let s = {
-                let mut diag = diag.clone();
-                let mut subdiag = subdiag.clone();
-                compute_bidiag_real_svd(
-                    &mut diag,
-                    &mut subdiag,
-                    Some(u.as_mut()),
-                    Some(v.as_mut()),
-                    5,
-                    0,
-                    f64::EPSILON,
-                    f64::MIN_POSITIVE,
-                    Parallelism::None,
-                    make_stack!(bidiag_real_svd_req::<f64>(
-                        n,
-                        5,
-                        true,
-                        true,
-                        Parallelism::None
-                    )),
-                );
-                Mat::from_fn(n + 1, n, |i, j| if i == j { diag[i] } else { 0.0 })
-            };
+            compute_bidiag_real_svd(
+                &mut s,
+                &mut subdiag.clone(),
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                4,
+                4,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(bidiag_real_svd_req::<f64>(
+                    n,
+                    4,
+                    true,
+                    true,
+                    Parallelism::None
+                )),
+                SvdParams {
+                    algorithm: SvdAlgorithm::DivideConquer,
+                    ..SvdParams::default()
+                },
+            );
 
+            let s = Mat::from_fn(n + 1, n, |i, j| if i == j { s[i] } else { 0.0 });
             let reconstructed = &u * &s * v.transpose();
+            let expected = bidiag_to_mat(&diag, &subdiag);
             for j in 0..n {
                 for i in 0..n + 1 {
-                    let target = if i == j {
-                        diag[j]
-                    } else if i == j + 1 {
-                        subdiag[j]
-                    } else {
-                        0.0
-                    };
-
-                    assert_approx_eq!(reconstructed.read(i, j), target, 1e-10);
+                    assert_approx_eq!(reconstructed.read(i, j), expected.read(i, j), 1e-12);
                 }
             }
         }
@@ -2318,7 +9303,7 @@ mod tests {
     #[test]
     fn test_svd_4() {
         let diag = vec_static![1.0, 2.0, 3.0, 4.0];
-        let subdiag = vec_static![1.0, 1.0, 1.0];
+        let subdiag = vec_static![1.0, 1.0, 1.0, 0.0];
 
         let n = diag.len();
         let mut u = Mat::from_fn(n, n, |_, _| f64::NAN);
@@ -2331,8 +9316,12 @@ mod tests {
                 &mut subdiag,
                 Some(u.as_mut()),
                 Some(v.as_mut()),
+                None,
                 f64::EPSILON,
                 f64::MIN_POSITIVE,
+                false,
+                ShiftStrategy::Wilkinson,
+                ConvergenceCriterion::RelativeOrAbsolute,
             );
             Mat::from_fn(n, n, |i, j| if i == j { diag[i] } else { 0.0 })
         };
@@ -2353,6 +9342,77 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_svd_qr_u_shapes() {
+        let diag = [1.0, 2.0, 3.0, 4.0];
+        let subdiag = [1.0, 1.0, 1.0, 0.0];
+        let n = diag.len();
+
+        let decompose = |u_nrows, u_ncols| {
+            let mut diag = diag;
+            let mut subdiag = subdiag;
+            let mut u = Mat::from_fn(u_nrows, u_ncols, |_, _| f64::NAN);
+            let mut v = Mat::from_fn(n, n, |_, _| f64::NAN);
+            bidiag_svd_qr_algorithm_impl(
+                &mut diag,
+                &mut subdiag,
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                None,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                false,
+                ShiftStrategy::Wilkinson,
+                ConvergenceCriterion::RelativeOrAbsolute,
+            );
+            (diag, u, v)
+        };
+
+        // the shapes of the divide and conquer driver give the same vectors, padded with the
+        // identity
+        let (s, u, v) = decompose(n, n);
+        for (u_nrows, u_ncols) in [(n + 1, n + 1), (n + 1, n)] {
+            let (s_padded, u_padded, v_padded) = decompose(u_nrows, u_ncols);
+            assert!(s_padded == s);
+            assert!(v_padded == v);
+            for j in 0..u_ncols {
+                for i in 0..u_nrows {
+                    let target = if i < n && j < n {
+                        u.read(i, j)
+                    } else if i == j {
+                        1.0
+                    } else {
+                        0.0
+                    };
+                    assert!(u_padded.read(i, j) == target);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "`u` must have shape `(n, n)`, `(n + 1, n + 1)` or `(n + 1, n)`")]
+    fn test_svd_qr_u_shape_mismatch() {
+        let mut diag = [1.0, 2.0, 3.0, 4.0];
+        let mut subdiag = [1.0, 1.0, 1.0, 0.0];
+        let n = diag.len();
+
+        let mut u = Mat::<f64>::zeros(n, n + 1);
+        let mut v = Mat::<f64>::zeros(n, n);
+        bidiag_svd_qr_algorithm_impl(
+            &mut diag,
+            &mut subdiag,
+            Some(u.as_mut()),
+            Some(v.as_mut()),
+            None,
+            f64::EPSILON,
+            f64::MIN_POSITIVE,
+            false,
+            ShiftStrategy::Wilkinson,
+            ConvergenceCriterion::RelativeOrAbsolute,
+        );
+    }
+
     #[test]
     fn test_svd_64() {
         let diag = vec_static![
@@ -2511,6 +9571,7 @@ mod tests {
                     true,
                     Parallelism::None
                 )),
+                SvdParams::default(),
             );
             Mat::from_fn(n + 1, n, |i, j| if i == j { diag[i] } else { 0.0 })
         };
@@ -2817,6 +9878,7 @@ mod tests {
                     true,
                     Parallelism::None
                 )),
+                SvdParams::default(),
             );
             Mat::from_fn(n + 1, n, |i, j| if i == j { diag[i] } else { 0.0 })
         };
@@ -4915,6 +11977,7 @@ mod tests {
                     true,
                     Parallelism::None
                 )),
+                SvdParams::default(),
             );
             Mat::from_fn(n + 1, n, |i, j| if i == j { diag[i] } else { 0.0 })
         };
@@ -5989,6 +13052,7 @@ mod tests {
                     true,
                     Parallelism::None
                 )),
+                SvdParams::default(),
             );
             Mat::from_fn(n + 1, n, |i, j| if i == j { diag[i] } else { 0.0 })
         };
@@ -8087,6 +15151,7 @@ mod tests {
                     true,
                     Parallelism::None
                 )),
+                SvdParams::default(),
             );
             Mat::from_fn(n + 1, n, |i, j| if i == j { diag[i] } else { 0.0 })
         };
@@ -10185,6 +17250,7 @@ mod tests {
                     true,
                     Parallelism::None
                 )),
+                SvdParams::default(),
             );
             Mat::from_fn(n + 1, n, |i, j| if i == j { diag[i] } else { 0.0 })
         };
@@ -10204,4 +17270,265 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_arrow_singular_values_windowed() {
+        let n = 60;
+        let mut diag = (0..n).map(|_| rand::random::<f64>()).collect::<Vec<_>>();
+        diag.sort_by(f64::total_cmp);
+        let col0 = (0..n)
+            .map(|_| rand::random::<f64>() - 0.5)
+            .collect::<Vec<_>>();
+
+        // repeated entries of the diagonal, tiny entries of the diagonal and of the first column,
+        // which are all deflated
+        let mut deflated_diag = diag.clone();
+        deflated_diag[1] = 1e-20;
+        deflated_diag[20] = deflated_diag[19];
+        deflated_diag[21] = deflated_diag[19];
+        let mut deflated_col0 = col0.clone();
+        deflated_col0[5] = 1e-20;
+        deflated_col0[0] = 0.0;
+
+        for (diag, col0) in [(&diag, &col0), (&deflated_diag, &deflated_col0)] {
+            let m = Mat::from_fn(n, n, |i, j| {
+                if j == 0 {
+                    col0[i]
+                } else if i == j {
+                    diag[i]
+                } else {
+                    0.0
+                }
+            });
+            let mut expected = Mat::<f64>::zeros(n, 1);
+            crate::compute_svd(
+                m.as_ref(),
+                expected.as_mut(),
+                None,
+                None,
+                Parallelism::None,
+                make_stack!(crate::compute_svd_req::<f64>(
+                    n,
+                    n,
+                    crate::ComputeVectors::No,
+                    crate::ComputeVectors::No,
+                    Parallelism::None,
+                    SvdParams::default(),
+                )),
+                SvdParams::default(),
+            );
+
+            for window in [1, 7, n, 2 * n] {
+                let mut out = vec![f64::NAN; n];
+                compute_arrow_real_singular_values_windowed(
+                    &mut out,
+                    &mut diag.clone(),
+                    &mut col0.clone(),
+                    window,
+                    f64::EPSILON,
+                    f64::MIN_POSITIVE,
+                    Parallelism::None,
+                    make_stack!(arrow_real_singular_values_windowed_req::<f64>(window)),
+                    SvdParams::default(),
+                );
+                for (i, &out) in out.iter().enumerate() {
+                    assert_approx_eq!(out, expected.read(i, 0), 1e-13);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_bidiag_singular_values_windowed() {
+        // larger than the other divide and conquer tests, which the linear workspace allows
+        let n = 4096;
+        let diag = (0..n).map(|_| rand::random::<f64>()).collect::<Vec<_>>();
+        let subdiag = (0..n).map(|_| rand::random::<f64>()).collect::<Vec<_>>();
+
+        let singular_values = |params: SvdParams, req: Result<StackReq, SizeOverflow>| {
+            let mut s = diag.clone();
+            let mut e = subdiag.clone();
+            compute_bidiag_real_svd(
+                &mut s,
+                &mut e,
+                None,
+                None,
+                5,
+                0,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(req),
+                params,
+            );
+            s
+        };
+
+        let expected = singular_values(
+            SvdParams {
+                algorithm: SvdAlgorithm::QrIteration,
+                ..Default::default()
+            },
+            bidiag_real_svd_req::<f64>(n, 5, false, false, Parallelism::None),
+        );
+        for (window, max_depth, split_strategy) in [
+            (1, None, SplitStrategy::Half),
+            (n, None, SplitStrategy::Half),
+            (7, None, SplitStrategy::Half),
+            (7, None, SplitStrategy::ByMass),
+            (7, Some(1), SplitStrategy::Half),
+            (7, Some(4), SplitStrategy::Half),
+        ] {
+            let req =
+                bidiag_real_singular_values_windowed_req::<f64>(n, 5, window, Parallelism::None)
+                    .unwrap();
+            // the documented bound, with some room for the padding of the allocations
+            if window == 7 {
+                assert!(req.size_bytes() < 12 * n * core::mem::size_of::<f64>());
+            }
+
+            // every merge step is windowed, so the divide and conquer algorithm runs with the
+            // linear workspace
+            let s = singular_values(
+                SvdParams {
+                    secular_window: Some(window),
+                    max_depth,
+                    split_strategy,
+                    ..Default::default()
+                },
+                Ok(req),
+            );
+            for (x, y) in zip(&expected, &s) {
+                assert_approx_eq!(x, y, 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_qr_single_nonzero_block() {
+        // bidiagonal matrices with a single nonzero singular value, whose active block shrinks to
+        // a single entry, possibly after chasing the zero diagonal entries that precede it
+        for (diag, subdiag) in [
+            (vec![3.0, 0.0, 0.0, 0.0], vec![0.0, 0.0, 0.0, 0.0]),
+            (vec![0.0, 0.0, 0.0, 3.0], vec![0.0, 0.0, 0.0, 0.0]),
+            (vec![0.0, 0.0, 3.0, 0.0], vec![0.0, 0.0, 0.0, 0.0]),
+            (vec![3.0, 0.0, 0.0, 0.0], vec![4.0, 0.0, 0.0, 0.0]),
+            (vec![0.0, 3.0, 0.0, 0.0], vec![4.0, 0.0, 0.0, 0.0]),
+            (vec![0.0, 0.0, 0.0, 0.0], vec![0.0, 0.0, 4.0, 0.0]),
+            (vec![0.0, 0.0, 0.0, 0.0], vec![0.0, 0.0, 0.0, 4.0]),
+            (vec![0.0, 0.0, 0.0, 0.0, 3.0], vec![0.0, 0.0, 0.0, 0.0, 4.0]),
+            (vec![1e-300, 0.0, 3.0, 0.0], vec![0.0, 1e-300, 0.0, 0.0]),
+        ] {
+            let n = diag.len();
+            let expected = bidiag_to_mat(&diag, &subdiag);
+            let norm = (0..n)
+                .map(|i| diag[i] * diag[i] + subdiag[i] * subdiag[i])
+                .sum::<f64>()
+                .sqrt();
+
+            let mut u = Mat::from_fn(n + 1, n + 1, |_, _| f64::NAN);
+            let mut v = Mat::from_fn(n, n, |_, _| f64::NAN);
+            let mut s = diag.clone();
+            let mut e = subdiag.clone();
+            let mut stats = BidiagSvdStats::default();
+            compute_bidiag_real_svd_with_stats(
+                &mut s,
+                &mut e,
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                &mut stats,
+                0,
+                128,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(bidiag_real_svd_req::<f64>(
+                    n,
+                    0,
+                    true,
+                    true,
+                    Parallelism::None
+                )),
+                SvdParams::default(),
+            );
+            assert!(stats.algorithm == SvdAlgorithm::QrIteration);
+
+            assert_approx_eq!(s[0], norm, 1e-12);
+            for &s in &s[1..] {
+                assert!(s == 0.0);
+            }
+            crate::testing::assert_orthogonal(u.as_ref(), 1e-12);
+            crate::testing::assert_orthogonal(v.as_ref(), 1e-12);
+            let s = Mat::from_fn(n + 1, n, |i, j| if i == j { s[i] } else { 0.0 });
+            let reconstructed = &u * &s * v.transpose();
+            for j in 0..n {
+                for i in 0..n + 1 {
+                    assert_approx_eq!(reconstructed.read(i, j), expected.read(i, j), 1e-10);
+                }
+            }
+
+            // the square case, without the last row
+            if subdiag[n - 1] == 0.0 {
+                let mut u = Mat::from_fn(n, n, |_, _| f64::NAN);
+                let mut v = Mat::from_fn(n, n, |_, _| f64::NAN);
+                let mut s = diag.clone();
+                let mut e = subdiag.clone();
+                bidiag_svd_qr_algorithm_impl(
+                    &mut s,
+                    &mut e,
+                    Some(u.as_mut()),
+                    Some(v.as_mut()),
+                    None,
+                    f64::EPSILON,
+                    f64::MIN_POSITIVE,
+                    false,
+                    ShiftStrategy::Wilkinson,
+                    ConvergenceCriterion::RelativeOrAbsolute,
+                );
+                assert_approx_eq!(s[0], norm, 1e-12);
+                for &s in &s[1..] {
+                    assert!(s == 0.0);
+                }
+                let s = Mat::from_fn(n, n, |i, j| if i == j { s[i] } else { 0.0 });
+                let reconstructed = &u * &s * v.transpose();
+                for j in 0..n {
+                    for i in 0..n {
+                        assert_approx_eq!(reconstructed.read(i, j), expected.read(i, j), 1e-10);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_qr_negative_zero() {
+        // the second entry rounds to `-0.0` once the matrix is scaled by its largest entry, and
+        // the others are negative zeros from the start
+        for diag in [
+            vec![1e300, -1e-300, 2.0],
+            vec![-0.0, 1.0, -0.0],
+            vec![-0.0, -0.0, -0.0],
+        ] {
+            let n = diag.len();
+            let mut diag = diag;
+            let mut subdiag = vec![0.0; n];
+            let mut u = Mat::<f64>::zeros(n + 1, n + 1);
+            let mut v = Mat::<f64>::zeros(n, n);
+            bidiag_svd_qr_algorithm_impl(
+                &mut diag,
+                &mut subdiag,
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                None,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                false,
+                ShiftStrategy::Wilkinson,
+                ConvergenceCriterion::RelativeOrAbsolute,
+            );
+            for d in diag {
+                assert!(d.is_sign_positive());
+            }
+        }
+    }
 }
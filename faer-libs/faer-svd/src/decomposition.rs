@@ -0,0 +1,618 @@
+use crate::{compute_svd, compute_svd_req, ComputeVectors, SvdParams};
+use alloc::vec::Vec;
+use dyn_stack::{GlobalPodBuffer, PodStack};
+use faer_core::{
+    assert, get_global_parallelism, unzipped, zipped, ColRef, ComplexField, Entity, Mat, MatRef,
+    Parallelism, RealField,
+};
+
+/// Owned thin singular value decomposition of a matrix $A$ of shape $(m, n)$, such that
+/// $A = U S V^H$.
+///
+/// $U$ has shape $(m, r)$ and $V$ has shape $(n, r)$, both with orthonormal columns, and $S$ is
+/// diagonal with nonincreasing nonnegative entries.
+#[derive(Clone, Debug)]
+pub struct SvdDecomposition<E: Entity> {
+    u: Mat<E>,
+    s: Mat<E>,
+    v: Mat<E>,
+}
+
+pub(crate) fn thin_svd<E: ComplexField>(
+    matrix: MatRef<'_, E>,
+    parallelism: Parallelism,
+    params: SvdParams,
+) -> (Mat<E>, Mat<E>, Mat<E>) {
+    let m = matrix.nrows();
+    let n = matrix.ncols();
+    let size = Ord::min(m, n);
+
+    let mut s = Mat::<E>::zeros(size, 1);
+    let mut u = Mat::<E>::zeros(m, size);
+    let mut v = Mat::<E>::zeros(n, size);
+
+    compute_svd(
+        matrix,
+        s.as_mut(),
+        Some(u.as_mut()),
+        Some(v.as_mut()),
+        parallelism,
+        PodStack::new(&mut GlobalPodBuffer::new(
+            compute_svd_req::<E>(
+                m,
+                n,
+                ComputeVectors::Thin,
+                ComputeVectors::Thin,
+                parallelism,
+                params,
+            )
+            .unwrap(),
+        )),
+        params,
+    );
+
+    (u, s, v)
+}
+
+/// returns the number of leading singular values in `s` that are larger than `tol_factor` times
+/// the largest one
+pub(crate) fn effective_rank<E: ComplexField>(s: MatRef<'_, E>, tol_factor: E::Real) -> usize {
+    let mut rank = s.nrows();
+    if rank == 0 {
+        return 0;
+    }
+    let tol = tol_factor.faer_mul(s.read(0, 0).faer_real());
+    while rank > 0 && s.read(rank - 1, 0).faer_real() <= tol {
+        rank -= 1;
+    }
+    rank
+}
+
+/// splits the columns of `x` into their components in the span of the orthonormal `basis`, and
+/// in an orthonormal basis of the residual, keeping only the directions whose singular values are
+/// larger than `tol`.
+///
+/// returns the extended basis `[basis p]` and the coordinates of `x` in it
+fn extend_basis<E: ComplexField>(
+    basis: MatRef<'_, E>,
+    x: MatRef<'_, E>,
+    tol: E::Real,
+) -> (Mat<E>, Mat<E>) {
+    let m = basis.nrows();
+    let r = basis.ncols();
+    let k = x.ncols();
+
+    // second pass of gram-schmidt to keep the residual orthogonal to `basis`
+    let mut proj = basis.adjoint() * x;
+    let mut residual = x.to_owned() - basis * &proj;
+    let correction = basis.adjoint() * &residual;
+    residual -= basis * &correction;
+    proj += correction;
+
+    let (res_u, res_s, res_v) =
+        thin_svd(residual.as_ref(), Parallelism::None, SvdParams::default());
+
+    // the directions of the residual that are kept
+    let mut extra = 0;
+    while extra < res_s.nrows() && res_s.read(extra, 0).faer_real() > tol {
+        extra += 1;
+    }
+
+    let mut extended = Mat::<E>::zeros(m, r + extra);
+    extended.as_mut().submatrix_mut(0, 0, m, r).copy_from(basis);
+    extended
+        .as_mut()
+        .submatrix_mut(0, r, m, extra)
+        .copy_from(res_u.as_ref().subcols(0, extra));
+
+    // residual = res_u res_s res_v^H, truncated to the kept directions
+    let mut coords = Mat::<E>::zeros(r + extra, k);
+    coords
+        .as_mut()
+        .submatrix_mut(0, 0, r, k)
+        .copy_from(proj.as_ref());
+    for j in 0..k {
+        for i in 0..extra {
+            coords.write(
+                r + i,
+                j,
+                res_v
+                    .read(j, i)
+                    .faer_conj()
+                    .faer_scale_real(res_s.read(i, 0).faer_real()),
+            );
+        }
+    }
+
+    (extended, coords)
+}
+
+impl<E: ComplexField> SvdDecomposition<E> {
+    /// Computes the thin singular value decomposition of `matrix`.
+    ///
+    /// Singular values that are negligible compared to the largest one are dropped, so that the
+    /// rank $r$ is the effective rank of `matrix`.
+    ///
+    /// # Example
+    /// ```
+    /// use faer_core::mat;
+    /// use faer_svd::SvdDecomposition;
+    ///
+    /// // the third column is the sum of the first two
+    /// let a = mat![
+    ///     [1.0, 2.0, 3.0],
+    ///     [4.0, 5.0, 9.0],
+    ///     [7.0, 8.0, 15.0],
+    ///     [1.0, 0.0, 1.0f64],
+    /// ];
+    /// let svd = SvdDecomposition::new(a.as_ref());
+    /// assert!(svd.singular_values().len() == 2);
+    ///
+    /// let reconstructed = svd.reconstruct();
+    /// for j in 0..a.ncols() {
+    ///     for i in 0..a.nrows() {
+    ///         assert!((reconstructed.read(i, j) - a.read(i, j)).abs() < 1e-12);
+    ///     }
+    /// }
+    ///
+    /// // the columns of `U` and `V` are orthonormal
+    /// for w in [svd.u(), svd.v()] {
+    ///     let gram = w.transpose() * w;
+    ///     for j in 0..2 {
+    ///         for i in 0..2 {
+    ///             let target = if i == j { 1.0 } else { 0.0 };
+    ///             assert!((gram.read(i, j) - target).abs() < 1e-12);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn new(matrix: MatRef<'_, E>) -> Self {
+        let (u, s, v) = thin_svd(matrix, get_global_parallelism(), SvdParams::default());
+        Self::from_thin_parts(u, s, v)
+    }
+
+    /// builds the decomposition from a thin svd with nonincreasing singular values, dropping the
+    /// negligible ones
+    pub(crate) fn from_thin_parts(u: Mat<E>, s: Mat<E>, v: Mat<E>) -> Self {
+        let epsilon = E::Real::faer_epsilon().unwrap();
+        let tol_factor =
+            epsilon.faer_mul(E::Real::faer_from_f64(Ord::max(u.nrows(), v.nrows()) as f64));
+        let rank = effective_rank(s.as_ref(), tol_factor);
+
+        Self {
+            u: u.as_ref().subcols(0, rank).to_owned(),
+            s: s.as_ref().subrows(0, rank).to_owned(),
+            v: v.as_ref().subcols(0, rank).to_owned(),
+        }
+    }
+
+    /// Returns the factor $U$ of the decomposition.
+    pub fn u(&self) -> MatRef<'_, E> {
+        self.u.as_ref()
+    }
+
+    /// Returns the diagonal of the factor $S$ of the decomposition, as a column.
+    pub fn s_diagonal(&self) -> MatRef<'_, E> {
+        self.s.as_ref()
+    }
+
+    /// Returns the factor $V$ of the decomposition.
+    pub fn v(&self) -> MatRef<'_, E> {
+        self.v.as_ref()
+    }
+
+    /// Returns the singular values of the decomposition, in nonincreasing order.
+    pub fn singular_values(&self) -> Vec<E::Real> {
+        (0..self.s.nrows())
+            .map(|i| self.s.read(i, 0).faer_real())
+            .collect()
+    }
+
+    /// Returns the decomposition truncated to the singular values that are larger than `rcond`
+    /// times the largest one.
+    ///
+    /// Since the singular values are sorted in nonincreasing order, the truncated factors are
+    /// copies of the leading $r$ columns of $U$ and $V$ and entries of $S$, where $r$ is the
+    /// number of kept singular values. The result is the best rank $r$ approximation of the
+    /// original matrix.
+    pub fn truncate(&self, rcond: E::Real) -> Self {
+        let rank = effective_rank(self.s.as_ref(), rcond);
+        Self {
+            u: self.u.as_ref().subcols(0, rank).to_owned(),
+            s: self.s.as_ref().subrows(0, rank).to_owned(),
+            v: self.v.as_ref().subcols(0, rank).to_owned(),
+        }
+    }
+
+    /// Returns $U S V^H$, which approximates the original matrix up to the dropped negligible
+    /// singular values.
+    pub fn reconstruct(&self) -> Mat<E> {
+        let s = self.s.as_ref();
+        let us = Mat::<E>::from_fn(self.u.nrows(), self.u.ncols(), |i, j| {
+            self.u.read(i, j).faer_scale_real(s.read(j, 0).faer_real())
+        });
+        us * self.v.adjoint()
+    }
+
+    /// Returns the Moore-Penrose pseudo-inverse $V S^{-1} U^H$ of the original matrix.
+    pub fn pseudo_inverse(&self) -> Mat<E> {
+        let s = self.s.as_ref();
+        let vs_inv = Mat::<E>::from_fn(self.v.nrows(), self.v.ncols(), |i, j| {
+            self.v
+                .read(i, j)
+                .faer_scale_real(s.read(j, 0).faer_real().faer_inv())
+        });
+        vs_inv * self.u.adjoint()
+    }
+
+    /// Returns the minimum norm least squares solution $X$ of $A X = B$, where $B$ is `rhs`.
+    ///
+    /// # Panics
+    /// Panics if `rhs.nrows()` is not equal to the number of rows of $A$.
+    #[track_caller]
+    pub fn solve(&self, rhs: MatRef<'_, E>) -> Mat<E> {
+        assert!(rhs.nrows() == self.u.nrows());
+
+        let mut x = self.u.adjoint() * rhs;
+        let s = self.s.as_ref();
+        for j in 0..x.ncols() {
+            zipped!(x.as_mut().col_mut(j).as_2d_mut(), s).for_each(|unzipped!(mut x, s)| {
+                x.write(x.read().faer_scale_real(s.read().faer_real().faer_inv()))
+            });
+        }
+        &self.v * x
+    }
+
+    /// Updates the decomposition of $A$ to that of $[A\ c]$, where $c$ is the column `col`, using
+    /// Brand's rank-one update.
+    ///
+    /// Only the $(r + 1, r + 1)$ core matrix is decomposed from scratch, so the cost is
+    /// $\mathcal{O}((m + n) r^2)$ instead of that of a full recomputation. Singular values that
+    /// become negligible are dropped, so that the rank $r$ tracks the effective rank of the data.
+    ///
+    /// # Panics
+    /// Panics if `col.nrows()` is not equal to the number of rows of $A$.
+    #[track_caller]
+    pub fn update_append_column(&mut self, col: ColRef<'_, E>) {
+        let m = self.u.nrows();
+        let n = self.v.nrows();
+        let r = self.s.nrows();
+        assert!(col.nrows() == m);
+
+        let epsilon = E::Real::faer_epsilon().unwrap();
+        let tol_factor = epsilon.faer_mul(E::Real::faer_from_f64(Ord::max(m, n + 1) as f64));
+
+        // split `col` into its component in the span of U and the orthogonal residual, with a
+        // second pass of gram-schmidt to keep the residual orthogonal to U
+        let col = col.as_2d();
+        let mut proj = self.u.adjoint() * col;
+        let mut residual = col.to_owned() - &self.u * &proj;
+        let correction = self.u.adjoint() * &residual;
+        residual -= &self.u * &correction;
+        proj += correction;
+
+        let s_max = if r > 0 {
+            self.s.read(0, 0).faer_real()
+        } else {
+            E::Real::faer_zero()
+        };
+        let residual_norm = residual.norm_l2();
+
+        // if the residual is negligible, the column space doesn't grow and the core matrix only
+        // has `r` rows
+        let extend = residual_norm > tol_factor.faer_mul(s_max);
+        let p = r + extend as usize;
+
+        // [A c] = [U p] K [V 0; 0 1]^H, with K = [S proj; 0 |residual|]
+        let mut core = Mat::<E>::zeros(p, r + 1);
+        for i in 0..r {
+            core.write(i, i, self.s.read(i, 0));
+            core.write(i, r, proj.read(i, 0));
+        }
+
+        let mut u_ext = Mat::<E>::zeros(m, p);
+        u_ext
+            .as_mut()
+            .submatrix_mut(0, 0, m, r)
+            .copy_from(self.u.as_ref());
+
+        if extend {
+            let inv = residual_norm.faer_inv();
+            zipped!(residual.as_mut())
+                .for_each(|unzipped!(mut x)| x.write(x.read().faer_scale_real(inv)));
+            core.write(r, r, E::faer_from_real(residual_norm));
+            u_ext
+                .as_mut()
+                .col_mut(r)
+                .as_2d_mut()
+                .copy_from(residual.as_ref());
+        }
+
+        let (core_u, core_s, core_v) =
+            thin_svd(core.as_ref(), Parallelism::None, SvdParams::default());
+
+        let mut v_ext = Mat::<E>::zeros(n + 1, r + 1);
+        v_ext
+            .as_mut()
+            .submatrix_mut(0, 0, n, r)
+            .copy_from(self.v.as_ref());
+        v_ext.write(n, r, E::faer_one());
+
+        let rank = Ord::min(effective_rank(core_s.as_ref(), tol_factor), m);
+
+        self.u = u_ext * core_u.as_ref().subcols(0, rank);
+        self.v = v_ext * core_v.as_ref().subcols(0, rank);
+        self.s = core_s.as_ref().subrows(0, rank).to_owned();
+    }
+
+    /// Updates the decomposition of $A$ to that of $A - X Y^H$, where $X$ is `u_update` and $Y$ is
+    /// `v_update`, using Brand's rank-$k$ update.
+    ///
+    /// The components of $X$ and $Y$ outside of the spans of $U$ and $V$ extend the bases, and
+    /// only the $(r + k, r + k)$ core matrix is decomposed from scratch, so the cost is
+    /// $\mathcal{O}((m + n) (r + k)^2)$ instead of that of a full recomputation. Singular values
+    /// that become negligible are dropped, so that the rank $r$ tracks the effective rank of the
+    /// result.
+    ///
+    /// The accuracy is relative to the norms of $A$ and of $X Y^H$, so singular values of the
+    /// result that are much smaller than those lose relative accuracy to cancellation.
+    ///
+    /// # Panics
+    /// Panics if `u_update` doesn't have the same number of rows as $A$, if `v_update` doesn't
+    /// have the same number of rows as the number of columns of $A$, or if they don't have the
+    /// same number of columns.
+    #[track_caller]
+    pub fn downdate(&mut self, u_update: MatRef<'_, E>, v_update: MatRef<'_, E>) {
+        let m = self.u.nrows();
+        let n = self.v.nrows();
+        let r = self.s.nrows();
+        let k = u_update.ncols();
+        assert!(all(
+            u_update.nrows() == m,
+            v_update.nrows() == n,
+            v_update.ncols() == k,
+        ));
+
+        let epsilon = E::Real::faer_epsilon().unwrap();
+        let tol_factor = epsilon.faer_mul(E::Real::faer_from_f64(Ord::max(m, n) as f64));
+
+        // A - X Y^H = [U P] K [V Q]^H, with K = [S 0; 0 0] - cx cy^H, where P and Q are orthonormal
+        // bases of the components of X and Y outside of the spans of U and V
+        let (u_ext, cx) = extend_basis(
+            self.u.as_ref(),
+            u_update,
+            tol_factor.faer_mul(u_update.norm_l2()),
+        );
+        let (v_ext, cy) = extend_basis(
+            self.v.as_ref(),
+            v_update,
+            tol_factor.faer_mul(v_update.norm_l2()),
+        );
+
+        let mut core = -(&cx * cy.adjoint());
+        for i in 0..r {
+            core.write(i, i, core.read(i, i).faer_add(self.s.read(i, 0)));
+        }
+
+        let (core_u, core_s, core_v) =
+            thin_svd(core.as_ref(), Parallelism::None, SvdParams::default());
+
+        let rank = effective_rank(core_s.as_ref(), tol_factor);
+
+        self.u = u_ext * core_u.as_ref().subcols(0, rank);
+        self.v = v_ext * core_v.as_ref().subcols(0, rank);
+        self.s = core_s.as_ref().subrows(0, rank).to_owned();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_approx_eq::assert_approx_eq;
+    use faer_core::{assert, c64};
+
+    #[test]
+    fn test_solve_reconstruct() {
+        let m = 30;
+        let n = 20;
+        let rank = 12;
+
+        let b = Mat::from_fn(m, rank, |_, _| c64::new(rand::random(), rand::random()));
+        let c = Mat::from_fn(rank, n, |_, _| c64::new(rand::random(), rand::random()));
+        let a = &b * &c;
+
+        let svd = SvdDecomposition::new(a.as_ref());
+        let s = svd.singular_values();
+        assert!(s.len() == rank);
+        for i in 0..rank - 1 {
+            assert!(s[i] >= s[i + 1]);
+        }
+
+        let reconstructed = svd.reconstruct();
+        for j in 0..n {
+            for i in 0..m {
+                assert_approx_eq!(reconstructed.read(i, j), a.read(i, j), 1e-10);
+            }
+        }
+
+        // A A^+ A = A, and A^+ A A^+ = A^+
+        let pinv = svd.pseudo_inverse();
+        assert!(all(pinv.nrows() == n, pinv.ncols() == m));
+        let a_pinv_a = &a * &pinv * &a;
+        let pinv_a_pinv = &pinv * &a * &pinv;
+        for j in 0..n {
+            for i in 0..m {
+                assert_approx_eq!(a_pinv_a.read(i, j), a.read(i, j), 1e-10);
+                assert_approx_eq!(pinv_a_pinv.read(j, i), pinv.read(j, i), 1e-10);
+            }
+        }
+
+        // consistent right hand side, solved exactly by the minimum norm solution
+        let x_true = Mat::from_fn(n, 3, |_, _| c64::new(rand::random(), rand::random()));
+        let rhs = &a * &x_true;
+        let x = svd.solve(rhs.as_ref());
+        let x_expected = &pinv * &rhs;
+        let residual = &a * &x;
+        for j in 0..3 {
+            for i in 0..n {
+                assert_approx_eq!(x.read(i, j), x_expected.read(i, j), 1e-10);
+            }
+            for i in 0..m {
+                assert_approx_eq!(residual.read(i, j), rhs.read(i, j), 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_truncate() {
+        let n = 100;
+        let rank = 5;
+
+        let b = Mat::from_fn(n, rank, |_, _| rand::random::<f64>());
+        let c = Mat::from_fn(rank, n, |_, _| rand::random::<f64>());
+        let a = &b * &c;
+        // the noise keeps some of the trailing singular values above the tolerance of `new`
+        let noisy = Mat::from_fn(n, n, |i, j| a.read(i, j) + 1e-10 * rand::random::<f64>());
+
+        let svd = SvdDecomposition::new(noisy.as_ref());
+        assert!(svd.s_diagonal().nrows() > rank);
+
+        let truncated = svd.truncate(1e-8);
+        assert!(all(
+            truncated.u().nrows() == n,
+            truncated.u().ncols() == rank,
+            truncated.s_diagonal().nrows() == rank,
+            truncated.v().nrows() == n,
+            truncated.v().ncols() == rank,
+        ));
+        for i in 0..rank {
+            assert!(truncated.s_diagonal().read(i, 0) == svd.s_diagonal().read(i, 0));
+        }
+
+        let reconstructed = truncated.reconstruct();
+        for j in 0..n {
+            for i in 0..n {
+                assert_approx_eq!(reconstructed.read(i, j), a.read(i, j), 1e-8);
+            }
+        }
+
+        // a threshold above the largest singular value drops everything
+        assert!(svd.truncate(2.0).s_diagonal().nrows() == 0);
+    }
+
+    #[test]
+    fn test_update_append_column() {
+        let m = 40;
+        let n = 60;
+        let rank = 3;
+
+        let b = Mat::from_fn(m, rank, |_, _| c64::new(rand::random(), rand::random()));
+        let c = Mat::from_fn(rank, n, |_, _| c64::new(rand::random(), rand::random()));
+        let a = &b * &c;
+
+        let mut svd = SvdDecomposition::new(a.as_ref().subcols(0, 5));
+        for j in 5..n {
+            svd.update_append_column(a.as_ref().col(j));
+
+            let u = svd.u();
+            let v = svd.v();
+            let s = svd.s_diagonal();
+            assert!(all(u.nrows() == m, v.nrows() == j + 1));
+            assert!(s.nrows() <= rank);
+
+            let us = Mat::from_fn(m, s.nrows(), |i, k| {
+                u.read(i, k).faer_scale_real(s.read(k, 0).re)
+            });
+            let reconstructed = &us * v.adjoint();
+            for jj in 0..j + 1 {
+                for i in 0..m {
+                    assert_approx_eq!(reconstructed.read(i, jj), a.read(i, jj), 1e-10);
+                }
+            }
+            for o in [u.adjoint() * u, v.adjoint() * v] {
+                for jj in 0..o.ncols() {
+                    for i in 0..o.nrows() {
+                        let target = if i == jj {
+                            c64::new(1.0, 0.0)
+                        } else {
+                            c64::new(0.0, 0.0)
+                        };
+                        assert_approx_eq!(o.read(i, jj), target, 1e-10);
+                    }
+                }
+            }
+        }
+        assert!(svd.s_diagonal().nrows() == rank);
+
+        let expected = SvdDecomposition::new(a.as_ref());
+        for i in 0..rank {
+            assert_approx_eq!(
+                svd.s_diagonal().read(i, 0),
+                expected.s_diagonal().read(i, 0),
+                1e-10
+            );
+        }
+    }
+    #[test]
+    fn test_downdate() {
+        let m = 50;
+        let n = 35;
+        let rank = 4;
+        let k = 2;
+
+        let b = Mat::from_fn(m, rank, |_, _| c64::new(rand::random(), rand::random()));
+        let c = Mat::from_fn(rank, n, |_, _| c64::new(rand::random(), rand::random()));
+        let x = Mat::from_fn(m, k, |_, _| c64::new(rand::random(), rand::random()));
+        let y = Mat::from_fn(n, k, |_, _| c64::new(rand::random(), rand::random()));
+        let target = &b * &c;
+        let a = &target + &x * y.adjoint();
+
+        let mut svd = SvdDecomposition::new(a.as_ref());
+        assert!(svd.s_diagonal().nrows() == rank + k);
+        svd.downdate(x.as_ref(), y.as_ref());
+
+        let u = svd.u();
+        let v = svd.v();
+        let s = svd.s_diagonal();
+        assert!(all(u.nrows() == m, v.nrows() == n, s.nrows() == rank));
+
+        let reconstructed = svd.reconstruct();
+        for j in 0..n {
+            for i in 0..m {
+                assert_approx_eq!(reconstructed.read(i, j), target.read(i, j), 1e-10);
+            }
+        }
+        for o in [u.adjoint() * u, v.adjoint() * v] {
+            for j in 0..o.ncols() {
+                for i in 0..o.nrows() {
+                    let target = if i == j {
+                        c64::new(1.0, 0.0)
+                    } else {
+                        c64::new(0.0, 0.0)
+                    };
+                    assert_approx_eq!(o.read(i, j), target, 1e-10);
+                }
+            }
+        }
+
+        let expected = SvdDecomposition::new(target.as_ref());
+        for i in 0..rank {
+            assert_approx_eq!(s.read(i, 0), expected.s_diagonal().read(i, 0), 1e-10);
+        }
+
+        // a term that lies in the spans of U and V doesn't extend the bases
+        let mut svd = SvdDecomposition::new(target.as_ref());
+        let x = svd.u().subcols(0, 1).to_owned();
+        let y = svd.v().subcols(0, 1) * faer_core::scale(svd.s_diagonal().read(0, 0));
+        svd.downdate(x.as_ref(), y.as_ref());
+        assert!(svd.s_diagonal().nrows() == rank - 1);
+        for i in 0..rank - 1 {
+            assert_approx_eq!(
+                svd.s_diagonal().read(i, 0),
+                expected.s_diagonal().read(i + 1, 0),
+                1e-10
+            );
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use crate::{
+    decomposition::{effective_rank, thin_svd},
+    SvdDecomposition, SvdParams,
+};
+use alloc::{vec, vec::Vec};
+use faer_core::{c64, get_global_parallelism, ComplexField, Mat, MatMut, MatRef};
+
+/// `x^H y` for the columns `i` of `x` and `j` of `y`
+fn dot(x: MatRef<'_, c64>, i: usize, y: MatRef<'_, c64>, j: usize) -> c64 {
+    let mut acc = c64::faer_zero();
+    for k in 0..x.nrows() {
+        acc = acc.faer_add(x.read(k, i).faer_conj().faer_mul(y.read(k, j)));
+    }
+    acc
+}
+
+/// `x[:, i] -= alpha * y[:, j]`
+fn axpy(mut x: MatMut<'_, c64>, i: usize, alpha: c64, y: MatRef<'_, c64>, j: usize) {
+    for k in 0..x.nrows() {
+        x.write(k, i, x.read(k, i).faer_sub(alpha.faer_mul(y.read(k, j))));
+    }
+}
+
+fn norm(x: MatRef<'_, c64>, i: usize) -> f64 {
+    let mut acc = 0.0;
+    for k in 0..x.nrows() {
+        acc += x.read(k, i).faer_abs2();
+    }
+    acc.faer_sqrt()
+}
+
+/// Computes the thin singular value decomposition of the complex `matrix`, using only the real
+/// SVD.
+///
+/// The matrix $A = X + iY$ of shape $(m, n)$ is embedded in the real matrix
+/// $$\begin{bmatrix} X & -Y \\ Y & X \end{bmatrix}$$
+/// of shape $(2m, 2n)$, whose singular values are those of $A$, each repeated twice. A real
+/// singular vector $\begin{bmatrix} p \\ q \end{bmatrix}$ of the embedding corresponds to the
+/// complex singular vector $p + iq$ of $A$, so one complex vector is extracted from each pair
+/// of real ones. When singular values of $A$ are repeated, the real vectors of the cluster are
+/// orthogonalized against the complex vectors that are already extracted, so that the
+/// extracted ones stay orthonormal.
+///
+/// This is slower and uses about four times as much memory as [`SvdDecomposition::new`], and
+/// is meant as a cross-check of the complex SVD.
+#[track_caller]
+pub fn complex_svd_via_real_embedding(matrix: MatRef<'_, c64>) -> SvdDecomposition<c64> {
+    let m = matrix.nrows();
+    let n = matrix.ncols();
+
+    let embedded = Mat::<f64>::from_fn(2 * m, 2 * n, |i, j| {
+        let z = matrix.read(i % m, j % n);
+        match (i < m, j < n) {
+            (true, true) | (false, false) => z.re,
+            (true, false) => -z.im,
+            (false, true) => z.im,
+        }
+    });
+    let (u, s, v) = thin_svd(
+        embedded.as_ref(),
+        get_global_parallelism(),
+        SvdParams::default(),
+    );
+
+    // the singular vectors of the zero singular values are arbitrary in the left and right null
+    // spaces, and can't be paired up, so they are dropped before the extraction
+    let tol_factor = f64::EPSILON * Ord::max(2 * m, 2 * n) as f64;
+    let real_rank = effective_rank(s.as_ref(), tol_factor);
+    let rank = (real_rank + 1) / 2;
+
+    let mut candidates_u = Mat::<c64>::from_fn(m, real_rank, |i, j| {
+        c64::new(u.read(i, j), u.read(m + i, j))
+    });
+    let mut candidates_v = Mat::<c64>::from_fn(n, real_rank, |i, j| {
+        c64::new(v.read(i, j), v.read(n + i, j))
+    });
+
+    let mut out_u = Mat::<c64>::zeros(m, rank);
+    let mut out_v = Mat::<c64>::zeros(n, rank);
+    let mut out_s = Vec::with_capacity(rank);
+    let mut available = vec![true; real_rank];
+
+    for k in 0..rank {
+        // the candidate with the largest component orthogonal to the extracted vectors. within a
+        // cluster of `p` repeated singular values, that component has a norm of at least
+        // `1 / sqrt(p)`
+        let mut best = 0;
+        let mut best_norm = -1.0;
+        for (j, _) in available.iter().enumerate().filter(|(_, &a)| a) {
+            let norm = norm(candidates_u.as_ref(), j);
+            if norm > best_norm {
+                best = j;
+                best_norm = norm;
+            }
+        }
+        available[best] = false;
+
+        // scaling both vectors by the same factor keeps $A v = \sigma u$
+        let inv = best_norm.recip();
+        for i in 0..m {
+            out_u.write(i, k, candidates_u.read(i, best).faer_scale_real(inv));
+        }
+        for i in 0..n {
+            out_v.write(i, k, candidates_v.read(i, best).faer_scale_real(inv));
+        }
+        out_s.push(s.read(best, 0));
+
+        // twice is enough to make the remaining candidates orthogonal to working precision
+        for (j, _) in available.iter().enumerate().filter(|(_, &a)| a) {
+            for _ in 0..2 {
+                let alpha = dot(out_u.as_ref(), k, candidates_u.as_ref(), j);
+                axpy(candidates_u.as_mut(), j, alpha, out_u.as_ref(), k);
+                axpy(candidates_v.as_mut(), j, alpha, out_v.as_ref(), k);
+            }
+        }
+    }
+
+    // the candidates are picked by their norm, which can mix up the order of distinct singular
+    // values when they are close
+    let mut perm: Vec<usize> = (0..rank).collect();
+    perm.sort_by(|&i, &j| out_s[j].total_cmp(&out_s[i]));
+
+    SvdDecomposition::from_thin_parts(
+        Mat::from_fn(m, rank, |i, k| out_u.read(i, perm[k])),
+        Mat::from_fn(rank, 1, |k, _| c64::new(out_s[perm[k]], 0.0)),
+        Mat::from_fn(n, rank, |i, k| out_v.read(i, perm[k])),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_approx_eq::assert_approx_eq;
+    use faer_core::assert;
+
+    fn check(a: MatRef<'_, c64>, expected_rank: usize) {
+        let m = a.nrows();
+        let n = a.ncols();
+
+        let svd = complex_svd_via_real_embedding(a);
+        let expected = SvdDecomposition::new(a);
+        let s = svd.singular_values();
+        let s_expected = expected.singular_values();
+        assert!(s.len() == expected_rank);
+        assert!(s_expected.len() == expected_rank);
+        for i in 0..expected_rank {
+            assert_approx_eq!(s[i], s_expected[i], 1e-10);
+        }
+
+        let reconstructed = svd.reconstruct();
+        for j in 0..n {
+            for i in 0..m {
+                assert_approx_eq!(reconstructed.read(i, j), a.read(i, j), 1e-10);
+            }
+        }
+        for o in [svd.u().adjoint() * svd.u(), svd.v().adjoint() * svd.v()] {
+            for j in 0..o.ncols() {
+                for i in 0..o.nrows() {
+                    let target = if i == j {
+                        c64::new(1.0, 0.0)
+                    } else {
+                        c64::new(0.0, 0.0)
+                    };
+                    assert_approx_eq!(o.read(i, j), target, 1e-10);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_complex_svd_via_real_embedding() {
+        let random = |m, n| Mat::from_fn(m, n, |_, _| c64::new(rand::random(), rand::random()));
+
+        for (m, n) in [(1, 1), (7, 7), (13, 5), (5, 13), (40, 30)] {
+            check(random(m, n).as_ref(), Ord::min(m, n));
+        }
+
+        // rank deficient
+        let a = random(20, 4) * random(4, 15);
+        check(a.as_ref(), 4);
+
+        // repeated singular values, whose real singular vectors can mix the complex directions
+        let (m, n) = (12, 9);
+        let q_left = SvdDecomposition::new(random(m, n).as_ref());
+        let q_right = SvdDecomposition::new(random(n, n).as_ref());
+        let s = [3.0, 3.0, 3.0, 3.0, 2.0, 1.0, 1.0, 0.5, 0.5];
+        let us = Mat::from_fn(m, n, |i, j| q_left.u().read(i, j).faer_scale_real(s[j]));
+        let a = &us * q_right.u().adjoint();
+        check(a.as_ref(), n);
+
+        // identity
+        let a = Mat::from_fn(6, 6, |i, j| {
+            if i == j {
+                c64::new(1.0, 0.0)
+            } else {
+                c64::new(0.0, 0.0)
+            }
+        });
+        check(a.as_ref(), 6);
+    }
+}
@@ -0,0 +1,273 @@
+use crate::jacobi::MAX_SWEEPS;
+use faer_core::{jacobi::JacobiRotation, RealField};
+
+/// returns `(c x + s y, c y - s x)`, i.e., the rotation applied to the pair `(x, y)`
+#[inline(always)]
+fn rotate<E: RealField>(rot: JacobiRotation<E>, x: E, y: E) -> (E, E) {
+    let JacobiRotation { c, s } = rot;
+    (
+        c.faer_mul(x).faer_add(s.faer_mul(y)),
+        c.faer_mul(y).faer_sub(s.faer_mul(x)),
+    )
+}
+
+/// two-sided jacobi rotations that diagonalize the `2×2` matrix `[m00 m01; m10 m11]`
+fn rotations_2x2<E: RealField>(
+    m00: E,
+    m01: E,
+    m10: E,
+    m11: E,
+) -> (JacobiRotation<E>, JacobiRotation<E>) {
+    let t = m00.faer_add(m11);
+    let d = m10.faer_sub(m01);
+
+    // symmetrize the block first, then diagonalize it
+    let rot1 = if d == E::faer_zero() {
+        JacobiRotation {
+            c: E::faer_one(),
+            s: E::faer_zero(),
+        }
+    } else {
+        let u = t.faer_div(d);
+        let tmp = E::faer_one().faer_add(u.faer_mul(u)).faer_sqrt().faer_inv();
+        let tmp = if tmp == E::faer_zero() {
+            u.faer_abs().faer_inv()
+        } else {
+            tmp
+        };
+        JacobiRotation {
+            c: u.faer_mul(tmp),
+            s: tmp,
+        }
+    };
+    let j_right = {
+        let (m00, m01, _, m11) = rot1.apply_on_the_left_2x2(m00, m01, m10, m11);
+        JacobiRotation::from_triplet(m00, m01, m11)
+    };
+    let j_left = rot1 * j_right.transpose();
+
+    (j_left, j_right)
+}
+
+/// Computes the SVD of the square matrix `a` of compile time size `N`, stored by rows, so that
+/// `a[i][j]` is the entry at row `i` and column `j`.
+///
+/// Returns `(s, u, v)` such that $A = U \operatorname{diag}(s) V^T$, with the singular values `s`
+/// in nonincreasing order, and the orthogonal factors `u` and `v` also stored by rows.
+///
+/// This is meant for the small fixed sizes of geometric applications, such as `2`, `3` or `4`.
+/// Everything is stored on the stack, so this doesn't allocate, nor need any workspace. The
+/// matrix is diagonalized by two-sided Jacobi sweeps, which reach machine precision for these
+/// sizes, and reduce to the closed form SVD for `N == 2`, which is diagonalized by a single pair
+/// of rotations.
+///
+/// At most `30` sweeps are performed. If `a` contains infinite or NaN values, the sweeps are
+/// skipped and every entry of `s`, `u` and `v` is NaN.
+///
+/// # Panics
+/// Panics if the type `E` does not have a fixed precision at compile time, e.g. a dynamic
+/// multiprecision floating point type.
+#[track_caller]
+pub fn svd_fixed<const N: usize, E: RealField>(
+    a: [[E; N]; N],
+) -> ([E; N], [[E; N]; N], [[E; N]; N]) {
+    let epsilon = E::faer_epsilon().unwrap();
+    let consider_zero_threshold = E::faer_zero_threshold().unwrap();
+
+    // the convergence test never passes once a NaN value shows up in the matrix
+    if a.iter().flatten().any(|x| !x.faer_is_finite()) {
+        let nan = E::faer_nan();
+        return ([nan; N], [[nan; N]; N], [[nan; N]; N]);
+    }
+
+    let mut a = a;
+    let identity = || {
+        let mut id = [[E::faer_zero(); N]; N];
+        for (i, row) in id.iter_mut().enumerate() {
+            row[i] = E::faer_one();
+        }
+        id
+    };
+    let mut u = identity();
+    let mut v = identity();
+
+    let mut max_diag = E::faer_zero();
+    for (i, row) in a.iter().enumerate() {
+        let d = row[i].faer_abs();
+        if d > max_diag {
+            max_diag = d;
+        }
+    }
+
+    let precision = epsilon.faer_scale_power_of_two(E::faer_one().faer_add(E::faer_one()));
+    for _ in 0..MAX_SWEEPS {
+        let mut failed = false;
+        for p in 1..N {
+            for q in 0..p {
+                let threshold = precision.faer_mul(max_diag);
+                let threshold = if threshold > consider_zero_threshold {
+                    threshold
+                } else {
+                    consider_zero_threshold
+                };
+
+                if a[p][q].faer_abs() <= threshold && a[q][p].faer_abs() <= threshold {
+                    continue;
+                }
+                failed = true;
+
+                let (j_left, j_right) = rotations_2x2(a[p][p], a[p][q], a[q][p], a[q][q]);
+
+                // rows `p` and `q` of `a`, then its columns `p` and `q`, and the columns of the
+                // accumulated factors
+                let (top, bottom) = a.split_at_mut(p);
+                for (x, y) in bottom[0].iter_mut().zip(top[q].iter_mut()) {
+                    (*x, *y) = rotate(j_left, *x, *y);
+                }
+                for row in a.iter_mut().chain(v.iter_mut()) {
+                    (row[p], row[q]) = rotate(j_right.transpose(), row[p], row[q]);
+                }
+                for row in u.iter_mut() {
+                    (row[p], row[q]) = rotate(j_left, row[p], row[q]);
+                }
+
+                for idx in [p, q] {
+                    let d = a[idx][idx].faer_abs();
+                    if d > max_diag {
+                        max_diag = d;
+                    }
+                }
+            }
+        }
+        if !failed {
+            break;
+        }
+    }
+
+    let mut s = [E::faer_zero(); N];
+    for (j, s) in s.iter_mut().enumerate() {
+        let d = a[j][j];
+        if d < E::faer_zero() {
+            *s = d.faer_neg();
+            for row in u.iter_mut() {
+                row[j] = row[j].faer_neg();
+            }
+        } else {
+            *s = d;
+        }
+    }
+
+    // selection sort, which takes at most `N - 1` swaps
+    for i in 0..N {
+        let mut largest = i;
+        for j in i + 1..N {
+            if s[j] > s[largest] {
+                largest = j;
+            }
+        }
+        if largest != i {
+            s.swap(i, largest);
+            for row in u.iter_mut().chain(v.iter_mut()) {
+                row.swap(i, largest);
+            }
+        }
+    }
+
+    (s, u, v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{compute_svd, compute_svd_req, ComputeVectors, SvdParams};
+    use faer_core::{assert, Mat, Parallelism};
+
+    fn check<const N: usize>(a: [[f64; N]; N]) {
+        let (s, u, v) = svd_fixed(a);
+
+        let mat = Mat::from_fn(N, N, |i, j| a[i][j]);
+        let mut expected = Mat::<f64>::zeros(N, 1);
+        compute_svd(
+            mat.as_ref(),
+            expected.as_mut(),
+            None,
+            None,
+            Parallelism::None,
+            ::dyn_stack::PodStack::new(&mut ::dyn_stack::GlobalPodBuffer::new(
+                compute_svd_req::<f64>(
+                    N,
+                    N,
+                    ComputeVectors::No,
+                    ComputeVectors::No,
+                    Parallelism::None,
+                    SvdParams::default(),
+                )
+                .unwrap(),
+            )),
+            SvdParams::default(),
+        );
+
+        // the rotations of the jacobi sweeps accumulate rounding errors in the singular vectors.
+        // the reference values have their own errors, which add up to about `13 eps` for `N == 4`
+        let norm = expected.read(0, 0);
+        let tol = 16.0 * f64::EPSILON * norm;
+        for i in 0..N {
+            assert!((s[i] - expected.read(i, 0)).abs() <= tol);
+            if i + 1 < N {
+                assert!(s[i] >= s[i + 1]);
+            }
+        }
+
+        for i in 0..N {
+            for j in 0..N {
+                let mut reconstructed = 0.0;
+                let mut u_dot = 0.0;
+                let mut v_dot = 0.0;
+                for k in 0..N {
+                    reconstructed += u[i][k] * s[k] * v[j][k];
+                    u_dot += u[k][i] * u[k][j];
+                    v_dot += v[k][i] * v[k][j];
+                }
+                let target = if i == j { 1.0 } else { 0.0 };
+                assert!((reconstructed - a[i][j]).abs() <= 2.0 * tol);
+                assert!((u_dot - target).abs() <= 32.0 * f64::EPSILON);
+                assert!((v_dot - target).abs() <= 32.0 * f64::EPSILON);
+            }
+        }
+    }
+
+    #[test]
+    fn test_svd_fixed() {
+        for _ in 0..100 {
+            check::<1>([[rand::random::<f64>() - 0.5]]);
+            check::<2>(core::array::from_fn(|_| {
+                core::array::from_fn(|_| rand::random::<f64>() - 0.5)
+            }));
+            check::<3>(core::array::from_fn(|_| {
+                core::array::from_fn(|_| rand::random::<f64>() - 0.5)
+            }));
+            check::<4>(core::array::from_fn(|_| {
+                core::array::from_fn(|_| rand::random::<f64>() - 0.5)
+            }));
+        }
+
+        // rank deficient and diagonal matrices
+        check::<3>([[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [1.0, 0.0, 1.0]]);
+        check::<3>([[0.0; 3]; 3]);
+        check::<4>([
+            [0.0, 0.0, 3.0, 0.0],
+            [-1.0, 0.0, 0.0, 0.0],
+            [0.0, 0.0, 0.0, 2.0],
+            [0.0, 5.0, 0.0, 0.0],
+        ]);
+    }
+
+    #[test]
+    fn test_svd_fixed_non_finite() {
+        for bad in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let (s, u, v) = svd_fixed([[1.0, 2.0, 0.0], [0.0, bad, 1.0], [3.0, 0.0, 1.0]]);
+            assert!(s.iter().all(|x| x.is_nan()));
+            assert!(u.iter().chain(v.iter()).flatten().all(|x| x.is_nan()));
+        }
+    }
+}
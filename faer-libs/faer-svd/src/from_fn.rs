@@ -0,0 +1,545 @@
+use crate::{
+    bidiag_real_svd::{bidiag_real_svd_req, compute_bidiag_real_svd},
+    jacobi_fallback_threshold, SvdParams, BIDIAG_QR_FALLBACK_THRESHOLD,
+};
+use dyn_stack::{PodStack, SizeOverflow, StackReq};
+use faer_core::{
+    assert, mul::matmul, temp_mat_req, temp_mat_uninit, temp_mat_zeroed, unzipped, zipped, MatMut,
+    MatRef, Parallelism, RealField,
+};
+use reborrow::*;
+
+/// number of entries of the matrix that are generated at a time. the rows are queried in panels
+/// of about this size, which are then multiplied by the current lanczos vector
+const PANEL_SIZE: usize = 1 << 16;
+
+fn panel_rows(nrows: usize, ncols: usize) -> usize {
+    Ord::min(nrows, Ord::max(1, PANEL_SIZE / Ord::max(1, ncols)))
+}
+
+/// fills `panel` with the rows `row_start..row_start + panel.nrows()` of the matrix
+fn fill_panel<E: RealField>(
+    mut panel: MatMut<'_, E>,
+    row_start: usize,
+    f: &impl Fn(usize, usize) -> E,
+) {
+    for j in 0..panel.ncols() {
+        for i in 0..panel.nrows() {
+            panel.write(i, j, f(row_start + i, j));
+        }
+    }
+}
+
+/// `dst = A * rhs`, querying the entries of `A` one panel of rows at a time
+fn mul_from_fn<E: RealField>(
+    mut dst: MatMut<'_, E>,
+    rhs: MatRef<'_, E>,
+    mut panel: MatMut<'_, E>,
+    f: &impl Fn(usize, usize) -> E,
+    parallelism: Parallelism,
+) {
+    let m = dst.nrows();
+    let blocksize = panel.nrows();
+    let mut row_start = 0;
+    while row_start < m {
+        let bs = Ord::min(blocksize, m - row_start);
+        let mut panel = panel.rb_mut().subrows_mut(0, bs);
+        fill_panel(panel.rb_mut(), row_start, f);
+        matmul(
+            dst.rb_mut().subrows_mut(row_start, bs),
+            panel.rb(),
+            rhs,
+            None,
+            E::faer_one(),
+            parallelism,
+        );
+        row_start += bs;
+    }
+}
+
+/// `dst = A^T * rhs`, querying the entries of `A` one panel of rows at a time
+fn mul_transpose_from_fn<E: RealField>(
+    mut dst: MatMut<'_, E>,
+    rhs: MatRef<'_, E>,
+    mut panel: MatMut<'_, E>,
+    f: &impl Fn(usize, usize) -> E,
+    parallelism: Parallelism,
+) {
+    let m = rhs.nrows();
+    let blocksize = panel.nrows();
+    dst.fill_zero();
+    let mut row_start = 0;
+    while row_start < m {
+        let bs = Ord::min(blocksize, m - row_start);
+        let mut panel = panel.rb_mut().subrows_mut(0, bs);
+        fill_panel(panel.rb_mut(), row_start, f);
+        matmul(
+            dst.rb_mut(),
+            panel.rb().transpose(),
+            rhs.subrows(row_start, bs),
+            Some(E::faer_one()),
+            E::faer_one(),
+            parallelism,
+        );
+        row_start += bs;
+    }
+}
+
+/// orthogonalizes `x` against the columns of `basis`, which must be orthonormal. the projection is
+/// done twice, which is enough to keep the lanczos vectors orthogonal to working precision
+fn reorthogonalize<E: RealField>(
+    mut x: MatMut<'_, E>,
+    basis: MatRef<'_, E>,
+    mut coeffs: MatMut<'_, E>,
+    parallelism: Parallelism,
+) {
+    let mut coeffs = coeffs.rb_mut().subrows_mut(0, basis.ncols());
+    for _ in 0..2 {
+        matmul(
+            coeffs.rb_mut(),
+            basis.transpose(),
+            x.rb(),
+            None,
+            E::faer_one(),
+            parallelism,
+        );
+        matmul(
+            x.rb_mut(),
+            basis,
+            coeffs.rb(),
+            Some(E::faer_one()),
+            E::faer_one().faer_neg(),
+            parallelism,
+        );
+    }
+}
+
+/// scales `x` by `1 / norm`
+fn normalize<E: RealField>(x: MatMut<'_, E>, norm: E) {
+    let inv = norm.faer_inv();
+    zipped!(x).for_each(|unzipped!(mut x)| x.write(x.read().faer_mul(inv)));
+}
+
+/// Computes the size and alignment of required workspace for computing a truncated svd of rank
+/// `rank` of an implicitly defined matrix with [`svd_from_fn`].
+pub fn svd_from_fn_req<E: RealField>(
+    nrows: usize,
+    ncols: usize,
+    rank: usize,
+    compute_u: bool,
+    compute_v: bool,
+    parallelism: Parallelism,
+    params: SvdParams,
+) -> Result<StackReq, SizeOverflow> {
+    let k = rank;
+    StackReq::try_all_of([
+        // lanczos vectors
+        temp_mat_req::<E>(nrows, k + 1)?,
+        temp_mat_req::<E>(ncols, k)?,
+        temp_mat_req::<E>(panel_rows(nrows, ncols), ncols)?,
+        temp_mat_req::<E>(k + 1, 1)?,
+        // bidiagonal matrix and its singular vectors
+        StackReq::try_new::<E>(k)?,
+        StackReq::try_new::<E>(k)?,
+        temp_mat_req::<E>(k + 1, if compute_u { k } else { 0 })?,
+        temp_mat_req::<E>(k, if compute_v { k } else { 0 })?,
+        bidiag_real_svd_req::<E>(
+            k,
+            jacobi_fallback_threshold(params, compute_u || compute_v),
+            compute_u,
+            compute_v,
+            parallelism,
+        )?,
+    ])
+}
+
+/// Computes a truncated svd of rank at most `s.nrows()` of the matrix of shape `(nrows, ncols)`
+/// whose entry at `(i, j)` is `f(i, j)`, without storing it.
+///
+/// The matrix is reduced to a lower bidiagonal matrix $B$ of shape `(k + 1, k)` with the
+/// Golub-Kahan-Lanczos process, such that $A V_k = U_{k + 1} B$, where the columns of $U_{k + 1}$
+/// and $V_k$ are orthonormal, and $k$ is `s.nrows()`. Each step of the process queries every entry
+/// of the matrix twice, one panel of rows at a time, so `f` should be cheap to evaluate and must
+/// return the same value each time it's called with the same arguments. The svd of $B$ is then
+/// computed with the bidiagonal svd of this crate.
+///
+/// The process stops early if it finds an invariant subspace, which is the case when the rank of
+/// the matrix is smaller than `k`. The number of computed singular values `r` is returned. They
+/// are stored in `s[0..r]` in nonincreasing order, and the first `r` columns of `u` and `v`
+/// contain the corresponding singular vectors. The rest of `s`, `u` and `v` is filled with zeros.
+///
+/// The result is exact, up to rounding errors, when the rank of the matrix is at most `k`.
+/// Otherwise, the largest singular values and their vectors are approximated, with an accuracy
+/// that improves as `k` grows.
+///
+/// # Panics
+/// Panics if `s.nrows()` is larger than `Ord::min(nrows, ncols)`, if `s` isn't a column vector,
+/// or if `u` (resp. `v`) doesn't have shape `(nrows, s.nrows())` (resp. `(ncols, s.nrows())`).
+///
+/// This can also panic if the provided memory in `stack` is insufficient (see
+/// [`svd_from_fn_req`]).
+#[track_caller]
+pub fn svd_from_fn<E: RealField>(
+    nrows: usize,
+    ncols: usize,
+    f: impl Fn(usize, usize) -> E,
+    mut s: MatMut<'_, E>,
+    u: Option<MatMut<'_, E>>,
+    v: Option<MatMut<'_, E>>,
+    epsilon: E,
+    zero_threshold: E,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+    params: SvdParams,
+) -> usize {
+    let m = nrows;
+    let n = ncols;
+    let k = s.nrows();
+    assert!(s.ncols() == 1);
+    assert!(k <= Ord::min(m, n));
+    if let Some(u) = u.rb() {
+        assert!(all(u.nrows() == m, u.ncols() == k));
+    }
+    if let Some(v) = v.rb() {
+        assert!(all(v.nrows() == n, v.ncols() == k));
+    }
+
+    s.fill_zero();
+    let mut u = u;
+    let mut v = v;
+    for mut x in [u.rb_mut(), v.rb_mut()].into_iter().flatten() {
+        x.fill_zero();
+    }
+    if k == 0 {
+        return 0;
+    }
+
+    let (mut lanczos_u, stack) = temp_mat_zeroed::<E>(m, k + 1, stack);
+    let (mut lanczos_v, stack) = temp_mat_zeroed::<E>(n, k, stack);
+    let (mut panel, stack) = temp_mat_uninit::<E>(panel_rows(m, n), n, stack);
+    let (mut coeffs, stack) = temp_mat_uninit::<E>(k + 1, 1, stack);
+    let mut lanczos_u = lanczos_u.as_mut();
+    let mut lanczos_v = lanczos_v.as_mut();
+    let mut panel = panel.as_mut();
+    let mut coeffs = coeffs.as_mut();
+
+    let (diag, stack) = stack.make_with(k, |_| E::faer_zero());
+    let (subdiag, mut stack) = stack.make_with(k, |_| E::faer_zero());
+
+    // deterministic pseudorandom starting vector, so that the results are reproducible
+    let mut state = 0x853c49e6748fea9bu64;
+    for i in 0..m {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        let x = (state >> 11) as f64 / (1u64 << 53) as f64 - 0.5;
+        lanczos_u.write(i, 0, E::faer_from_f64(x));
+    }
+    let norm = lanczos_u.rb().col(0).norm_l2();
+    normalize(lanczos_u.rb_mut().col_mut(0).as_2d_mut(), norm);
+
+    // the process has found an invariant subspace once the norm of the new direction is
+    // negligible compared to the norm of the matrix, which is estimated by the largest entry of
+    // the bidiagonal matrix. the rounding errors in the lanczos vectors are amplified by the ratio
+    // of that norm to the entries of the bidiagonal matrix, so the residual of an exact invariant
+    // subspace can be well above `epsilon`. stopping there is harmless, since the dropped entry
+    // bounds the error of the computed singular triplets
+    let tol_factor = epsilon.faer_sqrt();
+    let mut anorm = E::faer_zero();
+    let negligible = |x: E, anorm: E| x <= zero_threshold || x <= tol_factor.faer_mul(anorm);
+
+    let mut r = 0;
+    'lanczos: for j in 0..k {
+        // alpha_j v_j = A^T u_j - beta_{j - 1} v_{j - 1}
+        {
+            let (v_prev, v_next) = lanczos_v.rb_mut().split_at_col_mut(j);
+            let mut q = v_next.col_mut(0).as_2d_mut();
+            mul_transpose_from_fn(
+                q.rb_mut(),
+                lanczos_u.rb().col(j).as_2d(),
+                panel.rb_mut(),
+                &f,
+                parallelism,
+            );
+            reorthogonalize(q.rb_mut(), v_prev.rb(), coeffs.rb_mut(), parallelism);
+            let alpha = q.rb().col(0).norm_l2();
+            if alpha > anorm {
+                anorm = alpha;
+            }
+            if negligible(alpha, anorm) {
+                break 'lanczos;
+            }
+            normalize(q, alpha);
+            diag[j] = alpha;
+            r = j + 1;
+        }
+
+        // beta_j u_{j + 1} = A v_j - alpha_j u_j
+        {
+            let (u_prev, u_next) = lanczos_u.rb_mut().split_at_col_mut(j + 1);
+            let mut p = u_next.col_mut(0).as_2d_mut();
+            mul_from_fn(
+                p.rb_mut(),
+                lanczos_v.rb().col(j).as_2d(),
+                panel.rb_mut(),
+                &f,
+                parallelism,
+            );
+            reorthogonalize(p.rb_mut(), u_prev.rb(), coeffs.rb_mut(), parallelism);
+            let beta = p.rb().col(0).norm_l2();
+            if beta > anorm {
+                anorm = beta;
+            }
+            if negligible(beta, anorm) {
+                // A v_j already lies in the span of the previous left vectors
+                p.fill_zero();
+                break 'lanczos;
+            }
+            normalize(p, beta);
+            subdiag[j] = beta;
+        }
+    }
+
+    if r == 0 {
+        return 0;
+    }
+
+    let compute_u = u.is_some();
+    let compute_v = v.is_some();
+    let (mut u_b, stack) =
+        temp_mat_uninit::<E>(r + 1, if compute_u { r } else { 0 }, stack.rb_mut());
+    let (mut v_b, mut stack) = temp_mat_uninit::<E>(r, if compute_v { r } else { 0 }, stack);
+    let mut u_b = u_b.as_mut();
+    let mut v_b = v_b.as_mut();
+
+    let diag = &mut diag[..r];
+    compute_bidiag_real_svd(
+        diag,
+        &mut subdiag[..r],
+        compute_u.then_some(u_b.rb_mut()),
+        compute_v.then_some(v_b.rb_mut()),
+        jacobi_fallback_threshold(params, compute_u || compute_v),
+        BIDIAG_QR_FALLBACK_THRESHOLD,
+        epsilon,
+        zero_threshold,
+        parallelism,
+        stack.rb_mut(),
+        params,
+    );
+
+    for (i, &x) in diag.iter().enumerate() {
+        s.write(i, 0, x);
+    }
+    if let Some(u) = u {
+        matmul(
+            u.subcols_mut(0, r),
+            lanczos_u.rb().subcols(0, r + 1),
+            u_b.rb(),
+            None,
+            E::faer_one(),
+            parallelism,
+        );
+    }
+    if let Some(v) = v {
+        matmul(
+            v.subcols_mut(0, r),
+            lanczos_v.rb().subcols(0, r),
+            v_b.rb(),
+            None,
+            E::faer_one(),
+            parallelism,
+        );
+    }
+
+    r
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{compute_svd, compute_svd_req, ComputeVectors};
+    use assert_approx_eq::assert_approx_eq;
+    use dyn_stack::GlobalPodBuffer;
+    use faer_core::{assert, Mat};
+
+    macro_rules! make_stack {
+        ($req: expr) => {
+            ::dyn_stack::PodStack::new(&mut ::dyn_stack::GlobalPodBuffer::new($req.unwrap()))
+        };
+    }
+
+    fn svd_from_mat(mat: &Mat<f64>, k: usize) -> (usize, Mat<f64>, Mat<f64>, Mat<f64>) {
+        let (m, n) = (mat.nrows(), mat.ncols());
+        let mut s = Mat::zeros(k, 1);
+        let mut u = Mat::zeros(m, k);
+        let mut v = Mat::zeros(n, k);
+        let r = svd_from_fn(
+            m,
+            n,
+            |i, j| mat.read(i, j),
+            s.as_mut(),
+            Some(u.as_mut()),
+            Some(v.as_mut()),
+            f64::EPSILON,
+            f64::MIN_POSITIVE,
+            Parallelism::None,
+            make_stack!(svd_from_fn_req::<f64>(
+                m,
+                n,
+                k,
+                true,
+                true,
+                Parallelism::None,
+                SvdParams::default()
+            )),
+            SvdParams::default(),
+        );
+        (r, s, u, v)
+    }
+
+    fn dense_singular_values(mat: &Mat<f64>) -> Mat<f64> {
+        let (m, n) = (mat.nrows(), mat.ncols());
+        let mut s = Mat::zeros(Ord::min(m, n), 1);
+        compute_svd(
+            mat.as_ref(),
+            s.as_mut(),
+            None,
+            None,
+            Parallelism::None,
+            make_stack!(compute_svd_req::<f64>(
+                m,
+                n,
+                ComputeVectors::No,
+                ComputeVectors::No,
+                Parallelism::None,
+                SvdParams::default(),
+            )),
+            SvdParams::default(),
+        );
+        s
+    }
+
+    fn check_orthonormal(q: MatRef<'_, f64>) {
+        let qtq = q.transpose() * q;
+        for j in 0..q.ncols() {
+            for i in 0..q.ncols() {
+                let target = if i == j { 1.0 } else { 0.0 };
+                assert_approx_eq!(qtq.read(i, j), target, 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_svd_from_fn_low_rank() {
+        for (m, n, rank, k) in [(300, 200, 5, 8), (200, 300, 5, 5), (120, 90, 1, 10)] {
+            let a = Mat::from_fn(m, rank, |_, _| rand::random::<f64>());
+            let b = Mat::from_fn(rank, n, |_, _| rand::random::<f64>());
+            let mat = &a * &b;
+
+            let (r, s, u, v) = svd_from_mat(&mat, k);
+            assert!(r == rank);
+
+            let s_dense = dense_singular_values(&mat);
+            for i in 0..k {
+                let target = if i < rank { s_dense.read(i, 0) } else { 0.0 };
+                assert_approx_eq!(s.read(i, 0), target, 1e-10 * s_dense.read(0, 0));
+                if i >= rank {
+                    assert!(u.as_ref().col(i).norm_l2() == 0.0);
+                    assert!(v.as_ref().col(i).norm_l2() == 0.0);
+                }
+            }
+
+            let u = u.as_ref().subcols(0, r);
+            let v = v.as_ref().subcols(0, r);
+            check_orthonormal(u);
+            check_orthonormal(v);
+
+            let s = Mat::from_fn(r, r, |i, j| if i == j { s.read(i, 0) } else { 0.0 });
+            let reconstructed = u * &s * v.transpose();
+            let tol = 1e-10 * s_dense.read(0, 0);
+            for j in 0..n {
+                for i in 0..m {
+                    assert_approx_eq!(reconstructed.read(i, j), mat.read(i, j), tol);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_svd_from_fn_full_rank() {
+        // running as many steps as the smallest dimension gives the full svd
+        let (m, n) = (40, 30);
+        let mat = Mat::from_fn(m, n, |_, _| rand::random::<f64>());
+        let (r, s, u, v) = svd_from_mat(&mat, n);
+        assert!(r == n);
+
+        let s_dense = dense_singular_values(&mat);
+        for i in 0..n {
+            assert_approx_eq!(s.read(i, 0), s_dense.read(i, 0), 1e-10);
+        }
+        check_orthonormal(u.as_ref());
+        check_orthonormal(v.as_ref());
+
+        let s = Mat::from_fn(n, n, |i, j| if i == j { s.read(i, 0) } else { 0.0 });
+        let reconstructed = &u * &s * v.transpose();
+        for j in 0..n {
+            for i in 0..m {
+                assert_approx_eq!(reconstructed.read(i, j), mat.read(i, j), 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_svd_from_fn_zero() {
+        let (r, s, u, v) = svd_from_mat(&Mat::zeros(20, 10), 4);
+        assert!(r == 0);
+        assert!(s.norm_max() == 0.0);
+        assert!(u.norm_max() == 0.0);
+        assert!(v.norm_max() == 0.0);
+    }
+
+    #[test]
+    #[ignore = "takes too long in CI"]
+    fn test_svd_from_fn_large() {
+        // rank 3 matrix of size 10000, which would take 800MB if stored densely
+        let n = 10000;
+        let k = 4;
+        let x = |i: usize| i as f64 / n as f64;
+        let f = |i: usize, j: usize| {
+            let (xi, xj) = (x(i), x(j));
+            1.0 + xi * xj + (xi * xj) * (xi * xj)
+        };
+
+        let mut s = Mat::zeros(k, 1);
+        let mut u = Mat::zeros(n, k);
+        let mut v = Mat::zeros(n, k);
+        let req =
+            svd_from_fn_req::<f64>(n, n, k, true, true, Parallelism::None, SvdParams::default())
+                .unwrap();
+        // the workspace only holds a few vectors and a panel of rows
+        let dense_bytes = n * n * core::mem::size_of::<f64>();
+        assert!(req.size_bytes() * 100 < dense_bytes);
+        let r = svd_from_fn(
+            n,
+            n,
+            f,
+            s.as_mut(),
+            Some(u.as_mut()),
+            Some(v.as_mut()),
+            f64::EPSILON,
+            f64::MIN_POSITIVE,
+            Parallelism::None,
+            PodStack::new(&mut GlobalPodBuffer::new(req)),
+            SvdParams::default(),
+        );
+        assert!(r == 3);
+
+        for (i, j) in [(0, 0), (17, 9000), (4321, 1234), (n - 1, n - 1)] {
+            let mut x = 0.0;
+            for l in 0..r {
+                x += u.read(i, l) * s.read(l, 0) * v.read(j, l);
+            }
+            assert_approx_eq!(x, f(i, j), 1e-10);
+        }
+    }
+}
@@ -8,7 +8,9 @@
 // Public License v. 2.0. If a copy of the MPL was not distributed
 // with this file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use faer_core::{assert, jacobi::JacobiRotation, permutation::swap_cols, MatMut, RealField};
+use faer_core::{
+    assert, jacobi::JacobiRotation, permutation::swap_cols, MatMut, MatRef, RealField,
+};
 use reborrow::*;
 
 fn compute_2x2<E: RealField>(
@@ -49,6 +51,11 @@ fn compute_2x2<E: RealField>(
     (j_left, j_right)
 }
 
+/// maximum number of sweeps of the one-sided jacobi algorithm, same as LAPACK's `dgesvj`. the
+/// columns are usually orthogonal after a handful of sweeps, so this is only reached when the
+/// convergence test can't pass, e.g. because of rounding errors in the last few bits
+pub(crate) const MAX_SWEEPS: usize = 30;
+
 pub enum Skip {
     None,
     First,
@@ -62,6 +69,35 @@ pub fn jacobi_svd<E: RealField>(
     skip: Skip,
     epsilon: E,
     consider_zero_threshold: E,
+) -> usize {
+    jacobi_svd_with_perm(
+        matrix,
+        u,
+        v,
+        None,
+        skip,
+        false,
+        epsilon,
+        consider_zero_threshold,
+    )
+}
+
+/// Same as [`jacobi_svd`], but also stores in `perm` the permutation applied when sorting the
+/// singular values, such that the `j`-th singular value comes from the `perm[j]`-th diagonal
+/// position of the (non skipped part of the) converged matrix.
+///
+/// If `accumulate_v` is true, the rotations are applied to the columns of `v` as they are,
+/// instead of being accumulated from the identity, so that `v` may have any number of rows and
+/// ends up multiplied by the right singular vectors. This is only supported with `Skip::None`.
+pub(crate) fn jacobi_svd_with_perm<E: RealField>(
+    matrix: MatMut<'_, E>,
+    u: Option<MatMut<'_, E>>,
+    v: Option<MatMut<'_, E>>,
+    mut perm: Option<&mut [usize]>,
+    skip: Skip,
+    accumulate_v: bool,
+    epsilon: E,
+    consider_zero_threshold: E,
 ) -> usize {
     assert!(matrix.nrows() == matrix.ncols());
     let n = matrix.nrows();
@@ -73,6 +109,7 @@ pub fn jacobi_svd<E: RealField>(
     if let Some(v) = v.rb() {
         assert!(n == v.ncols());
     }
+    assert!(!accumulate_v || matches!(skip, Skip::None));
 
     let mut matrix = matrix;
     let mut u = u;
@@ -90,7 +127,7 @@ pub fn jacobi_svd<E: RealField>(
         }
     }
 
-    if let Some(mut v) = v.rb_mut() {
+    if let (Some(mut v), false) = (v.rb_mut(), accumulate_v) {
         if matches!(skip, Skip::First) {
             for i in 0..n - 1 {
                 v.rb_mut().write(i, 0, E::faer_zero());
@@ -212,6 +249,13 @@ pub fn jacobi_svd<E: RealField>(
     });
 
     let n = new_n;
+    if let Some(perm) = perm.as_deref_mut() {
+        assert!(perm.len() == n);
+        for (i, p) in perm.iter_mut().enumerate() {
+            *p = i;
+        }
+    }
+
     let mut nnz_count = n;
     for i in 0..n {
         let mut largest_elem = E::faer_zero();
@@ -240,8 +284,256 @@ pub fn jacobi_svd<E: RealField>(
             if let Some(v) = v.rb_mut() {
                 swap_cols(v, i, largest_pos);
             }
+            if let Some(perm) = perm.as_deref_mut() {
+                perm.swap(i, largest_pos);
+            }
+        }
+    }
+    nnz_count
+}
+
+/// One-sided (Hestenes) Jacobi SVD of a matrix with at least as many rows as columns.
+///
+/// Column pairs are rotated until they are numerically orthogonal, so that the singular values
+/// are the column norms. Since this is invariant under column scaling, the singular values of
+/// matrices with badly scaled columns are computed to high relative accuracy.
+///
+/// On exit, `matrix` holds the singular values on its diagonal and zeros elsewhere, `u` holds the
+/// (thin) left singular vectors and `v` the right singular vectors. Returns the number of nonzero
+/// singular values.
+///
+/// At most `30` sweeps are performed. If `matrix` contains infinite or NaN values, the sweeps are
+/// skipped, and `matrix`, `u` and `v` are filled with NaN values instead.
+pub fn jacobi_svd_one_sided<E: RealField>(
+    matrix: MatMut<'_, E>,
+    u: Option<MatMut<'_, E>>,
+    v: Option<MatMut<'_, E>>,
+    epsilon: E,
+    consider_zero_threshold: E,
+) -> usize {
+    let m = matrix.nrows();
+    let n = matrix.ncols();
+    assert!(m >= n);
+
+    if let Some(u) = u.rb() {
+        assert!(all(u.nrows() == m, u.ncols() == n));
+    };
+    if let Some(v) = v.rb() {
+        assert!(all(v.nrows() == n, v.ncols() == n));
+    }
+
+    let mut matrix = matrix;
+    let mut u = u;
+    let mut v = v;
+
+    // the convergence test never passes once a NaN value shows up in the inner products
+    if !matrix.rb().is_all_finite() {
+        for mut x in [Some(matrix.rb_mut()), u.rb_mut(), v.rb_mut()]
+            .into_iter()
+            .flatten()
+        {
+            for j in 0..x.ncols() {
+                for i in 0..x.nrows() {
+                    x.write(i, j, E::faer_nan());
+                }
+            }
+        }
+        return 0;
+    }
+
+    if let Some(mut v) = v.rb_mut() {
+        for j in 0..n {
+            for i in 0..n {
+                v.write(
+                    i,
+                    j,
+                    if i == j {
+                        E::faer_one()
+                    } else {
+                        E::faer_zero()
+                    },
+                );
+            }
+        }
+    }
+
+    let dot = |x: MatRef<'_, E>, y: MatRef<'_, E>| {
+        let mut acc = E::faer_zero();
+        for i in 0..x.nrows() {
+            acc = acc.faer_add(x.read(i, 0).faer_mul(y.read(i, 0)));
+        }
+        acc
+    };
+    // inner product of `x / norm_x` and `y / norm_y`
+    let scaled_dot = |x: MatRef<'_, E>, y: MatRef<'_, E>, norm_x: E, norm_y: E| {
+        let mut acc = E::faer_zero();
+        for i in 0..x.nrows() {
+            acc = acc.faer_add(
+                x.read(i, 0)
+                    .faer_div(norm_x)
+                    .faer_mul(y.read(i, 0).faer_div(norm_y)),
+            );
+        }
+        acc
+    };
+
+    let one = E::faer_one();
+    let two = one.faer_add(one);
+    // same tolerance as LAPACK's `dgesvj`
+    let tol = epsilon.faer_mul(E::faer_from_f64(m as f64).faer_sqrt());
+    for _ in 0..MAX_SWEEPS {
+        let mut failed = false;
+        for p in 0..n {
+            for q in p + 1..n {
+                let col_p = matrix.rb().col(p).as_2d();
+                let col_q = matrix.rb().col(q).as_2d();
+                let norm_p = col_p.norm_l2();
+                let norm_q = col_q.norm_l2();
+                if norm_p <= consider_zero_threshold || norm_q <= consider_zero_threshold {
+                    continue;
+                }
+
+                // scale the columns before computing the inner product to avoid overflow
+                let gamma = scaled_dot(col_p, col_q, norm_p, norm_q);
+                if gamma.faer_abs() <= tol {
+                    continue;
+                }
+                failed = true;
+
+                // zeta = (norm_q^2 - norm_p^2) / (2 * gamma * norm_p * norm_q)
+                let zeta = (norm_q.faer_div(norm_p).faer_sub(norm_p.faer_div(norm_q)))
+                    .faer_div(two.faer_mul(gamma));
+                let abs_zeta = zeta.faer_abs();
+                let hypot = if abs_zeta > one {
+                    abs_zeta.faer_mul(one.faer_add(abs_zeta.faer_inv().faer_abs2()).faer_sqrt())
+                } else {
+                    one.faer_add(abs_zeta.faer_abs2()).faer_sqrt()
+                };
+                let t = abs_zeta.faer_add(hypot).faer_inv();
+                let t = if zeta < E::faer_zero() {
+                    t.faer_neg()
+                } else {
+                    t
+                };
+                let c = one.faer_add(t.faer_abs2()).faer_sqrt().faer_inv();
+                let rot = JacobiRotation {
+                    c,
+                    s: c.faer_mul(t),
+                };
+
+                let (left, right) = matrix.rb_mut().split_at_col_mut(q);
+                rot.apply_on_the_right_in_place(
+                    left.col_mut(p).as_2d_mut(),
+                    right.col_mut(0).as_2d_mut(),
+                );
+                if let Some(v) = v.rb_mut() {
+                    let (left, right) = v.split_at_col_mut(q);
+                    rot.apply_on_the_right_in_place(
+                        left.col_mut(p).as_2d_mut(),
+                        right.col_mut(0).as_2d_mut(),
+                    );
+                }
+            }
+        }
+        if !failed {
+            break;
+        }
+    }
+
+    // the singular values are the column norms, and the normalized columns are the left singular
+    // vectors
+    for j in 0..n {
+        let norm = matrix.rb().col(j).as_2d().norm_l2();
+        if let Some(mut u) = u.rb_mut() {
+            let inv = if norm > consider_zero_threshold {
+                norm.faer_inv()
+            } else {
+                E::faer_zero()
+            };
+            for i in 0..m {
+                u.write(i, j, matrix.read(i, j).faer_mul(inv));
+            }
+        }
+        for i in 0..m {
+            matrix.write(i, j, E::faer_zero());
+        }
+        matrix.write(j, j, norm);
+    }
+
+    // sort singular values and count nonzero ones
+    let mut nnz_count = n;
+    for i in 0..n {
+        let mut largest_elem = E::faer_zero();
+        let mut largest_pos = i;
+
+        for j in i..n {
+            let mjj = matrix.read(j, j);
+            (largest_elem, largest_pos) = if mjj > largest_elem {
+                (mjj, j)
+            } else {
+                (largest_elem, largest_pos)
+            };
+        }
+
+        if largest_elem <= consider_zero_threshold && nnz_count == n {
+            nnz_count = i;
+        }
+
+        if largest_pos > i {
+            let mii = matrix.read(i, i);
+            matrix.write(i, i, largest_elem);
+            matrix.write(largest_pos, largest_pos, mii);
+            if let Some(u) = u.rb_mut() {
+                swap_cols(u, i, largest_pos);
+            }
+            if let Some(v) = v.rb_mut() {
+                swap_cols(v, i, largest_pos);
+            }
+        }
+    }
+
+    // the left singular vectors of the (numerically) zero singular values are undetermined, so we
+    // complete the basis with orthonormalized unit vectors. the unit vector that is the furthest
+    // away from the current basis is the one whose row in the basis has the smallest norm
+    if let Some(mut u) = u.rb_mut() {
+        for j in nnz_count..n {
+            let mut best_pos = 0;
+            let mut best_norm = E::faer_zero().faer_inv();
+            for i in 0..m {
+                let norm = u.rb().row(i).subcols(0, j).as_2d().norm_l2();
+                if norm < best_norm {
+                    (best_pos, best_norm) = (i, norm);
+                }
+            }
+
+            for i in 0..m {
+                u.write(i, j, E::faer_zero());
+            }
+            u.write(best_pos, j, E::faer_one());
+
+            // two passes of modified gram-schmidt
+            for _ in 0..2 {
+                for k in 0..j {
+                    let (left, mut right) = u.rb_mut().split_at_col_mut(j);
+                    let basis = left.rb().col(k).as_2d();
+                    let proj = dot(basis, right.rb().col(0).as_2d());
+                    for i in 0..m {
+                        right.write(
+                            i,
+                            0,
+                            right.read(i, 0).faer_sub(proj.faer_mul(basis.read(i, 0))),
+                        );
+                    }
+                }
+            }
+
+            let inv = u.rb().col(j).as_2d().norm_l2().faer_inv();
+            for i in 0..m {
+                u.write(i, j, u.read(i, j).faer_mul(inv));
+            }
         }
     }
+
     nnz_count
 }
 
@@ -390,6 +682,123 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_jacobi_one_sided() {
+        for (m, n) in [
+            (0, 0),
+            (1, 1),
+            (2, 2),
+            (4, 4),
+            (8, 8),
+            (15, 10),
+            (16, 16),
+            (31, 31),
+        ] {
+            for rank in [n, n / 2] {
+                let mat =
+                    Mat::<f64>::from_fn(
+                        m,
+                        n,
+                        |_, j| {
+                            if j < rank {
+                                rand::random::<f64>()
+                            } else {
+                                0.0
+                            }
+                        },
+                    );
+
+                let mut s = mat.clone();
+                let mut u = Mat::<f64>::zeros(m, n);
+                let mut v = Mat::<f64>::zeros(n, n);
+
+                let nnz = jacobi_svd_one_sided(
+                    s.as_mut(),
+                    Some(u.as_mut()),
+                    Some(v.as_mut()),
+                    f64::EPSILON,
+                    f64::MIN_POSITIVE,
+                );
+                assert!(nnz == rank);
+                if m == n {
+                    check_svd(mat.as_ref(), u.as_ref(), v.as_ref(), s.as_ref());
+                } else {
+                    let reconstructed = &u * s.as_ref().submatrix(0, 0, n, n) * v.transpose();
+                    let utu = u.transpose() * &u;
+                    for j in 0..n {
+                        for i in 0..m {
+                            assert_approx_eq!(reconstructed.read(i, j), mat.read(i, j));
+                        }
+                        for i in 0..n {
+                            let target = if i == j { 1.0 } else { 0.0 };
+                            assert_approx_eq!(utu.read(i, j), target);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_jacobi_one_sided_large_columns() {
+        // the inner products of these columns overflow unless they're normalized first
+        let (m, n) = (8, 4);
+        let scale = 1e200;
+        let mat = Mat::<f64>::from_fn(m, n, |_, _| scale * rand::random::<f64>());
+
+        let mut s = mat.clone();
+        let mut u = Mat::<f64>::zeros(m, n);
+        let mut v = Mat::<f64>::zeros(n, n);
+        let nnz = jacobi_svd_one_sided(
+            s.as_mut(),
+            Some(u.as_mut()),
+            Some(v.as_mut()),
+            f64::EPSILON,
+            f64::MIN_POSITIVE,
+        );
+        assert!(nnz == n);
+
+        let reconstructed = &u * s.as_ref().submatrix(0, 0, n, n) * v.transpose();
+        let utu = u.transpose() * &u;
+        let vtv = v.transpose() * &v;
+        for j in 0..n {
+            assert!(s.read(j, j).is_finite());
+            for i in 0..m {
+                assert_approx_eq!(reconstructed.read(i, j) / scale, mat.read(i, j) / scale);
+            }
+            for i in 0..n {
+                let target = if i == j { 1.0 } else { 0.0 };
+                assert_approx_eq!(utu.read(i, j), target);
+                assert_approx_eq!(vtv.read(i, j), target);
+            }
+        }
+    }
+
+    #[test]
+    fn test_jacobi_one_sided_non_finite() {
+        for bad in [f64::NAN, f64::INFINITY] {
+            let n = 4;
+            let mut s = Mat::<f64>::from_fn(n, n, |i, j| if (i, j) == (1, 2) { bad } else { 1.0 });
+            let mut u = Mat::<f64>::zeros(n, n);
+            let mut v = Mat::<f64>::zeros(n, n);
+            let nnz = jacobi_svd_one_sided(
+                s.as_mut(),
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+            );
+            assert!(nnz == 0);
+            for j in 0..n {
+                assert!(s.read(j, j).is_nan());
+                for i in 0..n {
+                    assert!(u.read(i, j).is_nan());
+                    assert!(v.read(i, j).is_nan());
+                }
+            }
+        }
+    }
+
     #[test]
     fn eigen_286() {
         let mat = faer_core::mat![[-7.90884e-313, -4.94e-324], [0.0, 5.60844e-313]];
@@ -30,17 +30,59 @@ use faer_core::{
 use num_complex::Complex;
 use reborrow::*;
 
-use crate::bidiag_real_svd::compute_bidiag_real_svd;
+use crate::bidiag_real_svd::{compute_bidiag_real_svd_with_stats, BidiagSvdStats};
 
+extern crate alloc;
+
+mod decomposition;
+mod embedding;
+mod fixed;
+mod from_fn;
+mod plan;
+pub use decomposition::SvdDecomposition;
+pub use embedding::complex_svd_via_real_embedding;
+pub use fixed::svd_fixed;
+pub use from_fn::{svd_from_fn, svd_from_fn_req};
+pub use plan::{SvdPlan, SvdTriplets};
+
+pub use bidiag_real_svd::{reconstruct_lowner_vector, wilkinson_shift_2x2};
+
+pub mod banded;
 #[doc(hidden)]
 pub mod bidiag;
 #[doc(hidden)]
 pub mod bidiag_real_svd;
 #[doc(hidden)]
 pub mod jacobi;
+#[cfg(feature = "std")]
+pub mod testing;
 
 const JACOBI_FALLBACK_THRESHOLD: usize = 4;
 const BIDIAG_QR_FALLBACK_THRESHOLD: usize = 128;
+const PARALLEL_THRESHOLD: usize = 256;
+
+/// size of the subproblems below which the divide and conquer bidiagonal svd switches to the
+/// jacobi algorithm, taken from `params` if provided
+fn jacobi_fallback_threshold(params: SvdParams, compute_vectors: bool) -> usize {
+    if compute_vectors {
+        params.leaf_size_vectors
+    } else {
+        params.leaf_size_values
+    }
+    .unwrap_or(JACOBI_FALLBACK_THRESHOLD)
+}
+
+/// whether a real matrix is decomposed by the jacobi algorithm applied to its qr decomposition,
+/// instead of going through its bidiagonalization
+fn use_real_svd_small(size: usize, algorithm: SvdAlgorithm) -> bool {
+    match algorithm {
+        SvdAlgorithm::Auto => size <= JACOBI_FALLBACK_THRESHOLD,
+        // the closed form only applies to the bidiagonal matrix, which is skipped otherwise
+        SvdAlgorithm::ClosedForm => size != 2 && size <= JACOBI_FALLBACK_THRESHOLD,
+        SvdAlgorithm::OneSidedJacobi => true,
+        _ => false,
+    }
+}
 
 /// Indicates whether the singular vectors are fully computed, partially computed, or skipped.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -108,6 +150,7 @@ fn compute_svd_big_req<E: Entity>(
         parallelism: Parallelism,
     ) -> Result<StackReq, SizeOverflow>,
     parallelism: Parallelism,
+    params: SvdParams,
 ) -> Result<StackReq, SizeOverflow> {
     assert!(m >= n);
     let householder_blocksize = faer_qr::no_pivoting::compute::recommended_blocksize::<E>(m, n);
@@ -127,7 +170,7 @@ fn compute_svd_big_req<E: Entity>(
 
     let compute_bidiag_svd = bidiag_svd_req(
         n,
-        JACOBI_FALLBACK_THRESHOLD,
+        jacobi_fallback_threshold(params, compute_ub || compute_vb),
         compute_ub,
         compute_vb,
         parallelism,
@@ -180,6 +223,7 @@ fn compute_real_svd_small<E: RealField>(
     zero_threshold: E,
     parallelism: Parallelism,
     stack: PodStack<'_>,
+    params: SvdParams,
 ) {
     let mut u = u;
     let mut v = v;
@@ -196,14 +240,18 @@ fn compute_real_svd_small<E: RealField>(
         zipped!(jacobi_mat.rb_mut(), matrix)
             .for_each(|unzipped!(mut dst, src)| dst.write(src.read()));
 
-        jacobi::jacobi_svd(
-            jacobi_mat.rb_mut(),
-            u,
-            v,
-            jacobi::Skip::None,
-            epsilon,
-            zero_threshold,
-        );
+        if params.algorithm == SvdAlgorithm::OneSidedJacobi {
+            jacobi::jacobi_svd_one_sided(jacobi_mat.rb_mut(), u, v, epsilon, zero_threshold);
+        } else {
+            jacobi::jacobi_svd(
+                jacobi_mat.rb_mut(),
+                u,
+                v,
+                jacobi::Skip::None,
+                epsilon,
+                zero_threshold,
+            );
+        }
         zipped!(s, jacobi_mat.rb().diagonal().column_vector().as_2d())
             .for_each(|unzipped!(mut dst, src)| dst.write(src.read()));
         return;
@@ -238,14 +286,24 @@ fn compute_real_svd_small<E: RealField>(
             });
 
         // r = u s v
-        jacobi::jacobi_svd(
-            r.rb_mut(),
-            u.rb_mut().map(|u| u.submatrix_mut(0, 0, n, n)),
-            v.rb_mut(),
-            jacobi::Skip::None,
-            epsilon,
-            zero_threshold,
-        );
+        if params.algorithm == SvdAlgorithm::OneSidedJacobi {
+            jacobi::jacobi_svd_one_sided(
+                r.rb_mut(),
+                u.rb_mut().map(|u| u.submatrix_mut(0, 0, n, n)),
+                v.rb_mut(),
+                epsilon,
+                zero_threshold,
+            );
+        } else {
+            jacobi::jacobi_svd(
+                r.rb_mut(),
+                u.rb_mut().map(|u| u.submatrix_mut(0, 0, n, n)),
+                v.rb_mut(),
+                jacobi::Skip::None,
+                epsilon,
+                zero_threshold,
+            );
+        }
         zipped!(s, r.rb().diagonal().column_vector().as_2d())
             .for_each(|unzipped!(mut dst, src)| dst.write(src.read()));
     }
@@ -283,12 +341,14 @@ fn compute_bidiag_cplx_svd<E: ComplexField>(
     subdiag: &mut [E],
     mut u: Option<MatMut<'_, E>>,
     mut v: Option<MatMut<'_, E>>,
+    stats: &mut BidiagSvdStats,
     jacobi_fallback_threshold: usize,
     bidiag_qr_fallback_threshold: usize,
     epsilon: E::Real,
     consider_zero_threshold: E::Real,
     parallelism: Parallelism,
     stack: PodStack<'_>,
+    params: SvdParams,
 ) {
     let n = diag.len();
     let (mut u_real, stack) =
@@ -323,17 +383,19 @@ fn compute_bidiag_cplx_svd<E: ComplexField>(
         col_mul[i] = col_normalized;
     }
 
-    compute_bidiag_real_svd::<E::Real>(
+    compute_bidiag_real_svd_with_stats::<E::Real>(
         diag_real,
         subdiag_real,
         u.is_some().then_some(u_real.rb_mut()),
         v.is_some().then_some(v_real.rb_mut()),
+        stats,
         jacobi_fallback_threshold,
         bidiag_qr_fallback_threshold,
         epsilon,
         consider_zero_threshold,
         parallelism,
         stack,
+        params,
     );
 
     for i in 0..n {
@@ -411,18 +473,21 @@ fn compute_svd_big<E: ComplexField>(
         subdiag: &mut [E],
         u: Option<MatMut<'_, E>>,
         v: Option<MatMut<'_, E>>,
+        stats: &mut BidiagSvdStats,
         jacobi_fallback_threshold: usize,
         bidiag_qr_fallback_threshold: usize,
         epsilon: E::Real,
         consider_zero_threshold: E::Real,
         parallelism: Parallelism,
         stack: PodStack<'_>,
+        params: SvdParams,
     ),
     epsilon: E::Real,
     zero_threshold: E::Real,
     parallelism: Parallelism,
     stack: PodStack<'_>,
-) {
+    params: SvdParams,
+) -> BidiagSvdStats {
     let mut stack = stack;
 
     assert!(matrix.nrows() >= matrix.ncols());
@@ -439,6 +504,81 @@ fn compute_svd_big<E: ComplexField>(
         temp_mat_uninit::<E>(householder_blocksize, n - 1, stack);
     let mut householder_right = householder_right.as_mut();
 
+    bidiagonalize_with_householder_factors(
+        matrix,
+        bid.rb_mut(),
+        householder_left.rb_mut(),
+        householder_right.rb_mut(),
+        parallelism,
+        stack.rb_mut(),
+    );
+
+    let bid = bid.into_const();
+
+    let (diag, stack) = stack.make_with(n, |i| bid.read(i, i).faer_conj());
+    let (subdiag, stack) = stack.make_with(n, |i| {
+        if i < n - 1 {
+            bid.read(i, i + 1).faer_conj()
+        } else {
+            E::faer_zero()
+        }
+    });
+
+    let (mut u_b, stack) = temp_mat_uninit::<E>(if v.is_some() { n + 1 } else { 0 }, n + 1, stack);
+    let mut u_b = u_b.as_mut();
+    let (mut v_b, mut stack) = temp_mat_uninit::<E>(n, if u.is_some() { n } else { 0 }, stack);
+    let mut v_b = v_b.as_mut();
+
+    let mut stats = BidiagSvdStats::default();
+    bidiag_svd(
+        diag,
+        subdiag,
+        v.is_some().then_some(u_b.rb_mut()),
+        u.is_some().then_some(v_b.rb_mut()),
+        &mut stats,
+        jacobi_fallback_threshold(params, u.is_some() || v.is_some()),
+        BIDIAG_QR_FALLBACK_THRESHOLD,
+        epsilon,
+        zero_threshold,
+        parallelism,
+        stack.rb_mut(),
+        params,
+    );
+
+    for (idx, &diag) in diag.iter().enumerate() {
+        s.write(idx, 0, diag);
+    }
+
+    apply_bidiag_householder_sequences(
+        bid,
+        householder_left.rb(),
+        householder_right.rb(),
+        u_b.rb(),
+        v_b.rb(),
+        u,
+        v,
+        parallelism,
+        stack,
+    );
+
+    stats
+}
+
+/// copies `matrix` into `bid` and bidiagonalizes it in place, storing the block householder
+/// factors of the left and right householder sequences in `householder_left` and
+/// `householder_right`
+fn bidiagonalize_with_householder_factors<E: ComplexField>(
+    matrix: MatRef<'_, E>,
+    mut bid: MatMut<'_, E>,
+    mut householder_left: MatMut<'_, E>,
+    mut householder_right: MatMut<'_, E>,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+) {
+    let m = matrix.nrows();
+    let n = matrix.ncols();
+    let householder_blocksize = householder_left.nrows();
+
     zipped!(bid.rb_mut(), matrix).for_each(|unzipped!(mut dst, src)| dst.write(src.read()));
 
     bidiag::bidiagonalize_in_place(
@@ -454,20 +594,11 @@ fn compute_svd_big<E: ComplexField>(
             .transpose_mut()
             .as_2d_mut(),
         parallelism,
-        stack.rb_mut(),
+        stack,
     );
 
     let bid = bid.into_const();
 
-    let (diag, stack) = stack.make_with(n, |i| bid.read(i, i).faer_conj());
-    let (subdiag, stack) = stack.make_with(n, |i| {
-        if i < n - 1 {
-            bid.read(i, i + 1).faer_conj()
-        } else {
-            E::faer_zero()
-        }
-    });
-
     let mut j_base = 0;
     while j_base < n {
         let bs = Ord::min(householder_blocksize, n - j_base);
@@ -491,34 +622,29 @@ fn compute_svd_big<E: ComplexField>(
         upgrade_householder_factor(householder, essentials, bs, 1, parallelism);
         j_base += bs;
     }
+}
 
-    let (mut u_b, stack) = temp_mat_uninit::<E>(if v.is_some() { n + 1 } else { 0 }, n + 1, stack);
-    let mut u_b = u_b.as_mut();
-    let (mut v_b, mut stack) = temp_mat_uninit::<E>(n, if u.is_some() { n } else { 0 }, stack);
-    let mut v_b = v_b.as_mut();
-
-    bidiag_svd(
-        diag,
-        subdiag,
-        v.is_some().then_some(u_b.rb_mut()),
-        u.is_some().then_some(v_b.rb_mut()),
-        JACOBI_FALLBACK_THRESHOLD,
-        BIDIAG_QR_FALLBACK_THRESHOLD,
-        epsilon,
-        zero_threshold,
-        parallelism,
-        stack.rb_mut(),
-    );
-
-    for (idx, &diag) in diag.iter().enumerate() {
-        s.write(idx, 0, diag);
-    }
+/// computes the singular vectors `u` and `v` of the matrix bidiagonalized in `bid`, from the left
+/// and right singular vectors `u_b` and `v_b` of the adjoint of its bidiagonal factor
+fn apply_bidiag_householder_sequences<E: ComplexField>(
+    bid: MatRef<'_, E>,
+    householder_left: MatRef<'_, E>,
+    householder_right: MatRef<'_, E>,
+    u_b: MatRef<'_, E>,
+    v_b: MatRef<'_, E>,
+    u: Option<MatMut<'_, E>>,
+    v: Option<MatMut<'_, E>>,
+    parallelism: Parallelism,
+    mut stack: PodStack<'_>,
+) {
+    let m = bid.nrows();
+    let n = bid.ncols();
 
     if let Some(mut u) = u {
         let ncols = u.ncols();
         zipped!(
             u.rb_mut().submatrix_mut(0, 0, n, n),
-            v_b.rb().submatrix(0, 0, n, n),
+            v_b.submatrix(0, 0, n, n),
         )
         .for_each(|unzipped!(mut dst, src)| dst.write(src.read()));
 
@@ -536,7 +662,7 @@ fn compute_svd_big<E: ComplexField>(
 
         apply_block_householder_sequence_on_the_left_in_place_with_conj(
             bid,
-            householder_left.rb(),
+            householder_left,
             Conj::No,
             u,
             parallelism,
@@ -546,7 +672,7 @@ fn compute_svd_big<E: ComplexField>(
     if let Some(mut v) = v {
         zipped!(
             v.rb_mut().submatrix_mut(0, 0, n, n),
-            u_b.rb().submatrix(0, 0, n, n),
+            u_b.submatrix(0, 0, n, n),
         )
         .for_each(|unzipped!(mut dst, src)| dst.write(src.read()));
 
@@ -563,7 +689,7 @@ fn compute_svd_big<E: ComplexField>(
 
         apply_block_householder_sequence_on_the_left_in_place_with_conj(
             bid_col_major.rb(),
-            householder_right.rb(),
+            householder_right,
             Conj::No,
             v.submatrix_mut(1, 0, n - 1, n),
             parallelism,
@@ -572,9 +698,308 @@ fn compute_svd_big<E: ComplexField>(
     }
 }
 
-#[derive(Default, Copy, Clone)]
+/// Algorithm used to compute the singular value decomposition.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SvdAlgorithm {
+    /// Selects the algorithm depending on the size of the matrix.
+    #[default]
+    Auto,
+    /// One-sided Jacobi, applied to the triangular factor of the QR decomposition of the matrix.
+    ///
+    /// Slower than the default, but computes the singular values of matrices with badly scaled
+    /// columns to high relative accuracy. Currently only used for real matrices, complex matrices
+    /// fall back to [`SvdAlgorithm::Auto`].
+    OneSidedJacobi,
+    /// Jacobi algorithm applied to the bidiagonal matrix, regardless of its size.
+    ///
+    /// This and the following variants are meant for benchmarks and accuracy studies, since the
+    /// automatic selection is usually faster.
+    Jacobi,
+    /// Implicitly shifted QR iteration applied to the bidiagonal matrix, regardless of its size.
+    QrIteration,
+    /// Divide and conquer algorithm applied to the bidiagonal matrix, regardless of its size.
+    ///
+    /// The subproblems below the leaf size are still solved with the Jacobi algorithm, as well as
+    /// matrices with fewer than 3 columns, which can't be split.
+    DivideConquer,
+    /// Closed form SVD of the bidiagonal matrix, when it has size 2, without any iteration.
+    ///
+    /// This is what [`SvdAlgorithm::Auto`] uses for bidiagonal matrices of size 2, but it
+    /// decomposes small dense matrices with the Jacobi algorithm without bidiagonalizing them,
+    /// while this variant always goes through the bidiagonal matrix when the smaller dimension is
+    /// 2. Like [`SvdAlgorithm::DivideConquer`] for the matrices it can't split, the other sizes
+    /// fall back to [`SvdAlgorithm::Auto`].
+    ClosedForm,
+}
+
+/// Order of the singular values computed by the SVD, and of the corresponding singular vectors.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SvdSort {
+    /// Nonincreasing order. The relative order of equal singular values is unspecified.
+    #[default]
+    Descending,
+    /// Nondecreasing order, i.e., the reverse of [`SvdSort::Descending`].
+    Ascending,
+    /// Nonincreasing order, with a canonical sign for each pair of singular vectors and a fixed
+    /// order among equal singular values.
+    ///
+    /// The singular vectors are multiplied by a unit scalar so that the entry of largest magnitude
+    /// of the right singular vector is real and positive. The singular vectors of singular values
+    /// that are equal up to the accuracy of the decomposition are then sorted by the index of
+    /// that entry, while the singular values themselves stay in nonincreasing order. If $V$ isn't
+    /// computed, $U$ is used instead.
+    StableDescending,
+}
+
+/// Column at which the divide and conquer bidiagonal SVD splits each problem into two
+/// subproblems.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SplitStrategy {
+    /// Splits each problem into two halves.
+    #[default]
+    Half,
+    /// Splits each problem in the middle, unless a column of its middle half has a negligible
+    /// coupling with its neighbors, in which case the split closest to the middle among those is
+    /// chosen.
+    ///
+    /// The merge step then deflates almost all of the singular values, so this reduces the
+    /// number of secular equations to solve when the large entries of the matrix are concentrated
+    /// in blocks that are separated by tiny subdiagonal entries. The split is only moved if the
+    /// workspace of the two subproblems fits in the one provided for the problem.
+    ByMass,
+}
+
+/// How the divide and conquer bidiagonal SVD distributes the work of its merge steps when
+/// parallelism is enabled.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MergeParallelism {
+    /// The products that combine the singular vectors of the subproblems are split into parallel
+    /// tasks, each of which runs a multithreaded matrix multiplication.
+    #[default]
+    Nested,
+    /// The products that combine the singular vectors of the subproblems run on a single thread,
+    /// while the subproblems themselves are still solved in parallel.
+    ///
+    /// This avoids nesting multithreaded matrix multiplications inside the parallel recursion,
+    /// which can oversubscribe the thread pool and end up slower than single threaded products.
+    Sequential,
+}
+
+/// How the divide and conquer bidiagonal SVD handles a secular equation whose initial bracket
+/// doesn't contain its root.
+///
+/// This can only happen when the evaluations of the secular equation are dominated by rounding
+/// errors, for example on nearly degenerate inputs whose tiny off-diagonal entries escaped
+/// deflation.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SecularBracketMode {
+    /// The bracket is widened to the whole interval between the two poles that enclose the root,
+    /// which may cost a few more iterations and slightly reduce the accuracy of the result.
+    ///
+    /// If no bracket can be found at all, the singular value is set to NaN.
+    #[default]
+    Permissive,
+    /// Panics instead, which is useful to detect the inputs that trigger the issue.
+    Strict,
+}
+
+/// Formula used by the bidiagonal QR algorithm to compute its shift, the eigenvalue of the
+/// trailing $2 \times 2$ block of $B^\top B$ that is closest to its last diagonal entry.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ShiftStrategy {
+    /// The eigenvalue is computed by [`wilkinson_shift_2x2`], as a correction of the last
+    /// diagonal entry that is accurate relative to the size of the correction.
+    #[default]
+    Wilkinson,
+    /// Both eigenvalues are computed from the discriminant of the characteristic polynomial of
+    /// the block, and the one closest to the last diagonal entry is picked.
+    ///
+    /// This is accurate relative to the norm of the block, which can round the shift differently
+    /// when the off-diagonal entry is tiny, and is mostly useful to compare the convergence of the
+    /// QR algorithm with the two formulas. When the discriminant isn't positive, which only
+    /// happens for a double eigenvalue or because of rounding errors, the last diagonal entry is
+    /// used instead.
+    Discriminant,
+}
+
+/// Criterion used by the bidiagonal QR algorithm to decide which entries of the bidiagonal
+/// matrix are negligible.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConvergenceCriterion {
+    /// An off-diagonal entry is neglected when it's small compared to its two neighbors on the
+    /// diagonal, or compared to the largest entry of the matrix, and a diagonal entry when it's
+    /// small compared to the largest entry of the matrix.
+    ///
+    /// The singular values are then accurate in the absolute sense, relative to the largest one.
+    #[default]
+    RelativeOrAbsolute,
+    /// An off-diagonal entry is only neglected when it's small compared to its two neighbors on
+    /// the diagonal, and the diagonal entries are never neglected.
+    ///
+    /// This keeps the entries that are tiny compared to the largest one, but significant for the
+    /// small singular values of the block they're in, so that a block of tiny entries that is
+    /// decoupled from the large ones is decomposed as accurately as if it was scaled up. This
+    /// can take more iterations.
+    Relative,
+}
+
+/// Errors that can occur when computing the singular value decomposition with
+/// [`try_compute_svd`] or [`try_compute_svd_custom_epsilon`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SvdError {
+    /// The matrix contains infinite or NaN values.
+    NonFinite,
+    /// The dimensions of `s`, `u` or `v` don't match the ones of the matrix.
+    ShapeMismatch,
+    /// The QR iteration reached its iteration limit before all the singular values converged.
+    NotConverged,
+    /// The secular equation of one of the singular values couldn't be solved. See
+    /// [`SecularBracketMode`].
+    SecularDivergence,
+    /// The size of the workspace overflows, or the provided workspace is too small.
+    Alloc,
+    /// A merge step of the divide and conquer algorithm received an arrow matrix whose first
+    /// column has a nonzero entry in a row where its diagonal is zero. The entries of the first
+    /// column must be zeroed along with the corresponding diagonal entries when they are deflated.
+    InconsistentInput,
+    /// The decomposition was computed, but the residual of one of its singular triplets is above
+    /// the tolerance. This is only checked when [`SvdParams::verify`] is set.
+    VerificationFailed,
+}
+
+impl core::fmt::Display for SvdError {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SvdError {}
+
+/// SVD tuning parameters.
+#[derive(Default, Copy, Clone, Debug)]
 #[non_exhaustive]
-pub struct SvdParams {}
+pub struct SvdParams {
+    /// Algorithm used to compute the decomposition
+    pub algorithm: SvdAlgorithm,
+    /// Subproblem size below which the divide and conquer recursion and the secular equation
+    /// solves of its merge steps run sequentially, even when parallelism is enabled
+    pub parallel_threshold: Option<usize>,
+    /// Range `[lo, hi]` of the singular values of interest, only used when neither $U$ nor $V$
+    /// is requested
+    ///
+    /// The secular equation of the divide and conquer algorithm is not solved for singular values
+    /// that are known to lie outside of the range. These are replaced by a lower bound that also
+    /// lies outside of the range, and should be discarded by the caller.
+    pub singular_value_range: Option<(f64, f64)>,
+    /// Whether the bidiagonal QR algorithm should compute the singular values to high relative
+    /// accuracy, instead of high absolute accuracy
+    ///
+    /// When enabled, only entries that are negligible relative to the singular values they affect
+    /// are deflated, and the Demmel-Kahan zero shift sweep is used when the singular values of the
+    /// current block span many orders of magnitude. This only affects bidiagonal matrices that are
+    /// small enough to be handled by the QR algorithm. Since the bidiagonalization of a dense
+    /// matrix is only backward stable in the absolute sense, this is mostly useful when
+    /// decomposing a bidiagonal matrix directly.
+    pub high_relative_accuracy: bool,
+    /// Formula used by the bidiagonal QR algorithm to compute its shift
+    pub shift_strategy: ShiftStrategy,
+    /// Criterion used by the bidiagonal QR algorithm to neglect the entries of the bidiagonal
+    /// matrix, unless [`high_relative_accuracy`](SvdParams::high_relative_accuracy) is enabled,
+    /// in which case its own criterion is used instead
+    pub convergence_criterion: ConvergenceCriterion,
+    /// Size of the subproblems below which the divide and conquer bidiagonal SVD switches to the
+    /// Jacobi algorithm, when neither $U$ nor $V$ is requested
+    pub leaf_size_values: Option<usize>,
+    /// Size of the subproblems below which the divide and conquer bidiagonal SVD switches to the
+    /// Jacobi algorithm, when $U$ or $V$ is requested
+    pub leaf_size_vectors: Option<usize>,
+    /// Depth of the divide and conquer recursion at which the subproblems are no longer split,
+    /// and are solved by the bidiagonal QR algorithm instead
+    ///
+    /// The top level problem has depth `0` and is always split, so `Some(0)` behaves like
+    /// `Some(1)`. Each level of the recursion halves the workspace available to the subproblems
+    /// and nests the closures of the parallel tasks, so this bounds the stack usage for large
+    /// matrices, at the cost of solving the remaining subproblems with the slower QR algorithm.
+    /// Defaults to `None`, which splits them until they reach the leaf size.
+    pub max_depth: Option<usize>,
+    /// Order of the singular values and vectors computed by [`compute_svd`]
+    pub sort: SvdSort,
+    /// Column at which the divide and conquer bidiagonal SVD splits its subproblems
+    pub split_strategy: SplitStrategy,
+    /// How the divide and conquer bidiagonal SVD parallelizes its merge steps
+    pub merge_parallelism: MergeParallelism,
+    /// Number of Newton steps taken on the secular equation of the divide and conquer bidiagonal
+    /// SVD after each singular value has converged
+    ///
+    /// The secant and bisection iterations stop once the root is known to working precision
+    /// relative to the size of the bracketing interval, which may leave a residual a few orders
+    /// of magnitude above the machine epsilon. Each Newton step uses the analytic derivative of
+    /// the secular equation, and is only taken if it reduces the residual. Defaults to `0`.
+    pub refine_iters: usize,
+    /// How the divide and conquer bidiagonal SVD handles a secular equation whose initial bracket
+    /// doesn't contain its root
+    pub secular_bracket: SecularBracketMode,
+    /// Number of secular equations solved at a time by the merge steps of the divide and conquer
+    /// bidiagonal SVD, when neither $U$ nor $V$ is requested
+    ///
+    /// The singular values of each merge step are then computed with
+    /// [`compute_arrow_real_singular_values_windowed`](bidiag_real_svd::compute_arrow_real_singular_values_windowed),
+    /// and the first and last rows of the left singular vectors that the merge steps below the top
+    /// level need are updated one singular vector at a time, so that no temporary matrix of the
+    /// size of the arrow matrix is formed. The secular equations are solved twice, once for the
+    /// singular values and once for the singular vectors. This is ignored when
+    /// [`singular_value_range`](SvdParams::singular_value_range) or an initial guess of the
+    /// singular values is provided. The singular values of a large bidiagonal matrix are then
+    /// computed with the workspace given by
+    /// [`bidiag_real_singular_values_windowed_req`](bidiag_real_svd::bidiag_real_singular_values_windowed_req),
+    /// which is linear in its size. Defaults to `None`, which solves all of them at once.
+    pub secular_window: Option<usize>,
+    /// Threshold, relative to the largest entry of the diagonal of the arrow matrix of a merge
+    /// step, below which the divide and conquer bidiagonal SVD flushes the entries of its first
+    /// column to zero
+    ///
+    /// Each flushed entry deflates a singular value, which is then read off the diagonal instead
+    /// of solving a secular equation, so raising the threshold reduces the
+    /// [`secular_eq_solves`](bidiag_real_svd::BidiagSvdStats::secular_eq_solves) and the time
+    /// spent on them. Flushing the entries that are at most $t$ times the largest diagonal entry
+    /// perturbs the bidiagonal matrix $B$ by as much, so the singular values are only accurate to
+    /// about $t \|B\|$, and the singular vectors of singular values separated by a gap $\delta$
+    /// from the others to about $t \|B\| / \delta$. Thresholds below the epsilon of the
+    /// decomposition are raised to it. Defaults to `None`, which uses the epsilon.
+    pub col0_flush_threshold: Option<f64>,
+    /// Whether [`try_compute_svd`] and [`try_compute_svd_custom_epsilon`] check the computed
+    /// decomposition, and return [`SvdError::VerificationFailed`] if it's inaccurate
+    ///
+    /// The residual $\|A v_j - s_j u_j\|$ of each singular triplet is compared to
+    /// $32 \max(m, n) \varepsilon \|A\|_F$. This costs about as much as a matrix product, and
+    /// allocates its own storage. It's only done when both $U$ and $V$ are requested and the `std`
+    /// feature is enabled, and ignored by the other functions. Defaults to `false`.
+    pub verify: bool,
+    /// Whether the computed singular vectors are reorthogonalized with a pass of modified
+    /// Gram-Schmidt
+    ///
+    /// The singular vectors are only orthogonal up to the accuracy of the decomposition, so their
+    /// departure from orthogonality grows with the epsilon passed to
+    /// [`compute_svd_custom_epsilon`], in particular for the left singular vectors of
+    /// [`SvdAlgorithm::OneSidedJacobi`], which are the normalized columns of $AV$. Each column of
+    /// $U$ and $V$ is orthogonalized against the previous ones and normalized, which brings
+    /// $\|U^H U - I\|$ and $\|V^H V - I\|$ back down to a small multiple of the machine epsilon,
+    /// while the reconstruction $U S V^H$ stays accurate to about the epsilon of the
+    /// decomposition. This costs about $2 m k^2$ operations for a $U$ of shape $(m, k)$,
+    /// and as much for $V$, which is comparable to forming $U^H U$, and doesn't allocate.
+    /// Defaults to `false`.
+    pub reorthogonalize: bool,
+}
 
 /// Computes the size and alignment of required workspace for performing a singular value
 /// decomposition. $U$ and $V$ may be computed fully, partially, or not computed at all.
@@ -608,9 +1033,8 @@ pub fn compute_svd_req<E: ComplexField>(
         (size, size)
     };
 
-    let _ = params;
     let squareish_svd = if coe::is_same::<E, E::Real>() {
-        if size <= JACOBI_FALLBACK_THRESHOLD {
+        if use_real_svd_small(size, params.algorithm) {
             compute_real_svd_small_req::<E>(svd_nrows, svd_ncols, compute_u, compute_v, parallelism)
         } else {
             compute_svd_big_req::<E::Real>(
@@ -620,6 +1044,7 @@ pub fn compute_svd_req<E: ComplexField>(
                 compute_v,
                 bidiag_real_svd_req::<E::Real>,
                 parallelism,
+                params,
             )
         }
     } else {
@@ -630,11 +1055,18 @@ pub fn compute_svd_req<E: ComplexField>(
             compute_v,
             bidiag_cplx_svd_req::<E>,
             parallelism,
+            params,
         )
     }?;
 
-    if skip_qr {
-        Ok(squareish_svd)
+    let sort = if params.sort == SvdSort::StableDescending {
+        StackReq::try_new::<usize>(size)?
+    } else {
+        StackReq::empty()
+    };
+
+    let svd = if skip_qr {
+        squareish_svd
     } else {
         let householder_blocksize =
             faer_qr::no_pivoting::compute::recommended_blocksize::<E>(nrows, ncols);
@@ -659,11 +1091,83 @@ pub fn compute_svd_req<E: ComplexField>(
                 apply_block_householder_sequence_on_the_left_in_place_req::<E>(
                     nrows,
                     householder_blocksize,
-                    nrows,
+                    match compute_u {
+                        ComputeVectors::No => 0,
+                        ComputeVectors::Thin => size,
+                        ComputeVectors::Full => nrows,
+                    },
                 )?,
             ])?,
-        ])
-    }
+        ])?
+    };
+
+    StackReq::try_any_of([svd, sort])
+}
+
+/// Estimates the number of floating point operations taken by [`compute_svd`] on a real square
+/// matrix of size `n`, when the bidiagonal SVD is computed with `algorithm`.
+///
+/// The estimate is derived from the asymptotic cost of each step, with fixed guesses for the
+/// quantities that depend on the matrix, such as the number of Jacobi sweeps or of QR sweeps
+/// per singular value, and ignores the deflation of the divide and conquer algorithm, which
+/// can only make it cheaper. It is meant to compare the algorithms with each other for a given
+/// size, rather than to predict the running time. A complex matrix takes about four times as
+/// many real operations.
+///
+/// [`SvdAlgorithm::Auto`] is estimated as the algorithm that it selects for a matrix of size `n`
+/// with the default parameters.
+pub fn svd_cost_estimate(
+    n: usize,
+    compute_u: ComputeVectors,
+    compute_v: ComputeVectors,
+    algorithm: SvdAlgorithm,
+) -> u64 {
+    // number of sweeps that the jacobi algorithms usually take to converge in double precision
+    const JACOBI_SWEEPS: f64 = 8.0;
+
+    let nf = n as f64;
+    let n2 = nf * nf;
+    let n3 = n2 * nf;
+    let has_u = compute_u != ComputeVectors::No;
+    let has_v = compute_v != ComputeVectors::No;
+    let sides = has_u as usize as f64 + has_v as usize as f64;
+
+    // each of the `n (n - 1) / 2` rotations of a sweep updates two rows and two columns of the
+    // matrix, and two columns of each requested factor
+    let two_sided_jacobi = JACOBI_SWEEPS * (n2 / 2.0) * (12.0 * nf + 6.0 * sides * nf);
+    // each pair of columns needs three dot products, then the rotation of the pair of columns
+    // of the matrix and of `v`
+    let one_sided_jacobi =
+        JACOBI_SWEEPS * (n2 / 2.0) * (12.0 * nf + if has_v { 6.0 * nf } else { 0.0 });
+    // two sweeps per singular value on average, over an active block that shrinks as the
+    // singular values converge, so about `n^2` rotations in total
+    let qr_iteration = 20.0 * n2 + 6.0 * sides * n3;
+    // the secular equations of a merge of size `k` take `O(k^2)` operations, and the update of
+    // the singular vectors multiplies the block diagonal factors, for `k^3` operations per
+    // factor. the subproblems halve at each level
+    let divide_conquer = 64.0 * n2 + (4.0 / 3.0) * sides * n3;
+    // the householder reflections, then their application to the singular vectors of the
+    // bidiagonal matrix
+    let bidiag = (8.0 / 3.0) * n3 + 2.0 * sides * n3;
+    // a few square roots and divisions, then one rotation of each requested factor
+    let closed_form = 32.0 + 6.0 * sides * nf;
+
+    let cost = match algorithm {
+        SvdAlgorithm::ClosedForm if n == 2 => bidiag + closed_form,
+        SvdAlgorithm::Auto | SvdAlgorithm::ClosedForm if use_real_svd_small(n, algorithm) => {
+            two_sided_jacobi
+        }
+        SvdAlgorithm::Auto | SvdAlgorithm::ClosedForm if n <= BIDIAG_QR_FALLBACK_THRESHOLD => {
+            bidiag + qr_iteration
+        }
+        SvdAlgorithm::Auto | SvdAlgorithm::ClosedForm => bidiag + divide_conquer,
+        SvdAlgorithm::OneSidedJacobi => one_sided_jacobi,
+        SvdAlgorithm::Jacobi => bidiag + two_sided_jacobi,
+        SvdAlgorithm::QrIteration => bidiag + qr_iteration,
+        SvdAlgorithm::DivideConquer if n < 3 => bidiag + two_sided_jacobi,
+        SvdAlgorithm::DivideConquer => bidiag + divide_conquer,
+    };
+    cost as u64
 }
 
 /// Computes the singular value decomposition of `matrix`.
@@ -679,11 +1183,82 @@ pub fn compute_svd_req<E: ComplexField>(
 /// minimum of `matrix.nrows()` and `matrix.ncols()`, in which case only the singular vectors
 /// corresponding to the provided column storage are computed.
 ///
+/// A matrix with more columns than rows is decomposed through its transpose, with the roles of
+/// `u` and `v` swapped internally, so it doesn't need to be transposed beforehand and costs the
+/// same as its transpose.
+///
+/// When the longer dimension is more than about twice the shorter one, the matrix is first
+/// reduced to a square triangular matrix by a QR decomposition along it, so that the
+/// decomposition of an $(m, n)$ matrix with $m \ll n$ takes $O(m^2 n)$ operations. In that case,
+/// requesting the thin $V$ of shape $(n, m)$ with [`ComputeVectors::Thin`] also keeps the workspace
+/// proportional to $m n$, while the full $V$ of shape $(n, n)$ has to be completed by the
+/// householder sequence of the QR decomposition.
+///
 /// # Panics
 /// Panics if any of the conditions described above is violated, or if the type `E` does not have a
 /// fixed precision at compile time, e.g. a dynamic multiprecision floating point type.
 ///
 /// This can also panic if the provided memory in `stack` is insufficient (see [`compute_svd_req`]).
+///
+/// # Example
+/// ```
+/// use dyn_stack::{GlobalPodBuffer, PodStack};
+/// use faer_core::{mat, Mat, Parallelism};
+/// use faer_svd::{compute_svd, compute_svd_req, ComputeVectors, SvdParams};
+///
+/// let a = mat![
+///     [1.0, 2.0, 3.0],
+///     [4.0, 5.0, 6.0],
+///     [7.0, 8.0, 10.0],
+///     [1.0, 0.0, 1.0f64],
+/// ];
+/// let (m, n) = (a.nrows(), a.ncols());
+///
+/// let mut s = Mat::<f64>::zeros(n, 1);
+/// let mut u = Mat::<f64>::zeros(m, m);
+/// let mut v = Mat::<f64>::zeros(n, n);
+/// let params = SvdParams::default();
+/// let mut mem = GlobalPodBuffer::new(
+///     compute_svd_req::<f64>(
+///         m,
+///         n,
+///         ComputeVectors::Full,
+///         ComputeVectors::Full,
+///         Parallelism::None,
+///         params,
+///     )
+///     .unwrap(),
+/// );
+/// compute_svd(
+///     a.as_ref(),
+///     s.as_mut(),
+///     Some(u.as_mut()),
+///     Some(v.as_mut()),
+///     Parallelism::None,
+///     PodStack::new(&mut mem),
+///     params,
+/// );
+///
+/// // `A = U S V^H`, where `S` is the `(m, n)` matrix whose diagonal is `s`
+/// let s_mat = Mat::<f64>::from_fn(m, n, |i, j| if i == j { s.read(i, 0) } else { 0.0 });
+/// let reconstructed = &u * &s_mat * v.transpose();
+/// for j in 0..n {
+///     for i in 0..m {
+///         assert!((reconstructed.read(i, j) - a.read(i, j)).abs() < 1e-12);
+///     }
+/// }
+///
+/// // `U` and `V` are orthogonal
+/// for w in [&u, &v] {
+///     let gram = w.transpose() * w;
+///     for j in 0..w.ncols() {
+///         for i in 0..w.ncols() {
+///             let target = if i == j { 1.0 } else { 0.0 };
+///             assert!((gram.read(i, j) - target).abs() < 1e-12);
+///         }
+///     }
+/// }
+/// ```
 #[track_caller]
 pub fn compute_svd<E: ComplexField>(
     matrix: MatRef<'_, E>,
@@ -707,250 +1282,1373 @@ pub fn compute_svd<E: ComplexField>(
     );
 }
 
-/// See [`compute_svd`].
-///
-/// This function takes an additional `epsilon` and `zero_threshold` parameters. `epsilon`
-/// represents the precision of the values in the matrix, and `zero_threshold` is the value below
-/// which the precision starts to deteriorate, e.g. due to denormalized numbers.
+/// Same as [`compute_svd`], but decomposes the conjugate of `matrix` if `conj_matrix` is
+/// [`Conj::Yes`].
 ///
-/// These values need to be provided manually for types that do not have a known precision at
-/// compile time, e.g. a dynamic multiprecision floating point type.
+/// This accepts the views returned by [`MatRef::canonicalize`], such as the adjoint of a complex
+/// matrix, without materializing their conjugate. A matrix and its conjugate have the same
+/// singular values, so only the singular vectors are conjugated, in place, after the
+/// decomposition.
 #[track_caller]
-pub fn compute_svd_custom_epsilon<E: ComplexField>(
+pub fn compute_svd_with_conj<E: ComplexField>(
     matrix: MatRef<'_, E>,
+    conj_matrix: Conj,
     s: MatMut<'_, E>,
-    u: Option<MatMut<'_, E>>,
-    v: Option<MatMut<'_, E>>,
-    epsilon: E::Real,
-    zero_threshold: E::Real,
+    mut u: Option<MatMut<'_, E>>,
+    mut v: Option<MatMut<'_, E>>,
     parallelism: Parallelism,
     stack: PodStack<'_>,
     params: SvdParams,
 ) {
-    let size = Ord::min(matrix.nrows(), matrix.ncols());
-    assert!(all(s.nrows() == size, s.ncols() == 1));
-    if let Some(u) = u.rb() {
-        assert!(u.nrows() == matrix.nrows());
-        assert!(u.ncols() == matrix.nrows() || u.ncols() == size);
-    }
-    if let Some(v) = v.rb() {
-        assert!(v.nrows() == matrix.ncols());
-        assert!(v.ncols() == matrix.ncols() || v.ncols() == size);
-    }
+    compute_svd(
+        matrix,
+        s,
+        u.rb_mut(),
+        v.rb_mut(),
+        parallelism,
+        stack,
+        params,
+    );
 
-    #[cfg(feature = "perf-warn")]
-    match (u.rb(), v.rb()) {
-        (Some(matrix), _) | (_, Some(matrix)) => {
-            if matrix.row_stride().unsigned_abs() != 1 && faer_core::__perf_warn!(QR_WARN) {
-                if matrix.col_stride().unsigned_abs() == 1 {
-                    log::warn!(target: "faer_perf", "SVD prefers column-major singular vector matrices. Found row-major matrix.");
-                } else {
-                    log::warn!(target: "faer_perf", "SVD prefers column-major singular vector matrices. Found matrix with generic strides.");
-                }
-            }
+    if conj_matrix == Conj::Yes && !coe::is_same::<E, E::Real>() {
+        for x in [u, v].into_iter().flatten() {
+            zipped!(x).for_each(|unzipped!(mut x)| x.write(x.read().faer_conj()));
         }
-        _ => {}
     }
+}
 
-    if !matrix.is_all_finite() {
-        { s }.fill(E::faer_nan());
-        if let Some(mut u) = u {
-            u.fill(E::faer_nan());
-        }
-        if let Some(mut v) = v {
-            v.fill(E::faer_nan());
-        }
-        return;
-    }
+/// Computes the size and alignment of required workspace for performing a singular value
+/// decomposition with [`svd_into`].
+pub fn svd_into_req<E: ComplexField>(
+    nrows: usize,
+    ncols: usize,
+    compute_u: ComputeVectors,
+    compute_v: ComputeVectors,
+    parallelism: Parallelism,
+    params: SvdParams,
+) -> Result<StackReq, SizeOverflow> {
+    let size = Ord::min(nrows, ncols);
+    StackReq::try_all_of([
+        temp_mat_req::<E>(size, 1)?,
+        compute_svd_req::<E>(nrows, ncols, compute_u, compute_v, parallelism, params)?,
+    ])
+}
 
-    let mut u = u;
-    let mut v = v;
-    let mut matrix = matrix;
+/// Same as [`compute_svd`], but stores the singular values in the slice `s`, and always computes
+/// both $U$ and $V$.
+///
+/// `s` must have length `min(m, n)`, and `u` and `v` the shapes described in [`compute_svd`],
+/// for the thin or the full decomposition. The results are only written to `u`, `s` and `v`, and
+/// every intermediate result is taken from `stack`, whose size is given by [`svd_into_req`] with
+/// the [`ComputeVectors`] matching the shapes of `u` and `v`. The decomposition doesn't allocate
+/// any other storage itself, with the exception of the packing buffers that the matrix
+/// multiplication kernels of `gemm` allocate for the larger products, which are freed before
+/// they return.
+///
+/// # Panics
+/// Panics if `s.len() != min(m, n)`, or under the same conditions as [`compute_svd`].
+///
+/// This can also panic if the provided memory in `stack` is insufficient (see [`svd_into_req`]).
+#[track_caller]
+pub fn svd_into<E: ComplexField>(
+    matrix: MatRef<'_, E>,
+    s: &mut [E::Real],
+    u: MatMut<'_, E>,
+    v: MatMut<'_, E>,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+    params: SvdParams,
+) {
+    let size = Ord::min(matrix.nrows(), matrix.ncols());
+    assert!(
+        s.len() == size,
+        "`s` must have length `min(m, n)`, the number of singular values",
+    );
+
+    let (mut s_mat, stack) = temp_mat_uninit::<E>(size, 1, stack);
+    let mut s_mat = s_mat.as_mut();
+    compute_svd(
+        matrix,
+        s_mat.rb_mut(),
+        Some(u),
+        Some(v),
+        parallelism,
+        stack,
+        params,
+    );
+
+    for (i, s) in s.iter_mut().enumerate() {
+        *s = s_mat.read(i, 0).faer_real();
+    }
+}
+
+/// Computes the size and alignment of required workspace for computing the SVD in the layout of
+/// LAPACK with [`svd_lapack_layout`].
+pub fn svd_lapack_layout_req<E: ComplexField>(
+    nrows: usize,
+    ncols: usize,
+    compute_u: ComputeVectors,
+    compute_vt: ComputeVectors,
+    parallelism: Parallelism,
+    params: SvdParams,
+) -> Result<StackReq, SizeOverflow> {
+    let size = Ord::min(nrows, ncols);
+    let v_ncols = match compute_vt {
+        ComputeVectors::No => 0,
+        ComputeVectors::Thin => size,
+        ComputeVectors::Full => ncols,
+    };
+    StackReq::try_all_of([
+        temp_mat_req::<E>(ncols, v_ncols)?,
+        compute_svd_req::<E>(
+            nrows,
+            ncols,
+            compute_u,
+            compute_vt,
+            parallelism,
+            SvdParams {
+                sort: SvdSort::StableDescending,
+                ..params
+            },
+        )?,
+    ])
+}
+
+/// Same as [`compute_svd`], but stores $V^H$ in `vt` instead of $V$, matching the output of
+/// LAPACK's `gesdd`.
+///
+/// `vt` has shape `(n, n)` for the full decomposition, or `(min(m, n), n)` for the thin one, and
+/// its rows are the conjugates of the right singular vectors.
+///
+/// LAPACK doesn't specify the signs of the singular vectors, or their unit phases for complex
+/// matrices, so they depend on the implementation. They're normalized as described in
+/// [`SvdSort::StableDescending`], which replaces `params.sort`. [`canonicalize_svd_lapack_layout`]
+/// applies the same normalization to the results of `gesdd`, so that both can be compared
+/// entrywise.
+///
+/// The workspace is given by [`svd_lapack_layout_req`].
+///
+/// # Panics
+/// Panics if `vt` doesn't have `n` columns, or under the same conditions as [`compute_svd`] for
+/// the transpose of `vt`.
+#[track_caller]
+pub fn svd_lapack_layout<E: ComplexField>(
+    matrix: MatRef<'_, E>,
+    s: MatMut<'_, E>,
+    u: Option<MatMut<'_, E>>,
+    vt: Option<MatMut<'_, E>>,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+    params: SvdParams,
+) {
+    let n = matrix.ncols();
+    if let Some(vt) = vt.rb() {
+        assert!(vt.ncols() == n);
+    }
+
+    let v_ncols = vt.rb().map(|vt| vt.nrows()).unwrap_or(0);
+    let (mut v, stack) = temp_mat_uninit::<E>(n, v_ncols, stack);
+    let mut v = v.as_mut();
+    compute_svd(
+        matrix,
+        s,
+        u,
+        vt.is_some().then_some(v.rb_mut()),
+        parallelism,
+        stack,
+        SvdParams {
+            sort: SvdSort::StableDescending,
+            ..params
+        },
+    );
+
+    if let Some(vt) = vt {
+        zipped!(vt, v.rb().transpose())
+            .for_each(|unzipped!(mut dst, src)| dst.write(src.read().faer_conj()));
+    }
+}
+
+/// Computes the size and alignment of required workspace for normalizing an SVD with
+/// [`canonicalize_svd_lapack_layout`].
+pub fn canonicalize_svd_lapack_layout_req<E: Entity>(
+    nrows: usize,
+    ncols: usize,
+) -> Result<StackReq, SizeOverflow> {
+    let size = Ord::min(nrows, ncols);
+    StackReq::try_all_of([
+        temp_mat_req::<E>(ncols, size)?,
+        StackReq::try_new::<usize>(size)?,
+    ])
+}
+
+/// Normalizes the singular vectors of an SVD in the layout of LAPACK's `gesdd`, such as the ones
+/// computed by LAPACK itself, in the same way as [`svd_lapack_layout`].
+///
+/// `s` contains the singular values in nonincreasing order, and `u` and `vt` have the shapes
+/// described in [`svd_lapack_layout`]. Only the singular vectors of the `min(m, n)` singular
+/// values are modified. The workspace is given by [`canonicalize_svd_lapack_layout_req`].
+///
+/// # Panics
+/// Panics if `u` has fewer than `s.nrows()` columns, or if `vt` has fewer than `s.nrows()` rows.
+#[track_caller]
+pub fn canonicalize_svd_lapack_layout<E: ComplexField>(
+    s: MatMut<'_, E>,
+    u: Option<MatMut<'_, E>>,
+    vt: Option<MatMut<'_, E>>,
+    stack: PodStack<'_>,
+) {
+    let size = s.nrows();
+    if let Some(u) = u.rb() {
+        assert!(u.ncols() >= size);
+    }
+    if let Some(vt) = vt.rb() {
+        assert!(vt.nrows() >= size);
+    }
+
+    let n = vt.rb().map(|vt| vt.ncols()).unwrap_or(0);
+    let (mut v, stack) = temp_mat_uninit::<E>(n, size, stack);
+    let mut v = v.as_mut();
+    if let Some(vt) = vt.rb() {
+        zipped!(v.rb_mut(), vt.subrows(0, size).transpose())
+            .for_each(|unzipped!(mut dst, src)| dst.write(src.read().faer_conj()));
+    }
+
+    sort_svd(
+        s,
+        u,
+        vt.is_some().then_some(v.rb_mut()),
+        SvdSort::StableDescending,
+        E::Real::faer_epsilon().unwrap(),
+        stack,
+    );
+
+    if let Some(vt) = vt {
+        zipped!(vt.subrows_mut(0, size), v.rb().transpose())
+            .for_each(|unzipped!(mut dst, src)| dst.write(src.read().faer_conj()));
+    }
+}
+
+/// Computes the size and alignment of required workspace for performing a singular value
+/// decomposition with [`compute_svd_with_f32_vectors`].
+pub fn compute_svd_with_f32_vectors_req(
+    nrows: usize,
+    ncols: usize,
+    compute_u: ComputeVectors,
+    compute_v: ComputeVectors,
+    parallelism: Parallelism,
+    params: SvdParams,
+) -> Result<StackReq, SizeOverflow> {
+    let size = Ord::min(nrows, ncols);
+    let vectors_ncols = |dim: usize, compute: ComputeVectors| match compute {
+        ComputeVectors::No => 0,
+        ComputeVectors::Thin => size,
+        ComputeVectors::Full => dim,
+    };
+
+    StackReq::try_all_of([
+        temp_mat_req::<f64>(nrows, vectors_ncols(nrows, compute_u))?,
+        temp_mat_req::<f64>(ncols, vectors_ncols(ncols, compute_v))?,
+        compute_svd_req::<f64>(nrows, ncols, compute_u, compute_v, parallelism, params)?,
+    ])
+}
+
+/// Same as [`compute_svd`] for an `f64` matrix, but stores the singular vectors in `f32`.
+///
+/// The decomposition, including the bidiagonal solver and the normalization of the singular
+/// vectors, runs entirely in `f64`, and the singular values are returned in `f64`. The singular
+/// vectors are computed in `f64` scratch space inside `stack`, then rounded to `f32` once when
+/// they are written to `u` and `v`, so each entry is within `f32` rounding of the corresponding
+/// entry computed by [`compute_svd`].
+///
+/// # Panics
+/// Panics under the same conditions as [`compute_svd`].
+///
+/// This can also panic if the provided memory in `stack` is insufficient (see
+/// [`compute_svd_with_f32_vectors_req`]).
+#[track_caller]
+pub fn compute_svd_with_f32_vectors(
+    matrix: MatRef<'_, f64>,
+    s: MatMut<'_, f64>,
+    u: Option<MatMut<'_, f32>>,
+    v: Option<MatMut<'_, f32>>,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+    params: SvdParams,
+) {
+    let u_ncols = u.rb().map(|u| u.ncols()).unwrap_or(0);
+    let v_ncols = v.rb().map(|v| v.ncols()).unwrap_or(0);
+    let (mut u64, stack) = temp_mat_uninit::<f64>(matrix.nrows(), u_ncols, stack);
+    let (mut v64, stack) = temp_mat_uninit::<f64>(matrix.ncols(), v_ncols, stack);
+
+    compute_svd(
+        matrix,
+        s,
+        u.is_some().then_some(u64.rb_mut()),
+        v.is_some().then_some(v64.rb_mut()),
+        parallelism,
+        stack,
+        params,
+    );
+
+    for (dst, src) in [(u, u64.rb()), (v, v64.rb())] {
+        if let Some(mut dst) = dst {
+            for j in 0..dst.ncols() {
+                for i in 0..dst.nrows() {
+                    dst.write(i, j, src.read(i, j) as f32);
+                }
+            }
+        }
+    }
+}
+
+/// See [`compute_svd`].
+///
+/// This function takes an additional `epsilon` and `zero_threshold` parameters. `epsilon`
+/// represents the precision of the values in the matrix, and `zero_threshold` is the value below
+/// which the precision starts to deteriorate, e.g. due to denormalized numbers.
+///
+/// These values need to be provided manually for types that do not have a known precision at
+/// compile time, e.g. a dynamic multiprecision floating point type.
+#[track_caller]
+pub fn compute_svd_custom_epsilon<E: ComplexField>(
+    matrix: MatRef<'_, E>,
+    s: MatMut<'_, E>,
+    u: Option<MatMut<'_, E>>,
+    v: Option<MatMut<'_, E>>,
+    epsilon: E::Real,
+    zero_threshold: E::Real,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+    params: SvdParams,
+) {
+    compute_svd_impl(
+        matrix,
+        s,
+        u,
+        v,
+        epsilon,
+        zero_threshold,
+        parallelism,
+        stack,
+        params,
+    );
+}
+
+/// Same as [`compute_svd`], but returns an error instead of panicking or producing NaN values.
+///
+/// The dimensions of `s`, `u` and `v`, the size of `stack` and the finiteness of `matrix` are
+/// checked before anything is written to the outputs. The contents of the outputs are unspecified
+/// if the decomposition itself fails with [`SvdError::NotConverged`] or
+/// [`SvdError::SecularDivergence`]. The decomposition can also be checked after it's computed,
+/// with [`SvdParams::verify`].
+///
+/// # Panics
+/// Panics if the type `E` does not have a fixed precision at compile time, e.g. a dynamic
+/// multiprecision floating point type. See [`try_compute_svd_custom_epsilon`] for such types.
+#[track_caller]
+pub fn try_compute_svd<E: ComplexField>(
+    matrix: MatRef<'_, E>,
+    s: MatMut<'_, E>,
+    u: Option<MatMut<'_, E>>,
+    v: Option<MatMut<'_, E>>,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+    params: SvdParams,
+) -> Result<(), SvdError> {
+    try_compute_svd_custom_epsilon(
+        matrix,
+        s,
+        u,
+        v,
+        E::Real::faer_epsilon().unwrap(),
+        E::Real::faer_zero_threshold().unwrap(),
+        parallelism,
+        stack,
+        params,
+    )
+}
+
+/// Same as [`compute_svd_custom_epsilon`], but returns an error instead of panicking or producing
+/// NaN values. See [`try_compute_svd`].
+pub fn try_compute_svd_custom_epsilon<E: ComplexField>(
+    matrix: MatRef<'_, E>,
+    mut s: MatMut<'_, E>,
+    mut u: Option<MatMut<'_, E>>,
+    mut v: Option<MatMut<'_, E>>,
+    epsilon: E::Real,
+    zero_threshold: E::Real,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+    params: SvdParams,
+) -> Result<(), SvdError> {
+    let m = matrix.nrows();
+    let n = matrix.ncols();
+    let size = Ord::min(m, n);
+
+    // the shape of each vector matrix is checked along with the part of it that is computed
+    let compute_vectors = |vectors: Option<MatRef<'_, E>>, dim: usize| match vectors {
+        None => Ok(ComputeVectors::No),
+        Some(vectors) if vectors.nrows() != dim => Err(SvdError::ShapeMismatch),
+        Some(vectors) if vectors.ncols() == dim => Ok(ComputeVectors::Full),
+        Some(vectors) if vectors.ncols() == size => Ok(ComputeVectors::Thin),
+        Some(_) => Err(SvdError::ShapeMismatch),
+    };
+    if s.nrows() != size || s.ncols() != 1 {
+        return Err(SvdError::ShapeMismatch);
+    }
+    let compute_u = compute_vectors(u.rb().map(|u| u.into_const()), m)?;
+    let compute_v = compute_vectors(v.rb().map(|v| v.into_const()), n)?;
+
+    let req = compute_svd_req::<E>(m, n, compute_u, compute_v, parallelism, params)
+        .map_err(|_| SvdError::Alloc)?;
+    if !stack.can_hold(req) {
+        return Err(SvdError::Alloc);
+    }
+
+    if !matrix.is_all_finite() {
+        return Err(SvdError::NonFinite);
+    }
+
+    let stats = compute_svd_impl(
+        matrix,
+        s.rb_mut(),
+        u.rb_mut(),
+        v.rb_mut(),
+        epsilon,
+        zero_threshold,
+        parallelism,
+        stack,
+        params,
+    );
+    if stats.inconsistent_inputs > 0 {
+        return Err(SvdError::InconsistentInput);
+    } else if stats.secular_divergences > 0 {
+        return Err(SvdError::SecularDivergence);
+    } else if stats.qr.unconverged > 0 {
+        return Err(SvdError::NotConverged);
+    }
+
+    #[cfg(feature = "std")]
+    if let (true, Some(u), Some(v)) = (params.verify, u, v) {
+        return verify_svd(matrix, s.rb(), u.rb(), v.rb(), epsilon);
+    }
+    Ok(())
+}
+
+/// checks the decomposition computed by [`try_compute_svd_custom_epsilon`] when
+/// [`SvdParams::verify`] is set, by comparing the residual $\|A v_j - s_j u_j\|$ of each singular
+/// triplet to $32 \max(m, n) \varepsilon \|A\|_F$
+#[cfg(feature = "std")]
+fn verify_svd<E: ComplexField>(
+    matrix: MatRef<'_, E>,
+    s: MatRef<'_, E>,
+    u: MatRef<'_, E>,
+    v: MatRef<'_, E>,
+    epsilon: E::Real,
+) -> Result<(), SvdError> {
+    let dim = Ord::max(matrix.nrows(), matrix.ncols());
+    let tol = E::Real::faer_from_f64(32.0 * dim as f64)
+        .faer_mul(epsilon)
+        .faer_mul(matrix.norm_l2());
+
+    let residual = testing::svd_per_triplet_residual(matrix, u, s, v);
+    for j in 0..s.nrows() {
+        // the residuals are relative to the singular values, which is undone so that every
+        // triplet is held to the same backward error
+        let s_j = s.read(j, 0).faer_abs();
+        let err = if s_j == E::Real::faer_zero() {
+            residual.read(j)
+        } else {
+            residual.read(j).faer_mul(s_j)
+        };
+        if err > tol || !err.faer_is_finite() {
+            return Err(SvdError::VerificationFailed);
+        }
+    }
+    Ok(())
+}
+
+/// implementation of [`compute_svd_custom_epsilon`], returning the statistics of the bidiagonal
+/// svd, which are left zeroed if it isn't used
+#[track_caller]
+fn compute_svd_impl<E: ComplexField>(
+    matrix: MatRef<'_, E>,
+    mut s: MatMut<'_, E>,
+    u: Option<MatMut<'_, E>>,
+    v: Option<MatMut<'_, E>>,
+    epsilon: E::Real,
+    zero_threshold: E::Real,
+    parallelism: Parallelism,
+    mut stack: PodStack<'_>,
+    params: SvdParams,
+) -> BidiagSvdStats {
+    let size = Ord::min(matrix.nrows(), matrix.ncols());
+    assert!(all(s.nrows() == size, s.ncols() == 1));
+    if let Some(u) = u.rb() {
+        assert!(u.nrows() == matrix.nrows());
+        assert!(u.ncols() == matrix.nrows() || u.ncols() == size);
+    }
+    if let Some(v) = v.rb() {
+        assert!(v.nrows() == matrix.ncols());
+        assert!(v.ncols() == matrix.ncols() || v.ncols() == size);
+    }
+
+    #[cfg(feature = "perf-warn")]
+    match (u.rb(), v.rb()) {
+        (Some(matrix), _) | (_, Some(matrix)) => {
+            if matrix.row_stride().unsigned_abs() != 1 && faer_core::__perf_warn!(QR_WARN) {
+                if matrix.col_stride().unsigned_abs() == 1 {
+                    log::warn!(target: "faer_perf", "SVD prefers column-major singular vector matrices. Found row-major matrix.");
+                } else {
+                    log::warn!(target: "faer_perf", "SVD prefers column-major singular vector matrices. Found matrix with generic strides.");
+                }
+            }
+        }
+        _ => {}
+    }
+
+    if !matrix.is_all_finite() {
+        { s }.fill(E::faer_nan());
+        if let Some(mut u) = u {
+            u.fill(E::faer_nan());
+        }
+        if let Some(mut v) = v {
+            v.fill(E::faer_nan());
+        }
+        return BidiagSvdStats::default();
+    }
+
+    let mut u = u;
+    let mut v = v;
+    let mut matrix = matrix;
     let do_transpose = matrix.ncols() > matrix.nrows();
     if do_transpose {
-        matrix = matrix.transpose();
+        matrix = matrix.transpose();
+        swap(&mut u, &mut v);
+    }
+
+    let m = matrix.nrows();
+    let n = matrix.ncols();
+
+    if n == 0 {
+        if let Some(mut u) = u {
+            zipped!(u.rb_mut()).for_each(|unzipped!(mut dst)| dst.write(E::faer_zero()));
+            zipped!(u
+                .submatrix_mut(0, 0, n, n)
+                .diagonal_mut()
+                .column_vector_mut()
+                .as_2d_mut())
+            .for_each(|unzipped!(mut dst)| dst.write(E::faer_one()));
+        }
+
+        return BidiagSvdStats::default();
+    }
+
+    let stats = if m as f64 / n as f64 <= 11.0 / 6.0 {
+        squareish_svd(
+            matrix,
+            s.rb_mut(),
+            u.rb_mut(),
+            v.rb_mut(),
+            epsilon,
+            zero_threshold,
+            parallelism,
+            stack.rb_mut(),
+            params,
+        )
+    } else {
+        // do a qr first, then do the svd
+        let householder_blocksize = faer_qr::no_pivoting::compute::recommended_blocksize::<E>(m, n);
+
+        let (mut qr, stack) = temp_mat_uninit::<E>(m, n, stack.rb_mut());
+        let mut qr = qr.as_mut();
+        let (mut householder, mut stack) = temp_mat_uninit::<E>(householder_blocksize, n, stack);
+        let mut householder = householder.as_mut();
+
+        let stats = {
+            let (mut r, mut stack) = temp_mat_uninit::<E>(n, n, stack.rb_mut());
+            let mut r = r.as_mut();
+
+            zipped!(qr.rb_mut(), matrix).for_each(|unzipped!(mut dst, src)| dst.write(src.read()));
+
+            // matrix = q * r
+            faer_qr::no_pivoting::compute::qr_in_place(
+                qr.rb_mut(),
+                householder.rb_mut(),
+                parallelism,
+                stack.rb_mut(),
+                Default::default(),
+            );
+            zipped!(r.rb_mut()).for_each_triangular_lower(Diag::Skip, |unzipped!(mut dst)| {
+                dst.write(E::faer_zero())
+            });
+            zipped!(r.rb_mut(), qr.rb().submatrix(0, 0, n, n))
+                .for_each_triangular_upper(Diag::Include, |unzipped!(mut dst, src)| {
+                    dst.write(src.read())
+                });
+
+            // r = u s v
+            squareish_svd(
+                r.rb(),
+                s.rb_mut(),
+                u.rb_mut().map(|u| u.submatrix_mut(0, 0, n, n)),
+                v.rb_mut(),
+                epsilon,
+                zero_threshold,
+                parallelism,
+                stack,
+                params,
+            )
+        };
+
+        // matrix = q u s v
+        if let Some(mut u) = u.rb_mut() {
+            let ncols = u.ncols();
+            zipped!(u.rb_mut().submatrix_mut(n, 0, m - n, n))
+                .for_each(|unzipped!(mut dst)| dst.write(E::faer_zero()));
+            zipped!(u.rb_mut().submatrix_mut(0, n, m, ncols - n))
+                .for_each(|unzipped!(mut dst)| dst.write(E::faer_zero()));
+            if ncols == m {
+                zipped!(u
+                    .rb_mut()
+                    .submatrix_mut(n, n, m - n, m - n)
+                    .diagonal_mut()
+                    .column_vector_mut()
+                    .as_2d_mut())
+                .for_each(|unzipped!(mut dst)| dst.write(E::faer_one()));
+            }
+
+            faer_core::householder::apply_block_householder_sequence_on_the_left_in_place_with_conj(
+                qr.rb(),
+                householder.rb(),
+                Conj::No,
+                u,
+                parallelism,
+                stack.rb_mut(),
+            );
+        }
+
+        stats
+    };
+
+    if do_transpose {
+        // conjugate u and v
+        if let Some(u) = u.rb_mut() {
+            zipped!(u).for_each(|unzipped!(mut x)| x.write(x.read().faer_conj()))
+        }
+        if let Some(v) = v.rb_mut() {
+            zipped!(v).for_each(|unzipped!(mut x)| x.write(x.read().faer_conj()))
+        }
         swap(&mut u, &mut v);
     }
 
-    let m = matrix.nrows();
-    let n = matrix.ncols();
+    if params.reorthogonalize {
+        for w in [u.rb_mut(), v.rb_mut()].into_iter().flatten() {
+            reorthogonalize(w);
+        }
+    }
+
+    sort_svd(s, u, v, params.sort, epsilon, stack);
+    stats
+}
+
+/// orthonormalizes the columns of `w` in place with modified Gram-Schmidt, assuming they're
+/// already close to orthonormal
+fn reorthogonalize<E: ComplexField>(mut w: MatMut<'_, E>) {
+    for j in 0..w.ncols() {
+        let (done, mut rest) = w.rb_mut().split_at_col_mut(j);
+        let done = done.into_const();
+        let mut col = rest.rb_mut().col_mut(0).as_2d_mut();
+        for i in 0..j {
+            let q = done.col(i).as_2d();
+            let dot =
+                faer_core::mul::inner_prod::inner_prod_with_conj(q, Conj::Yes, col.rb(), Conj::No);
+            zipped!(col.rb_mut(), q)
+                .for_each(|unzipped!(mut x, q)| x.write(x.read().faer_sub(dot.faer_mul(q.read()))));
+        }
+        let norm = faer_core::mul::inner_prod::inner_prod_with_conj(
+            col.rb(),
+            Conj::Yes,
+            col.rb(),
+            Conj::No,
+        )
+        .faer_real()
+        .faer_sqrt();
+        let inv = norm.faer_inv();
+        zipped!(col).for_each(|unzipped!(mut x)| x.write(x.read().faer_scale_real(inv)));
+    }
+}
+
+/// swaps the columns `i` and `j` of `u` and `v`
+fn swap_singular_vectors<E: ComplexField>(
+    u: Option<MatMut<'_, E>>,
+    v: Option<MatMut<'_, E>>,
+    i: usize,
+    j: usize,
+) {
+    if let Some(u) = u {
+        faer_core::permutation::swap_cols(u, i, j);
+    }
+    if let Some(v) = v {
+        faer_core::permutation::swap_cols(v, i, j);
+    }
+}
+
+/// reorders the singular values, initially in nonincreasing order, and the first `s.nrows()`
+/// columns of `u` and `v` according to `sort`
+fn sort_svd<E: ComplexField>(
+    mut s: MatMut<'_, E>,
+    mut u: Option<MatMut<'_, E>>,
+    mut v: Option<MatMut<'_, E>>,
+    sort: SvdSort,
+    epsilon: E::Real,
+    stack: PodStack<'_>,
+) {
+    let size = s.nrows();
+
+    match sort {
+        SvdSort::Descending => {}
+        SvdSort::Ascending => {
+            for i in 0..size / 2 {
+                let j = size - i - 1;
+                let si = s.read(i, 0);
+                s.write(i, 0, s.read(j, 0));
+                s.write(j, 0, si);
+                swap_singular_vectors(u.rb_mut(), v.rb_mut(), i, j);
+            }
+        }
+        SvdSort::StableDescending => {
+            let w = match (u.rb(), v.rb()) {
+                (_, Some(w)) | (Some(w), None) => w,
+                (None, None) => return,
+            };
+            let (pivots, _) = stack.make_with(size, |j| {
+                let mut pivot = 0;
+                let mut max = E::Real::faer_zero();
+                for i in 0..w.nrows() {
+                    let abs2 = w.read(i, j).faer_abs2();
+                    if abs2 > max {
+                        max = abs2;
+                        pivot = i;
+                    }
+                }
+                pivot
+            });
+
+            // scaling a pair of singular vectors by the same unit scalar leaves `u s v^H` unchanged
+            for (j, &pivot) in pivots.iter().enumerate() {
+                let x = match (u.rb(), v.rb()) {
+                    (_, Some(w)) | (Some(w), None) => w.read(pivot, j),
+                    (None, None) => unreachable!(),
+                };
+                if x == E::faer_zero() {
+                    continue;
+                }
+                let unit = x.faer_conj().faer_scale_real(x.faer_abs().faer_inv());
+                for w in [u.rb_mut(), v.rb_mut()].into_iter().flatten() {
+                    zipped!(w.col_mut(j).as_2d_mut())
+                        .for_each(|unzipped!(mut x)| x.write(x.read().faer_mul(unit)));
+                }
+            }
+
+            // the computed values of a repeated singular value differ by rounding errors, so the
+            // ones that are equal up to the accuracy of the decomposition are grouped together.
+            // only the singular vectors are swapped, which keeps the values sorted
+            let tol = epsilon
+                .faer_mul(E::Real::faer_from_f64(size as f64))
+                .faer_mul(s.read(0, 0).faer_real());
+            let mut start = 0;
+            while start < size {
+                let first = s.read(start, 0).faer_real();
+                let mut end = start + 1;
+                while end < size && first.faer_sub(s.read(end, 0).faer_real()) <= tol {
+                    end += 1;
+                }
+                for i in start..end {
+                    let mut k = i;
+                    for j in i + 1..end {
+                        if pivots[j] < pivots[k] {
+                            k = j;
+                        }
+                    }
+                    if k != i {
+                        pivots.swap(i, k);
+                        swap_singular_vectors(u.rb_mut(), v.rb_mut(), i, k);
+                    }
+                }
+                start = end;
+            }
+        }
+    }
+}
+
+fn squareish_svd<E: ComplexField>(
+    matrix: MatRef<E>,
+    s: MatMut<E>,
+    mut u: Option<MatMut<E>>,
+    mut v: Option<MatMut<E>>,
+    epsilon: E::Real,
+    zero_threshold: E::Real,
+    parallelism: Parallelism,
+    stack: PodStack,
+    params: SvdParams,
+) -> BidiagSvdStats {
+    let size = matrix.ncols();
+    if coe::is_same::<E, E::Real>() {
+        if use_real_svd_small(size, params.algorithm) {
+            compute_real_svd_small::<E::Real>(
+                matrix.coerce(),
+                s.coerce(),
+                u.rb_mut().map(coe::Coerce::coerce),
+                v.rb_mut().map(coe::Coerce::coerce),
+                coe::coerce_static(epsilon),
+                coe::coerce_static(zero_threshold),
+                parallelism,
+                stack,
+                params,
+            );
+            // the jacobi algorithm has no failure mode to report
+            BidiagSvdStats::default()
+        } else {
+            compute_svd_big::<E::Real>(
+                matrix.coerce(),
+                s.coerce(),
+                u.rb_mut().map(coe::Coerce::coerce),
+                v.rb_mut().map(coe::Coerce::coerce),
+                compute_bidiag_real_svd_with_stats::<E::Real>,
+                coe::coerce_static(epsilon),
+                coe::coerce_static(zero_threshold),
+                parallelism,
+                stack,
+                params,
+            )
+        }
+    } else {
+        compute_svd_big::<E>(
+            matrix.coerce(),
+            s,
+            u,
+            v,
+            compute_bidiag_cplx_svd::<E>,
+            coe::coerce_static(epsilon),
+            coe::coerce_static(zero_threshold),
+            parallelism,
+            stack,
+            params,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_approx_eq::assert_approx_eq;
+    use faer_core::{assert, c32, c64, Mat};
+
+    macro_rules! make_stack {
+        ($req: expr) => {
+            ::dyn_stack::PodStack::new(&mut ::dyn_stack::GlobalPodBuffer::new($req.unwrap()))
+        };
+    }
+
+    #[test]
+    fn test_real_big() {
+        for (m, n) in [(3, 2), (2, 2), (4, 4), (15, 10), (10, 10), (15, 15)] {
+            let mat = Mat::from_fn(m, n, |_, _| rand::random::<f64>());
+            let size = m.min(n);
+
+            let mut s = Mat::zeros(m, n);
+            let mut u = Mat::zeros(m, m);
+            let mut v = Mat::zeros(n, n);
+
+            compute_svd_big(
+                mat.as_ref(),
+                s.as_mut()
+                    .submatrix_mut(0, 0, size, size)
+                    .diagonal_mut()
+                    .column_vector_mut()
+                    .as_2d_mut(),
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                compute_bidiag_real_svd_with_stats::<f64>,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(compute_svd_big_req::<f64>(
+                    m,
+                    n,
+                    ComputeVectors::Full,
+                    ComputeVectors::Full,
+                    bidiag_real_svd_req::<f64>,
+                    Parallelism::None,
+                    SvdParams::default(),
+                )),
+                SvdParams::default(),
+            );
+
+            let reconstructed = &u * &s * v.transpose();
+
+            for j in 0..n {
+                for i in 0..m {
+                    assert_approx_eq!(reconstructed.read(i, j), mat.read(i, j), 1e-10);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_svd_cost_estimate() {
+        use ComputeVectors::{Full, No};
+        use SvdAlgorithm::*;
+
+        assert!(svd_cost_estimate(0, Full, Full, Auto) == 0);
+
+        let n = 1000;
+        let cost = |compute_vectors, algorithm| {
+            svd_cost_estimate(n, compute_vectors, compute_vectors, algorithm)
+        };
+        for algorithm in [
+            Auto,
+            OneSidedJacobi,
+            Jacobi,
+            QrIteration,
+            DivideConquer,
+            ClosedForm,
+        ] {
+            assert!(cost(No, algorithm) < cost(Full, algorithm));
+        }
+
+        // the closed form only applies to matrices of size 2, and falls back to the automatic
+        // selection otherwise
+        assert!(cost(Full, ClosedForm) == cost(Full, Auto));
+        assert!(
+            svd_cost_estimate(2, Full, Full, ClosedForm) < svd_cost_estimate(2, Full, Full, Jacobi)
+        );
+
+        // the divide and conquer algorithm is the fastest for large matrices with vectors, and
+        // is the one selected automatically
+        assert!(cost(Full, DivideConquer) < cost(Full, QrIteration));
+        assert!(cost(Full, QrIteration) < cost(Full, Jacobi));
+        assert!(cost(Full, Auto) == cost(Full, DivideConquer));
+        assert!(
+            svd_cost_estimate(64, Full, Full, Auto)
+                == svd_cost_estimate(64, Full, Full, QrIteration)
+        );
+
+        // the singular values alone are dominated by the bidiagonalization
+        let bidiag = (8.0 / 3.0) * (n as f64).powi(3);
+        for algorithm in [QrIteration, DivideConquer] {
+            assert!((cost(No, algorithm) as f64) < 1.1 * bidiag);
+        }
+    }
+
+    #[test]
+    fn test_real_identity() {
+        for (m, n) in [(15, 10), (10, 10), (15, 15)] {
+            let mut mat = Mat::zeros(m, n);
+            let size = m.min(n);
+            for i in 0..size {
+                mat.write(i, i, 1.0);
+            }
+
+            let mut s = Mat::zeros(m, n);
+            let mut u = Mat::zeros(m, m);
+            let mut v = Mat::zeros(n, n);
+
+            compute_svd_big(
+                mat.as_ref(),
+                s.as_mut()
+                    .submatrix_mut(0, 0, size, size)
+                    .diagonal_mut()
+                    .column_vector_mut()
+                    .as_2d_mut(),
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                compute_bidiag_real_svd_with_stats::<f64>,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(compute_svd_big_req::<f64>(
+                    m,
+                    n,
+                    ComputeVectors::Full,
+                    ComputeVectors::Full,
+                    bidiag_real_svd_req::<f64>,
+                    Parallelism::None,
+                    SvdParams::default(),
+                )),
+                SvdParams::default(),
+            );
+
+            let reconstructed = &u * &s * v.transpose();
+
+            for j in 0..n {
+                for i in 0..m {
+                    assert_approx_eq!(reconstructed.read(i, j), mat.read(i, j), 1e-10);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_real_zero() {
+        for (m, n) in [(15, 10), (10, 10), (15, 15)] {
+            let mat = Mat::zeros(m, n);
+            let size = m.min(n);
 
-    if n == 0 {
-        if let Some(mut u) = u {
-            zipped!(u.rb_mut()).for_each(|unzipped!(mut dst)| dst.write(E::faer_zero()));
-            zipped!(u
-                .submatrix_mut(0, 0, n, n)
-                .diagonal_mut()
-                .column_vector_mut()
-                .as_2d_mut())
-            .for_each(|unzipped!(mut dst)| dst.write(E::faer_one()));
+            let mut s = Mat::zeros(m, n);
+            let mut u = Mat::zeros(m, m);
+            let mut v = Mat::zeros(n, n);
+
+            compute_svd_big(
+                mat.as_ref(),
+                s.as_mut()
+                    .submatrix_mut(0, 0, size, size)
+                    .diagonal_mut()
+                    .column_vector_mut()
+                    .as_2d_mut(),
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                compute_bidiag_real_svd_with_stats::<f64>,
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(compute_svd_big_req::<f64>(
+                    m,
+                    n,
+                    ComputeVectors::Full,
+                    ComputeVectors::Full,
+                    bidiag_real_svd_req::<f64>,
+                    Parallelism::None,
+                    SvdParams::default(),
+                )),
+                SvdParams::default(),
+            );
+
+            let reconstructed = &u * &s * v.transpose();
+
+            for j in 0..n {
+                for i in 0..m {
+                    assert_approx_eq!(reconstructed.read(i, j), mat.read(i, j), 1e-10);
+                }
+            }
         }
+    }
 
-        return;
+    #[test]
+    fn test_real_small() {
+        for (m, n) in [(4, 4), (5, 5), (15, 10), (10, 10), (15, 15)] {
+            let mat = Mat::from_fn(m, n, |_, _| rand::random::<f64>());
+            let size = m.min(n);
+
+            let mut s = Mat::zeros(m, n);
+            let mut u = Mat::zeros(m, m);
+            let mut v = Mat::zeros(n, n);
+
+            compute_real_svd_small(
+                mat.as_ref(),
+                s.as_mut()
+                    .submatrix_mut(0, 0, size, size)
+                    .diagonal_mut()
+                    .column_vector_mut()
+                    .as_2d_mut(),
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                f64::EPSILON,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(compute_real_svd_small_req::<f64>(
+                    m,
+                    n,
+                    ComputeVectors::Full,
+                    ComputeVectors::Full,
+                    Parallelism::None,
+                )),
+                SvdParams::default(),
+            );
+
+            let reconstructed = &u * &s * v.transpose();
+
+            for j in 0..n {
+                for i in 0..m {
+                    assert_approx_eq!(reconstructed.read(i, j), mat.read(i, j), 1e-10);
+                }
+            }
+        }
     }
 
-    let _ = params;
+    #[test]
+    fn test_real() {
+        for m in 0..20 {
+            for n in 0..20 {
+                let mat = Mat::from_fn(m, n, |_, _| rand::random::<f64>());
+                let size = m.min(n);
 
-    if m as f64 / n as f64 <= 11.0 / 6.0 {
-        squareish_svd(
-            matrix,
-            s,
-            u.rb_mut(),
-            v.rb_mut(),
-            epsilon,
-            zero_threshold,
-            parallelism,
-            stack,
-        );
-    } else {
-        // do a qr first, then do the svd
-        let householder_blocksize = faer_qr::no_pivoting::compute::recommended_blocksize::<E>(m, n);
+                let mut s = Mat::zeros(m, n);
+                let mut u = Mat::zeros(m, m);
+                let mut v = Mat::zeros(n, n);
 
-        let (mut qr, stack) = temp_mat_uninit::<E>(m, n, stack);
-        let mut qr = qr.as_mut();
-        let (mut householder, mut stack) = temp_mat_uninit::<E>(householder_blocksize, n, stack);
-        let mut householder = householder.as_mut();
+                compute_svd(
+                    mat.as_ref(),
+                    s.as_mut()
+                        .submatrix_mut(0, 0, size, size)
+                        .diagonal_mut()
+                        .column_vector_mut()
+                        .as_2d_mut(),
+                    Some(u.as_mut()),
+                    Some(v.as_mut()),
+                    Parallelism::None,
+                    make_stack!(compute_svd_req::<f64>(
+                        m,
+                        n,
+                        ComputeVectors::Full,
+                        ComputeVectors::Full,
+                        Parallelism::None,
+                        SvdParams::default(),
+                    )),
+                    SvdParams::default(),
+                );
 
-        {
-            let (mut r, mut stack) = temp_mat_uninit::<E>(n, n, stack.rb_mut());
-            let mut r = r.as_mut();
+                let reconstructed = &u * &s * v.transpose();
 
-            zipped!(qr.rb_mut(), matrix).for_each(|unzipped!(mut dst, src)| dst.write(src.read()));
+                for j in 0..n {
+                    for i in 0..m {
+                        assert_approx_eq!(reconstructed.read(i, j), mat.read(i, j), 1e-10);
+                    }
+                }
+            }
+        }
+    }
 
-            // matrix = q * r
-            faer_qr::no_pivoting::compute::qr_in_place(
-                qr.rb_mut(),
-                householder.rb_mut(),
-                parallelism,
-                stack.rb_mut(),
-                Default::default(),
+    #[test]
+    fn test_real_leaf_size() {
+        let n = 300;
+        let mat = Mat::from_fn(n, n, |_, _| rand::random::<f64>());
+
+        let svd = |params: SvdParams, compute_vectors: bool| {
+            let vectors = if compute_vectors {
+                ComputeVectors::Full
+            } else {
+                ComputeVectors::No
+            };
+            let mut s = Mat::zeros(n, 1);
+            let mut u = Mat::zeros(n, n);
+            let mut v = Mat::zeros(n, n);
+            // the workspace is sized exactly, so this also checks that the requirement accounts
+            // for the leaf size
+            compute_svd(
+                mat.as_ref(),
+                s.as_mut(),
+                compute_vectors.then_some(u.as_mut()),
+                compute_vectors.then_some(v.as_mut()),
+                Parallelism::None,
+                make_stack!(compute_svd_req::<f64>(
+                    n,
+                    n,
+                    vectors,
+                    vectors,
+                    Parallelism::None,
+                    params,
+                )),
+                params,
             );
-            zipped!(r.rb_mut()).for_each_triangular_lower(Diag::Skip, |unzipped!(mut dst)| {
-                dst.write(E::faer_zero())
-            });
-            zipped!(r.rb_mut(), qr.rb().submatrix(0, 0, n, n))
-                .for_each_triangular_upper(Diag::Include, |unzipped!(mut dst, src)| {
-                    dst.write(src.read())
-                });
+            (s, u, v)
+        };
 
-            // r = u s v
-            squareish_svd(
-                r.rb(),
-                s,
-                u.rb_mut().map(|u| u.submatrix_mut(0, 0, n, n)),
-                v.rb_mut(),
-                epsilon,
-                zero_threshold,
-                parallelism,
-                stack,
-            );
+        let (s_ref, _, _) = svd(SvdParams::default(), false);
+        for (leaf_size_values, leaf_size_vectors) in [(Some(40), Some(16)), (Some(1000), Some(2))] {
+            let params = SvdParams {
+                leaf_size_values,
+                leaf_size_vectors,
+                ..Default::default()
+            };
+
+            let (s, _, _) = svd(params, false);
+            for i in 0..n {
+                assert_approx_eq!(s.read(i, 0), s_ref.read(i, 0), 1e-10);
+            }
+
+            let (s, u, v) = svd(params, true);
+            for i in 0..n {
+                assert_approx_eq!(s.read(i, 0), s_ref.read(i, 0), 1e-10);
+            }
+            let s = Mat::from_fn(n, n, |i, j| if i == j { s.read(i, 0) } else { 0.0 });
+            let reconstructed = &u * &s * v.transpose();
+            for j in 0..n {
+                for i in 0..n {
+                    assert_approx_eq!(reconstructed.read(i, j), mat.read(i, j), 1e-10);
+                }
+            }
         }
+    }
 
-        // matrix = q u s v
-        if let Some(mut u) = u.rb_mut() {
-            let ncols = u.ncols();
-            zipped!(u.rb_mut().submatrix_mut(n, 0, m - n, n))
-                .for_each(|unzipped!(mut dst)| dst.write(E::faer_zero()));
-            zipped!(u.rb_mut().submatrix_mut(0, n, m, ncols - n))
-                .for_each(|unzipped!(mut dst)| dst.write(E::faer_zero()));
-            if ncols == m {
-                zipped!(u
-                    .rb_mut()
-                    .submatrix_mut(n, n, m - n, m - n)
-                    .diagonal_mut()
-                    .column_vector_mut()
-                    .as_2d_mut())
-                .for_each(|unzipped!(mut dst)| dst.write(E::faer_one()));
+    #[test]
+    fn test_real_wide() {
+        // a wide matrix is decomposed through its transpose, so decomposing it directly must give
+        // the same result as decomposing its transpose and swapping the singular vectors, up to
+        // the sign of each pair of singular vectors
+        for (m, n) in [(30, 200), (4, 7), (30, 45), (200, 200)] {
+            let mat = Mat::from_fn(m, n, |_, _| rand::random::<f64>());
+            let size = m.min(n);
+
+            let svd = |mat: MatRef<'_, f64>| {
+                let (m, n) = (mat.nrows(), mat.ncols());
+                let mut s = Mat::zeros(size, 1);
+                let mut u = Mat::zeros(m, m);
+                let mut v = Mat::zeros(n, n);
+                compute_svd(
+                    mat,
+                    s.as_mut(),
+                    Some(u.as_mut()),
+                    Some(v.as_mut()),
+                    Parallelism::None,
+                    make_stack!(compute_svd_req::<f64>(
+                        m,
+                        n,
+                        ComputeVectors::Full,
+                        ComputeVectors::Full,
+                        Parallelism::None,
+                        SvdParams::default(),
+                    )),
+                    SvdParams::default(),
+                );
+                (s, u, v)
+            };
+
+            let (s, u, v) = svd(mat.as_ref());
+            let (s_t, u_t, v_t) = svd(mat.transpose());
+            for k in 0..size {
+                assert_approx_eq!(s.read(k, 0), s_t.read(k, 0), 1e-10);
+                let sign = if u.read(0, k) * v_t.read(0, k) < 0.0 {
+                    -1.0
+                } else {
+                    1.0
+                };
+                for i in 0..m {
+                    assert_approx_eq!(u.read(i, k), sign * v_t.read(i, k), 1e-8);
+                }
+                for j in 0..n {
+                    assert_approx_eq!(v.read(j, k), sign * u_t.read(j, k), 1e-8);
+                }
             }
 
-            faer_core::householder::apply_block_householder_sequence_on_the_left_in_place_with_conj(
-                qr.rb(),
-                householder.rb(),
-                Conj::No,
-                u,
-                parallelism,
-                stack.rb_mut(),
-            );
+            let mut s_full = Mat::zeros(m, n);
+            for i in 0..size {
+                s_full.write(i, i, s.read(i, 0));
+            }
+            let reconstructed = &u * &s_full * v.transpose();
+            for j in 0..n {
+                for i in 0..m {
+                    assert_approx_eq!(reconstructed.read(i, j), mat.read(i, j), 1e-10);
+                }
+            }
         }
     }
 
-    if do_transpose {
-        // conjugate u and v
-        if let Some(u) = u {
-            zipped!(u).for_each(|unzipped!(mut x)| x.write(x.read().faer_conj()))
+    #[test]
+    fn test_real_very_wide() {
+        let (m, n) = (20, 20000);
+        let mat = Mat::from_fn(m, n, |_, _| rand::random::<f64>());
+
+        // the matrix is reduced by a qr decomposition along its rows, and the workspace doesn't
+        // contain any `(n, n)` matrix
+        let req = compute_svd_req::<f64>(
+            m,
+            n,
+            ComputeVectors::Thin,
+            ComputeVectors::Thin,
+            Parallelism::None,
+            SvdParams::default(),
+        )
+        .unwrap();
+        assert!(req.size_bytes() < 4 * m * n * core::mem::size_of::<f64>());
+
+        let mut s = Mat::zeros(m, 1);
+        let mut u = Mat::zeros(m, m);
+        let mut v = Mat::zeros(n, m);
+        compute_svd(
+            mat.as_ref(),
+            s.as_mut(),
+            Some(u.as_mut()),
+            Some(v.as_mut()),
+            Parallelism::None,
+            PodStack::new(&mut ::dyn_stack::GlobalPodBuffer::new(req)),
+            SvdParams::default(),
+        );
+
+        let reconstructed =
+            &u * Mat::from_fn(m, m, |i, j| if i == j { s.read(i, 0) } else { 0.0 }) * v.transpose();
+        for j in 0..n {
+            for i in 0..m {
+                assert_approx_eq!(reconstructed.read(i, j), mat.read(i, j), 1e-10);
+            }
         }
-        if let Some(v) = v {
-            zipped!(v).for_each(|unzipped!(mut x)| x.write(x.read().faer_conj()))
+        let vtv = v.transpose() * &v;
+        for j in 0..m {
+            for i in 0..m {
+                assert_approx_eq!(vtv.read(i, j), if i == j { 1.0 } else { 0.0 }, 1e-10);
+            }
         }
     }
-}
 
-fn squareish_svd<E: ComplexField>(
-    matrix: MatRef<E>,
-    s: MatMut<E>,
-    mut u: Option<MatMut<E>>,
-    mut v: Option<MatMut<E>>,
-    epsilon: E::Real,
-    zero_threshold: E::Real,
-    parallelism: Parallelism,
-    stack: PodStack,
-) {
-    let size = matrix.ncols();
-    if coe::is_same::<E, E::Real>() {
-        if size <= JACOBI_FALLBACK_THRESHOLD {
-            compute_real_svd_small::<E::Real>(
-                matrix.coerce(),
-                s.coerce(),
-                u.rb_mut().map(coe::Coerce::coerce),
-                v.rb_mut().map(coe::Coerce::coerce),
-                coe::coerce_static(epsilon),
-                coe::coerce_static(zero_threshold),
-                parallelism,
-                stack,
-            );
-        } else {
-            compute_svd_big::<E::Real>(
-                matrix.coerce(),
-                s.coerce(),
-                u.rb_mut().map(coe::Coerce::coerce),
-                v.rb_mut().map(coe::Coerce::coerce),
-                compute_bidiag_real_svd::<E::Real>,
-                coe::coerce_static(epsilon),
-                coe::coerce_static(zero_threshold),
-                parallelism,
-                stack,
-            );
-        }
-    } else {
-        compute_svd_big::<E>(
-            matrix.coerce(),
-            s,
-            u,
-            v,
-            compute_bidiag_cplx_svd::<E>,
-            coe::coerce_static(epsilon),
-            coe::coerce_static(zero_threshold),
-            parallelism,
-            stack,
-        );
-    }
-}
+    #[test]
+    fn test_real_f32() {
+        for m in 0..20 {
+            for n in 0..20 {
+                let mat = Mat::from_fn(m, n, |_, _| rand::random::<f32>());
+                let size = m.min(n);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use assert_approx_eq::assert_approx_eq;
-    use faer_core::{assert, c32, c64, Mat};
+                let mut s = Mat::zeros(m, n);
+                let mut u = Mat::zeros(m, m);
+                let mut v = Mat::zeros(n, n);
 
-    macro_rules! make_stack {
-        ($req: expr) => {
-            ::dyn_stack::PodStack::new(&mut ::dyn_stack::GlobalPodBuffer::new($req.unwrap()))
-        };
+                compute_svd(
+                    mat.as_ref(),
+                    s.as_mut()
+                        .submatrix_mut(0, 0, size, size)
+                        .diagonal_mut()
+                        .column_vector_mut()
+                        .as_2d_mut(),
+                    Some(u.as_mut()),
+                    Some(v.as_mut()),
+                    Parallelism::None,
+                    make_stack!(compute_svd_req::<f32>(
+                        m,
+                        n,
+                        ComputeVectors::Full,
+                        ComputeVectors::Full,
+                        Parallelism::None,
+                        SvdParams::default(),
+                    )),
+                    SvdParams::default(),
+                );
+
+                let reconstructed = &u * &s * v.transpose();
+
+                for j in 0..n {
+                    for i in 0..m {
+                        assert_approx_eq!(reconstructed.read(i, j), mat.read(i, j), 1e-3);
+                    }
+                }
+            }
+        }
     }
 
     #[test]
-    fn test_real_big() {
-        for (m, n) in [(3, 2), (2, 2), (4, 4), (15, 10), (10, 10), (15, 15)] {
+    fn test_real_one_sided_jacobi() {
+        let params = SvdParams {
+            algorithm: SvdAlgorithm::OneSidedJacobi,
+            ..Default::default()
+        };
+        for (m, n) in [
+            (0, 0),
+            (3, 2),
+            (2, 3),
+            (10, 10),
+            (15, 10),
+            (10, 15),
+            (40, 10),
+        ] {
             let mat = Mat::from_fn(m, n, |_, _| rand::random::<f64>());
             let size = m.min(n);
 
@@ -958,7 +2656,7 @@ mod tests {
             let mut u = Mat::zeros(m, m);
             let mut v = Mat::zeros(n, n);
 
-            compute_svd_big(
+            compute_svd(
                 mat.as_ref(),
                 s.as_mut()
                     .submatrix_mut(0, 0, size, size)
@@ -967,18 +2665,16 @@ mod tests {
                     .as_2d_mut(),
                 Some(u.as_mut()),
                 Some(v.as_mut()),
-                compute_bidiag_real_svd::<f64>,
-                f64::EPSILON,
-                f64::MIN_POSITIVE,
                 Parallelism::None,
-                make_stack!(compute_svd_big_req::<f64>(
+                make_stack!(compute_svd_req::<f64>(
                     m,
                     n,
                     ComputeVectors::Full,
                     ComputeVectors::Full,
-                    bidiag_real_svd_req::<f64>,
                     Parallelism::None,
+                    params,
                 )),
+                params,
             );
 
             let reconstructed = &u * &s * v.transpose();
@@ -992,63 +2688,234 @@ mod tests {
     }
 
     #[test]
-    fn test_real_identity() {
-        for (m, n) in [(15, 10), (10, 10), (15, 15)] {
-            let mut mat = Mat::zeros(m, n);
-            let size = m.min(n);
-            for i in 0..size {
-                mat.write(i, i, 1.0);
-            }
+    fn test_real_one_sided_jacobi_graded() {
+        for (m, n) in [(8, 8), (12, 8), (30, 8)] {
+            // orthonormal columns
+            let q = {
+                let mat = Mat::from_fn(m, n, |_, _| rand::random::<f64>());
+                let mut s = Mat::zeros(n, 1);
+                let mut u = Mat::zeros(m, n);
+                compute_svd(
+                    mat.as_ref(),
+                    s.as_mut(),
+                    Some(u.as_mut()),
+                    None,
+                    Parallelism::None,
+                    make_stack!(compute_svd_req::<f64>(
+                        m,
+                        n,
+                        ComputeVectors::Thin,
+                        ComputeVectors::No,
+                        Parallelism::None,
+                        SvdParams::default(),
+                    )),
+                    SvdParams::default(),
+                );
+                u
+            };
+
+            // the columns are scaled by factors ranging from 1 to 1e-14, so the singular values
+            // are (up to a tiny relative perturbation) the scaling factors
+            let scale = |j: usize| 10.0f64.powi(-2 * ((3 * j) % n) as i32);
+            let mat = Mat::from_fn(m, n, |i, j| q.read(i, j) * scale(j));
+            let mut expected = (0..n).map(scale).collect::<Vec<_>>();
+            expected.sort_by(|a, b| b.partial_cmp(a).unwrap());
+
+            let params = SvdParams {
+                algorithm: SvdAlgorithm::OneSidedJacobi,
+                ..Default::default()
+            };
+            let mut s = Mat::zeros(n, 1);
+            compute_svd(
+                mat.as_ref(),
+                s.as_mut(),
+                None,
+                None,
+                Parallelism::None,
+                make_stack!(compute_svd_req::<f64>(
+                    m,
+                    n,
+                    ComputeVectors::No,
+                    ComputeVectors::No,
+                    Parallelism::None,
+                    params,
+                )),
+                params,
+            );
+
+            for (i, &expected) in expected.iter().enumerate() {
+                assert!((s.read(i, 0) - expected).abs() <= 1e-12 * expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_real_thin() {
+        for m in 0..20 {
+            for n in 0..20 {
+                use ComputeVectors::*;
+                for compute_u in [No, Thin, Full] {
+                    for compute_v in [No, Thin, Full] {
+                        #[cfg(feature = "std")]
+                        dbg!(m, n, compute_u, compute_v);
+                        let mat = Mat::from_fn(m, n, |_, _| rand::random::<f64>());
+                        let size = m.min(n);
+
+                        let mut s = Mat::zeros(m, n);
+                        let mut u = Mat::zeros(
+                            m,
+                            match compute_u {
+                                No => 0,
+                                Thin => size,
+                                Full => m,
+                            },
+                        );
+                        let mut v = Mat::zeros(
+                            n,
+                            match compute_v {
+                                No => 0,
+                                Thin => size,
+                                Full => n,
+                            },
+                        );
+
+                        compute_svd(
+                            mat.as_ref(),
+                            s.as_mut()
+                                .submatrix_mut(0, 0, size, size)
+                                .diagonal_mut()
+                                .column_vector_mut()
+                                .as_2d_mut(),
+                            if compute_u == No {
+                                None
+                            } else {
+                                Some(u.as_mut())
+                            },
+                            if compute_v == No {
+                                None
+                            } else {
+                                Some(v.as_mut())
+                            },
+                            Parallelism::None,
+                            make_stack!(compute_svd_req::<f64>(
+                                m,
+                                n,
+                                compute_u,
+                                compute_v,
+                                Parallelism::None,
+                                SvdParams::default(),
+                            )),
+                            SvdParams::default(),
+                        );
+
+                        let mut s_target = Mat::zeros(m, n);
+                        let mut u_target = Mat::zeros(m, m);
+                        let mut v_target = Mat::zeros(n, n);
+
+                        compute_svd(
+                            mat.as_ref(),
+                            s_target
+                                .as_mut()
+                                .submatrix_mut(0, 0, size, size)
+                                .diagonal_mut()
+                                .column_vector_mut()
+                                .as_2d_mut(),
+                            Some(u_target.as_mut()),
+                            Some(v_target.as_mut()),
+                            Parallelism::None,
+                            make_stack!(compute_svd_req::<f64>(
+                                m,
+                                n,
+                                ComputeVectors::Full,
+                                ComputeVectors::Full,
+                                Parallelism::None,
+                                SvdParams::default(),
+                            )),
+                            SvdParams::default(),
+                        );
+
+                        for j in 0..u.ncols() {
+                            for i in 0..u.nrows() {
+                                assert_approx_eq!(u.read(i, j), u_target.read(i, j), 1e-10);
+                            }
+                        }
+                        for j in 0..v.ncols() {
+                            for i in 0..v.nrows() {
+                                assert_approx_eq!(v.read(i, j), v_target.read(i, j), 1e-10);
+                            }
+                        }
+                        for j in 0..s.ncols() {
+                            for i in 0..s.nrows() {
+                                assert_approx_eq!(s.read(i, j), s_target.read(i, j), 1e-10);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_cplx() {
+        for m in 0..20 {
+            for n in 0..20 {
+                let mat = Mat::from_fn(m, n, |_, _| c64::new(rand::random(), rand::random()));
+                let size = m.min(n);
 
-            let mut s = Mat::zeros(m, n);
-            let mut u = Mat::zeros(m, m);
-            let mut v = Mat::zeros(n, n);
+                let mut s = Mat::zeros(m, n);
+                let mut u = Mat::zeros(m, m);
+                let mut v = Mat::zeros(n, n);
 
-            compute_svd_big(
-                mat.as_ref(),
-                s.as_mut()
-                    .submatrix_mut(0, 0, size, size)
-                    .diagonal_mut()
-                    .column_vector_mut()
-                    .as_2d_mut(),
-                Some(u.as_mut()),
-                Some(v.as_mut()),
-                compute_bidiag_real_svd::<f64>,
-                f64::EPSILON,
-                f64::MIN_POSITIVE,
-                Parallelism::None,
-                make_stack!(compute_svd_big_req::<f64>(
-                    m,
-                    n,
-                    ComputeVectors::Full,
-                    ComputeVectors::Full,
-                    bidiag_real_svd_req::<f64>,
+                compute_svd(
+                    mat.as_ref(),
+                    s.as_mut()
+                        .submatrix_mut(0, 0, size, size)
+                        .diagonal_mut()
+                        .column_vector_mut()
+                        .as_2d_mut(),
+                    Some(u.as_mut()),
+                    Some(v.as_mut()),
                     Parallelism::None,
-                )),
-            );
+                    make_stack!(compute_svd_req::<c64>(
+                        m,
+                        n,
+                        ComputeVectors::Full,
+                        ComputeVectors::Full,
+                        Parallelism::None,
+                        SvdParams::default(),
+                    )),
+                    SvdParams::default(),
+                );
 
-            let reconstructed = &u * &s * v.transpose();
+                let reconstructed = &u * &s * v.adjoint();
 
-            for j in 0..n {
-                for i in 0..m {
-                    assert_approx_eq!(reconstructed.read(i, j), mat.read(i, j), 1e-10);
+                for j in 0..n {
+                    for i in 0..m {
+                        assert_approx_eq!(reconstructed.read(i, j), mat.read(i, j), 1e-10);
+                    }
                 }
             }
         }
     }
 
     #[test]
-    fn test_real_zero() {
-        for (m, n) in [(15, 10), (10, 10), (15, 15)] {
-            let mat = Mat::zeros(m, n);
+    fn test_cplx_conj() {
+        for (m, n) in [(0, 3), (7, 4), (4, 7), (20, 20), (40, 5)] {
+            let mat = Mat::from_fn(m, n, |_, _| c64::new(rand::random(), rand::random()));
             let size = m.min(n);
 
-            let mut s = Mat::zeros(m, n);
-            let mut u = Mat::zeros(m, m);
-            let mut v = Mat::zeros(n, n);
+            // the adjoint is a strided view of `mat` with a conjugation flag
+            let (adjoint, conj) = mat.adjoint().canonicalize();
+            assert!(conj == Conj::Yes);
+            assert!(adjoint.as_ptr() == mat.as_ptr());
 
-            compute_svd_big(
-                mat.as_ref(),
+            let mut s = Mat::zeros(n, m);
+            let mut u = Mat::zeros(n, n);
+            let mut v = Mat::zeros(m, m);
+
+            compute_svd_with_conj(
+                adjoint,
+                conj,
                 s.as_mut()
                     .submatrix_mut(0, 0, size, size)
                     .diagonal_mut()
@@ -1056,93 +2923,74 @@ mod tests {
                     .as_2d_mut(),
                 Some(u.as_mut()),
                 Some(v.as_mut()),
-                compute_bidiag_real_svd::<f64>,
-                f64::EPSILON,
-                f64::MIN_POSITIVE,
                 Parallelism::None,
-                make_stack!(compute_svd_big_req::<f64>(
-                    m,
+                make_stack!(compute_svd_req::<c64>(
                     n,
+                    m,
                     ComputeVectors::Full,
                     ComputeVectors::Full,
-                    bidiag_real_svd_req::<f64>,
                     Parallelism::None,
+                    SvdParams::default(),
                 )),
+                SvdParams::default(),
             );
 
-            let reconstructed = &u * &s * v.transpose();
-
-            for j in 0..n {
-                for i in 0..m {
-                    assert_approx_eq!(reconstructed.read(i, j), mat.read(i, j), 1e-10);
+            let reconstructed = &u * &s * v.adjoint();
+            for j in 0..m {
+                for i in 0..n {
+                    assert_approx_eq!(reconstructed.read(i, j), mat.read(j, i).conj(), 1e-10);
                 }
             }
         }
-    }
-
-    #[test]
-    fn test_real_small() {
-        for (m, n) in [(4, 4), (5, 5), (15, 10), (10, 10), (15, 15)] {
-            let mat = Mat::from_fn(m, n, |_, _| rand::random::<f64>());
-            let size = m.min(n);
-
-            let mut s = Mat::zeros(m, n);
-            let mut u = Mat::zeros(m, m);
-            let mut v = Mat::zeros(n, n);
 
-            compute_real_svd_small(
-                mat.as_ref(),
-                s.as_mut()
-                    .submatrix_mut(0, 0, size, size)
-                    .diagonal_mut()
-                    .column_vector_mut()
-                    .as_2d_mut(),
-                Some(u.as_mut()),
-                Some(v.as_mut()),
-                f64::EPSILON,
-                f64::MIN_POSITIVE,
+        // conjugating a real matrix does nothing
+        let mat = Mat::from_fn(6, 4, |_, _| rand::random::<f64>());
+        let mut s = Mat::zeros(4, 1);
+        let mut u = Mat::zeros(4, 4);
+        let mut v = Mat::zeros(6, 6);
+        compute_svd_with_conj(
+            mat.transpose(),
+            Conj::Yes,
+            s.as_mut(),
+            Some(u.as_mut()),
+            Some(v.as_mut()),
+            Parallelism::None,
+            make_stack!(compute_svd_req::<f64>(
+                4,
+                6,
+                ComputeVectors::Full,
+                ComputeVectors::Full,
                 Parallelism::None,
-                make_stack!(compute_real_svd_small_req::<f64>(
-                    m,
-                    n,
-                    ComputeVectors::Full,
-                    ComputeVectors::Full,
-                    Parallelism::None,
-                )),
-            );
-
-            let reconstructed = &u * &s * v.transpose();
-
-            for j in 0..n {
-                for i in 0..m {
-                    assert_approx_eq!(reconstructed.read(i, j), mat.read(i, j), 1e-10);
-                }
+                SvdParams::default(),
+            )),
+            SvdParams::default(),
+        );
+        let s = Mat::from_fn(4, 6, |i, j| if i == j { s.read(i, 0) } else { 0.0 });
+        let reconstructed = &u * &s * v.transpose();
+        for j in 0..6 {
+            for i in 0..4 {
+                assert_approx_eq!(reconstructed.read(i, j), mat.read(j, i), 1e-10);
             }
         }
     }
 
     #[test]
-    fn test_real() {
-        for m in 0..20 {
-            for n in 0..20 {
-                let mat = Mat::from_fn(m, n, |_, _| rand::random::<f64>());
-                let size = m.min(n);
-
-                let mut s = Mat::zeros(m, n);
-                let mut u = Mat::zeros(m, m);
-                let mut v = Mat::zeros(n, n);
+    fn test_f32_vectors() {
+        for (m, n) in [(0, 3), (7, 4), (4, 7), (20, 20), (60, 5)] {
+            let mat = Mat::from_fn(m, n, |_, _| rand::random::<f64>());
+            let size = m.min(n);
 
-                compute_svd(
+            for (u_ncols, v_ncols) in [(m, n), (size, size)] {
+                let mut s = Mat::<f64>::zeros(size, 1);
+                let mut u = Mat::<f32>::zeros(m, u_ncols);
+                let mut v = Mat::<f32>::zeros(n, v_ncols);
+                compute_svd_with_f32_vectors(
                     mat.as_ref(),
-                    s.as_mut()
-                        .submatrix_mut(0, 0, size, size)
-                        .diagonal_mut()
-                        .column_vector_mut()
-                        .as_2d_mut(),
+                    s.as_mut(),
                     Some(u.as_mut()),
                     Some(v.as_mut()),
                     Parallelism::None,
-                    make_stack!(compute_svd_req::<f64>(
+                    make_stack!(compute_svd_with_f32_vectors_req(
                         m,
                         n,
                         ComputeVectors::Full,
@@ -1153,39 +3001,16 @@ mod tests {
                     SvdParams::default(),
                 );
 
-                let reconstructed = &u * &s * v.transpose();
-
-                for j in 0..n {
-                    for i in 0..m {
-                        assert_approx_eq!(reconstructed.read(i, j), mat.read(i, j), 1e-10);
-                    }
-                }
-            }
-        }
-    }
-
-    #[test]
-    fn test_real_f32() {
-        for m in 0..20 {
-            for n in 0..20 {
-                let mat = Mat::from_fn(m, n, |_, _| rand::random::<f32>());
-                let size = m.min(n);
-
-                let mut s = Mat::zeros(m, n);
-                let mut u = Mat::zeros(m, m);
-                let mut v = Mat::zeros(n, n);
-
+                let mut s64 = Mat::<f64>::zeros(size, 1);
+                let mut u64 = Mat::<f64>::zeros(m, u_ncols);
+                let mut v64 = Mat::<f64>::zeros(n, v_ncols);
                 compute_svd(
                     mat.as_ref(),
-                    s.as_mut()
-                        .submatrix_mut(0, 0, size, size)
-                        .diagonal_mut()
-                        .column_vector_mut()
-                        .as_2d_mut(),
-                    Some(u.as_mut()),
-                    Some(v.as_mut()),
+                    s64.as_mut(),
+                    Some(u64.as_mut()),
+                    Some(v64.as_mut()),
                     Parallelism::None,
-                    make_stack!(compute_svd_req::<f32>(
+                    make_stack!(compute_svd_req::<f64>(
                         m,
                         n,
                         ComputeVectors::Full,
@@ -1196,11 +3021,29 @@ mod tests {
                     SvdParams::default(),
                 );
 
-                let reconstructed = &u * &s * v.transpose();
+                // the values and vectors are computed in f64, only the stored vectors are rounded
+                assert!(s == s64);
+                for j in 0..u_ncols {
+                    for i in 0..m {
+                        assert!(u.read(i, j) == u64.read(i, j) as f32);
+                    }
+                }
+                for j in 0..v_ncols {
+                    for i in 0..n {
+                        assert!(v.read(i, j) == v64.read(i, j) as f32);
+                    }
+                }
 
+                let u = Mat::<f64>::from_fn(m, u_ncols, |i, j| u.read(i, j) as f64);
+                let v = Mat::<f64>::from_fn(n, v_ncols, |i, j| v.read(i, j) as f64);
+                let mut s_mat = Mat::<f64>::zeros(u_ncols, v_ncols);
+                for i in 0..size {
+                    s_mat.write(i, i, s.read(i, 0));
+                }
+                let reconstructed = &u * &s_mat * v.transpose();
                 for j in 0..n {
                     for i in 0..m {
-                        assert_approx_eq!(reconstructed.read(i, j), mat.read(i, j), 1e-3);
+                        assert_approx_eq!(reconstructed.read(i, j), mat.read(i, j), 1e-5);
                     }
                 }
             }
@@ -1208,149 +3051,442 @@ mod tests {
     }
 
     #[test]
-    fn test_real_thin() {
-        for m in 0..20 {
-            for n in 0..20 {
-                use ComputeVectors::*;
-                for compute_u in [No, Thin, Full] {
-                    for compute_v in [No, Thin, Full] {
-                        dbg!(m, n, compute_u, compute_v);
-                        let mat = Mat::from_fn(m, n, |_, _| rand::random::<f64>());
-                        let size = m.min(n);
+    fn test_try_compute_svd() {
+        let (m, n) = (13, 7);
+        let req = || {
+            compute_svd_req::<f64>(
+                m,
+                n,
+                ComputeVectors::Full,
+                ComputeVectors::Full,
+                Parallelism::None,
+                SvdParams::default(),
+            )
+        };
+        let mat = Mat::from_fn(m, n, |_, _| rand::random::<f64>());
 
-                        let mut s = Mat::zeros(m, n);
-                        let mut u = Mat::zeros(
-                            m,
-                            match compute_u {
-                                No => 0,
-                                Thin => size,
-                                Full => m,
-                            },
-                        );
-                        let mut v = Mat::zeros(
-                            n,
-                            match compute_v {
-                                No => 0,
-                                Thin => size,
-                                Full => n,
-                            },
-                        );
+        let mut s = Mat::<f64>::zeros(n, 1);
+        let mut u = Mat::<f64>::zeros(m, m);
+        let mut v = Mat::<f64>::zeros(n, n);
+        assert!(
+            try_compute_svd(
+                mat.as_ref(),
+                s.as_mut(),
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                Parallelism::None,
+                make_stack!(req()),
+                SvdParams::default(),
+            ) == Ok(())
+        );
+
+        let mut s_ref = Mat::<f64>::zeros(n, 1);
+        compute_svd(
+            mat.as_ref(),
+            s_ref.as_mut(),
+            None,
+            None,
+            Parallelism::None,
+            make_stack!(req()),
+            SvdParams::default(),
+        );
+        assert!(s == s_ref);
+
+        // wrong number of singular values
+        let mut bad_s = Mat::<f64>::zeros(m, 1);
+        assert!(
+            try_compute_svd(
+                mat.as_ref(),
+                bad_s.as_mut(),
+                None,
+                None,
+                Parallelism::None,
+                make_stack!(req()),
+                SvdParams::default(),
+            ) == Err(SvdError::ShapeMismatch)
+        );
+
+        // u is neither full nor thin
+        let mut bad_u = Mat::<f64>::zeros(m, n + 1);
+        assert!(
+            try_compute_svd(
+                mat.as_ref(),
+                s.as_mut(),
+                Some(bad_u.as_mut()),
+                None,
+                Parallelism::None,
+                make_stack!(req()),
+                SvdParams::default(),
+            ) == Err(SvdError::ShapeMismatch)
+        );
+
+        // empty workspace
+        assert!(
+            try_compute_svd(
+                mat.as_ref(),
+                s.as_mut(),
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                Parallelism::None,
+                make_stack!(Ok::<_, SizeOverflow>(StackReq::empty())),
+                SvdParams::default(),
+            ) == Err(SvdError::Alloc)
+        );
+
+        let mut nan_mat = mat.clone();
+        nan_mat.write(3, 2, f64::NAN);
+        assert!(
+            try_compute_svd(
+                nan_mat.as_ref(),
+                s.as_mut(),
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                Parallelism::None,
+                make_stack!(req()),
+                SvdParams::default(),
+            ) == Err(SvdError::NonFinite)
+        );
+    }
+
+    #[test]
+    fn test_try_compute_svd_verify() {
+        let (m, n) = (13, 7);
+        let mat = Mat::from_fn(m, n, |_, _| rand::random::<f64>());
+        let params = SvdParams {
+            verify: true,
+            ..Default::default()
+        };
+
+        let mut s = Mat::<f64>::zeros(n, 1);
+        let mut u = Mat::<f64>::zeros(m, n);
+        let mut v = Mat::<f64>::zeros(n, n);
+        assert!(
+            try_compute_svd(
+                mat.as_ref(),
+                s.as_mut(),
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                Parallelism::None,
+                make_stack!(compute_svd_req::<f64>(
+                    m,
+                    n,
+                    ComputeVectors::Thin,
+                    ComputeVectors::Full,
+                    Parallelism::None,
+                    params,
+                )),
+                params,
+            ) == Ok(())
+        );
+        assert!(
+            verify_svd(
+                mat.as_ref(),
+                s.as_ref(),
+                u.as_ref(),
+                v.as_ref(),
+                f64::EPSILON
+            ) == Ok(())
+        );
 
-                        compute_svd(
-                            mat.as_ref(),
-                            s.as_mut()
-                                .submatrix_mut(0, 0, size, size)
-                                .diagonal_mut()
-                                .column_vector_mut()
-                                .as_2d_mut(),
-                            if compute_u == No {
-                                None
-                            } else {
-                                Some(u.as_mut())
-                            },
-                            if compute_v == No {
-                                None
-                            } else {
-                                Some(v.as_mut())
-                            },
-                            Parallelism::None,
-                            make_stack!(compute_svd_req::<f64>(
-                                m,
-                                n,
-                                compute_u,
-                                compute_v,
-                                Parallelism::None,
-                                SvdParams::default(),
-                            )),
-                            SvdParams::default(),
-                        );
+        // a corrupted singular vector, and a corrupted singular value
+        let mut bad_u = u.clone();
+        bad_u.write(0, n - 1, bad_u.read(0, n - 1) + 1e-8);
+        assert!(
+            verify_svd(
+                mat.as_ref(),
+                s.as_ref(),
+                bad_u.as_ref(),
+                v.as_ref(),
+                f64::EPSILON
+            ) == Err(SvdError::VerificationFailed)
+        );
+        let mut bad_s = s.clone();
+        bad_s.write(0, 0, bad_s.read(0, 0) * (1.0 + 1e-10));
+        assert!(
+            verify_svd(
+                mat.as_ref(),
+                bad_s.as_ref(),
+                u.as_ref(),
+                v.as_ref(),
+                f64::EPSILON
+            ) == Err(SvdError::VerificationFailed)
+        );
+    }
 
-                        let mut s_target = Mat::zeros(m, n);
-                        let mut u_target = Mat::zeros(m, m);
-                        let mut v_target = Mat::zeros(n, n);
+    #[test]
+    fn test_svd_lapack_layout() {
+        for (m, n) in [(15, 9), (9, 15), (12, 12)] {
+            let mat = Mat::from_fn(m, n, |_, _| c64::new(rand::random(), rand::random()));
+            let size = m.min(n);
 
-                        compute_svd(
-                            mat.as_ref(),
-                            s_target
-                                .as_mut()
-                                .submatrix_mut(0, 0, size, size)
-                                .diagonal_mut()
-                                .column_vector_mut()
-                                .as_2d_mut(),
-                            Some(u_target.as_mut()),
-                            Some(v_target.as_mut()),
-                            Parallelism::None,
-                            make_stack!(compute_svd_req::<f64>(
-                                m,
-                                n,
-                                ComputeVectors::Full,
-                                ComputeVectors::Full,
-                                Parallelism::None,
-                                SvdParams::default(),
-                            )),
-                            SvdParams::default(),
-                        );
+            let mut s = Mat::<c64>::zeros(size, 1);
+            let mut u = Mat::<c64>::zeros(m, m);
+            let mut vt = Mat::<c64>::zeros(n, n);
+            svd_lapack_layout(
+                mat.as_ref(),
+                s.as_mut(),
+                Some(u.as_mut()),
+                Some(vt.as_mut()),
+                Parallelism::None,
+                make_stack!(svd_lapack_layout_req::<c64>(
+                    m,
+                    n,
+                    ComputeVectors::Full,
+                    ComputeVectors::Full,
+                    Parallelism::None,
+                    SvdParams::default(),
+                )),
+                SvdParams::default(),
+            );
 
-                        for j in 0..u.ncols() {
-                            for i in 0..u.nrows() {
-                                assert_approx_eq!(u.read(i, j), u_target.read(i, j), 1e-10);
-                            }
-                        }
-                        for j in 0..v.ncols() {
-                            for i in 0..v.nrows() {
-                                assert_approx_eq!(v.read(i, j), v_target.read(i, j), 1e-10);
-                            }
-                        }
-                        for j in 0..s.ncols() {
-                            for i in 0..s.nrows() {
-                                assert_approx_eq!(s.read(i, j), s_target.read(i, j), 1e-10);
-                            }
-                        }
-                    }
+            let mut s_mat = Mat::<c64>::zeros(m, n);
+            for i in 0..size {
+                s_mat.write(i, i, s.read(i, 0));
+            }
+            let reconstructed = &u * &s_mat * &vt;
+            for j in 0..n {
+                for i in 0..m {
+                    assert_approx_eq!(reconstructed.read(i, j), mat.read(i, j), 1e-10);
+                }
+            }
+
+            // the result of an implementation with another phase convention, such as LAPACK's
+            let mut other_s = Mat::<c64>::zeros(size, 1);
+            let mut other_u = Mat::<c64>::zeros(m, m);
+            let mut other_v = Mat::<c64>::zeros(n, n);
+            compute_svd(
+                mat.as_ref(),
+                other_s.as_mut(),
+                Some(other_u.as_mut()),
+                Some(other_v.as_mut()),
+                Parallelism::None,
+                make_stack!(compute_svd_req::<c64>(
+                    m,
+                    n,
+                    ComputeVectors::Full,
+                    ComputeVectors::Full,
+                    Parallelism::None,
+                    SvdParams::default(),
+                )),
+                SvdParams::default(),
+            );
+            for j in 0..size {
+                let phase = c64::new((j as f64).cos(), (j as f64).sin());
+                for i in 0..m {
+                    other_u.write(i, j, other_u.read(i, j) * phase);
+                }
+                for i in 0..n {
+                    other_v.write(i, j, other_v.read(i, j) * phase);
+                }
+            }
+            let mut other_vt = other_v.adjoint().to_owned();
+
+            canonicalize_svd_lapack_layout(
+                other_s.as_mut(),
+                Some(other_u.as_mut()),
+                Some(other_vt.as_mut()),
+                make_stack!(canonicalize_svd_lapack_layout_req::<c64>(m, n)),
+            );
+            for i in 0..size {
+                assert_approx_eq!(other_s.read(i, 0), s.read(i, 0), 1e-10);
+                for k in 0..m {
+                    assert_approx_eq!(other_u.read(k, i), u.read(k, i), 1e-10);
+                }
+                for k in 0..n {
+                    assert_approx_eq!(other_vt.read(i, k), vt.read(i, k), 1e-10);
                 }
             }
         }
     }
 
     #[test]
-    fn test_cplx() {
-        for m in 0..20 {
-            for n in 0..20 {
+    fn test_svd_into() {
+        for (m, n) in [(15, 9), (9, 15), (12, 12)] {
+            let size = m.min(n);
+            for (compute, u_ncols, v_ncols) in [
+                (ComputeVectors::Full, m, n),
+                (ComputeVectors::Thin, size, size),
+            ] {
                 let mat = Mat::from_fn(m, n, |_, _| c64::new(rand::random(), rand::random()));
-                let size = m.min(n);
 
-                let mut s = Mat::zeros(m, n);
-                let mut u = Mat::zeros(m, m);
-                let mut v = Mat::zeros(n, n);
+                let mut s = vec![f64::NAN; size];
+                let mut u = Mat::from_fn(m, u_ncols, |_, _| c64::new(f64::NAN, f64::NAN));
+                let mut v = Mat::from_fn(n, v_ncols, |_, _| c64::new(f64::NAN, f64::NAN));
+                svd_into(
+                    mat.as_ref(),
+                    &mut s,
+                    u.as_mut(),
+                    v.as_mut(),
+                    Parallelism::None,
+                    make_stack!(svd_into_req::<c64>(
+                        m,
+                        n,
+                        compute,
+                        compute,
+                        Parallelism::None,
+                        SvdParams::default(),
+                    )),
+                    SvdParams::default(),
+                );
 
+                let mut expected = Mat::<c64>::zeros(size, 1);
                 compute_svd(
                     mat.as_ref(),
-                    s.as_mut()
-                        .submatrix_mut(0, 0, size, size)
-                        .diagonal_mut()
-                        .column_vector_mut()
-                        .as_2d_mut(),
-                    Some(u.as_mut()),
-                    Some(v.as_mut()),
+                    expected.as_mut(),
+                    None,
+                    None,
                     Parallelism::None,
                     make_stack!(compute_svd_req::<c64>(
                         m,
                         n,
-                        ComputeVectors::Full,
-                        ComputeVectors::Full,
+                        ComputeVectors::No,
+                        ComputeVectors::No,
                         Parallelism::None,
                         SvdParams::default(),
                     )),
                     SvdParams::default(),
                 );
+                for (i, &s) in s.iter().enumerate() {
+                    assert_approx_eq!(s, expected.read(i, 0).re, 1e-10);
+                }
 
-                let reconstructed = &u * &s * v.adjoint();
-
+                let us = Mat::from_fn(m, size, |i, j| u.read(i, j) * s[j]);
+                let reconstructed = us * v.as_ref().subcols(0, size).adjoint();
                 for j in 0..n {
                     for i in 0..m {
                         assert_approx_eq!(reconstructed.read(i, j), mat.read(i, j), 1e-10);
                     }
                 }
+                crate::testing::assert_orthogonal(u.as_ref(), 1e-10);
+                crate::testing::assert_orthogonal(v.as_ref(), 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "`s` must have length `min(m, n)`")]
+    fn test_svd_into_len() {
+        let (m, n) = (6, 4);
+        let mat = Mat::from_fn(m, n, |_, _| rand::random::<f64>());
+        let mut s = vec![0.0; n + 1];
+        svd_into(
+            mat.as_ref(),
+            &mut s,
+            Mat::zeros(m, m).as_mut(),
+            Mat::zeros(n, n).as_mut(),
+            Parallelism::None,
+            make_stack!(svd_into_req::<f64>(
+                m,
+                n,
+                ComputeVectors::Full,
+                ComputeVectors::Full,
+                Parallelism::None,
+                SvdParams::default(),
+            )),
+            SvdParams::default(),
+        );
+    }
+
+    #[test]
+    fn test_svd_reorthogonalize() {
+        // clusters of 10 singular values with relative gaps of 1e-9
+        let n = 60;
+        let q_left =
+            crate::SvdDecomposition::new(Mat::from_fn(n, n, |_, _| rand::random::<f64>()).as_ref());
+        let q_right =
+            crate::SvdDecomposition::new(Mat::from_fn(n, n, |_, _| rand::random::<f64>()).as_ref());
+        let values = (0..n)
+            .map(|i| 1.0 + 0.1 * (i / 10) as f64 + 1e-9 * (i % 10) as f64)
+            .collect::<Vec<_>>();
+        let us = Mat::from_fn(n, n, |i, j| q_left.u().read(i, j) * values[j]);
+        let mat = &us * q_right.u().transpose();
+
+        let orthogonality_error = |w: MatRef<'_, f64>| {
+            let gram = w.transpose() * w;
+            let mut err = 0.0f64;
+            for j in 0..n {
+                for i in 0..n {
+                    let target = if i == j { 1.0 } else { 0.0 };
+                    err = err.max((gram.read(i, j) - target).abs());
+                }
+            }
+            err
+        };
+
+        let mut errors = [0.0; 2];
+        for (reorthogonalize, error) in [false, true].into_iter().zip(&mut errors) {
+            let params = SvdParams {
+                algorithm: SvdAlgorithm::OneSidedJacobi,
+                reorthogonalize,
+                ..SvdParams::default()
+            };
+            let mut s = Mat::zeros(n, 1);
+            let mut u = Mat::zeros(n, n);
+            let mut v = Mat::zeros(n, n);
+            compute_svd_custom_epsilon(
+                mat.as_ref(),
+                s.as_mut(),
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                1e-12,
+                f64::MIN_POSITIVE,
+                Parallelism::None,
+                make_stack!(compute_svd_req::<f64>(
+                    n,
+                    n,
+                    ComputeVectors::Full,
+                    ComputeVectors::Full,
+                    Parallelism::None,
+                    params,
+                )),
+                params,
+            );
+            *error = orthogonality_error(u.as_ref()).max(orthogonality_error(v.as_ref()));
+
+            let us = Mat::from_fn(n, n, |i, j| u.read(i, j) * s.read(j, 0));
+            let reconstructed = us * v.transpose();
+            for j in 0..n {
+                for i in 0..n {
+                    assert_approx_eq!(reconstructed.read(i, j), mat.read(i, j), 1e-10);
+                }
+            }
+        }
+        assert!(errors[0] > 1e-13);
+        assert!(errors[1] < 1e-14);
+
+        // complex thin vectors
+        let (m, n) = (20, 12);
+        let mat = Mat::from_fn(m, n, |_, _| c64::new(rand::random(), rand::random()));
+        let params = SvdParams {
+            reorthogonalize: true,
+            ..SvdParams::default()
+        };
+        let mut s = Mat::zeros(n, 1);
+        let mut u = Mat::zeros(m, n);
+        let mut v = Mat::zeros(n, n);
+        compute_svd(
+            mat.as_ref(),
+            s.as_mut(),
+            Some(u.as_mut()),
+            Some(v.as_mut()),
+            Parallelism::None,
+            make_stack!(compute_svd_req::<c64>(
+                m,
+                n,
+                ComputeVectors::Thin,
+                ComputeVectors::Thin,
+                Parallelism::None,
+                params,
+            )),
+            params,
+        );
+        crate::testing::assert_orthogonal(u.as_ref(), 1e-14);
+        crate::testing::assert_orthogonal(v.as_ref(), 1e-14);
+        let us = Mat::from_fn(m, n, |i, j| u.read(i, j) * s.read(j, 0));
+        let reconstructed = us * v.adjoint();
+        for j in 0..n {
+            for i in 0..m {
+                assert_approx_eq!(reconstructed.read(i, j), mat.read(i, j), 1e-10);
             }
         }
     }
@@ -1405,6 +3541,7 @@ mod tests {
                 use ComputeVectors::*;
                 for compute_u in [No, Thin, Full] {
                     for compute_v in [No, Thin, Full] {
+                        #[cfg(feature = "std")]
                         dbg!(m, n, compute_u, compute_v);
                         let mat =
                             Mat::from_fn(m, n, |_, _| c64::new(rand::random(), rand::random()));
@@ -1537,7 +3674,9 @@ mod tests {
                     ComputeVectors::Full,
                     bidiag_cplx_svd_req::<f64>,
                     Parallelism::None,
+                    SvdParams::default(),
                 )),
+                SvdParams::default(),
             );
 
             let reconstructed = &u * &s * v.transpose();
@@ -1580,7 +3719,9 @@ mod tests {
                     ComputeVectors::Full,
                     bidiag_cplx_svd_req::<f64>,
                     Parallelism::None,
+                    SvdParams::default(),
                 )),
+                SvdParams::default(),
             );
 
             let reconstructed = &u * &s * v.transpose();
@@ -1667,4 +3808,230 @@ mod tests {
             }
         }
     }
+
+    fn svd_sorted(mat: MatRef<'_, f64>, sort: SvdSort) -> (Mat<f64>, Mat<f64>, Mat<f64>) {
+        svd_with_params(
+            mat,
+            SvdParams {
+                sort,
+                ..Default::default()
+            },
+        )
+    }
+
+    fn svd_with_params(mat: MatRef<'_, f64>, params: SvdParams) -> (Mat<f64>, Mat<f64>, Mat<f64>) {
+        let (m, n) = (mat.nrows(), mat.ncols());
+
+        let mut s = Mat::zeros(m, n);
+        let mut u = Mat::zeros(m, m);
+        let mut v = Mat::zeros(n, n);
+        compute_svd(
+            mat,
+            s.as_mut().diagonal_mut().column_vector_mut().as_2d_mut(),
+            Some(u.as_mut()),
+            Some(v.as_mut()),
+            Parallelism::None,
+            make_stack!(compute_svd_req::<f64>(
+                m,
+                n,
+                ComputeVectors::Full,
+                ComputeVectors::Full,
+                Parallelism::None,
+                params,
+            )),
+            params,
+        );
+
+        let reconstructed = &u * &s * v.transpose();
+        for j in 0..n {
+            for i in 0..m {
+                assert_approx_eq!(reconstructed.read(i, j), mat.read(i, j), 1e-10);
+            }
+        }
+        (s, u, v)
+    }
+
+    #[test]
+    fn test_forced_algorithm() {
+        for (m, n) in [(2, 2), (3, 3), (4, 2), (10, 7), (150, 140)] {
+            let mat = Mat::from_fn(m, n, |_, _| rand::random::<f64>());
+            let (s_auto, _, _) = svd_with_params(mat.as_ref(), SvdParams::default());
+            for algorithm in [
+                SvdAlgorithm::Jacobi,
+                SvdAlgorithm::QrIteration,
+                SvdAlgorithm::DivideConquer,
+                SvdAlgorithm::ClosedForm,
+            ] {
+                let params = SvdParams {
+                    algorithm,
+                    ..Default::default()
+                };
+                let (s, _, _) = svd_with_params(mat.as_ref(), params);
+                for i in 0..Ord::min(m, n) {
+                    assert_approx_eq!(s.read(i, i), s_auto.read(i, i), 1e-10);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sort_ascending() {
+        for (m, n) in [(10, 7), (7, 10), (40, 30)] {
+            let mat = Mat::from_fn(m, n, |_, _| rand::random::<f64>());
+            let (s, _, _) = svd_sorted(mat.as_ref(), SvdSort::Ascending);
+            for i in 1..Ord::min(m, n) {
+                assert!(s.read(i - 1, i - 1) <= s.read(i, i));
+            }
+        }
+    }
+
+    #[test]
+    fn test_sort_stable_descending() {
+        // entries of a diagonal matrix with shuffled rows, whose singular vectors are the columns
+        // of the identity
+        let d = [1.0, 3.0, 3.0, 1.0, 3.0, 2.0];
+        let rows = [6, 0, 3, 7, 1, 2];
+        let mut mat = Mat::<f64>::zeros(8, 6);
+        for (j, (&d, &i)) in d.iter().zip(&rows).enumerate() {
+            mat.write(i, j, d);
+        }
+
+        let (s, u, v) = svd_sorted(mat.as_ref(), SvdSort::StableDescending);
+        for (k, j) in [1, 2, 4, 5, 0, 3].into_iter().enumerate() {
+            assert_approx_eq!(s.read(k, k), d[j], 1e-10);
+            for i in 0..6 {
+                assert_approx_eq!(v.read(i, k), if i == j { 1.0 } else { 0.0 }, 1e-10);
+            }
+            for i in 0..8 {
+                assert_approx_eq!(u.read(i, k), if i == rows[j] { 1.0 } else { 0.0 }, 1e-10);
+            }
+        }
+
+        // a dense matrix with repeated singular values, obtained by applying householder
+        // reflections on both sides
+        let reflection = |n: usize| {
+            let w = Mat::from_fn(n, 1, |_, _| rand::random::<f64>());
+            let norm2 = (w.transpose() * &w).read(0, 0);
+            Mat::from_fn(n, n, |i, j| {
+                let id = if i == j { 1.0 } else { 0.0 };
+                id - 2.0 * w.read(i, 0) * w.read(j, 0) / norm2
+            })
+        };
+        let (m, n) = (30, 20);
+        let s = Mat::from_fn(
+            m,
+            n,
+            |i, j| {
+                if i == j {
+                    [4.0, 2.0, 1.0][i % 3]
+                } else {
+                    0.0
+                }
+            },
+        );
+        let mat = reflection(m) * s * reflection(n);
+
+        let (s0, u0, v0) = svd_sorted(mat.as_ref(), SvdSort::StableDescending);
+        let (s1, u1, v1) = svd_sorted(mat.as_ref(), SvdSort::StableDescending);
+        assert!(s0 == s1);
+        assert!(u0 == u1);
+        assert!(v0 == v1);
+
+        let mut prev = (f64::INFINITY, 0);
+        for j in 0..n {
+            let pivot = (0..n)
+                .max_by(|&a, &b| v0.read(a, j).abs().total_cmp(&v0.read(b, j).abs()))
+                .unwrap();
+            assert!(v0.read(pivot, j) > 0.0);
+            assert!(s0.read(j, j) <= prev.0);
+            if (s0.read(j, j) - prev.0).abs() < 1e-10 {
+                assert!(pivot >= prev.1);
+            }
+            prev = (s0.read(j, j), pivot);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_orthogonal() {
+        use crate::testing::assert_orthogonal;
+
+        for _ in 0..64 {
+            let m = 1 + rand::random::<usize>() % 80;
+            let n = 1 + rand::random::<usize>() % 80;
+            let size = Ord::min(m, n);
+            let thin = rand::random::<bool>();
+            let compute = if thin {
+                ComputeVectors::Thin
+            } else {
+                ComputeVectors::Full
+            };
+
+            let mat = Mat::from_fn(m, n, |_, _| c64::new(rand::random(), rand::random()));
+            let mut s = Mat::zeros(size, 1);
+            let mut u = Mat::zeros(m, if thin { size } else { m });
+            let mut v = Mat::zeros(n, if thin { size } else { n });
+
+            compute_svd(
+                mat.as_ref(),
+                s.as_mut(),
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                Parallelism::None,
+                make_stack!(compute_svd_req::<c64>(
+                    m,
+                    n,
+                    compute,
+                    compute,
+                    Parallelism::None,
+                    SvdParams::default(),
+                )),
+                SvdParams::default(),
+            );
+            assert_orthogonal(u.as_ref(), 1e-10);
+            assert_orthogonal(v.as_ref(), 1e-10);
+
+            let mat = Mat::from_fn(m, n, |_, _| rand::random::<f64>());
+            let mut s = Mat::zeros(size, 1);
+            let mut u = Mat::zeros(m, if thin { size } else { m });
+            let mut v = Mat::zeros(n, if thin { size } else { n });
+
+            compute_svd(
+                mat.as_ref(),
+                s.as_mut(),
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                Parallelism::None,
+                make_stack!(compute_svd_req::<f64>(
+                    m,
+                    n,
+                    compute,
+                    compute,
+                    Parallelism::None,
+                    SvdParams::default(),
+                )),
+                SvdParams::default(),
+            );
+            assert_orthogonal(u.as_ref(), 1e-10);
+            assert_orthogonal(v.as_ref(), 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_thin_req() {
+        let (m, n) = (100_000, 50);
+        for (nrows, ncols) in [(m, n), (n, m)] {
+            let req = compute_svd_req::<f64>(
+                nrows,
+                ncols,
+                ComputeVectors::Thin,
+                ComputeVectors::Thin,
+                Parallelism::None,
+                SvdParams::default(),
+            )
+            .unwrap();
+            // the workspace holds a copy of the matrix, and nothing that scales with `m * m`
+            assert!(req.unaligned_bytes_required() < 2 * m * n * core::mem::size_of::<f64>());
+        }
+    }
 }
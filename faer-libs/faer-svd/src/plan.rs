@@ -0,0 +1,1110 @@
+use crate::{
+    apply_bidiag_householder_sequences,
+    bidiag::bidiagonalize_in_place_req,
+    bidiag_cplx_svd_req,
+    bidiag_real_svd::{
+        bidiag_real_svd_req, bidiag_svd_qr_algorithm_accumulate, compute_bidiag_real_svd,
+    },
+    bidiagonalize_with_householder_factors, compute_bidiag_cplx_svd, jacobi_fallback_threshold,
+    SvdParams, BIDIAG_QR_FALLBACK_THRESHOLD,
+};
+use alloc::vec::Vec;
+use coe::Coerce;
+use dyn_stack::{GlobalPodBuffer, PodStack, SizeOverflow, StackReq};
+use faer_core::{
+    assert, get_global_parallelism,
+    householder::{
+        apply_block_householder_sequence_on_the_left_in_place_req,
+        apply_block_householder_sequence_on_the_left_in_place_with_conj,
+        apply_block_householder_sequence_transpose_on_the_left_in_place_req,
+        apply_block_householder_sequence_transpose_on_the_left_in_place_with_conj,
+    },
+    temp_mat_req, unzipped, zipped, Col, ColMut, ComplexField, Conj, Entity, Mat, MatMut, MatRef,
+    Parallelism, RealField,
+};
+use reborrow::*;
+
+/// Thin singular value decomposition of a matrix $A$ of shape $(m, n)$, such that
+/// $A = U S V^H$, whose singular vectors are only computed when they are first requested.
+///
+/// The bidiagonalization $A = Q_1 B Q_2^H$ and the singular values of the bidiagonal matrix $B$
+/// are computed on construction, which costs about as much as computing the singular values
+/// alone. The singular vectors of $B$, and their products with the householder sequences $Q_1$ and
+/// $Q_2$, are computed by the first call to [`SvdPlan::left_vectors`] or
+/// [`SvdPlan::right_vectors`], and cached for the following calls.
+#[derive(Clone, Debug)]
+pub struct SvdPlan<E: Entity> {
+    /// whether the stored bidiagonalization is that of the transpose of $A$, which is the case
+    /// when $A$ has more columns than rows
+    transposed: bool,
+    bid: Mat<E>,
+    householder_left: Mat<E>,
+    householder_right: Mat<E>,
+    diag: Vec<E>,
+    subdiag: Vec<E>,
+    s: Mat<E>,
+    parallelism: Parallelism,
+    params: SvdParams,
+    vectors: Option<(Mat<E>, Mat<E>)>,
+}
+
+/// computes the singular values of the bidiagonal matrix whose adjoint has the diagonal `diag`
+/// and the subdiagonal `subdiag`, as well as its singular vectors if `u` and `v` are provided
+fn bidiag_svd<E: ComplexField>(
+    diag: &mut [E],
+    subdiag: &mut [E],
+    u: Option<MatMut<'_, E>>,
+    v: Option<MatMut<'_, E>>,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+    params: SvdParams,
+) {
+    let jacobi_fallback_threshold = jacobi_fallback_threshold(params, u.is_some() || v.is_some());
+    let epsilon = E::Real::faer_epsilon().unwrap();
+    let zero_threshold = E::Real::faer_zero_threshold().unwrap();
+
+    if coe::is_same::<E, E::Real>() {
+        compute_bidiag_real_svd::<E::Real>(
+            diag.coerce(),
+            subdiag.coerce(),
+            u.map(|u| u.coerce()),
+            v.map(|v| v.coerce()),
+            jacobi_fallback_threshold,
+            BIDIAG_QR_FALLBACK_THRESHOLD,
+            epsilon,
+            zero_threshold,
+            parallelism,
+            stack,
+            params,
+        );
+    } else {
+        compute_bidiag_cplx_svd::<E>(
+            diag,
+            subdiag,
+            u,
+            v,
+            &mut Default::default(),
+            jacobi_fallback_threshold,
+            BIDIAG_QR_FALLBACK_THRESHOLD,
+            epsilon,
+            zero_threshold,
+            parallelism,
+            stack,
+            params,
+        );
+    }
+}
+
+fn bidiag_svd_req<E: ComplexField>(
+    n: usize,
+    compute_vectors: bool,
+    parallelism: Parallelism,
+    params: SvdParams,
+) -> Result<StackReq, SizeOverflow> {
+    let jacobi_fallback_threshold = jacobi_fallback_threshold(params, compute_vectors);
+    if coe::is_same::<E, E::Real>() {
+        bidiag_real_svd_req::<E::Real>(
+            n,
+            jacobi_fallback_threshold,
+            compute_vectors,
+            compute_vectors,
+            parallelism,
+        )
+    } else {
+        bidiag_cplx_svd_req::<E>(
+            n,
+            jacobi_fallback_threshold,
+            compute_vectors,
+            compute_vectors,
+            parallelism,
+        )
+    }
+}
+
+/// lu factorization with partial pivoting of the golub-kahan tridiagonal matrix, which has a zero
+/// diagonal and the off-diagonal `t`, shifted by `-sigma`. the layout is that of lapack's `gttrf`:
+/// `u` has the diagonal `d` and the superdiagonals `du` and `du2`, and `l` has the multipliers
+/// `dl`, with `swapped[i]` telling whether the rows `i` and `i + 1` were interchanged
+struct ShiftedTridiagLu<T> {
+    d: Vec<T>,
+    du: Vec<T>,
+    du2: Vec<T>,
+    dl: Vec<T>,
+    swapped: Vec<bool>,
+}
+
+impl<T: RealField> ShiftedTridiagLu<T> {
+    fn new(t: &[T], sigma: T, tiny: T) -> Self {
+        let n = t.len() + 1;
+        let mut d = alloc::vec![sigma.faer_neg(); n];
+        let mut du = t.to_vec();
+        let mut dl = t.to_vec();
+        let mut du2 = alloc::vec![T::faer_zero(); n.saturating_sub(2)];
+        let mut swapped = alloc::vec![false; n - 1];
+
+        for i in 0..n - 1 {
+            if d[i].faer_abs() >= dl[i].faer_abs() {
+                let fact = if d[i] == T::faer_zero() {
+                    T::faer_zero()
+                } else {
+                    dl[i].faer_div(d[i])
+                };
+                dl[i] = fact;
+                d[i + 1] = d[i + 1].faer_sub(fact.faer_mul(du[i]));
+            } else {
+                let fact = d[i].faer_div(dl[i]);
+                d[i] = dl[i];
+                dl[i] = fact;
+                let tmp = du[i];
+                du[i] = d[i + 1];
+                d[i + 1] = tmp.faer_sub(fact.faer_mul(d[i + 1]));
+                if i + 2 < n {
+                    du2[i] = du[i + 1];
+                    du[i + 1] = fact.faer_neg().faer_mul(du[i + 1]);
+                }
+                swapped[i] = true;
+            }
+        }
+
+        // the shift is a singular value, so the matrix is singular up to rounding errors, and
+        // its tiny pivots are perturbed to keep the solution finite. this only changes the
+        // solution along the eigenvector, which is the direction that's being computed
+        for d in &mut d {
+            if d.faer_abs() < tiny {
+                *d = if *d < T::faer_zero() {
+                    tiny.faer_neg()
+                } else {
+                    tiny
+                };
+            }
+        }
+
+        Self {
+            d,
+            du,
+            du2,
+            dl,
+            swapped,
+        }
+    }
+
+    fn solve_in_place(&self, b: &mut [T]) {
+        let n = self.d.len();
+        for i in 0..n - 1 {
+            if self.swapped[i] {
+                let tmp = b[i];
+                b[i] = b[i + 1];
+                b[i + 1] = tmp.faer_sub(self.dl[i].faer_mul(b[i]));
+            } else {
+                b[i + 1] = b[i + 1].faer_sub(self.dl[i].faer_mul(b[i]));
+            }
+        }
+        for i in (0..n).rev() {
+            let mut x = b[i];
+            if i + 1 < n {
+                x = x.faer_sub(self.du[i].faer_mul(b[i + 1]));
+            }
+            if i + 2 < n {
+                x = x.faer_sub(self.du2[i].faer_mul(b[i + 2]));
+            }
+            b[i] = x.faer_div(self.d[i]);
+        }
+    }
+}
+
+/// removes from the entries `offset, offset + 2, ...` of `z` their component along the unit
+/// vector `q`
+fn project_out_strided<T: RealField>(z: &mut [T], offset: usize, q: &[T]) {
+    let mut dot = T::faer_zero();
+    for (x, q) in z[offset..].iter().step_by(2).zip(q) {
+        dot = dot.faer_add(x.faer_mul(*q));
+    }
+    for (x, q) in z[offset..].iter_mut().step_by(2).zip(q) {
+        *x = x.faer_sub(dot.faer_mul(*q));
+    }
+}
+
+fn normalize<T: RealField>(x: &mut [T]) -> T {
+    let mut max = T::faer_zero();
+    for x in &*x {
+        if x.faer_abs() > max {
+            max = x.faer_abs();
+        }
+    }
+    if max == T::faer_zero() {
+        return max;
+    }
+    let mut norm2 = T::faer_zero();
+    for x in &*x {
+        norm2 = norm2.faer_add(x.faer_div(max).faer_abs2());
+    }
+    let norm = max.faer_mul(norm2.faer_sqrt());
+    let inv = norm.faer_inv();
+    for x in &mut *x {
+        *x = x.faer_mul(inv);
+    }
+    norm
+}
+
+/// computes the left and right singular vectors `(u, v)` of the real lower bidiagonal matrix with
+/// diagonal `diag` and subdiagonal `subdiag`, for its singular value `sigma`.
+///
+/// the interleaved vector `[u0, v0, u1, v1, ...]` is an eigenvector of the golub-kahan
+/// tridiagonal matrix for the eigenvalue `sigma`, and is computed by inverse iteration. the
+/// vectors of the previous singular values of the same cluster are given in `cluster`, and the
+/// two halves of the iterate are kept orthogonal to them, which also keeps it orthogonal to the
+/// eigenvectors of the opposite eigenvalues. `index` is the index of the singular value, which
+/// gives the vectors of the zero matrix
+fn bidiag_singular_vector_pair<T: RealField>(
+    diag: &[T],
+    subdiag: &[T],
+    sigma: T,
+    cluster: &[(Vec<T>, Vec<T>)],
+    index: usize,
+) -> (Vec<T>, Vec<T>) {
+    let n = diag.len();
+    let mut scale = sigma.faer_abs();
+    for x in diag.iter().chain(&subdiag[..n - 1]) {
+        if x.faer_abs() > scale {
+            scale = x.faer_abs();
+        }
+    }
+    if scale == T::faer_zero() {
+        let mut unit = alloc::vec![T::faer_zero(); n];
+        unit[index] = T::faer_one();
+        return (unit.clone(), unit);
+    }
+    let inv_scale = scale.faer_inv();
+
+    let t: Vec<T> = (0..2 * n - 1)
+        .map(|k| {
+            if k % 2 == 0 {
+                diag[k / 2]
+            } else {
+                subdiag[k / 2]
+            }
+            .faer_mul(inv_scale)
+        })
+        .collect();
+    let lu = ShiftedTridiagLu::new(&t, sigma.faer_mul(inv_scale), T::faer_epsilon().unwrap());
+
+    let run = |exclude_u: &[&[T]], exclude_v: &[&[T]]| {
+        // a fixed pseudo-random start, which is unlikely to be orthogonal to the eigenvector
+        let mut state = 0x2545_f491u32;
+        let mut z: Vec<T> = (0..2 * n)
+            .map(|_| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                T::faer_from_f64(((state >> 16) & 0x7fff) as f64 / 32768.0 - 0.5)
+            })
+            .collect();
+        for _ in 0..3 {
+            lu.solve_in_place(&mut z);
+            // twice is enough to keep the iterate orthogonal to working precision
+            for _ in 0..2 {
+                for q in exclude_u {
+                    project_out_strided(&mut z, 0, q);
+                }
+                for q in exclude_v {
+                    project_out_strided(&mut z, 1, q);
+                }
+            }
+            normalize(&mut z);
+        }
+        let u: Vec<T> = z.iter().step_by(2).copied().collect();
+        let v: Vec<T> = z[1..].iter().step_by(2).copied().collect();
+        (u, v)
+    };
+
+    let mut exclude_u: Vec<&[T]> = cluster.iter().map(|(u, _)| &**u).collect();
+    let mut exclude_v: Vec<&[T]> = cluster.iter().map(|(_, v)| &**v).collect();
+    let (mut u, mut v) = run(&exclude_u, &exclude_v);
+    let norm_u = normalize(&mut u);
+    let norm_v = normalize(&mut v);
+
+    // when `sigma` is too small to be told apart from `-sigma`, the iterate is a combination of
+    // the eigenvectors `[u, v]` and `[u, -v]`, which may have a negligible half. that half is
+    // then recomputed from an iterate that is kept orthogonal to the other one
+    let threshold = T::faer_from_f64(1e-3);
+    if norm_u < threshold.faer_mul(norm_v) {
+        exclude_v.push(&v);
+        u = run(&exclude_u, &exclude_v).0;
+        normalize(&mut u);
+    } else if norm_v < threshold.faer_mul(norm_u) {
+        exclude_u.push(&u);
+        v = run(&exclude_u, &exclude_v).1;
+        normalize(&mut v);
+    }
+
+    // the same combination can also flip the sign of `v`, which is fixed so that
+    // `B v = sigma u`
+    let mut dot = T::faer_zero();
+    for i in 0..n {
+        let mut bv = diag[i].faer_mul(v[i]);
+        if i > 0 {
+            bv = bv.faer_add(subdiag[i - 1].faer_mul(v[i - 1]));
+        }
+        dot = dot.faer_add(bv.faer_mul(u[i]));
+    }
+    if dot < T::faer_zero() {
+        for x in &mut v {
+            *x = x.faer_neg();
+        }
+    }
+
+    (u, v)
+}
+
+impl<E: ComplexField> SvdPlan<E> {
+    /// Computes the bidiagonalization and the singular values of `matrix`, without computing its
+    /// singular vectors.
+    #[track_caller]
+    pub fn new(matrix: MatRef<'_, E>) -> Self {
+        let parallelism = get_global_parallelism();
+        let params = SvdParams::default();
+
+        let transposed = matrix.ncols() > matrix.nrows();
+        let matrix = if transposed {
+            matrix.transpose()
+        } else {
+            matrix
+        };
+        let m = matrix.nrows();
+        let n = matrix.ncols();
+
+        if n == 0 || !matrix.is_all_finite() {
+            let value = if n == 0 {
+                E::faer_zero()
+            } else {
+                E::faer_nan()
+            };
+            let mut plan = Self {
+                transposed,
+                bid: Mat::new(),
+                householder_left: Mat::new(),
+                householder_right: Mat::new(),
+                diag: Vec::new(),
+                subdiag: Vec::new(),
+                s: Mat::from_fn(n, 1, |_, _| value),
+                parallelism,
+                params,
+                vectors: None,
+            };
+            plan.set_vectors(
+                Mat::from_fn(m, n, |_, _| value),
+                Mat::from_fn(n, n, |_, _| value),
+            );
+            return plan;
+        }
+
+        let householder_blocksize = faer_qr::no_pivoting::compute::recommended_blocksize::<E>(m, n);
+        let mut bid = Mat::<E>::zeros(m, n);
+        let mut householder_left = Mat::<E>::zeros(householder_blocksize, n);
+        let mut householder_right = Mat::<E>::zeros(householder_blocksize, n - 1);
+
+        let mut mem = GlobalPodBuffer::new(
+            StackReq::try_any_of([
+                bidiagonalize_in_place_req::<E>(m, n, parallelism).unwrap(),
+                bidiag_svd_req::<E>(n, false, parallelism, params).unwrap(),
+            ])
+            .unwrap(),
+        );
+        let mut stack = PodStack::new(&mut mem);
+
+        bidiagonalize_with_householder_factors(
+            matrix,
+            bid.as_mut(),
+            householder_left.as_mut(),
+            householder_right.as_mut(),
+            parallelism,
+            stack.rb_mut(),
+        );
+
+        let diag: Vec<E> = (0..n).map(|i| bid.read(i, i).faer_conj()).collect();
+        let subdiag: Vec<E> = (0..n)
+            .map(|i| {
+                if i < n - 1 {
+                    bid.read(i, i + 1).faer_conj()
+                } else {
+                    E::faer_zero()
+                }
+            })
+            .collect();
+
+        let mut values = diag.clone();
+        bidiag_svd(
+            &mut values,
+            &mut subdiag.clone(),
+            None,
+            None,
+            parallelism,
+            stack,
+            params,
+        );
+
+        Self {
+            transposed,
+            bid,
+            householder_left,
+            householder_right,
+            diag,
+            subdiag,
+            s: Mat::from_fn(n, 1, |i, _| values[i]),
+            parallelism,
+            params,
+            vectors: None,
+        }
+    }
+
+    /// stores the singular vectors `u` and `v` of the matrix that was bidiagonalized
+    fn set_vectors(&mut self, mut u: Mat<E>, mut v: Mat<E>) {
+        if self.transposed {
+            zipped!(u.as_mut()).for_each(|unzipped!(mut x)| x.write(x.read().faer_conj()));
+            zipped!(v.as_mut()).for_each(|unzipped!(mut x)| x.write(x.read().faer_conj()));
+            self.vectors = Some((v, u));
+        } else {
+            self.vectors = Some((u, v));
+        }
+    }
+
+    /// computes the singular vectors from the stored bidiagonalization, unless they are cached
+    fn vectors(&mut self) -> &(Mat<E>, Mat<E>) {
+        if self.vectors.is_none() {
+            let m = self.bid.nrows();
+            let n = self.bid.ncols();
+            let householder_blocksize = self.householder_left.nrows();
+            let parallelism = self.parallelism;
+
+            let mut mem = GlobalPodBuffer::new(
+                StackReq::try_any_of([
+                    bidiag_svd_req::<E>(n, true, parallelism, self.params).unwrap(),
+                    apply_block_householder_sequence_on_the_left_in_place_req::<E>(
+                        m,
+                        householder_blocksize,
+                        n,
+                    )
+                    .unwrap(),
+                    StackReq::try_all_of([
+                        temp_mat_req::<E>(n - 1, m).unwrap(),
+                        apply_block_householder_sequence_on_the_left_in_place_req::<E>(
+                            n - 1,
+                            householder_blocksize,
+                            n,
+                        )
+                        .unwrap(),
+                    ])
+                    .unwrap(),
+                ])
+                .unwrap(),
+            );
+            let mut stack = PodStack::new(&mut mem);
+
+            let mut diag = self.diag.clone();
+            let mut u_b = Mat::<E>::zeros(n + 1, n + 1);
+            let mut v_b = Mat::<E>::zeros(n, n);
+            bidiag_svd(
+                &mut diag,
+                &mut self.subdiag.clone(),
+                Some(u_b.as_mut()),
+                Some(v_b.as_mut()),
+                parallelism,
+                stack.rb_mut(),
+                self.params,
+            );
+
+            // the singular values computed along with the vectors may differ from the previous
+            // ones by rounding errors, and are kept so that the decomposition stays consistent
+            self.s = Mat::from_fn(n, 1, |i, _| diag[i]);
+
+            let mut u = Mat::<E>::zeros(m, n);
+            let mut v = Mat::<E>::zeros(n, n);
+            apply_bidiag_householder_sequences(
+                self.bid.as_ref(),
+                self.householder_left.as_ref(),
+                self.householder_right.as_ref(),
+                u_b.as_ref(),
+                v_b.as_ref(),
+                Some(u.as_mut()),
+                Some(v.as_mut()),
+                parallelism,
+                stack,
+            );
+            self.set_vectors(u, v);
+        }
+        self.vectors.as_ref().unwrap()
+    }
+
+    /// returns the unit factors `(col_mul, row_mul)` that make the bidiagonal matrix real and
+    /// nonnegative, from `compute_bidiag_cplx_svd`. the left and right singular vectors of the
+    /// bidiagonal matrix are the real ones, with their rows scaled by `row_mul` and `col_mul`
+    fn unit_factors(&self) -> (Vec<E>, Vec<E>) {
+        let n = self.diag.len();
+        let normalized = |x: E| {
+            if x == E::faer_zero() {
+                E::faer_one()
+            } else {
+                let re = x.faer_real().faer_abs();
+                let im = x.faer_imag().faer_abs();
+                let max = if re > im { re } else { im };
+                let x = x.faer_scale_real(max.faer_inv());
+                x.faer_scale_real(x.faer_abs().faer_inv())
+            }
+        };
+        let mut col_mul = Vec::with_capacity(n);
+        let mut row_mul = Vec::with_capacity(n);
+        let mut col_normalized = normalized(self.diag[0]).faer_conj();
+        col_mul.push(col_normalized);
+        row_mul.push(E::faer_one());
+        for i in 1..n {
+            let row_normalized =
+                normalized(self.subdiag[i - 1].faer_mul(col_normalized)).faer_conj();
+            row_mul.push(row_normalized.faer_conj());
+            col_normalized = normalized(self.diag[i].faer_mul(row_normalized)).faer_conj();
+            col_mul.push(col_normalized);
+        }
+        (col_mul, row_mul)
+    }
+
+    /// Returns whether the singular vectors have already been computed.
+    pub fn has_vectors(&self) -> bool {
+        self.vectors.is_some()
+    }
+
+    /// Returns the factor $U$ of the decomposition, computing the singular vectors if this is the
+    /// first time they are requested.
+    pub fn left_vectors(&mut self) -> MatRef<'_, E> {
+        self.vectors().0.as_ref()
+    }
+
+    /// Returns the factor $V$ of the decomposition, computing the singular vectors if this is the
+    /// first time they are requested.
+    pub fn right_vectors(&mut self) -> MatRef<'_, E> {
+        self.vectors().1.as_ref()
+    }
+
+    /// Returns the diagonal of the factor $S$ of the decomposition, as a column.
+    ///
+    /// The singular values are recomputed along with the singular vectors, so they may change by
+    /// rounding errors once the vectors are requested.
+    pub fn s_diagonal(&self) -> MatRef<'_, E> {
+        self.s.as_ref()
+    }
+
+    /// Returns the singular values of the decomposition, in nonincreasing order.
+    pub fn singular_values(&self) -> Vec<E::Real> {
+        (0..self.s.nrows())
+            .map(|i| self.s.read(i, 0).faer_real())
+            .collect()
+    }
+
+    /// Computes $V^H x$, which is $V^T x$ for real matrices, and stores the result in `x`, without
+    /// forming $V$.
+    ///
+    /// When $A$ has more columns than rows, $V$ is extended to a square unitary matrix, so that
+    /// the first `min(m, n)` entries of the result are the coordinates of `x` along the right
+    /// singular vectors, and the others are its coordinates in an orthonormal basis of their
+    /// complement.
+    ///
+    /// The householder sequence of the bidiagonalization is applied from its stored factors, and
+    /// the singular vectors of the bidiagonal matrix by replaying the rotations of the implicit QR
+    /// iteration on `x`, which takes $O(n^2)$ operations and $O(n)$ storage. Singular vectors are
+    /// only unique up to a unit factor, or a unitary transformation for repeated singular values,
+    /// so the ones used here may differ from those of [`SvdPlan::right_vectors`]. They are the same
+    /// for every call.
+    ///
+    /// # Panics
+    /// Panics if `x.nrows()` is not equal to the number of columns of $A$.
+    #[track_caller]
+    pub fn apply_vt(&self, x: ColMut<'_, E>) {
+        let mut x = x;
+        if self.diag.is_empty() {
+            // zero-sized or non-finite matrix, whose singular vectors are stored
+            let (_, v) = self.vectors.as_ref().unwrap();
+            assert!(x.nrows() == v.nrows());
+            if v.ncols() > 0 {
+                zipped!(x.as_2d_mut()).for_each(|unzipped!(mut x)| x.write(E::faer_nan()));
+            }
+            return;
+        }
+
+        let m = self.bid.nrows();
+        let n = self.bid.ncols();
+        let householder_blocksize = self.householder_left.nrows();
+        assert!(x.nrows() == if self.transposed { m } else { n });
+
+        let mut mem = GlobalPodBuffer::new(
+            apply_block_householder_sequence_transpose_on_the_left_in_place_req::<E>(
+                m,
+                householder_blocksize,
+                1,
+            )
+            .unwrap(),
+        );
+        let stack = PodStack::new(&mut mem);
+
+        let (col_mul, row_mul) = self.unit_factors();
+
+        // `V` is made of the left singular vectors of the bidiagonal matrix, which is the adjoint
+        // of the bidiagonalization, followed by the right householder sequence. when the matrix
+        // is transposed, it's the conjugate of its `U`, which is made of the right singular vectors
+        // followed by the left householder sequence
+        let unit_factors = if self.transposed { &col_mul } else { &row_mul };
+        if self.transposed {
+            zipped!(x.rb_mut().as_2d_mut())
+                .for_each(|unzipped!(mut x)| x.write(x.read().faer_conj()));
+            apply_block_householder_sequence_transpose_on_the_left_in_place_with_conj(
+                self.bid.as_ref(),
+                self.householder_left.as_ref(),
+                Conj::Yes,
+                x.rb_mut().as_2d_mut(),
+                self.parallelism,
+                stack,
+            );
+        } else if n > 1 {
+            apply_block_householder_sequence_transpose_on_the_left_in_place_with_conj(
+                self.bid.as_ref().submatrix(0, 1, m, n - 1).transpose(),
+                self.householder_right.as_ref(),
+                Conj::Yes,
+                x.rb_mut().subrows_mut(1, n - 1).as_2d_mut(),
+                self.parallelism,
+                stack,
+            );
+        }
+
+        // the rotations of the qr iteration are applied to the real and imaginary parts, stored
+        // as rows
+        let nrows = if coe::is_same::<E, E::Real>() { 1 } else { 2 };
+        let mut rows = Mat::<E::Real>::from_fn(nrows, n, |part, i| {
+            let y = x.read(i).faer_mul(unit_factors[i].faer_conj());
+            if part == 0 {
+                y.faer_real()
+            } else {
+                y.faer_imag()
+            }
+        });
+        let mut diag: Vec<E::Real> = self.diag.iter().map(|x| x.faer_abs()).collect();
+        let mut subdiag: Vec<E::Real> = self.subdiag.iter().map(|x| x.faer_abs()).collect();
+        let (u_rows, v_rows) = if self.transposed {
+            (None, Some(rows.as_mut()))
+        } else {
+            (Some(rows.as_mut()), None)
+        };
+        bidiag_svd_qr_algorithm_accumulate(
+            &mut diag,
+            &mut subdiag,
+            u_rows,
+            v_rows,
+            None,
+            E::Real::faer_epsilon().unwrap(),
+            E::Real::faer_zero_threshold().unwrap(),
+            self.params.high_relative_accuracy,
+            self.params.shift_strategy,
+            self.params.convergence_criterion,
+        );
+        if nrows == 1 {
+            for i in 0..n {
+                x.write(i, E::faer_from_real(rows.read(0, i)));
+            }
+        } else {
+            // the principal square root of `-1`
+            let imag_unit = E::faer_one().faer_neg().faer_sqrt();
+            for i in 0..n {
+                let re = E::faer_from_real(rows.read(0, i));
+                let im = E::faer_from_real(rows.read(1, i));
+                x.write(i, re.faer_add(im.faer_mul(imag_unit)));
+            }
+        }
+
+        if self.transposed {
+            zipped!(x.as_2d_mut()).for_each(|unzipped!(mut x)| x.write(x.read().faer_conj()));
+        }
+    }
+
+    /// Returns an iterator over the singular triplets $(\sigma_j, u_j, v_j)$ of the
+    /// decomposition, in nonincreasing order of the singular values, where $u_j$ and $v_j$ are
+    /// the columns of $U$ and $V$.
+    ///
+    /// The singular vectors of each triplet are only computed when it's reached, so consuming
+    /// the first $k$ triplets costs $O(k m n)$ operations, instead of decomposing the whole
+    /// matrix. The singular vectors of the bidiagonal matrix are computed by inverse iteration
+    /// on its Golub-Kahan tridiagonal form, and orthogonalized against those of the close
+    /// singular values, then multiplied by the householder sequences of the bidiagonalization.
+    /// Singular vectors are only unique up to a unit factor, or a unitary transformation for
+    /// repeated singular values, so the ones computed here may differ from those of
+    /// [`SvdPlan::left_vectors`] and [`SvdPlan::right_vectors`], which aren't computed by this.
+    pub fn triplets(&self) -> SvdTriplets<'_, E> {
+        let n = self.diag.len();
+        let (col_mul, row_mul) = if n == 0 {
+            (Vec::new(), Vec::new())
+        } else {
+            self.unit_factors()
+        };
+        let diag: Vec<E::Real> = self.diag.iter().map(|x| x.faer_abs()).collect();
+        let subdiag: Vec<E::Real> = self.subdiag.iter().map(|x| x.faer_abs()).collect();
+        let mut scale = E::Real::faer_zero();
+        for x in diag.iter().chain(&subdiag) {
+            if *x > scale {
+                scale = *x;
+            }
+        }
+
+        let mem = if n == 0 {
+            GlobalPodBuffer::new(StackReq::empty())
+        } else {
+            GlobalPodBuffer::new(
+                apply_block_householder_sequence_on_the_left_in_place_req::<E>(
+                    self.bid.nrows(),
+                    self.householder_left.nrows(),
+                    1,
+                )
+                .unwrap(),
+            )
+        };
+
+        SvdTriplets {
+            plan: self,
+            index: 0,
+            diag,
+            subdiag,
+            col_mul,
+            row_mul,
+            cluster_tol: scale.faer_mul(E::Real::faer_from_f64(1e-3)),
+            cluster: Vec::new(),
+            mem,
+        }
+    }
+}
+
+/// Iterator over the singular triplets of an [`SvdPlan`], created by [`SvdPlan::triplets`].
+///
+/// Each item is a tuple `(sigma, u, v)` of a singular value and its left and right singular
+/// vectors.
+pub struct SvdTriplets<'a, E: ComplexField> {
+    plan: &'a SvdPlan<E>,
+    index: usize,
+    /// absolute values of the entries of the bidiagonal matrix
+    diag: Vec<E::Real>,
+    subdiag: Vec<E::Real>,
+    col_mul: Vec<E>,
+    row_mul: Vec<E>,
+    /// singular values closer than this to the previous one are part of the same cluster
+    cluster_tol: E::Real,
+    /// singular vectors of the bidiagonal matrix for the current cluster
+    cluster: Vec<(Vec<E::Real>, Vec<E::Real>)>,
+    mem: GlobalPodBuffer,
+}
+
+impl<E: ComplexField> Iterator for SvdTriplets<'_, E> {
+    type Item = (E::Real, Col<E>, Col<E>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let plan = self.plan;
+        let j = self.index;
+        if j == plan.s.nrows() {
+            return None;
+        }
+        self.index += 1;
+        let sigma = plan.s.read(j, 0).faer_real();
+
+        if plan.diag.is_empty() {
+            // zero-sized or non-finite matrix, whose singular vectors are stored
+            let (u, v) = plan.vectors.as_ref().unwrap();
+            return Some((
+                sigma,
+                Col::from_fn(u.nrows(), |i| u.read(i, j)),
+                Col::from_fn(v.nrows(), |i| v.read(i, j)),
+            ));
+        }
+
+        let m = plan.bid.nrows();
+        let n = plan.bid.ncols();
+        if j > 0 && plan.s.read(j - 1, 0).faer_real().faer_sub(sigma) > self.cluster_tol {
+            self.cluster.clear();
+        }
+        let (u_b, v_b) =
+            bidiag_singular_vector_pair(&self.diag, &self.subdiag, sigma, &self.cluster, j);
+
+        // the bidiagonal matrix is the adjoint of the bidiagonalization, so its right singular
+        // vectors go through the left householder sequence, and the other way around
+        let mut stack = PodStack::new(&mut self.mem);
+        let mut u = Col::<E>::zeros(m);
+        for (i, &x) in v_b.iter().enumerate() {
+            u.write(i, self.col_mul[i].faer_scale_real(x));
+        }
+        apply_block_householder_sequence_on_the_left_in_place_with_conj(
+            plan.bid.as_ref(),
+            plan.householder_left.as_ref(),
+            Conj::No,
+            u.as_mut().as_2d_mut(),
+            plan.parallelism,
+            stack.rb_mut(),
+        );
+        let mut v = Col::<E>::from_fn(n, |i| self.row_mul[i].faer_scale_real(u_b[i]));
+        if n > 1 {
+            apply_block_householder_sequence_on_the_left_in_place_with_conj(
+                plan.bid.as_ref().submatrix(0, 1, m, n - 1).transpose(),
+                plan.householder_right.as_ref(),
+                Conj::No,
+                v.as_mut().subrows_mut(1, n - 1).as_2d_mut(),
+                plan.parallelism,
+                stack,
+            );
+        }
+        self.cluster.push((u_b, v_b));
+
+        if plan.transposed {
+            let conj = |x: Col<E>| Col::from_fn(x.nrows(), |i| x.read(i).faer_conj());
+            Some((sigma, conj(v), conj(u)))
+        } else {
+            Some((sigma, u, v))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.plan.s.nrows() - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<E: ComplexField> ExactSizeIterator for SvdTriplets<'_, E> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{compute_svd, compute_svd_req, ComputeVectors};
+    use assert_approx_eq::assert_approx_eq;
+    use faer_core::{assert, c64};
+
+    fn singular_values<E: ComplexField>(matrix: MatRef<'_, E>) -> Mat<E> {
+        let size = Ord::min(matrix.nrows(), matrix.ncols());
+        let mut s = Mat::<E>::zeros(size, 1);
+        compute_svd(
+            matrix,
+            s.as_mut(),
+            None,
+            None,
+            Parallelism::None,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                compute_svd_req::<E>(
+                    matrix.nrows(),
+                    matrix.ncols(),
+                    ComputeVectors::No,
+                    ComputeVectors::No,
+                    Parallelism::None,
+                    SvdParams::default(),
+                )
+                .unwrap(),
+            )),
+            SvdParams::default(),
+        );
+        s
+    }
+
+    #[test]
+    fn test_plan_lazy_vectors() {
+        for (m, n) in [
+            (1, 1),
+            (5, 1),
+            (1, 5),
+            (30, 20),
+            (20, 30),
+            (64, 64),
+            (200, 3),
+        ] {
+            let a = Mat::from_fn(m, n, |_, _| c64::new(rand::random(), rand::random()));
+            let size = Ord::min(m, n);
+
+            let mut plan = SvdPlan::new(a.as_ref());
+            assert!(!plan.has_vectors());
+
+            let expected = singular_values(a.as_ref());
+            let s = plan.s_diagonal();
+            assert!(s.nrows() == size);
+            for i in 0..size {
+                assert_approx_eq!(s.read(i, 0), expected.read(i, 0), 1e-10);
+            }
+
+            let u = plan.left_vectors().to_owned();
+            assert!(plan.has_vectors());
+            let v = plan.right_vectors().to_owned();
+            assert!(all(u.nrows() == m, u.ncols() == size));
+            assert!(all(v.nrows() == n, v.ncols() == size));
+
+            let s = plan.s_diagonal();
+            let us = Mat::from_fn(m, size, |i, k| {
+                u.read(i, k).faer_scale_real(s.read(k, 0).re)
+            });
+            let reconstructed = &us * v.adjoint();
+            for j in 0..n {
+                for i in 0..m {
+                    assert_approx_eq!(reconstructed.read(i, j), a.read(i, j), 1e-10);
+                }
+            }
+            for o in [u.adjoint() * &u, v.adjoint() * &v] {
+                for j in 0..size {
+                    for i in 0..size {
+                        let target = if i == j {
+                            c64::new(1.0, 0.0)
+                        } else {
+                            c64::new(0.0, 0.0)
+                        };
+                        assert_approx_eq!(o.read(i, j), target, 1e-10);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_plan_vectors_cached() {
+        let a = Mat::from_fn(40, 25, |_, _| rand::random::<f64>());
+        let mut plan = SvdPlan::new(a.as_ref());
+        assert!(!plan.has_vectors());
+
+        let u_ptr = plan.left_vectors().as_ptr();
+        let v_ptr = plan.right_vectors().as_ptr();
+        let s = plan.s_diagonal().to_owned();
+        assert!(plan.left_vectors().as_ptr() == u_ptr);
+        assert!(plan.right_vectors().as_ptr() == v_ptr);
+        assert!(plan.s_diagonal() == s.as_ref());
+
+        let mut plan = SvdPlan::new(Mat::<f64>::zeros(3, 0).as_ref());
+        assert!(plan.singular_values().is_empty());
+        assert!(all(
+            plan.left_vectors().nrows() == 3,
+            plan.left_vectors().ncols() == 0
+        ));
+    }
+
+    fn check_apply_vt<E: ComplexField>(a: MatRef<'_, E>, random: impl Fn() -> E) {
+        let m = a.nrows();
+        let n = a.ncols();
+        let size = Ord::min(m, n);
+
+        let plan = SvdPlan::new(a);
+        let x = Mat::from_fn(n, 2, |_, _| random());
+        let mut y = x.clone();
+        plan.apply_vt(y.as_mut().col_mut(0));
+        plan.apply_vt(y.as_mut().col_mut(1));
+        assert!(!plan.has_vectors());
+
+        // the columns of `V` are the same as the dense ones, up to unit factors that don't
+        // depend on `x`
+        let v = plan.clone().right_vectors().to_owned();
+        let expected = v.adjoint() * &x;
+        let tol = E::Real::faer_from_f64(1e-10);
+        for j in 0..size {
+            let factor = y.read(j, 0).faer_mul(expected.read(j, 0).faer_inv());
+            assert!(factor.faer_abs().faer_sub(E::Real::faer_one()).faer_abs() < tol);
+            let diff = y.read(j, 1).faer_sub(factor.faer_mul(expected.read(j, 1)));
+            assert!(diff.faer_abs() < tol);
+        }
+        // the extension of `V` is unitary
+        for k in 0..2 {
+            let norm = |mat: &Mat<E>| {
+                (0..n)
+                    .map(|i| mat.read(i, k).faer_abs2())
+                    .fold(E::Real::faer_zero(), |acc, x| acc.faer_add(x))
+            };
+            assert!(norm(&y).faer_sub(norm(&x)).faer_abs() < tol.faer_mul(norm(&x)));
+        }
+    }
+
+    #[test]
+    fn test_plan_apply_vt() {
+        for (m, n) in [(1, 1), (30, 20), (20, 30), (64, 64), (200, 3), (3, 200)] {
+            check_apply_vt(
+                Mat::from_fn(m, n, |_, _| rand::random::<f64>()).as_ref(),
+                rand::random::<f64>,
+            );
+            check_apply_vt(
+                Mat::from_fn(m, n, |_, _| c64::new(rand::random(), rand::random())).as_ref(),
+                || c64::new(rand::random(), rand::random()),
+            );
+        }
+
+        let plan = SvdPlan::new(Mat::<f64>::zeros(3, 0).as_ref());
+        plan.apply_vt(Mat::<f64>::zeros(0, 1).as_mut().col_mut(0));
+    }
+
+    fn check_triplets<E: ComplexField>(a: MatRef<'_, E>) {
+        let m = a.nrows();
+        let n = a.ncols();
+        let size = Ord::min(m, n);
+
+        let plan = SvdPlan::new(a);
+        let s = plan.singular_values();
+        let triplets: Vec<_> = plan.triplets().collect();
+        assert!(triplets.len() == size);
+        // the vectors of empty matrices are stored on construction
+        assert!(size == 0 || !plan.has_vectors());
+
+        let u = Mat::<E>::from_fn(m, size, |i, j| triplets[j].1.read(i));
+        let v = Mat::<E>::from_fn(n, size, |i, j| triplets[j].2.read(i));
+        let norm = s.first().copied().unwrap_or(E::Real::faer_zero());
+        let tol = E::Real::faer_from_f64(1e-10);
+
+        // A v_j = sigma_j u_j, A^H u_j = sigma_j v_j
+        let av = a * &v;
+        let ahu = a.adjoint() * &u;
+        for (j, (sigma, _, _)) in triplets.iter().enumerate() {
+            assert!(*sigma == s[j]);
+            for i in 0..m {
+                let residual = av.read(i, j).faer_sub(u.read(i, j).faer_scale_real(*sigma));
+                assert!(residual.faer_abs() <= tol.faer_mul(norm));
+            }
+            for i in 0..n {
+                let residual = ahu
+                    .read(i, j)
+                    .faer_sub(v.read(i, j).faer_scale_real(*sigma));
+                assert!(residual.faer_abs() <= tol.faer_mul(norm));
+            }
+        }
+        for o in [u.adjoint() * &u, v.adjoint() * &v] {
+            for j in 0..size {
+                for i in 0..size {
+                    let target = if i == j {
+                        E::faer_one()
+                    } else {
+                        E::faer_zero()
+                    };
+                    assert!(o.read(i, j).faer_sub(target).faer_abs() <= tol);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_plan_triplets() {
+        for (m, n) in [(1, 1), (5, 1), (1, 5), (30, 20), (20, 30), (64, 64)] {
+            check_triplets(Mat::from_fn(m, n, |_, _| rand::random::<f64>()).as_ref());
+            check_triplets(
+                Mat::from_fn(m, n, |_, _| c64::new(rand::random(), rand::random())).as_ref(),
+            );
+        }
+
+        // repeated and zero singular values, whose vectors are orthogonalized against each other
+        let (m, n) = (12, 8);
+        let q_left = SvdPlan::new(Mat::from_fn(m, n, |_, _| rand::random::<f64>()).as_ref())
+            .left_vectors()
+            .to_owned();
+        let q_right = SvdPlan::new(Mat::from_fn(n, n, |_, _| rand::random::<f64>()).as_ref())
+            .left_vectors()
+            .to_owned();
+        let s = [3.0, 3.0, 3.0, 2.0, 1.0, 1.0, 0.0, 0.0];
+        let us = Mat::from_fn(m, n, |i, j| q_left.read(i, j) * s[j]);
+        check_triplets((&us * q_right.transpose()).as_ref());
+        check_triplets(Mat::<f64>::identity(n, n).as_ref());
+        check_triplets(Mat::<f64>::zeros(m, n).as_ref());
+        check_triplets(Mat::<f64>::zeros(3, 0).as_ref());
+
+        // only the vectors of the consumed triplets are computed
+        let a = Mat::from_fn(100, 100, |_, _| rand::random::<f64>());
+        let plan = SvdPlan::new(a.as_ref());
+        let mut triplets = plan.triplets();
+        assert!(triplets.len() == 100);
+        let first: Vec<_> = triplets.by_ref().take(5).collect();
+        assert!(first.len() == 5);
+        assert!(triplets.len() == 95);
+        assert!(!plan.has_vectors());
+    }
+}
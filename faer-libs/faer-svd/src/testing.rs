@@ -0,0 +1,404 @@
+//! Reproducible test inputs for the singular value decomposition, and checks of its results.
+
+use faer_core::{assert, Col, ComplexField, Mat, MatRef, RealField};
+
+/// SplitMix64 generator, chosen for its simplicity and since its output only depends on the seed,
+/// and not on the platform.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    /// returns a value uniformly distributed in `[0, 1)`, computed exactly from 53 random bits
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+}
+
+/// Returns the diagonal and subdiagonal of a lower bidiagonal matrix of shape $(n + 1, n)$, with
+/// entries uniformly distributed in $[0, 1)$.
+///
+/// The output only depends on `n` and `seed`, and is identical across platforms.
+pub fn test_bidiag<E: RealField>(n: usize, seed: u64) -> (Vec<E>, Vec<E>) {
+    let mut rng = SplitMix64(seed);
+    let diag = (0..n)
+        .map(|_| E::faer_from_f64(rng.next_f64()))
+        .collect::<Vec<_>>();
+    let subdiag = (0..n)
+        .map(|_| E::faer_from_f64(rng.next_f64()))
+        .collect::<Vec<_>>();
+    (diag, subdiag)
+}
+
+/// Panics if the columns of `m` aren't orthonormal, i.e., if an entry of $M^H M - I$ has a
+/// magnitude larger than `tol`.
+///
+/// This can be used to check the singular vectors $U$ and $V$, since an error in their
+/// normalization doesn't necessarily show in the reconstruction $U S V^H$.
+#[track_caller]
+pub fn assert_orthogonal<E: ComplexField>(m: MatRef<'_, E>, tol: E::Real) {
+    let gram = m.adjoint() * m;
+    for j in 0..gram.ncols() {
+        for i in 0..gram.nrows() {
+            let mut x = gram.read(i, j);
+            if i == j {
+                x = x.faer_sub(E::faer_one());
+            }
+            let err = x.faer_abs();
+            if !err.faer_is_finite() || err > tol {
+                panic!(
+                    "the columns {i} and {j} of the matrix aren't orthonormal: the error is {err:?}, \
+                     above the tolerance {tol:?}",
+                );
+            }
+        }
+    }
+}
+
+/// Returns the relative residual $\|A v_j - s_j u_j\| / s_j$ of each singular triplet of the
+/// SVD $A = U S V^H$, where `s` contains the diagonal of $S$.
+///
+/// Only the first `s.nrows()` columns of `u` and `v` are used. The residual of a zero singular
+/// value isn't divided by it. This shows which singular vectors are inaccurate, which the global
+/// reconstruction error doesn't.
+///
+/// # Panics
+/// Panics if the shapes of `u`, `s` and `v` don't match the shape of `a`.
+#[track_caller]
+pub fn svd_per_triplet_residual<E: ComplexField>(
+    a: MatRef<'_, E>,
+    u: MatRef<'_, E>,
+    s: MatRef<'_, E>,
+    v: MatRef<'_, E>,
+) -> Col<E::Real> {
+    let size = s.nrows();
+    assert!(all(
+        s.ncols() == 1,
+        u.nrows() == a.nrows(),
+        v.nrows() == a.ncols(),
+        u.ncols() >= size,
+        v.ncols() >= size,
+    ));
+
+    let u = u.subcols(0, size);
+    let v = v.subcols(0, size);
+    let us = Mat::<E>::from_fn(a.nrows(), size, |i, j| u.read(i, j).faer_mul(s.read(j, 0)));
+    let residual = a * v - us;
+
+    Col::from_fn(size, |j| {
+        let norm = residual.as_ref().col(j).norm_l2();
+        let s = s.read(j, 0).faer_abs();
+        if s == E::Real::faer_zero() {
+            norm
+        } else {
+            norm.faer_div(s)
+        }
+    })
+}
+
+/// Checks whether the SVDs $A_1 = U_1 S_1 V_1^H$ and $A_2 = U_2 S_2 V_2^H$ are equal up to the
+/// ambiguity of the singular vectors, where `s1` and `s2` contain the diagonals of $S_1$ and
+/// $S_2$ in nonincreasing order.
+///
+/// The singular values are compared directly, with the tolerance `tol` relative to the largest
+/// one. The singular vectors are only unique up to a unit factor, or a unitary transformation
+/// for repeated singular values, so they're compared through the subspaces they span instead.
+/// The singular values of $S_1$ are grouped in clusters, separated by gaps larger than
+/// $\sqrt{\mathrm{tol}}$ times the largest one, and for each cluster, the distances
+/// $\|U_1 U_1^H - U_2 U_2^H\|_F$ and $\|V_1 V_1^H - V_2 V_2^H\|_F$ between the projectors on the
+/// singular vectors of the cluster must be at most `tol`. The left and right singular vectors
+/// must also be transformed together, so that $U_2^H U_1 = V_2^H V_1$ on each cluster, which
+/// rules out flipping the sign of only one of $u_j$ and $v_j$.
+///
+/// The singular vectors of the singular values that are negligible compared to the largest one
+/// are arbitrary in the null spaces of the matrix, and aren't compared. Only the first
+/// `s1.nrows()` columns of the singular vectors are used.
+///
+/// # Panics
+/// Panics if the shapes of `u1`, `s1` and `v1`, or `u2`, `s2` and `v2`, don't match.
+#[track_caller]
+pub fn svd_approx_equal<E: ComplexField>(
+    (u1, s1, v1): (MatRef<'_, E>, MatRef<'_, E>, MatRef<'_, E>),
+    (u2, s2, v2): (MatRef<'_, E>, MatRef<'_, E>, MatRef<'_, E>),
+    tol: E::Real,
+) -> bool {
+    let size = s1.nrows();
+    assert!(all(
+        s1.ncols() == 1,
+        s2.ncols() == 1,
+        u1.ncols() >= size,
+        v1.ncols() >= size,
+        u2.ncols() >= s2.nrows(),
+        v2.ncols() >= s2.nrows(),
+        u1.nrows() == u2.nrows(),
+        v1.nrows() == v2.nrows(),
+    ));
+    if s2.nrows() != size {
+        return false;
+    }
+    if size == 0 {
+        return true;
+    }
+
+    let mut norm = E::Real::faer_zero();
+    for s in [s1, s2] {
+        for j in 0..size {
+            let x = s.read(j, 0).faer_abs();
+            if x > norm {
+                norm = x;
+            }
+        }
+    }
+    let value_tol = if norm == E::Real::faer_zero() {
+        tol
+    } else {
+        tol.faer_mul(norm)
+    };
+    let gap_tol = if norm == E::Real::faer_zero() {
+        tol.faer_sqrt()
+    } else {
+        tol.faer_sqrt().faer_mul(norm)
+    };
+
+    for j in 0..size {
+        let err = s1.read(j, 0).faer_sub(s2.read(j, 0)).faer_abs();
+        if !err.faer_is_finite() || err > value_tol {
+            return false;
+        }
+    }
+
+    // `||P1 - P2||_F = sqrt(2) ||Q1 - Q2 Q2^H Q1||_F` for orthonormal bases `Q1` and `Q2` of
+    // subspaces of the same dimension, which avoids forming the projectors, and the
+    // cancellation of computing the distance from `||Q2^H Q1||_F`
+    let sqrt_2 = E::Real::faer_from_f64(2.0).faer_sqrt();
+    let rank = (0..size)
+        .take_while(|&j| s1.read(j, 0).faer_abs() > value_tol)
+        .count();
+    let mut start = 0;
+    while start < rank {
+        let mut end = start + 1;
+        while end < rank
+            && s1
+                .read(end - 1, 0)
+                .faer_real()
+                .faer_sub(s1.read(end, 0).faer_real())
+                <= gap_tol
+        {
+            end += 1;
+        }
+
+        let len = end - start;
+        let u1 = u1.subcols(start, len);
+        let u2 = u2.subcols(start, len);
+        let v1 = v1.subcols(start, len);
+        let v2 = v2.subcols(start, len);
+        let u21 = u2.adjoint() * u1;
+        let v21 = v2.adjoint() * v1;
+        let dist_u = (u1 - u2 * &u21).norm_l2().faer_mul(sqrt_2);
+        let dist_v = (v1 - v2 * &v21).norm_l2().faer_mul(sqrt_2);
+        let coupling = (&u21 - &v21).norm_l2();
+        for err in [dist_u, dist_v, coupling] {
+            if !err.faer_is_finite() || err > tol {
+                return false;
+            }
+        }
+        start = end;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use faer_core::assert;
+
+    #[test]
+    fn test_reproducible() {
+        let (diag, subdiag) = test_bidiag::<f64>(2, 0);
+        assert!(diag == [0.8833108082136426, 0.43152799704850997]);
+        assert!(subdiag == [0.026433771592597743, 0.9708819781538285]);
+
+        let (diag_f32, subdiag_f32) = test_bidiag::<f32>(2, 0);
+        assert!(diag_f32 == [diag[0] as f32, diag[1] as f32]);
+        assert!(subdiag_f32 == [subdiag[0] as f32, subdiag[1] as f32]);
+
+        assert!(test_bidiag::<f64>(2, 1) != (diag, subdiag));
+    }
+    #[test]
+    fn test_assert_orthogonal() {
+        let q = faer_core::mat![[0.6, -0.8], [0.8, 0.6], [0.0, 0.0]];
+        assert_orthogonal(q.as_ref(), 1e-14);
+        assert_orthogonal(q.as_ref().transpose().submatrix(0, 0, 2, 2), 1e-14);
+    }
+
+    #[test]
+    #[should_panic(expected = "the columns 1 and 1 of the matrix aren't orthonormal")]
+    fn test_assert_orthogonal_norm() {
+        let q = faer_core::mat![[1.0, 0.0], [0.0, 1.0 + 1e-6]];
+        assert_orthogonal(q.as_ref(), 1e-10);
+    }
+
+    #[test]
+    #[should_panic(expected = "the columns 1 and 0 of the matrix aren't orthonormal")]
+    fn test_assert_orthogonal_angle() {
+        let q = faer_core::mat![[1.0, 1e-6], [0.0, 1.0]];
+        assert_orthogonal(q.as_ref(), 1e-10);
+    }
+    #[test]
+    fn test_svd_per_triplet_residual() {
+        use crate::{compute_svd, compute_svd_req, ComputeVectors, SvdParams};
+        use dyn_stack::{GlobalPodBuffer, PodStack};
+        use faer_core::Parallelism;
+
+        let (m, n) = (12, 8);
+        let a = Mat::from_fn(m, n, |_, _| rand::random::<f64>());
+        let mut s = Mat::zeros(n, 1);
+        let mut u = Mat::zeros(m, n);
+        let mut v = Mat::zeros(n, n);
+        compute_svd(
+            a.as_ref(),
+            s.as_mut(),
+            Some(u.as_mut()),
+            Some(v.as_mut()),
+            Parallelism::None,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                compute_svd_req::<f64>(
+                    m,
+                    n,
+                    ComputeVectors::Thin,
+                    ComputeVectors::Thin,
+                    Parallelism::None,
+                    SvdParams::default(),
+                )
+                .unwrap(),
+            )),
+            SvdParams::default(),
+        );
+
+        let residual = svd_per_triplet_residual(a.as_ref(), u.as_ref(), s.as_ref(), v.as_ref());
+        assert!(residual.nrows() == n);
+        for j in 0..n {
+            assert!(residual.read(j) < 1e-12);
+        }
+
+        // spoil one of the left singular vectors
+        for i in 0..m {
+            u.write(i, 3, u.read(i, 3) + 1e-6 * u.read(i, 5));
+        }
+        let residual = svd_per_triplet_residual(a.as_ref(), u.as_ref(), s.as_ref(), v.as_ref());
+        let worst = (0..n)
+            .max_by(|&i, &j| residual.read(i).total_cmp(&residual.read(j)))
+            .unwrap();
+        assert!(worst == 3);
+
+        // the error of the reconstruction along each right singular vector
+        let us = Mat::from_fn(m, n, |i, j| u.read(i, j) * s.read(j, 0));
+        let error = &a - us * v.transpose();
+        for j in 0..n {
+            let expected = (&error * v.as_ref().col(j).as_2d()).norm_l2() / s.read(j, 0);
+            assert!((residual.read(j) - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_svd_approx_equal() {
+        use crate::{decomposition::thin_svd, SvdAlgorithm, SvdDecomposition, SvdParams};
+        use faer_core::Parallelism;
+
+        // repeated singular values, and a zero one, whose left singular vector is arbitrary
+        let (m, n) = (12, 9);
+        let random = |m, n| Mat::from_fn(m, n, |_, _| rand::random::<f64>());
+        let q_left = SvdDecomposition::new(random(m, n).as_ref());
+        let q_right = SvdDecomposition::new(random(n, n).as_ref());
+        let values = [3.0, 3.0, 3.0, 2.0, 1.0, 1.0, 0.5, 0.25, 0.0];
+        let us = Mat::from_fn(m, n, |i, j| q_left.u().read(i, j) * values[j]);
+        let a = &us * q_right.u().transpose();
+
+        let svds = [
+            SvdAlgorithm::Jacobi,
+            SvdAlgorithm::QrIteration,
+            SvdAlgorithm::DivideConquer,
+        ]
+        .map(|algorithm| {
+            let params = SvdParams {
+                algorithm,
+                ..SvdParams::default()
+            };
+            thin_svd(a.as_ref(), Parallelism::None, params)
+        });
+        for (u1, s1, v1) in &svds {
+            for (u2, s2, v2) in &svds {
+                assert!(svd_approx_equal(
+                    (u1.as_ref(), s1.as_ref(), v1.as_ref()),
+                    (u2.as_ref(), s2.as_ref(), v2.as_ref()),
+                    1e-10,
+                ));
+            }
+        }
+
+        // flip the signs of a pair of singular vectors, and rotate the vectors of the cluster of
+        // the largest singular value
+        let (u, s, v) = &svds[0];
+        let mut u2 = u.clone();
+        let mut v2 = v.clone();
+        let (c, sn) = (0.6, 0.8);
+        for x in [&mut u2, &mut v2] {
+            for i in 0..x.nrows() {
+                let (a, b) = (x.read(i, 0), x.read(i, 2));
+                x.write(i, 0, c * a - sn * b);
+                x.write(i, 2, sn * a + c * b);
+                x.write(i, 4, -x.read(i, 4));
+            }
+        }
+        assert!((&u2 - u).norm_max() > 0.1);
+        assert!(svd_approx_equal(
+            (u.as_ref(), s.as_ref(), v.as_ref()),
+            (u2.as_ref(), s.as_ref(), v2.as_ref()),
+            1e-10,
+        ));
+
+        // the sign of only one of the vectors of a triplet
+        let mut u3 = u2.clone();
+        for i in 0..m {
+            u3.write(i, 6, -u3.read(i, 6));
+        }
+        assert!(!svd_approx_equal(
+            (u.as_ref(), s.as_ref(), v.as_ref()),
+            (u3.as_ref(), s.as_ref(), v2.as_ref()),
+            1e-10,
+        ));
+
+        // vectors of distinct singular values
+        let mut v3 = v2.clone();
+        for i in 0..n {
+            let (a, b) = (v3.read(i, 3), v3.read(i, 4));
+            v3.write(i, 3, b);
+            v3.write(i, 4, a);
+        }
+        assert!(!svd_approx_equal(
+            (u.as_ref(), s.as_ref(), v.as_ref()),
+            (u2.as_ref(), s.as_ref(), v3.as_ref()),
+            1e-10,
+        ));
+
+        // singular values
+        let mut s2 = s.clone();
+        s2.write(5, 0, s2.read(5, 0) + 1e-6);
+        assert!(!svd_approx_equal(
+            (u.as_ref(), s.as_ref(), v.as_ref()),
+            (u.as_ref(), s2.as_ref(), v.as_ref()),
+            1e-10,
+        ));
+        assert!(!svd_approx_equal(
+            (u.as_ref(), s.as_ref(), v.as_ref()),
+            (u.as_ref(), s.as_ref().subrows(0, n - 1), v.as_ref()),
+            1e-10,
+        ));
+    }
+}
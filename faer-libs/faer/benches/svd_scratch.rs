@@ -0,0 +1,115 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use dyn_stack::{GlobalPodBuffer, PodStack};
+use faer::{modules::svd as faer_svd, prelude::*, Mat, Parallelism};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering::Relaxed},
+    time::Duration,
+};
+
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size(), Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+const N: usize = 64;
+
+/// svd through the facade, which draws its workspace from the thread-local pool.
+fn pooled(mat: &Mat<f64>) {
+    let _ = mat.svd();
+}
+
+/// svd with a freshly allocated workspace, which is what the facade did before the pool.
+fn fresh(mat: &Mat<f64>) {
+    let (m, n) = (mat.nrows(), mat.ncols());
+    let mut s = Mat::<f64>::zeros(n, 1);
+    let mut u = Mat::<f64>::zeros(m, m);
+    let mut v = Mat::<f64>::zeros(n, n);
+    let params = Default::default();
+    faer_svd::compute_svd(
+        mat.as_ref(),
+        s.as_mut(),
+        Some(u.as_mut()),
+        Some(v.as_mut()),
+        Parallelism::None,
+        PodStack::new(&mut GlobalPodBuffer::new(
+            faer_svd::compute_svd_req::<f64>(
+                m,
+                n,
+                faer_svd::ComputeVectors::Full,
+                faer_svd::ComputeVectors::Full,
+                Parallelism::None,
+                params,
+            )
+            .unwrap(),
+        )),
+        params,
+    );
+}
+
+fn run(pool: &rayon::ThreadPool, mats: &[Mat<f64>], f: fn(&Mat<f64>)) {
+    use rayon::prelude::*;
+    pool.install(|| mats.par_iter().for_each(f));
+}
+
+pub fn concurrent_svd(c: &mut Criterion) {
+    faer::set_global_parallelism(Parallelism::None);
+
+    let max_threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let mut thread_counts = vec![1, 2, 4, 8, max_threads];
+    thread_counts.retain(|&t| t <= max_threads);
+    thread_counts.dedup();
+
+    let mats = (0..256)
+        .map(|_| Mat::from_fn(N, N, |_, _| rand::random::<f64>()))
+        .collect::<Vec<_>>();
+
+    for &threads in &thread_counts {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap();
+
+        for (name, f) in [("pooled", pooled as fn(&Mat<f64>)), ("fresh", fresh)] {
+            // warm up the thread-local pools of the workers before counting
+            run(&pool, &mats, f);
+            let allocations = ALLOCATIONS.load(Relaxed);
+            let bytes = ALLOCATED_BYTES.load(Relaxed);
+            run(&pool, &mats, f);
+            println!(
+                "faer-{name}-svd-{N}x{N}-{threads}t: {} allocations, {} bytes per svd",
+                (ALLOCATIONS.load(Relaxed) - allocations) / mats.len(),
+                (ALLOCATED_BYTES.load(Relaxed) - bytes) / mats.len(),
+            );
+
+            c.bench_function(&format!("faer-{name}-svd-{N}x{N}-{threads}t"), |b| {
+                b.iter(|| run(&pool, &mats, f))
+            });
+        }
+    }
+}
+
+criterion_group!(
+    name = svd_scratch;
+    config = Criterion::default()
+        .warm_up_time(Duration::from_secs(1))
+        .measurement_time(Duration::from_secs(3))
+        .sample_size(10);
+    targets = concurrent_svd
+);
+criterion_main!(svd_scratch);
@@ -210,7 +210,8 @@ pub mod prelude {
 
 pub use faer_core::{
     col, complex_native, get_global_parallelism, mat, row, scale, set_global_parallelism, unzipped,
-    zipped, Col, ColMut, ColRef, Mat, MatMut, MatRef, Parallelism, Row, RowMut, RowRef, Side,
+    zipped, Col, ColMut, ColRef, DiagMut, DiagRef, Mat, MatMut, MatRef, Parallelism, Row, RowMut,
+    RowRef, Side,
 };
 #[cfg(feature = "std")]
 #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
@@ -222,7 +223,7 @@ use alloc::{vec, vec::Vec};
 /// Matrix solvers and decompositions.
 pub mod solvers {
     use super::*;
-    use faer_core::{assert, permutation::PermutationRef, zipped};
+    use faer_core::{assert, permutation::PermutationRef, zipped, RealField};
     use sparse::solvers::{SpSolverCore, SpSolverLstsqCore};
 
     pub trait SolverCore<E: Entity>: SpSolverCore<E> {
@@ -299,10 +300,54 @@ pub mod solvers {
         u: Mat<E>,
         v: Mat<E>,
     }
+    /// Errors that can occur when computing the singular value decomposition with
+    /// [`Svd::try_new`].
+    #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+    #[non_exhaustive]
+    pub enum SvdError {
+        /// The size of the workspace exceeds the maximum allocation size.
+        SizeOverflow,
+        /// Memory allocation failed.
+        OutOfMemory,
+        /// The decomposition itself failed, e.g. because the matrix contains infinite or NaN
+        /// values.
+        Svd(faer_svd::SvdError),
+    }
+
+    impl From<faer_svd::SvdError> for SvdError {
+        #[inline]
+        fn from(value: faer_svd::SvdError) -> Self {
+            Self::Svd(value)
+        }
+    }
+
+    impl core::fmt::Display for SvdError {
+        #[inline]
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            core::fmt::Debug::fmt(self, f)
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl std::error::Error for SvdError {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            match self {
+                Self::Svd(err) => Some(err),
+                _ => None,
+            }
+        }
+    }
     /// Thin singular value decomposition.
     pub struct ThinSvd<E: Entity> {
         inner: Svd<E>,
     }
+    /// Thin singular value decomposition of a matrix whose columns were first permuted by a
+    /// column-pivoted QR decomposition.
+    pub struct PivotedSvd<E: Entity> {
+        inner: Svd<E>,
+        col_perm: Vec<usize>,
+        col_perm_inv: Vec<usize>,
+    }
 
     /// Self-adjoint eigendecomposition.
     pub struct SelfAdjointEigendecomposition<E: Entity> {
@@ -1445,6 +1490,10 @@ pub mod solvers {
     impl<E: ComplexField> Svd<E> {
         #[track_caller]
         fn __new_impl((matrix, conj): (MatRef<'_, E>, Conj), thin: bool) -> Self {
+            if is_self_adjoint(matrix) {
+                return Self::__new_self_adjoint_impl((matrix, conj));
+            }
+
             let parallelism = get_global_parallelism();
             let m = matrix.nrows();
             let n = matrix.ncols();
@@ -1455,39 +1504,152 @@ pub mod solvers {
             let mut v = Mat::<E>::zeros(n, if thin { size } else { n });
 
             let params = Default::default();
-
             let compute_vecs = if thin {
                 faer_svd::ComputeVectors::Thin
             } else {
                 faer_svd::ComputeVectors::Full
             };
 
-            faer_svd::compute_svd(
+            let req = faer_svd::compute_svd_req::<E>(
+                m,
+                n,
+                compute_vecs,
+                compute_vecs,
+                parallelism,
+                params,
+            )
+            .unwrap();
+            scratch::with_scratch(req, |stack| {
+                Self::__compute_impl(
+                    (matrix, conj),
+                    s.as_mut(),
+                    u.as_mut(),
+                    v.as_mut(),
+                    parallelism,
+                    stack,
+                    params,
+                )
+            });
+
+            Self { s, u, v }
+        }
+
+        /// computes the svd of a self-adjoint matrix from its eigendecomposition $Q \Lambda Q^H$,
+        /// with $U = Q \operatorname{sign}(\Lambda)$, $S = |\Lambda|$ and $V = Q$
+        fn __new_self_adjoint_impl((matrix, conj): (MatRef<'_, E>, Conj)) -> Self {
+            let n = matrix.nrows();
+            let evd = SelfAdjointEigendecomposition::__new_impl((matrix, conj), Side::Lower);
+            let eigvals = evd.s_diagonal();
+            let q = evd.u();
+
+            let mut s = Mat::<E>::zeros(n, 1);
+            let mut u = Mat::<E>::zeros(n, n);
+            let mut v = Mat::<E>::zeros(n, n);
+
+            // the eigenvalues are sorted in nondecreasing order, so the next largest singular
+            // value is at one of the two ends of the remaining ones
+            let (mut lo, mut hi) = (0, n);
+            for k in 0..n {
+                let lambda_lo = eigvals.read(lo, 0).faer_real();
+                let lambda_hi = eigvals.read(hi - 1, 0).faer_real();
+                let j = if lambda_hi.faer_abs() >= lambda_lo.faer_abs() {
+                    hi -= 1;
+                    hi
+                } else {
+                    lo += 1;
+                    lo - 1
+                };
+
+                let lambda = eigvals.read(j, 0).faer_real();
+                s.write(k, 0, E::faer_from_real(lambda.faer_abs()));
+                for i in 0..n {
+                    let x = q.read(i, j);
+                    v.write(i, k, x);
+                    u.write(
+                        i,
+                        k,
+                        if lambda < E::Real::faer_zero() {
+                            x.faer_neg()
+                        } else {
+                            x
+                        },
+                    );
+                }
+            }
+
+            Self { s, u, v }
+        }
+
+        fn __try_new_impl((matrix, conj): (MatRef<'_, E>, Conj)) -> Result<Self, SvdError> {
+            let parallelism = get_global_parallelism();
+            let m = matrix.nrows();
+            let n = matrix.ncols();
+            let size = Ord::min(m, n);
+
+            let params = Default::default();
+            let req = faer_svd::compute_svd_req::<E>(
+                m,
+                n,
+                faer_svd::ComputeVectors::Full,
+                faer_svd::ComputeVectors::Full,
+                parallelism,
+                params,
+            )
+            .map_err(|_| SvdError::SizeOverflow)?;
+            if req.try_unaligned_bytes_required().unwrap_or(usize::MAX) > isize::MAX as usize {
+                return Err(SvdError::SizeOverflow);
+            }
+
+            let zeros = |nrows: usize, ncols: usize| {
+                let mut mat =
+                    Mat::<E>::try_with_capacity(nrows, ncols).map_err(|_| SvdError::OutOfMemory)?;
+                mat.resize_with(nrows, ncols, |_, _| E::faer_zero());
+                Ok(mat)
+            };
+            let mut s = zeros(size, 1)?;
+            let mut u = zeros(m, m)?;
+            let mut v = zeros(n, n)?;
+            let mut mem = GlobalPodBuffer::try_new(req).map_err(|_| SvdError::OutOfMemory)?;
+
+            faer_svd::try_compute_svd(
                 matrix,
                 s.as_mut(),
                 Some(u.as_mut()),
                 Some(v.as_mut()),
                 parallelism,
-                PodStack::new(&mut GlobalPodBuffer::new(
-                    faer_svd::compute_svd_req::<E>(
-                        m,
-                        n,
-                        compute_vecs,
-                        compute_vecs,
-                        parallelism,
-                        params,
-                    )
-                    .unwrap(),
-                )),
+                PodStack::new(&mut mem),
                 params,
-            );
+            )?;
 
-            if matches!(conj, Conj::Yes) {
+            // the conjugate of the matrix has the same singular values, and conjugated singular
+            // vectors
+            if conj == Conj::Yes {
                 zipped!(u.as_mut()).for_each(|unzipped!(mut x)| x.write(x.read().faer_conj()));
                 zipped!(v.as_mut()).for_each(|unzipped!(mut x)| x.write(x.read().faer_conj()));
             }
 
-            Self { s, u, v }
+            Ok(Self { s, u, v })
+        }
+
+        fn __compute_impl(
+            (matrix, conj): (MatRef<'_, E>, Conj),
+            s: MatMut<'_, E>,
+            u: MatMut<'_, E>,
+            v: MatMut<'_, E>,
+            parallelism: Parallelism,
+            stack: PodStack<'_>,
+            params: faer_svd::SvdParams,
+        ) {
+            faer_svd::compute_svd_with_conj(
+                matrix,
+                conj,
+                s,
+                Some(u),
+                Some(v),
+                parallelism,
+                stack,
+                params,
+            );
         }
 
         #[track_caller]
@@ -1495,15 +1657,82 @@ pub mod solvers {
             Self::__new_impl(matrix.canonicalize(), false)
         }
 
+        /// Same as [`Svd::new`], but returns an error instead of panicking if the size of the
+        /// workspace overflows, aborting if an allocation fails, or producing NaN values if the
+        /// decomposition fails. See [`faer_svd::try_compute_svd`] for the failures of the
+        /// decomposition itself.
+        pub fn try_new<ViewE: Conjugate<Canonical = E>>(
+            matrix: MatRef<'_, ViewE>,
+        ) -> Result<Self, SvdError> {
+            Self::__try_new_impl(matrix.canonicalize())
+        }
+
         pub fn u(&self) -> MatRef<'_, E> {
             self.u.as_ref()
         }
         pub fn s_diagonal(&self) -> MatRef<'_, E> {
             self.s.as_ref()
         }
+        /// Returns the singular values as a diagonal matrix view, in nonincreasing order.
+        ///
+        /// The view has size `min(nrows, ncols)`, so it can be multiplied directly by the leading
+        /// columns of `u` and `v`.
+        pub fn s_diag(&self) -> DiagRef<'_, E> {
+            self.s.as_ref().column_vector_as_diagonal()
+        }
         pub fn v(&self) -> MatRef<'_, E> {
             self.v.as_ref()
         }
+
+        /// Returns the Moore-Penrose pseudo-inverse of the original matrix.
+        ///
+        /// Singular values that are negligible compared to the largest one are treated as zero.
+        pub fn pseudo_inverse(&self) -> Mat<E> {
+            let m = self.nrows();
+            let n = self.ncols();
+            let size = Ord::min(m, n);
+
+            let u = self.u.as_ref().submatrix(0, 0, m, size);
+            let v = self.v.as_ref().submatrix(0, 0, n, size);
+            let s = self.s.as_ref();
+            let tol = negligible_singular_value_threshold(m, n, s);
+
+            let vs_inv = Mat::<E>::from_fn(n, size, |i, j| {
+                let s = s.read(j, 0).faer_real();
+                if s > tol {
+                    v.read(i, j).faer_scale_real(s.faer_inv())
+                } else {
+                    E::faer_zero()
+                }
+            });
+
+            vs_inv * u.adjoint()
+        }
+    }
+
+    /// returns `true` if `matrix` is square and exactly equal to its adjoint
+    fn is_self_adjoint<E: ComplexField>(matrix: MatRef<'_, E>) -> bool {
+        let n = matrix.nrows();
+        if matrix.ncols() != n {
+            return false;
+        }
+        (0..n).all(|j| (j..n).all(|i| matrix.read(i, j) == matrix.read(j, i).faer_conj()))
+    }
+
+    /// returns the threshold below which the singular values `s` of a matrix of shape `(m, n)`
+    /// are negligible compared to the largest one
+    fn negligible_singular_value_threshold<E: ComplexField>(
+        m: usize,
+        n: usize,
+        s: MatRef<'_, E>,
+    ) -> E::Real {
+        if s.nrows() == 0 {
+            return E::Real::faer_zero();
+        }
+        E::Real::faer_epsilon()
+            .unwrap()
+            .faer_mul(E::Real::faer_from_f64(Ord::max(m, n) as f64))
+            .faer_mul(s.read(0, 0).faer_real())
     }
     fn div_by_s<E: ComplexField>(rhs: MatMut<'_, E>, s: MatRef<'_, E>) {
         let mut rhs = rhs;
@@ -1611,9 +1840,63 @@ pub mod solvers {
         pub fn s_diagonal(&self) -> MatRef<'_, E> {
             self.inner.s.as_ref()
         }
+        /// Returns the singular values as a diagonal matrix view, in nonincreasing order.
+        pub fn s_diag(&self) -> DiagRef<'_, E> {
+            self.inner.s.as_ref().column_vector_as_diagonal()
+        }
         pub fn v(&self) -> MatRef<'_, E> {
             self.inner.v.as_ref()
         }
+
+        /// Returns the Moore-Penrose pseudo-inverse of the original matrix.
+        ///
+        /// Singular values that are negligible compared to the largest one are treated as zero.
+        pub fn pseudo_inverse(&self) -> Mat<E> {
+            self.inner.pseudo_inverse()
+        }
+
+        #[doc(hidden)]
+        #[track_caller]
+        pub fn __new_randomized_impl(
+            (matrix, conj): (MatRef<'_, E>, Conj),
+            k: usize,
+            oversampling: usize,
+            power_iters: usize,
+            mut rng: impl FnMut() -> E,
+        ) -> Self {
+            let m = matrix.nrows();
+            let n = matrix.ncols();
+            let size = Ord::min(m, n);
+            assert!(k <= size);
+            let sample_size = Ord::min(k.saturating_add(oversampling), size);
+
+            let orthonormal_basis = |y: Mat<E>| Qr::new(y.as_ref()).compute_thin_q();
+
+            let omega = Mat::<E>::from_fn(n, sample_size, |_, _| rng());
+            let mut q = orthonormal_basis(matrix * omega.as_ref());
+            for _ in 0..power_iters {
+                // the basis is orthonormalized after each product, otherwise the directions of
+                // the small singular values would be lost to rounding errors
+                let z = orthonormal_basis(matrix.adjoint() * q.as_ref());
+                q = orthonormal_basis(matrix * z.as_ref());
+            }
+
+            // A ~= Q Q^H A = (Q U_b) S V^H
+            let b = q.adjoint() * matrix;
+            let svd_b = ThinSvd::<E>::new(b.as_ref());
+            let mut u = (q.as_ref() * svd_b.u().subcols(0, k)).to_owned();
+            let s = svd_b.s_diagonal().subrows(0, k).to_owned();
+            let mut v = svd_b.v().subcols(0, k).to_owned();
+
+            if conj == Conj::Yes {
+                zipped!(u.as_mut()).for_each(|unzipped!(mut x)| x.write(x.read().faer_conj()));
+                zipped!(v.as_mut()).for_each(|unzipped!(mut x)| x.write(x.read().faer_conj()));
+            }
+
+            Self {
+                inner: Svd { s, u, v },
+            }
+        }
     }
     impl<E: ComplexField> SpSolverCore<E> for ThinSvd<E> {
         fn nrows(&self) -> usize {
@@ -1645,6 +1928,55 @@ pub mod solvers {
         }
     }
 
+    impl<E: ComplexField> PivotedSvd<E> {
+        /// Computes the column-pivoted QR decomposition $A P = QR$ of `matrix`, then the thin SVD
+        /// $R = U_R S V^H$ of its upper trapezoidal factor, so that $A P = U S V^H$ with
+        /// $U = Q U_R$.
+        ///
+        /// The right singular vectors of $A$ itself are the rows of $V$ permuted by $P$.
+        #[track_caller]
+        pub fn new<ViewE: Conjugate<Canonical = E>>(matrix: MatRef<'_, ViewE>) -> Self {
+            let qr = ColPivQr::<E>::new(matrix);
+            let r = qr.compute_thin_r();
+            let Svd { s, u: u_r, v } = Svd::__new_impl((r.as_ref(), Conj::No), true);
+            let u = qr.compute_thin_q() * u_r;
+
+            Self {
+                inner: Svd { s, u, v },
+                col_perm: qr.col_perm,
+                col_perm_inv: qr.col_perm_inv,
+            }
+        }
+
+        pub fn u(&self) -> MatRef<'_, E> {
+            self.inner.u.as_ref()
+        }
+        pub fn s_diagonal(&self) -> MatRef<'_, E> {
+            self.inner.s.as_ref()
+        }
+        /// Returns the singular values as a diagonal matrix view, in nonincreasing order.
+        pub fn s_diag(&self) -> DiagRef<'_, E> {
+            self.inner.s.as_ref().column_vector_as_diagonal()
+        }
+        pub fn v(&self) -> MatRef<'_, E> {
+            self.inner.v.as_ref()
+        }
+        pub fn col_permutation(&self) -> PermutationRef<'_, usize, E> {
+            unsafe { PermutationRef::new_unchecked(&self.col_perm, &self.col_perm_inv) }
+        }
+
+        /// Returns the numerical rank of the original matrix, which is the number of singular
+        /// values that are not negligible compared to the largest one.
+        pub fn rank(&self) -> usize {
+            let s = self.s_diagonal();
+            let tol =
+                negligible_singular_value_threshold(self.inner.u.nrows(), self.inner.v.nrows(), s);
+            (0..s.nrows())
+                .filter(|&i| s.read(i, 0).faer_real() > tol)
+                .count()
+        }
+    }
+
     impl<E: ComplexField> SelfAdjointEigendecomposition<E> {
         #[track_caller]
         fn __new_impl((matrix, conj): (MatRef<'_, E>, Conj), side: Side) -> Self {
@@ -2004,6 +2336,101 @@ pub mod solvers {
     }
 }
 
+/// per-thread cache of the workspace buffers used by the svd solvers, so that many small
+/// decompositions running concurrently don't each go through the global allocator.
+///
+/// a buffer is moved out of the pool for the whole duration of the computation it is used for, so
+/// a nested call on the same thread (e.g. a task that rayon steals while the outer one is blocked
+/// on a join) never sees a buffer that is in use, and simply allocates a fresh one instead.
+mod scratch {
+    use dyn_stack::{GlobalPodBuffer, PodStack, StackReq};
+
+    /// maximum number of buffers kept alive per thread.
+    #[cfg(feature = "std")]
+    const POOL_LEN: usize = 4;
+    /// buffers larger than this are freed after use, since the allocation cost is negligible
+    /// compared to the decomposition that needs them.
+    #[cfg(feature = "std")]
+    const POOL_MAX_BYTES: usize = 4 * 1024 * 1024;
+
+    #[cfg(feature = "std")]
+    std::thread_local! {
+        static POOL: core::cell::RefCell<Vec<GlobalPodBuffer>> =
+            const { core::cell::RefCell::new(Vec::new()) };
+    }
+
+    /// removes the smallest pooled buffer that can hold `req` from the current thread's pool.
+    #[cfg(feature = "std")]
+    fn take(req: StackReq) -> Option<GlobalPodBuffer> {
+        POOL.try_with(|pool| {
+            let mut pool = pool.try_borrow_mut().ok()?;
+            let mut best = None::<usize>;
+            for i in 0..pool.len() {
+                let len = pool[i].len();
+                if PodStack::new(&mut pool[i]).can_hold(req)
+                    && !matches!(best, Some(best) if pool[best].len() <= len)
+                {
+                    best = Some(i);
+                }
+            }
+            best.map(|idx| pool.swap_remove(idx))
+        })
+        .ok()
+        .flatten()
+    }
+
+    /// returns `mem` to the current thread's pool, evicting the smallest buffer if it is full.
+    #[cfg(feature = "std")]
+    fn give_back(mem: GlobalPodBuffer) {
+        if mem.is_empty() || mem.len() > POOL_MAX_BYTES {
+            return;
+        }
+        let _ = POOL.try_with(|pool| {
+            if let Ok(mut pool) = pool.try_borrow_mut() {
+                pool.push(mem);
+                if pool.len() > POOL_LEN {
+                    let idx = (0..pool.len()).min_by_key(|&i| pool[i].len()).unwrap();
+                    pool.swap_remove(idx);
+                }
+            }
+        });
+    }
+
+    /// calls `f` with a workspace that satisfies `req`, reusing a buffer from the current
+    /// thread's pool when possible.
+    #[cfg(feature = "std")]
+    pub(crate) fn with_scratch<R>(req: StackReq, f: impl FnOnce(PodStack<'_>) -> R) -> R {
+        let mut mem = take(req).unwrap_or_else(|| GlobalPodBuffer::new(req));
+        let result = f(PodStack::new(&mut mem));
+        give_back(mem);
+        result
+    }
+
+    /// calls `f` with a freshly allocated workspace that satisfies `req`.
+    #[cfg(not(feature = "std"))]
+    pub(crate) fn with_scratch<R>(req: StackReq, f: impl FnOnce(PodStack<'_>) -> R) -> R {
+        f(PodStack::new(&mut GlobalPodBuffer::new(req)))
+    }
+
+    /// returns the number of buffers currently pooled on this thread.
+    #[cfg(all(test, feature = "std"))]
+    pub(crate) fn pooled_len() -> usize {
+        POOL.with(|pool| pool.borrow().len())
+    }
+}
+
+/// returns the number of singular values in `s`, sorted in nonincreasing order, that are larger
+/// than `rcond` times the largest one
+fn numerical_rank<E: ComplexField>(s: MatRef<'_, E>, rcond: E::Real) -> usize {
+    if s.nrows() == 0 {
+        return 0;
+    }
+    let tol = rcond.faer_mul(s.read(0, 0).faer_real());
+    (0..s.nrows())
+        .take_while(|&i| s.read(i, 0).faer_real() > tol)
+        .count()
+}
+
 /// Extension trait for `faer` types.
 pub trait FaerMat<E: ComplexField> {
     /// Assuming `self` is a lower triangular matrix, solves the equation `self * X = rhs`, and
@@ -2077,9 +2504,135 @@ pub trait FaerMat<E: ComplexField> {
     /// Returns the QR decomposition of `self`, with column pivoting.
     fn col_piv_qr(&self) -> ColPivQr<E>;
     /// Returns the SVD of `self`.
+    ///
+    /// If `self` is exactly equal to its adjoint, the SVD is computed from its eigendecomposition,
+    /// which is cheaper than the general algorithm.
+    ///
+    /// # Example
+    /// ```
+    /// use faer::{mat, FaerMat, Mat};
+    ///
+    /// let a = mat![
+    ///     [1.0, 2.0, 3.0],
+    ///     [4.0, 5.0, 6.0],
+    ///     [7.0, 8.0, 10.0],
+    ///     [1.0, 0.0, 1.0f64],
+    /// ];
+    /// let svd = a.svd();
+    /// let (u, v) = (svd.u(), svd.v());
+    /// assert!((u.nrows(), u.ncols()) == (4, 4));
+    /// assert!((v.nrows(), v.ncols()) == (3, 3));
+    ///
+    /// // `A = U S V^H`, where `S` only has `min(m, n)` nonzero entries
+    /// let reconstructed = u.subcols(0, 3) * svd.s_diag() * v.adjoint();
+    /// assert!((&reconstructed - &a).norm_max() < 1e-12);
+    ///
+    /// // `U` and `V` are unitary
+    /// let identity = |n| Mat::<f64>::from_fn(n, n, |i, j| if i == j { 1.0 } else { 0.0 });
+    /// assert!((u.adjoint() * u - identity(4)).norm_max() < 1e-12);
+    /// assert!((v.adjoint() * v - identity(3)).norm_max() < 1e-12);
+    ///
+    /// // the singular values are nonnegative, in nonincreasing order
+    /// let s = svd.s_diagonal();
+    /// assert!(s.read(0, 0) >= s.read(1, 0) && s.read(1, 0) >= s.read(2, 0));
+    /// assert!(s.read(2, 0) >= 0.0);
+    /// ```
     fn svd(&self) -> Svd<E>;
-    /// Returns the thin SVD of `self`.
+    /// Returns the SVD of `self`, or an error if the required memory can't be allocated, or if
+    /// the decomposition fails.
+    fn try_svd(&self) -> Result<Svd<E>, SvdError>;
+    /// Returns the thin SVD of `self`, where $U$ and $V$ only have $\min(m, n)$ columns.
+    ///
+    /// This is much cheaper than [`FaerMat::svd`] for matrices that are far from square, since
+    /// the full $U$ or $V$ is never allocated. Self-adjoint matrices are handled as in
+    /// [`FaerMat::svd`].
+    ///
+    /// # Example
+    /// ```
+    /// use faer::{complex_native::c64, FaerMat, Mat};
+    ///
+    /// let a = Mat::<c64>::from_fn(8, 3, |i, j| c64::new((i + j) as f64, (i * j) as f64 + 1.0));
+    /// let svd = a.thin_svd();
+    /// let (u, v) = (svd.u(), svd.v());
+    /// assert!((u.nrows(), u.ncols()) == (8, 3));
+    /// assert!((v.nrows(), v.ncols()) == (3, 3));
+    ///
+    /// // `A = U S V^H`, where the columns of `U` and `V` are orthonormal
+    /// let reconstructed = u * svd.s_diag() * v.adjoint();
+    /// assert!((&reconstructed - &a).norm_max() < 1e-12);
+    /// let identity = Mat::<c64>::from_fn(3, 3, |i, j| {
+    ///     c64::new(if i == j { 1.0 } else { 0.0 }, 0.0)
+    /// });
+    /// assert!((u.adjoint() * u - &identity).norm_max() < 1e-12);
+    /// assert!((v.adjoint() * v - &identity).norm_max() < 1e-12);
+    /// ```
     fn thin_svd(&self) -> ThinSvd<E>;
+    /// Returns an approximation of the thin SVD of `self` restricted to its `k` largest singular
+    /// values, computed with a randomized range finder.
+    ///
+    /// The range of $A$ is sampled as $Y = A \Omega$, where $\Omega$ has `k + oversampling`
+    /// columns whose entries are generated by `rng`, and `power_iters` steps of subspace iteration
+    /// $Y \leftarrow A A^H Y$ improve the sample when the singular values decay slowly. The SVD of
+    /// the small matrix $B = Q^H A$, where $Q$ is an orthonormal basis of the range of $Y$, is then
+    /// lifted back to $A$ with $U = Q U_B$.
+    ///
+    /// This is much cheaper than [`FaerMat::thin_svd`] when `k` is small compared to the
+    /// dimensions of `self`. The entries generated by `rng` should be independent samples of a
+    /// centered distribution, such as the standard normal distribution.
+    ///
+    /// # Panics
+    /// Panics if `k` is larger than the minimum of the number of rows and columns of `self`.
+    fn randomized_svd(
+        &self,
+        k: usize,
+        oversampling: usize,
+        power_iters: usize,
+        rng: impl FnMut() -> E,
+    ) -> ThinSvd<E>;
+    /// Returns the thin SVD of `self` with its columns permuted by a column-pivoted QR
+    /// decomposition, which reveals its numerical rank.
+    fn svd_with_pivoting(&self) -> PivotedSvd<E>;
+    /// Returns the polar decomposition $A = U_p H$ of `self`, computed from its thin SVD
+    /// $A = U S V^H$ as $U_p = U V^H$ and $H = V S V^H$.
+    ///
+    /// $H$ is self-adjoint positive semidefinite, and $U_p$ has orthonormal columns if `self` has
+    /// at least as many rows as columns, or orthonormal rows otherwise.
+    fn polar_decomposition(&self) -> (Mat<E>, Mat<E>);
+    /// Returns the square root of `self`, assuming it is self-adjoint and positive semidefinite,
+    /// computed from its SVD $A = U S V^H$ as $V S^{1/2} V^H$.
+    ///
+    /// For other square matrices, this is the square root of the factor $H$ of their polar
+    /// decomposition.
+    fn symmetric_square_root(&self) -> Mat<E>;
+    /// Returns an orthonormal basis of the numerical null space of `self`, made of the right
+    /// singular vectors whose singular value is at most `rcond` times the largest one.
+    ///
+    /// When `self` has more columns than rows, the right singular vectors that don't correspond
+    /// to any singular value are always included.
+    #[track_caller]
+    fn null_space(&self, rcond: E::Real) -> Mat<E> {
+        let svd = self.svd();
+        let v = svd.v();
+        let rank = numerical_rank(svd.s_diagonal(), rcond);
+        v.subcols(rank, v.ncols() - rank).to_owned()
+    }
+    /// Returns an orthonormal basis of the numerical row space of `self`, i.e., the range of its
+    /// adjoint, made of the right singular vectors whose singular value is larger than `rcond`
+    /// times the largest one.
+    #[track_caller]
+    fn row_space(&self, rcond: E::Real) -> Mat<E> {
+        let svd = self.thin_svd();
+        let rank = numerical_rank(svd.s_diagonal(), rcond);
+        svd.v().subcols(0, rank).to_owned()
+    }
+    /// Returns an orthonormal basis of the numerical column space of `self`, made of the left
+    /// singular vectors whose singular value is larger than `rcond` times the largest one.
+    #[track_caller]
+    fn column_space(&self, rcond: E::Real) -> Mat<E> {
+        let svd = self.thin_svd();
+        let rank = numerical_rank(svd.s_diagonal(), rcond);
+        svd.u().subcols(0, rank).to_owned()
+    }
     /// Returns the eigendecomposition of `self`, assuming it is self-adjoint. Only the provided
     /// side is accessed.
     fn selfadjoint_eigendecomposition(&self, side: Side) -> SelfAdjointEigendecomposition<E>;
@@ -2093,7 +2646,120 @@ pub trait FaerMat<E: ComplexField> {
     /// Returns the determinant of `self`.
     fn determinant(&self) -> E;
     /// Returns the singular values of `self`, in nonincreasing order.
+    ///
+    /// # Example
+    /// ```
+    /// use faer::{mat, FaerMat};
+    ///
+    /// // the singular values of a diagonal matrix are the absolute values of its entries
+    /// let a = mat![
+    ///     [0.0, 0.0, -2.0],
+    ///     [0.0, 3.0, 0.0],
+    ///     [1.0, 0.0, 0.0f64],
+    /// ];
+    /// let s = a.singular_values();
+    /// for (s, expected) in s.iter().zip([3.0, 2.0, 1.0]) {
+    ///     assert!((s - expected).abs() < 1e-12);
+    /// }
+    /// ```
     fn singular_values(&self) -> Vec<E::Real>;
+    /// Returns the spectral norm of `self`, i.e., its largest singular value. The singular vectors
+    /// are not computed.
+    #[track_caller]
+    fn spectral_norm(&self) -> E::Real {
+        self.singular_values()
+            .first()
+            .copied()
+            .unwrap_or_else(E::Real::faer_zero)
+    }
+    /// Returns an estimate of the spectral norm of `self`, computed by `iters` steps of power
+    /// iteration on $A^H A$, starting from a vector whose entries are generated by `rng`.
+    ///
+    /// Each step only costs two matrix-vector products, which is much cheaper than
+    /// [`FaerMat::spectral_norm`] for large matrices. The estimate is $\|A x\|$ for a unit vector
+    /// $x$, so it's a lower bound of the spectral norm, up to rounding errors, and its relative
+    /// error decreases like $(\sigma_2 / \sigma_1)^{2k}$ after $k$ steps, where $\sigma_1$ and
+    /// $\sigma_2$ are the two largest singular values. The entries generated by `rng` should be
+    /// independent samples of a centered distribution, so that the starting vector isn't
+    /// orthogonal to the largest right singular vector.
+    #[track_caller]
+    fn spectral_norm_estimate(&self, iters: usize, rng: impl FnMut() -> E) -> E::Real;
+    /// Returns the nuclear norm of `self`, i.e., the sum of its singular values. The singular
+    /// vectors are not computed.
+    #[track_caller]
+    fn nuclear_norm(&self) -> E::Real {
+        let mut sum = E::Real::faer_zero();
+        for s in self.singular_values() {
+            sum = sum.faer_add(s);
+        }
+        sum
+    }
+    /// Returns the Schatten $p$-norm of `self`, i.e., the $\ell^p$ norm of its singular values.
+    /// The singular vectors are not computed.
+    ///
+    /// $p = 1$ gives the nuclear norm and $p = 2$ the Frobenius norm, which is cheaper to compute
+    /// with [`MatRef::norm_l2`]. The spectral norm is the limit as $p \to \infty$.
+    ///
+    /// # Panics
+    /// Panics if `p` is zero.
+    #[track_caller]
+    fn schatten_norm(&self, p: u32) -> E::Real {
+        assert!(p > 0);
+
+        let powi = |x: E::Real| {
+            let mut acc = E::Real::faer_one();
+            let mut base = x;
+            let mut p = p;
+            while p > 0 {
+                if p % 2 == 1 {
+                    acc = acc.faer_mul(base);
+                }
+                base = base.faer_mul(base);
+                p /= 2;
+            }
+            acc
+        };
+
+        let s = self.singular_values();
+        let max = match s.first() {
+            Some(&max) => max,
+            None => return E::Real::faer_zero(),
+        };
+        if max == E::Real::faer_zero() || !max.faer_is_finite() {
+            return max;
+        }
+
+        // scale by the largest singular value so that the powers can't overflow or underflow.
+        // the sum is then in [1, n]
+        let max_inv = max.faer_inv();
+        let mut sum = E::Real::faer_zero();
+        for s in s {
+            sum = sum.faer_add(powi(s.faer_mul(max_inv)));
+        }
+
+        // solve y^p = sum with newton's method, starting from 1 + (sum - 1) / p, which is above the
+        // root by bernoulli's inequality. the iterates then decrease monotonically until rounding
+        // errors take over
+        let one = E::Real::faer_one();
+        let p_real = E::Real::faer_from_f64(p as f64);
+        let mut y = one.faer_add(sum.faer_sub(one).faer_mul(p_real.faer_inv()));
+        loop {
+            let y_pow = powi(y);
+            let next = y.faer_sub(
+                y_pow
+                    .faer_sub(sum)
+                    .faer_mul(y)
+                    .faer_mul(p_real.faer_mul(y_pow).faer_inv()),
+            );
+            if next < y {
+                y = next;
+            } else {
+                break;
+            }
+        }
+
+        max.faer_mul(y)
+    }
     /// Returns the eigenvalues of `self`, assuming it is self-adjoint. Only the provided
     /// side is accessed. The order of the eigenvalues is currently unspecified.
     fn selfadjoint_eigenvalues(&self, side: Side) -> Vec<E::Real>;
@@ -3107,11 +3773,98 @@ where
     fn svd(&self) -> Svd<E::Canonical> {
         Svd::<E::Canonical>::new(self.as_ref())
     }
+    fn try_svd(&self) -> Result<Svd<E::Canonical>, SvdError> {
+        Svd::<E::Canonical>::try_new(self.as_ref())
+    }
     #[track_caller]
     fn thin_svd(&self) -> ThinSvd<E::Canonical> {
         ThinSvd::<E::Canonical>::new(self.as_ref())
     }
     #[track_caller]
+    fn randomized_svd(
+        &self,
+        k: usize,
+        oversampling: usize,
+        power_iters: usize,
+        rng: impl FnMut() -> E::Canonical,
+    ) -> ThinSvd<E::Canonical> {
+        ThinSvd::<E::Canonical>::__new_randomized_impl(
+            self.canonicalize(),
+            k,
+            oversampling,
+            power_iters,
+            rng,
+        )
+    }
+    #[track_caller]
+    fn svd_with_pivoting(&self) -> PivotedSvd<E::Canonical> {
+        PivotedSvd::<E::Canonical>::new(self.as_ref())
+    }
+    #[track_caller]
+    fn polar_decomposition(&self) -> (Mat<E::Canonical>, Mat<E::Canonical>) {
+        let svd = self.thin_svd();
+        let u = svd.u();
+        let s = svd.s_diagonal();
+        let v = svd.v();
+
+        let vs = Mat::<E::Canonical>::from_fn(v.nrows(), v.ncols(), |i, j| {
+            v.read(i, j).faer_mul(s.read(j, 0))
+        });
+        (u * v.adjoint(), vs * v.adjoint())
+    }
+    #[track_caller]
+    fn symmetric_square_root(&self) -> Mat<E::Canonical> {
+        assert!(self.nrows() == self.ncols());
+
+        let svd = self.thin_svd();
+        let s = svd.s_diagonal();
+        let v = svd.v();
+
+        let v_sqrt_s = Mat::<E::Canonical>::from_fn(v.nrows(), v.ncols(), |i, j| {
+            v.read(i, j)
+                .faer_scale_real(s.read(j, 0).faer_real().faer_sqrt())
+        });
+        v_sqrt_s * v.adjoint()
+    }
+    #[track_caller]
+    fn spectral_norm_estimate(
+        &self,
+        iters: usize,
+        rng: impl FnMut() -> E::Canonical,
+    ) -> <E::Canonical as ComplexField>::Real {
+        // the conjugate has the same singular values
+        let (matrix, _) = self.canonicalize();
+        if matrix.nrows() == 0 || matrix.ncols() == 0 {
+            return <E::Canonical as ComplexField>::Real::faer_zero();
+        }
+
+        let mut rng = rng;
+        let mut x = Mat::<E::Canonical>::from_fn(matrix.ncols(), 1, |_, _| rng());
+        // the iterates are normalized after each product, so that the powers of the singular
+        // values can't overflow or underflow
+        let normalize = |x: &mut Mat<E::Canonical>| {
+            let norm = x.norm_l2();
+            if norm > <E::Canonical as ComplexField>::Real::faer_zero() {
+                let inv = norm.faer_inv();
+                zipped!(x.as_mut())
+                    .for_each(|unzipped!(mut x)| x.write(x.read().faer_scale_real(inv)));
+            }
+            norm
+        };
+        normalize(&mut x);
+
+        let mut y = matrix * &x;
+        for _ in 0..iters {
+            if normalize(&mut y) == <E::Canonical as ComplexField>::Real::faer_zero() {
+                break;
+            }
+            x = matrix.adjoint() * &y;
+            normalize(&mut x);
+            y = matrix * &x;
+        }
+        y.norm_l2()
+    }
+    #[track_caller]
     fn selfadjoint_eigendecomposition(
         &self,
         side: Side,
@@ -3199,25 +3952,26 @@ where
 
         let mut s = Mat::<E::Canonical>::zeros(dim, 1);
         let params = Default::default();
-        faer_svd::compute_svd(
-            self.canonicalize().0,
-            s.as_mut(),
-            None,
-            None,
+        let req = faer_svd::compute_svd_req::<E::Canonical>(
+            self.nrows(),
+            self.ncols(),
+            faer_svd::ComputeVectors::No,
+            faer_svd::ComputeVectors::No,
             parallelism,
-            PodStack::new(&mut GlobalPodBuffer::new(
-                faer_svd::compute_svd_req::<E::Canonical>(
-                    self.nrows(),
-                    self.ncols(),
-                    faer_svd::ComputeVectors::No,
-                    faer_svd::ComputeVectors::No,
-                    parallelism,
-                    params,
-                )
-                .unwrap(),
-            )),
             params,
-        );
+        )
+        .unwrap();
+        scratch::with_scratch(req, |stack| {
+            faer_svd::compute_svd(
+                self.canonicalize().0,
+                s.as_mut(),
+                None,
+                None,
+                parallelism,
+                stack,
+                params,
+            )
+        });
 
         (0..dim).map(|i| s.read(i, 0).faer_real()).collect()
     }
@@ -3297,11 +4051,45 @@ where
     fn svd(&self) -> Svd<E::Canonical> {
         self.as_ref().svd()
     }
+    fn try_svd(&self) -> Result<Svd<E::Canonical>, SvdError> {
+        self.as_ref().try_svd()
+    }
     #[track_caller]
     fn thin_svd(&self) -> ThinSvd<E::Canonical> {
         self.as_ref().thin_svd()
     }
     #[track_caller]
+    fn randomized_svd(
+        &self,
+        k: usize,
+        oversampling: usize,
+        power_iters: usize,
+        rng: impl FnMut() -> E::Canonical,
+    ) -> ThinSvd<E::Canonical> {
+        self.as_ref()
+            .randomized_svd(k, oversampling, power_iters, rng)
+    }
+    #[track_caller]
+    fn svd_with_pivoting(&self) -> PivotedSvd<E::Canonical> {
+        self.as_ref().svd_with_pivoting()
+    }
+    #[track_caller]
+    fn polar_decomposition(&self) -> (Mat<E::Canonical>, Mat<E::Canonical>) {
+        self.as_ref().polar_decomposition()
+    }
+    #[track_caller]
+    fn symmetric_square_root(&self) -> Mat<E::Canonical> {
+        self.as_ref().symmetric_square_root()
+    }
+    #[track_caller]
+    fn spectral_norm_estimate(
+        &self,
+        iters: usize,
+        rng: impl FnMut() -> E::Canonical,
+    ) -> <E::Canonical as ComplexField>::Real {
+        self.as_ref().spectral_norm_estimate(iters, rng)
+    }
+    #[track_caller]
     fn selfadjoint_eigendecomposition(
         &self,
         side: Side,
@@ -3398,11 +4186,45 @@ where
     fn svd(&self) -> Svd<E::Canonical> {
         self.as_ref().svd()
     }
+    fn try_svd(&self) -> Result<Svd<E::Canonical>, SvdError> {
+        self.as_ref().try_svd()
+    }
     #[track_caller]
     fn thin_svd(&self) -> ThinSvd<E::Canonical> {
         self.as_ref().thin_svd()
     }
     #[track_caller]
+    fn randomized_svd(
+        &self,
+        k: usize,
+        oversampling: usize,
+        power_iters: usize,
+        rng: impl FnMut() -> E::Canonical,
+    ) -> ThinSvd<E::Canonical> {
+        self.as_ref()
+            .randomized_svd(k, oversampling, power_iters, rng)
+    }
+    #[track_caller]
+    fn svd_with_pivoting(&self) -> PivotedSvd<E::Canonical> {
+        self.as_ref().svd_with_pivoting()
+    }
+    #[track_caller]
+    fn polar_decomposition(&self) -> (Mat<E::Canonical>, Mat<E::Canonical>) {
+        self.as_ref().polar_decomposition()
+    }
+    #[track_caller]
+    fn symmetric_square_root(&self) -> Mat<E::Canonical> {
+        self.as_ref().symmetric_square_root()
+    }
+    #[track_caller]
+    fn spectral_norm_estimate(
+        &self,
+        iters: usize,
+        rng: impl FnMut() -> E::Canonical,
+    ) -> <E::Canonical as ComplexField>::Real {
+        self.as_ref().spectral_norm_estimate(iters, rng)
+    }
+    #[track_caller]
     fn selfadjoint_eigendecomposition(
         &self,
         side: Side,
@@ -4916,6 +5738,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_self_adjoint_svd() {
+        let n = 9;
+
+        let random = |_, _| c64::new(rand::random(), rand::random());
+        let A = Mat::from_fn(n, n, random);
+        // indefinite, with eigenvalues of both signs
+        let H = Mat::from_fn(n, n, |i, j| {
+            let shift = if i == j { 1.0 } else { 0.0 };
+            A.read(i, j) + A.adjoint().read(i, j) - c64::new(shift, 0.0)
+        });
+        let H_conj = H.conjugate().to_owned();
+
+        let check = |H: &Mat<c64>, u: MatRef<'_, c64>, s: MatRef<'_, c64>, v: MatRef<'_, c64>| {
+            let us = Mat::from_fn(n, n, |i, j| u.read(i, j) * s.read(j, 0));
+            assert_approx_eq(us * v.adjoint(), H);
+            assert_approx_eq(u.adjoint() * u, Mat::<c64>::identity(n, n));
+            assert_approx_eq(v.adjoint() * v, Mat::<c64>::identity(n, n));
+
+            let expected = H.singular_values();
+            for i in 0..n {
+                assert!(s.read(i, 0).im == 0.0);
+                assert!(s.read(i, 0).re >= 0.0);
+                assert!((s.read(i, 0).re - expected[i]).abs() < 1e-10);
+            }
+        };
+
+        let svd = H.svd();
+        check(&H, svd.u(), svd.s_diagonal(), svd.v());
+        let svd = H.conjugate().svd();
+        check(&H_conj, svd.u(), svd.s_diagonal(), svd.v());
+        let svd = H.thin_svd();
+        check(&H, svd.u(), svd.s_diagonal(), svd.v());
+    }
+
     #[test]
     fn test_thin_svd() {
         let n = 7;
@@ -4927,6 +5784,384 @@ mod tests {
         test_solver(&H.adjoint().to_owned(), &H.adjoint().thin_svd());
     }
 
+    #[test]
+    fn test_thin_svd_tall() {
+        let m = 100_000;
+        let n = 50;
+
+        let random = |_, _| rand::random::<f64>();
+        let H = Mat::from_fn(m, n, random);
+
+        for svd in [H.thin_svd(), H.transpose().to_owned().thin_svd()] {
+            let (u, s, v) = (svd.u(), svd.s_diagonal(), svd.v());
+            let size = s.nrows();
+            assert!(size == n);
+            assert!(u.ncols() == n);
+            assert!(v.ncols() == n);
+
+            let us = Mat::from_fn(u.nrows(), size, |i, j| u.read(i, j) * s.read(j, 0));
+            if u.nrows() == m {
+                assert_approx_eq(us * v.transpose(), &H);
+            } else {
+                assert_approx_eq(us * v.transpose(), H.transpose());
+            }
+        }
+    }
+
+    #[test]
+    fn test_svd_s_diag() {
+        let random = |_, _| rand::random::<f64>();
+        let H = Mat::from_fn(9, 6, random);
+
+        let svd = H.thin_svd();
+        let u = svd.u().to_owned();
+        let s = svd.s_diag();
+        assert!(s.column_vector().nrows() == 6);
+        for i in 0..5 {
+            assert!(s.column_vector().read(i) >= s.column_vector().read(i + 1));
+        }
+        assert_approx_eq(&u * svd.s_diag() * svd.v().transpose(), &H);
+
+        let svd = H.svd();
+        let u = svd.u().subcols(0, 6).to_owned();
+        assert_approx_eq(&u * svd.s_diag() * svd.v().transpose(), &H);
+
+        let svd = H.transpose().svd();
+        let v = svd.v().subcols(0, 6);
+        assert_approx_eq(svd.u() * svd.s_diag() * v.transpose(), H.transpose());
+
+        let svd = H.svd_with_pivoting();
+        let mut HP = Mat::zeros(9, 6);
+        faer_core::permutation::permute_cols(HP.as_mut(), H.as_ref(), svd.col_permutation());
+        let u = svd.u().to_owned();
+        assert_approx_eq(&u * svd.s_diag() * svd.v().transpose(), &HP);
+    }
+
+    #[test]
+    fn test_svd_scratch_pool() {
+        let random = |_, _| rand::random::<f64>();
+        let H = Mat::from_fn(64, 64, random);
+
+        let check = |H: &Mat<f64>| {
+            let svd = H.svd();
+            assert_approx_eq(
+                svd.u() * svd.s_diagonal().column_vector_as_diagonal() * svd.v().adjoint(),
+                H,
+            );
+        };
+
+        check(&H);
+        assert!(scratch::pooled_len() == 1);
+        for _ in 0..8 {
+            check(&H);
+            assert!(scratch::pooled_len() == 1);
+        }
+
+        // a nested decomposition on the same thread must not reuse the buffer that is in use
+        let req = dyn_stack::StackReq::new::<f64>(1 << 16);
+        scratch::with_scratch(req, |_| {
+            check(&H);
+            check(&H.transpose().to_owned());
+        });
+        assert!(scratch::pooled_len() == 2);
+
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    for _ in 0..4 {
+                        check(&H);
+                    }
+                    assert!(scratch::pooled_len() == 1);
+                });
+            }
+        });
+    }
+
+    #[test]
+    fn test_svd_with_pivoting() {
+        let random = |_, _| c64::new(rand::random(), rand::random());
+
+        for (m, n, rank) in [(20, 12, 5), (12, 20, 5), (15, 15, 15), (15, 15, 1)] {
+            let A = Mat::from_fn(m, rank, random) * Mat::from_fn(rank, n, random);
+
+            let svd = A.svd_with_pivoting();
+            assert!(svd.rank() == rank);
+
+            let mut AP = Mat::zeros(m, n);
+            faer_core::permutation::permute_cols(AP.as_mut(), A.as_ref(), svd.col_permutation());
+
+            let s = svd.s_diagonal();
+            let us = Mat::from_fn(m, s.nrows(), |i, j| svd.u().read(i, j) * s.read(j, 0));
+            assert_approx_eq(us * svd.v().adjoint(), &AP);
+
+            let size = Ord::min(m, n);
+            assert_approx_eq(
+                svd.u().adjoint() * svd.u(),
+                Mat::<c64>::identity(size, size),
+            );
+            assert_approx_eq(
+                svd.v().adjoint() * svd.v(),
+                Mat::<c64>::identity(size, size),
+            );
+        }
+    }
+
+    #[test]
+    fn test_polar_decomposition() {
+        let random = |_, _| c64::new(rand::random(), rand::random());
+
+        for (m, n) in [(8, 8), (12, 7), (7, 12)] {
+            let A = Mat::from_fn(m, n, random);
+            let (U, H) = A.polar_decomposition();
+            assert!(all(
+                U.nrows() == m,
+                U.ncols() == n,
+                H.nrows() == n,
+                H.ncols() == n
+            ));
+
+            assert_approx_eq(&U * &H, &A);
+            assert_approx_eq(H.adjoint().to_owned(), &H);
+            assert!(H
+                .selfadjoint_eigenvalues(Side::Lower)
+                .iter()
+                .all(|&x| x > -1e-12));
+
+            let size = Ord::min(m, n);
+            let UU = if m >= n {
+                U.adjoint() * &U
+            } else {
+                &U * U.adjoint()
+            };
+            assert!((UU - Mat::<c64>::identity(size, size)).norm_l2() < 1e-12);
+        }
+
+        let A = Mat::from_fn(6, 6, |_, _| rand::random::<f64>());
+        let (U, H) = A.as_ref().polar_decomposition();
+        assert_approx_eq(&U * &H, &A);
+        assert!((U.transpose() * &U - Mat::<f64>::identity(6, 6)).norm_l2() < 1e-12);
+    }
+
+    #[test]
+    fn test_symmetric_square_root() {
+        let random = |_, _| c64::new(rand::random(), rand::random());
+
+        let B = Mat::from_fn(10, 6, random);
+        // positive semidefinite, with rank 6
+        let A = &B * B.adjoint();
+        let R = A.symmetric_square_root();
+        assert_approx_eq(R.adjoint().to_owned(), &R);
+        assert_approx_eq(&R * &R, &A);
+        assert!(R
+            .selfadjoint_eigenvalues(Side::Lower)
+            .iter()
+            .all(|&x| x > -1e-12));
+
+        let B = Mat::from_fn(5, 5, |_, _| rand::random::<f64>());
+        let mut A = &B * B.transpose();
+        let R = A.as_mut().symmetric_square_root();
+        assert_approx_eq(&R * &R, &A);
+    }
+
+    #[test]
+    fn test_fundamental_subspaces() {
+        let random = |_, _| c64::new(rand::random(), rand::random());
+
+        for (m, n, rank) in [(20, 12, 5), (12, 20, 5), (15, 15, 15), (15, 15, 1)] {
+            let A = Mat::from_fn(m, rank, random) * Mat::from_fn(rank, n, random);
+            let rcond = 1e-10;
+
+            let N = A.null_space(rcond);
+            let R = A.row_space(rcond);
+            let C = A.column_space(rcond);
+            assert!(all(N.nrows() == n, N.ncols() == n - rank));
+            assert!(all(R.nrows() == n, R.ncols() == rank));
+            assert!(all(C.nrows() == m, C.ncols() == rank));
+
+            for basis in [&N, &R, &C] {
+                let k = basis.ncols();
+                assert_approx_eq(basis.adjoint() * basis, Mat::<c64>::identity(k, k));
+            }
+            assert!((&A * &N).norm_l2() < 1e-10);
+            // the row space and the null space are orthogonal complements
+            assert!((R.adjoint() * &N).norm_l2() < 1e-10);
+            // the columns of A are in its column space
+            assert_approx_eq(&C * (C.adjoint() * &A), &A);
+        }
+
+        let A = Mat::<f64>::zeros(3, 4);
+        assert!(A.null_space(1e-10).ncols() == 4);
+        assert!(A.column_space(1e-10).ncols() == 0);
+    }
+
+    #[test]
+    fn test_randomized_svd() {
+        fn check<ViewE: Conjugate<Canonical = c64>>(A: MatRef<'_, ViewE>, expected: &[f64]) {
+            let rank = expected.len();
+            let sample = || c64::new(rand::random::<f64>() - 0.5, rand::random::<f64>() - 0.5);
+            let svd = A.randomized_svd(rank, 10, 1, sample);
+            let (u, s, v) = (svd.u(), svd.s_diagonal(), svd.v());
+            assert!(all(
+                u.nrows() == A.nrows(),
+                u.ncols() == rank,
+                s.nrows() == rank,
+                v.nrows() == A.ncols(),
+                v.ncols() == rank,
+            ));
+
+            for i in 0..rank {
+                assert!((s.read(i, 0).re - expected[i]).abs() < 1e-8 * expected[0]);
+            }
+            assert_approx_eq(u.adjoint() * u, Mat::<c64>::identity(rank, rank));
+            assert_approx_eq(v.adjoint() * v, Mat::<c64>::identity(rank, rank));
+
+            let us = Mat::from_fn(u.nrows(), rank, |i, j| u.read(i, j) * s.read(j, 0));
+            assert!((us * v.adjoint() - A.to_owned()).norm_l2() < 1e-8 * expected[0]);
+        }
+
+        let rank = 20;
+        let sample = || c64::new(rand::random::<f64>() - 0.5, rand::random::<f64>() - 0.5);
+        for (m, n) in [(400, 300), (300, 400)] {
+            // numerically rank 20, with a perturbation below the requested accuracy
+            let A = Mat::from_fn(m, rank, |_, _| sample()) * Mat::from_fn(rank, n, |_, _| sample())
+                + Mat::from_fn(m, n, |_, _| sample() * c64::new(1e-12, 0.0));
+            let expected = A.singular_values();
+
+            check(A.as_ref(), &expected[..rank]);
+            // the factors of a conjugated view are conjugated back
+            check(A.adjoint(), &expected[..rank]);
+        }
+
+        let A = Mat::from_fn(50, 30, |_, _| rand::random::<f64>());
+        let svd = A.randomized_svd(30, 5, 0, rand::random::<f64>);
+        let s = svd.s_diagonal();
+        let us = Mat::from_fn(50, 30, |i, j| svd.u().read(i, j) * s.read(j, 0));
+        assert_approx_eq(us * svd.v().transpose(), &A);
+    }
+
+    #[test]
+    fn test_singular_value_norms() {
+        let random = |_, _| c64::new(rand::random(), rand::random());
+        let A = Mat::from_fn(7, 4, random);
+        let s = A.singular_values();
+
+        let close = |a: f64, b: f64| (a - b).abs() <= 1e-12 * b;
+        assert!(A.spectral_norm() == s[0]);
+        assert!(close(A.nuclear_norm(), s.iter().sum()));
+        assert!(close(A.schatten_norm(1), A.nuclear_norm()));
+        assert!(close(A.schatten_norm(2), A.norm_l2()));
+        assert!(close(
+            A.schatten_norm(3),
+            s.iter().map(|x| x.powi(3)).sum::<f64>().cbrt()
+        ));
+        assert!(close(A.schatten_norm(200), A.spectral_norm()));
+
+        // the singular values are scaled before being raised to the power p
+        let A = Mat::from_fn(3, 3, |i, j| if i == j { 1e300 } else { 0.0 });
+        assert!(close(
+            A.as_ref().schatten_norm(4),
+            3.0f64.powf(0.25) * 1e300
+        ));
+
+        let A = Mat::<f64>::zeros(0, 3);
+        assert!(A.spectral_norm() == 0.0);
+        assert!(A.nuclear_norm() == 0.0);
+        assert!(A.schatten_norm(3) == 0.0);
+    }
+
+    #[test]
+    fn test_spectral_norm_estimate() {
+        let sample = || c64::new(rand::random::<f64>() - 0.5, rand::random::<f64>() - 0.5);
+        let (m, n) = (60, 40);
+        let u = Mat::from_fn(m, n, |_, _| sample())
+            .thin_svd()
+            .u()
+            .to_owned();
+        let v = Mat::from_fn(n, n, |_, _| sample())
+            .thin_svd()
+            .u()
+            .to_owned();
+        // well separated, with a ratio of 2 between the two largest singular values
+        let us = Mat::from_fn(m, n, |i, j| {
+            u.read(i, j)
+                * c64::new(
+                    if j == 0 {
+                        10.0
+                    } else {
+                        5.0 * 0.9f64.powi(j as i32)
+                    },
+                    0.0,
+                )
+        });
+        let A = us * v.adjoint();
+        let norm = A.spectral_norm();
+        assert!((norm - 10.0).abs() < 1e-12);
+
+        // the error is divided by about 16 by each step
+        let mut prev_err = f64::INFINITY;
+        for iters in [0, 2, 4, 8, 16] {
+            let estimate = A.spectral_norm_estimate(iters, sample);
+            assert!(estimate <= norm * (1.0 + 1e-14));
+            let err = (norm - estimate) / norm;
+            assert!(err <= prev_err.max(1e-14));
+            prev_err = err;
+        }
+        assert!(prev_err < 1e-12);
+        assert!((A.adjoint().spectral_norm_estimate(16, sample) - norm).abs() < 1e-12 * norm);
+        assert!(
+            (A.as_ref().conjugate().spectral_norm_estimate(16, sample) - norm).abs() < 1e-12 * norm
+        );
+
+        // the iterates are normalized, so the powers of the singular values don't overflow
+        let A = Mat::from_fn(5, 3, |_, _| rand::random::<f64>() * 1e300);
+        let estimate = A.spectral_norm_estimate(50, rand::random::<f64>);
+        assert!((estimate - A.spectral_norm()).abs() < 1e-12 * estimate);
+
+        assert!(Mat::<f64>::zeros(4, 3).spectral_norm_estimate(5, rand::random::<f64>) == 0.0);
+        assert!(Mat::<f64>::zeros(0, 3).spectral_norm_estimate(5, rand::random::<f64>) == 0.0);
+    }
+
+    #[test]
+    fn test_try_svd() {
+        let A = Mat::from_fn(7, 4, |_, _| rand::random::<f64>());
+        let svd = A.svd();
+        let try_svd = A.try_svd().unwrap();
+        assert!(try_svd.s_diagonal() == svd.s_diagonal());
+        assert!(try_svd.u() == svd.u());
+        assert!(try_svd.v() == svd.v());
+
+        // a 10^9 x 10^9 matrix, whose entries all alias the same value
+        let x = 1.0f64;
+        let n = 1_000_000_000;
+        let A = unsafe { faer_core::mat::from_raw_parts::<f64>(&x, n, n, 0, 0) };
+        assert!(A.try_svd().err() == Some(SvdError::SizeOverflow));
+
+        // the error of the decomposition itself is forwarded
+        let mut A = Mat::from_fn(5, 3, |_, _| rand::random::<f64>());
+        A.write(2, 1, f64::NAN);
+        assert!(A.try_svd().err() == Some(SvdError::Svd(faer_svd::SvdError::NonFinite)));
+    }
+
+    #[test]
+    fn test_svd_pseudo_inverse() {
+        let m = 9;
+        let n = 6;
+        let rank = 4;
+
+        let random = |_, _| c64::new(rand::random(), rand::random());
+        let A = Mat::from_fn(m, rank, random) * Mat::from_fn(rank, n, random);
+
+        for pinv in [A.svd().pseudo_inverse(), A.thin_svd().pseudo_inverse()] {
+            assert!(all(pinv.nrows() == n, pinv.ncols() == m));
+            assert_approx_eq(&A * &pinv * &A, &A);
+            assert_approx_eq(&pinv * &A * &pinv, &pinv);
+        }
+
+        let A = Mat::from_fn(n, n, random);
+        let svd = A.svd();
+        assert_approx_eq(svd.pseudo_inverse(), svd.inverse());
+    }
+
     #[test]
     fn test_selfadjoint_eigendecomposition() {
         let n = 7;